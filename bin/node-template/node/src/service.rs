@@ -12,6 +12,7 @@ pub use sc_executor::NativeExecutor;
 use sp_consensus_aura::sr25519::{AuthorityPair as AuraPair};
 use sc_finality_grandpa::{
 	FinalityProofProvider as GrandpaFinalityProofProvider, StorageAndProofProvider, SharedVoterState,
+	WarpSyncProofProvider,
 };
 
 // Our native executor instance.
@@ -95,6 +96,10 @@ pub fn new_full(config: Configuration) -> Result<impl AbstractService, ServiceEr
 			let provider = client as Arc<dyn StorageAndProofProvider<_, _>>;
 			Ok(Arc::new(GrandpaFinalityProofProvider::new(backend, provider)) as _)
 		})?
+		.with_warp_sync_provider(|client, backend| {
+			let authority_set = grandpa_link.shared_authority_set().clone();
+			Ok(Arc::new(WarpSyncProofProvider::new(backend, client, authority_set)) as _)
+		})?
 		.build()?;
 
 	if role.is_authority() {
@@ -45,4 +45,11 @@ pub enum Subcommand {
 	/// The custom benchmark subcommmand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// The custom try-runtime subcommand testing a runtime upgrade against existing state.
+	#[structopt(
+		name = "try-runtime",
+		about = "Test the upgrade to a new runtime against the state of an existing chain."
+	)]
+	TryRuntime(try_runtime_cli::TryRuntimeCmd),
 }
@@ -16,8 +16,14 @@
 
 use crate::{chain_spec, service, Cli, Subcommand};
 use node_executor::Executor;
-use node_runtime::{Block, RuntimeApi};
+use node_primitives::DigestItem;
+use node_runtime::{Block, RuntimeApi, constants::time::SLOT_DURATION};
 use sc_cli::{Result, SubstrateCli};
+use sp_consensus_babe::{
+	digests::{CompatibleDigestItem, PreDigest, SecondaryPlainPreDigest},
+	inherents::BabeInherentData,
+};
+use sp_runtime::generic::Digest;
 
 impl SubstrateCli for Cli {
 	fn impl_name() -> &'static str {
@@ -92,6 +98,36 @@ pub fn run() -> Result<()> {
 				Ok(())
 			}
 		}
+		Some(Subcommand::TryRuntime(cmd)) => {
+			if cfg!(feature = "try-runtime") {
+				let runner = cli.create_runner(cmd)?;
+
+				runner.sync_run(|config| {
+					// the first block of the new runtime claims a secondary BABE slot at the
+					// current time.
+					let inherent_data_providers = sp_inherents::InherentDataProviders::new();
+					inherent_data_providers
+						.register_provider(sp_consensus_babe::inherents::InherentDataProvider::new(
+							SLOT_DURATION,
+						))
+						.map_err(|e| format!("{:?}", e))?;
+
+					cmd.run::<Block, Executor, _>(config, &inherent_data_providers, |inherent_data| {
+						let slot_number = inherent_data.babe_inherent_data()
+							.map_err(|e| format!("{:?}", e))?;
+						let pre_digest = PreDigest::SecondaryPlain(SecondaryPlainPreDigest {
+							authority_index: 0,
+							slot_number,
+						});
+						Ok(Digest { logs: vec![DigestItem::babe_pre_digest(pre_digest)] })
+					})
+				})
+			} else {
+				println!("Try-runtime wasn't enabled when building the node. \
+				You can enable it with `--features try-runtime`.");
+				Ok(())
+			}
+		}
 		Some(Subcommand::Base(subcommand)) => {
 			let runner = cli.create_runner(subcommand)?;
 
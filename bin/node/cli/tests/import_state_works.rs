@@ -0,0 +1,71 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(unix)]
+
+use assert_cmd::cargo::cargo_bin;
+use std::{process::Command, fs};
+use tempfile::tempdir;
+
+mod common;
+
+#[test]
+fn import_state_works() {
+	let base_path = tempdir().expect("could not create a temp dir");
+	let exported_block = base_path.path().join("exported_block");
+	let exported_header = base_path.path().join("exported_header");
+	let exported_state = base_path.path().join("exported_state");
+
+	common::run_dev_node_for_a_while(base_path.path());
+
+	let status = Command::new(cargo_bin("substrate"))
+		.args(&["export-blocks", "--dev", "--pruning", "archive", "-d"])
+		.arg(base_path.path())
+		.args(&["--from", "1", "--to", "1"])
+		.arg(&exported_block)
+		.status()
+		.unwrap();
+	assert!(status.success());
+
+	let block: serde_json::Value = serde_json::from_slice(&fs::read(&exported_block).unwrap())
+		.unwrap();
+	fs::write(&exported_header, block["block"]["header"].to_string()).unwrap();
+
+	let output = Command::new(cargo_bin("substrate"))
+		.args(&["export-state", "--dev", "--pruning", "archive", "-d"])
+		.arg(base_path.path())
+		.arg("1")
+		.output()
+		.unwrap();
+	assert!(output.status.success());
+	fs::write(&exported_state, &output.stdout).unwrap();
+
+	let imported_path = tempdir().expect("could not create a temp dir");
+
+	let status = Command::new(cargo_bin("substrate"))
+		.args(&["import-state", "--dev", "-d"])
+		.arg(imported_path.path())
+		.arg(&exported_state)
+		.arg("--header")
+		.arg(&exported_header)
+		.status()
+		.unwrap();
+	assert!(status.success());
+
+	// the node starts from the imported block, which requires calling the runtime at genesis
+	// to set up the consensus engines.
+	common::run_dev_node_for_a_while(imported_path.path());
+}
@@ -0,0 +1,84 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(all(unix, feature = "try-runtime"))]
+
+use assert_cmd::cargo::cargo_bin;
+use std::{process::Command, fs};
+use tempfile::tempdir;
+
+mod common;
+
+#[test]
+fn try_runtime_works() {
+	let base_path = tempdir().expect("could not create a temp dir");
+	let runtime = base_path.path().join("runtime.wasm");
+	let exported_state = base_path.path().join("exported_state");
+	fs::write(&runtime, node_runtime::WASM_BINARY).unwrap();
+
+	common::run_dev_node_for_a_while(base_path.path());
+
+	// state of the best block in the database.
+	let output = Command::new(cargo_bin("substrate"))
+		.args(&["try-runtime", "--dev", "--pruning", "archive", "-d"])
+		.arg(base_path.path())
+		.arg("--runtime")
+		.arg(&runtime)
+		.output()
+		.unwrap();
+	assert!(output.status.success());
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(stdout.contains("Runtime upgrade executed successfully"));
+	// the first block of the new runtime is executed on top of it.
+	assert!(stdout.contains("First block #"));
+	assert!(stdout.contains("executed successfully with"));
+
+	// state of a block exported to a chain spec.
+	let output = Command::new(cargo_bin("substrate"))
+		.args(&["export-state", "--dev", "--pruning", "archive", "-d"])
+		.arg(base_path.path())
+		.arg("1")
+		.output()
+		.unwrap();
+	assert!(output.status.success());
+	fs::write(&exported_state, &output.stdout).unwrap();
+
+	let output = Command::new(cargo_bin("substrate"))
+		.args(&["try-runtime", "--from-chain-spec", "--chain"])
+		.arg(&exported_state)
+		.arg("-d")
+		.arg(base_path.path())
+		.arg("--runtime")
+		.arg(&runtime)
+		.output()
+		.unwrap();
+	assert!(output.status.success());
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(stdout.contains("state of block #1 from the chain spec"));
+	assert!(stdout.contains("Runtime upgrade executed successfully"));
+	assert!(stdout.contains("First block #2 executed successfully"));
+
+	// a runtime that can't be executed is reported as a failure.
+	fs::write(&runtime, b"not wasm").unwrap();
+	let status = Command::new(cargo_bin("substrate"))
+		.args(&["try-runtime", "--dev", "--pruning", "archive", "-d"])
+		.arg(base_path.path())
+		.arg("--runtime")
+		.arg(&runtime)
+		.status()
+		.unwrap();
+	assert!(!status.success());
+}
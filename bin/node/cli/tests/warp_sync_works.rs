@@ -0,0 +1,106 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(unix)]
+
+use assert_cmd::cargo::cargo_bin;
+use nix::sys::signal::{kill, Signal::SIGINT};
+use nix::unistd::Pid;
+use std::{
+	convert::TryInto, io::{BufRead, BufReader}, process::{Child, Command, Stdio},
+	sync::mpsc, thread, time::{Duration, Instant},
+};
+use tempfile::tempdir;
+
+mod common;
+
+/// Forward the log lines of the given `child` to the returned channel.
+fn log_lines(child: &mut Child) -> mpsc::Receiver<String> {
+	let (tx, rx) = mpsc::channel();
+	let stderr = BufReader::new(child.stderr.take().unwrap());
+	thread::spawn(move || {
+		for line in stderr.lines() {
+			let line = match line {
+				Ok(line) => line,
+				Err(_) => break,
+			};
+			if tx.send(line).is_err() {
+				break;
+			}
+		}
+	});
+	rx
+}
+
+/// Wait for a log line containing `pattern` for at most `secs` seconds.
+fn wait_for_line(lines: &mpsc::Receiver<String>, pattern: &str, secs: u64) -> String {
+	let deadline = Instant::now() + Duration::from_secs(secs);
+	loop {
+		let timeout = deadline.saturating_duration_since(Instant::now());
+		match lines.recv_timeout(timeout) {
+			Ok(line) if line.contains(pattern) => return line,
+			Ok(_) => {},
+			Err(_) => panic!("no log line containing {:?} within {} seconds", pattern, secs),
+		}
+	}
+}
+
+fn stop(mut child: Child) {
+	kill(Pid::from_raw(child.id().try_into().unwrap()), SIGINT).unwrap();
+	assert!(common::wait_for(&mut child, 40).map(|x| x.success()).unwrap_or_default());
+}
+
+#[test]
+fn warp_sync_works() {
+	let authority_path = tempdir().expect("could not create a temp dir");
+	let warp_path = tempdir().expect("could not create a temp dir");
+
+	let mut authority = Command::new(cargo_bin("substrate"))
+		.args(&["--dev", "--port", "30533", "--rpc-port", "9953", "--ws-port", "9964"])
+		.args(&["--no-prometheus", "-d"])
+		.arg(authority_path.path())
+		.stderr(Stdio::piped())
+		.spawn()
+		.unwrap();
+	let authority_lines = log_lines(&mut authority);
+
+	let identity = wait_for_line(&authority_lines, "Local node identity is: ", 60);
+	let peer_id = identity.rsplit(' ').next().unwrap().to_string();
+
+	// let the authority produce and finalize some blocks.
+	thread::sleep(Duration::from_secs(30));
+	assert!(authority.try_wait().unwrap().is_none(), "the authority should still be running");
+
+	let mut warp = Command::new(cargo_bin("substrate"))
+		.args(&["--chain", "dev", "--sync", "warp"])
+		.args(&["--port", "30534", "--rpc-port", "9954", "--ws-port", "9965", "--no-prometheus"])
+		.arg("--bootnodes")
+		.arg(format!("/ip4/127.0.0.1/tcp/30533/p2p/{}", peer_id))
+		.arg("-d")
+		.arg(warp_path.path())
+		.stderr(Stdio::piped())
+		.spawn()
+		.unwrap();
+	let warp_lines = log_lines(&mut warp);
+
+	// the node downloads the finalized state, then imports the blocks authored with BABE on
+	// top of it.
+	wait_for_line(&warp_lines, "Warp sync complete", 120);
+	wait_for_line(&warp_lines, "Imported #", 60);
+
+	stop(warp);
+	stop(authority);
+}
@@ -620,6 +620,7 @@ fn deploying_wasm_contract_should_work() {
 					pallet_contracts::Call::instantiate::<Runtime>(
 						1 * DOLLARS,
 						500_000_000,
+						None,
 						transfer_ch,
 						Vec::new()
 					)
@@ -632,6 +633,7 @@ fn deploying_wasm_contract_should_work() {
 						pallet_indices::address::Address::Id(addr.clone()),
 						10,
 						500_000_000,
+						None,
 						vec![0x00, 0x01, 0x02, 0x03]
 					)
 				),
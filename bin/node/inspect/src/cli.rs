@@ -59,4 +59,50 @@ pub enum InspectSubCmd {
 		#[structopt(value_name = "BLOCK:INDEX or BYTES")]
 		input: String,
 	},
+	/// Decode storage at given block with the runtime metadata and print out the details.
+	Storage {
+		/// Address of the block which state should be printed out.
+		///
+		/// Can be either a block hash (no 0x prefix) or a number to retrieve existing block,
+		/// or a 0x-prefixed bytes hex string, representing SCALE encoding of
+		/// a block that is part of the database.
+		#[structopt(value_name = "HASH or NUMBER or BYTES")]
+		input: String,
+
+		#[allow(missing_docs)]
+		#[structopt(flatten)]
+		filter: StorageFilterParams,
+	},
+	/// Print out storage entries that differ between two blocks.
+	///
+	/// Useful to audit the effects of a runtime migration.
+	StorageDiff {
+		/// Address of the block to compare from.
+		#[structopt(value_name = "FROM: HASH or NUMBER or BYTES")]
+		from: String,
+
+		/// Address of the block to compare to.
+		#[structopt(value_name = "TO: HASH or NUMBER or BYTES")]
+		to: String,
+
+		#[allow(missing_docs)]
+		#[structopt(flatten)]
+		filter: StorageFilterParams,
+	},
+}
+
+/// Parameters selecting and formatting the storage to print out.
+#[derive(Debug, StructOpt, Clone)]
+pub struct StorageFilterParams {
+	/// Only print out storage of the pallet with given storage prefix (e.g. `Balances`).
+	#[structopt(long)]
+	pub pallet: Option<String>,
+
+	/// Only print out given storage item of the pallet (e.g. `TotalIssuance`).
+	#[structopt(long, requires = "pallet")]
+	pub item: Option<String>,
+
+	/// Print out the storage entries as JSON.
+	#[structopt(long)]
+	pub json: bool,
 }
@@ -48,6 +48,30 @@ impl InspectCmd {
 				println!("{}", res);
 				Ok(())
 			}
+			InspectSubCmd::Storage { input, filter } => {
+				let input = input.parse()?;
+				let res = inspect.storage(
+					input,
+					filter.pallet.as_deref(),
+					filter.item.as_deref(),
+					filter.json,
+				).map_err(|e| format!("{}", e))?;
+				println!("{}", res);
+				Ok(())
+			}
+			InspectSubCmd::StorageDiff { from, to, filter } => {
+				let from = from.parse()?;
+				let to = to.parse()?;
+				let res = inspect.storage_diff(
+					from,
+					to,
+					filter.pallet.as_deref(),
+					filter.item.as_deref(),
+					filter.json,
+				).map_err(|e| format!("{}", e))?;
+				println!("{}", res);
+				Ok(())
+			}
 		}
 	}
 }
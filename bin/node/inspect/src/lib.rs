@@ -15,15 +15,17 @@
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
 //! A CLI extension for substrate node, adding sub-command to pretty print debug info
-//! about blocks and extrinsics.
+//! about blocks, extrinsics and storage.
 //!
 //! The blocks and extrinsics can either be retrieved from the database (on-chain),
 //! or a raw SCALE-encoding can be provided.
+//! The storage is decoded using the runtime metadata of the inspected block.
 
 #![warn(missing_docs)]
 
 pub mod cli;
 pub mod command;
+pub mod storage;
 
 use std::{
 	fmt,
@@ -32,13 +34,17 @@ use std::{
 	str::FromStr,
 };
 use codec::{Encode, Decode};
-use sc_client_api::BlockBackend;
+use sc_client_api::{
+	BlockBackend, CallExecutor, ExecutionStrategy, ExecutorProvider, StorageProvider,
+};
+use sc_service::client::Client;
 use sp_blockchain::HeaderBackend;
-use sp_core::hexdisplay::HexDisplay;
+use sp_core::{hexdisplay::HexDisplay, storage::{StorageKey, StorageData}};
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block, HashFor, NumberFor, Hash}
 };
+use crate::storage::{StorageDiff, StorageEntry, StorageLayout, storage_prefix};
 
 /// A helper type for a generic block input.
 pub type BlockAddressFor<TBlock> = BlockAddress<
@@ -86,6 +92,8 @@ pub enum Error {
 	Blockchain(sp_blockchain::Error),
 	/// Given block has not been found.
 	NotFound(String),
+	/// Could not interpret the runtime metadata.
+	Metadata(String),
 }
 
 impl std::error::Error for Error {
@@ -94,19 +102,61 @@ impl std::error::Error for Error {
 			Self::Codec(ref e) => Some(e),
 			Self::Blockchain(ref e) => Some(e),
 			Self::NotFound(_) => None,
+			Self::Metadata(_) => None,
 		}
 	}
 }
 
-/// A helper trait to access block headers and bodies.
+/// A helper trait to access the state and the runtime metadata at given block.
+pub trait StateAccess<TBlock: Block> {
+	/// Returns all storage key/value pairs with keys starting with given prefix.
+	fn storage_pairs(
+		&self,
+		id: &BlockId<TBlock>,
+		prefix: &StorageKey,
+	) -> sp_blockchain::Result<Vec<(StorageKey, StorageData)>>;
+
+	/// Returns SCALE-encoded runtime metadata.
+	fn metadata(&self, id: &BlockId<TBlock>) -> sp_blockchain::Result<Vec<u8>>;
+}
+
+impl<B, E, TBlock, RA> StateAccess<TBlock> for Client<B, E, TBlock, RA> where
+	TBlock: Block,
+	B: sc_client_api::backend::Backend<TBlock>,
+	E: CallExecutor<TBlock>,
+{
+	fn storage_pairs(
+		&self,
+		id: &BlockId<TBlock>,
+		prefix: &StorageKey,
+	) -> sp_blockchain::Result<Vec<(StorageKey, StorageData)>> {
+		StorageProvider::storage_pairs(self, id, prefix)
+	}
+
+	fn metadata(&self, id: &BlockId<TBlock>) -> sp_blockchain::Result<Vec<u8>> {
+		let encoded = self.executor().call(
+			id,
+			"Metadata_metadata",
+			&[],
+			ExecutionStrategy::NativeElseWasm,
+			None,
+		)?;
+		// The runtime API returns `OpaqueMetadata`, i.e. length-prefixed bytes.
+		Decode::decode(&mut &*encoded)
+			.map_err(|e| sp_blockchain::Error::CallResultDecode("Metadata_metadata", e))
+	}
+}
+
+/// A helper trait to access block headers, bodies and state.
 pub trait ChainAccess<TBlock: Block>:
 	HeaderBackend<TBlock> +
-	BlockBackend<TBlock>
+	BlockBackend<TBlock> +
+	StateAccess<TBlock>
 {}
 
 impl<T, TBlock> ChainAccess<TBlock> for T where
 	TBlock: Block,
-	T: sp_blockchain::HeaderBackend<TBlock> + sc_client_api::BlockBackend<TBlock>,
+	T: sp_blockchain::HeaderBackend<TBlock> + sc_client_api::BlockBackend<TBlock> + StateAccess<TBlock>,
 {}
 
 /// Blockchain inspector.
@@ -175,6 +225,77 @@ impl<TBlock: Block, TPrinter: PrettyPrinter<TBlock>> Inspector<TBlock, TPrinter>
 		})
 	}
 
+	fn get_block_id(&self, input: BlockAddressFor<TBlock>) -> Result<BlockId<TBlock>, Error> {
+		Ok(match input {
+			BlockAddress::Bytes(bytes) => BlockId::hash(TBlock::decode(&mut &*bytes)?.hash()),
+			BlockAddress::Number(number) => BlockId::number(number),
+			BlockAddress::Hash(hash) => BlockId::hash(hash),
+		})
+	}
+
+	fn get_storage(
+		&self,
+		input: BlockAddressFor<TBlock>,
+		pallet: Option<&str>,
+		item: Option<&str>,
+	) -> Result<Vec<StorageEntry>, Error> {
+		let id = self.get_block_id(input)?;
+		if self.chain.header(id)?.is_none() {
+			return Err(Error::NotFound(format!("Could not find block {:?}", id)));
+		}
+
+		let layout = StorageLayout::from_metadata(&self.chain.metadata(&id)?)
+			.map_err(Error::Metadata)?;
+		let prefix = StorageKey(pallet.map(|pallet| storage_prefix(pallet, item)).unwrap_or_default());
+		Ok(self.chain.storage_pairs(&id, &prefix)?
+			.into_iter()
+			.map(|(key, value)| layout.decode(&key.0, &value.0))
+			.collect())
+	}
+
+	/// Get the storage at given block decoded with the runtime metadata.
+	///
+	/// The output can be limited to a single pallet or a storage item and is either
+	/// human-readable or JSON.
+	pub fn storage(
+		&self,
+		input: BlockAddressFor<TBlock>,
+		pallet: Option<&str>,
+		item: Option<&str>,
+		json: bool,
+	) -> Result<String, Error> {
+		let entries = self.get_storage(input, pallet, item)?;
+		if json {
+			return Ok(serde_json::to_string_pretty(&entries)
+				.expect("Storage entries consist of JSON-serializable types only; qed"));
+		}
+
+		Ok(entries.iter().map(|entry| entry.to_string()).collect())
+	}
+
+	/// Get the differences in storage between two blocks.
+	///
+	/// Each side is decoded using the runtime metadata of its block, so runtime upgrades
+	/// in between the blocks are taken into account.
+	pub fn storage_diff(
+		&self,
+		from: BlockAddressFor<TBlock>,
+		to: BlockAddressFor<TBlock>,
+		pallet: Option<&str>,
+		item: Option<&str>,
+		json: bool,
+	) -> Result<String, Error> {
+		let from = self.get_storage(from, pallet, item)?;
+		let to = self.get_storage(to, pallet, item)?;
+		let diff = StorageDiff::compute(from, to);
+		if json {
+			return Ok(serde_json::to_string_pretty(&diff)
+				.expect("Storage entries consist of JSON-serializable types only; qed"));
+		}
+
+		Ok(diff.iter().map(|diff| diff.to_string()).collect())
+	}
+
 	/// Get a pretty-printed extrinsic.
 	pub fn extrinsic(
 		&self,
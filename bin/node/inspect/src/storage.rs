@@ -0,0 +1,540 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Decoding of raw storage keys and values using the runtime metadata.
+//!
+//! The metadata only carries names of the key and value types, so apart from a handful
+//! of primitive types the values are printed as raw bytes annotated with their type.
+//! Keys hashed with a transparent hasher (`Blake2_128Concat`, `Twox64Concat`, `Identity`)
+//! have the original key extracted.
+
+use std::{
+	collections::{BTreeMap, HashMap},
+	fmt,
+};
+use codec::Decode;
+use frame_metadata::{
+	DecodeDifferent, RuntimeMetadata, RuntimeMetadataPrefixed, StorageEntryType, StorageHasher,
+};
+use serde::Serialize;
+use sp_core::{Bytes, hashing::twox_128, hexdisplay::HexDisplay};
+
+/// Length of the `twox_128(pallet) ++ twox_128(item)` prefix of every storage item key.
+const ENTRY_PREFIX_LEN: usize = 32;
+
+/// Compute a storage key prefix for all items of given pallet, or a particular item.
+///
+/// `pallet` is the storage prefix declared in `decl_storage!`.
+pub fn storage_prefix(pallet: &str, item: Option<&str>) -> Vec<u8> {
+	let mut prefix = twox_128(pallet.as_bytes()).to_vec();
+	if let Some(item) = item {
+		prefix.extend_from_slice(&twox_128(item.as_bytes()));
+	}
+	prefix
+}
+
+/// Storage items declared by the runtime, indexed by their key prefix.
+pub struct StorageLayout {
+	entries: HashMap<Vec<u8>, EntryInfo>,
+}
+
+struct EntryInfo {
+	pallet: String,
+	item: String,
+	ty: StorageEntryType,
+}
+
+impl StorageLayout {
+	/// Create the layout from SCALE-encoded `RuntimeMetadataPrefixed`.
+	pub fn from_metadata(metadata: &[u8]) -> Result<Self, String> {
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &*metadata)
+			.map_err(|e| format!("Unable to decode runtime metadata: {}", e.what()))?;
+		let modules = match metadata.1 {
+			RuntimeMetadata::V11(metadata) | RuntimeMetadata::V12(metadata) => metadata.modules,
+			_ => return Err("Only V11 and V12 runtime metadata are supported".into()),
+		};
+
+		let mut entries = HashMap::new();
+		for module in decoded(modules)? {
+			let storage = match module.storage {
+				Some(storage) => decoded(storage)?,
+				None => continue,
+			};
+			let pallet = decoded(storage.prefix)?;
+			for entry in decoded(storage.entries)? {
+				let item = decoded(entry.name)?;
+				entries.insert(
+					storage_prefix(&pallet, Some(&item)),
+					EntryInfo { pallet: pallet.clone(), item, ty: entry.ty },
+				);
+			}
+		}
+
+		Ok(StorageLayout { entries })
+	}
+
+	/// Decode a raw key/value pair.
+	pub fn decode(&self, key: &[u8], value: &[u8]) -> StorageEntry {
+		let mut result = StorageEntry {
+			key: key.to_vec().into(),
+			pallet: None,
+			item: None,
+			keys: Vec::new(),
+			value_type: None,
+			value: value.to_vec().into(),
+			decoded_value: None,
+		};
+
+		let info = match key.get(..ENTRY_PREFIX_LEN).and_then(|prefix| self.entries.get(prefix)) {
+			Some(info) => info,
+			None => return result,
+		};
+		result.pallet = Some(info.pallet.clone());
+		result.item = Some(info.item.clone());
+
+		let mut rest = &key[ENTRY_PREFIX_LEN..];
+		let value_type = match &info.ty {
+			StorageEntryType::Plain(value) => value,
+			StorageEntryType::Map { hasher, key, value, .. } => {
+				result.keys.extend(MapKey::split(hasher, key, &mut rest, true));
+				value
+			},
+			StorageEntryType::DoubleMap { hasher, key1, key2, value, key2_hasher } => {
+				if let Some(key1) = MapKey::split(hasher, key1, &mut rest, false) {
+					let complete = key1.key.is_some() || !is_concat(hasher);
+					result.keys.push(key1);
+					if complete {
+						result.keys.extend(MapKey::split(key2_hasher, key2, &mut rest, true));
+					}
+				}
+				value
+			},
+			StorageEntryType::NMap { keys, hashers, value } => {
+				let hashers = match hashers {
+					DecodeDifferent::Encode(hashers) => hashers.to_vec(),
+					DecodeDifferent::Decoded(hashers) => hashers.clone(),
+				};
+				let keys = match keys {
+					DecodeDifferent::Encode(keys) => keys.iter().map(|k| k.to_string()).collect(),
+					DecodeDifferent::Decoded(keys) => keys.clone(),
+				};
+				let count = hashers.len();
+				for (i, (hasher, key)) in hashers.iter().zip(keys).enumerate() {
+					let key = DecodeDifferent::Decoded(key);
+					match MapKey::split(hasher, &key, &mut rest, i + 1 == count) {
+						Some(map_key) => {
+							let complete = map_key.key.is_some() || !is_concat(hasher);
+							result.keys.push(map_key);
+							if !complete {
+								break;
+							}
+						},
+						None => break,
+					}
+				}
+				value
+			},
+		};
+
+		let value_type = type_name(value_type);
+		result.decoded_value = decode_exact(&value_type, value);
+		result.value_type = Some(value_type);
+		result
+	}
+}
+
+/// A decoded storage key/value pair.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageEntry {
+	/// Raw storage key.
+	pub key: Bytes,
+	/// Pallet (storage prefix) the key belongs to, if known.
+	pub pallet: Option<String>,
+	/// Storage item the key belongs to, if known.
+	pub item: Option<String>,
+	/// Keys of a map, double map or n-map entry.
+	pub keys: Vec<MapKey>,
+	/// Type name of the value as declared in the metadata.
+	pub value_type: Option<String>,
+	/// Raw storage value.
+	pub value: Bytes,
+	/// Decoded value, if the value type is a known primitive.
+	pub decoded_value: Option<String>,
+}
+
+impl StorageEntry {
+	fn name(&self) -> String {
+		match (&self.pallet, &self.item) {
+			(Some(pallet), Some(item)) => format!("{}::{}", pallet, item),
+			_ => "Unknown".into(),
+		}
+	}
+}
+
+impl fmt::Display for StorageEntry {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(fmt, "{}", self.name())?;
+		writeln!(fmt, " Key: 0x{}", HexDisplay::from(&*self.key))?;
+		for key in &self.keys {
+			write!(fmt, " Map key ({:?}, {}): 0x{}", key.hasher, key.key_type, HexDisplay::from(&*key.hash))?;
+			match (&key.decoded, &key.key) {
+				(Some(decoded), _) => writeln!(fmt, " = {}", decoded)?,
+				(None, Some(raw)) => writeln!(fmt, " = 0x{}", HexDisplay::from(&**raw))?,
+				(None, None) => writeln!(fmt)?,
+			}
+		}
+		write!(fmt, " Value")?;
+		if let Some(ref value_type) = self.value_type {
+			write!(fmt, " ({})", value_type)?;
+		}
+		match self.decoded_value {
+			Some(ref decoded) => writeln!(fmt, ": {}", decoded),
+			None => writeln!(fmt, ": 0x{}", HexDisplay::from(&*self.value)),
+		}
+	}
+}
+
+/// A single key of a map storage entry.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MapKey {
+	/// Hasher used for the key.
+	pub hasher: StorageHasher,
+	/// Type name of the key as declared in the metadata.
+	pub key_type: String,
+	/// Hash part of the key (empty for `Identity` hasher).
+	pub hash: Bytes,
+	/// The original key, if the hasher is transparent and the key could be extracted.
+	pub key: Option<Bytes>,
+	/// Decoded key, if the key type is a known primitive.
+	pub decoded: Option<String>,
+}
+
+impl MapKey {
+	/// Split a map key off the beginning of `input`.
+	///
+	/// If `last` is set the key is assumed to span the rest of the input, otherwise the key
+	/// length can only be determined if its type is a known primitive.
+	fn split(
+		hasher: &StorageHasher,
+		key_type: &DecodeDifferent<&'static str, String>,
+		input: &mut &[u8],
+		last: bool,
+	) -> Option<Self> {
+		let hash_len = match hasher {
+			StorageHasher::Blake2_128 | StorageHasher::Blake2_128Concat | StorageHasher::Twox128 => 16,
+			StorageHasher::Blake2_256 | StorageHasher::Twox256 => 32,
+			StorageHasher::Twox64Concat => 8,
+			StorageHasher::Identity => 0,
+		};
+		if input.len() < hash_len {
+			return None;
+		}
+
+		let key_type = type_name(key_type);
+		let hash = input[..hash_len].to_vec();
+		*input = &input[hash_len..];
+
+		let (key, decoded) = if !is_concat(hasher) {
+			(None, None)
+		} else if last {
+			let key = input.to_vec();
+			*input = &[];
+			let decoded = decode_exact(&key_type, &key);
+			(Some(key.into()), decoded)
+		} else {
+			let before = *input;
+			match decode_primitive(&key_type, input) {
+				Some(decoded) => {
+					let key = before[..before.len() - input.len()].to_vec();
+					(Some(key.into()), Some(decoded))
+				},
+				None => (None, None),
+			}
+		};
+
+		Some(MapKey { hasher: hasher.clone(), key_type, hash: hash.into(), key, decoded })
+	}
+}
+
+/// Difference of a single storage key between two blocks.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StorageDiff {
+	/// Key is only present in the second block.
+	Added(StorageEntry),
+	/// Key is only present in the first block.
+	Removed(StorageEntry),
+	/// Value of the key has changed.
+	Changed {
+		/// The entry in the first block.
+		from: StorageEntry,
+		/// The entry in the second block.
+		to: StorageEntry,
+	},
+}
+
+impl StorageDiff {
+	/// Compute differences between two sets of storage entries.
+	///
+	/// The result is ordered by key.
+	pub fn compute(
+		from: impl IntoIterator<Item=StorageEntry>,
+		to: impl IntoIterator<Item=StorageEntry>,
+	) -> Vec<Self> {
+		let mut from = from.into_iter().map(|e| (e.key.clone(), e)).collect::<BTreeMap<_, _>>();
+		let mut diff = BTreeMap::new();
+		for entry in to {
+			match from.remove(&entry.key) {
+				Some(old) if old.value == entry.value => {},
+				Some(old) => {
+					diff.insert(entry.key.clone(), StorageDiff::Changed { from: old, to: entry });
+				},
+				None => {
+					diff.insert(entry.key.clone(), StorageDiff::Added(entry));
+				},
+			}
+		}
+		diff.extend(from.into_iter().map(|(key, entry)| (key, StorageDiff::Removed(entry))));
+		diff.into_iter().map(|(_, diff)| diff).collect()
+	}
+}
+
+impl fmt::Display for StorageDiff {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			StorageDiff::Added(entry) => write!(fmt, "+ {}", entry),
+			StorageDiff::Removed(entry) => write!(fmt, "- {}", entry),
+			StorageDiff::Changed { from, to } => {
+				write!(fmt, "~ {}", to)?;
+				match from.decoded_value {
+					Some(ref decoded) => writeln!(fmt, " Previous value: {}", decoded),
+					None => writeln!(fmt, " Previous value: 0x{}", HexDisplay::from(&*from.value)),
+				}
+			},
+		}
+	}
+}
+
+fn decoded<B, O>(value: DecodeDifferent<B, O>) -> Result<O, String> {
+	match value {
+		DecodeDifferent::Decoded(value) => Ok(value),
+		DecodeDifferent::Encode(_) => Err("Runtime metadata is expected to be decoded".into()),
+	}
+}
+
+fn type_name(name: &DecodeDifferent<&'static str, String>) -> String {
+	match name {
+		DecodeDifferent::Encode(name) => name.to_string(),
+		DecodeDifferent::Decoded(name) => name.clone(),
+	}
+}
+
+fn is_concat(hasher: &StorageHasher) -> bool {
+	match hasher {
+		StorageHasher::Blake2_128Concat | StorageHasher::Twox64Concat | StorageHasher::Identity => true,
+		_ => false,
+	}
+}
+
+/// Decode the whole `data` as given type, if the type is a known primitive.
+fn decode_exact(type_name: &str, data: &[u8]) -> Option<String> {
+	let mut input = data;
+	decode_primitive(type_name, &mut input).filter(|_| input.is_empty())
+}
+
+/// Decode a known primitive type off the beginning of `input`.
+fn decode_primitive(type_name: &str, input: &mut &[u8]) -> Option<String> {
+	fn decode<T: Decode + fmt::Debug>(input: &mut &[u8]) -> Option<String> {
+		T::decode(input).ok().map(|value| format!("{:?}", value))
+	}
+
+	match type_name.trim() {
+		"bool" => decode::<bool>(input),
+		"u8" => decode::<u8>(input),
+		"u16" => decode::<u16>(input),
+		"u32" => decode::<u32>(input),
+		"u64" => decode::<u64>(input),
+		"u128" => decode::<u128>(input),
+		"i8" => decode::<i8>(input),
+		"i16" => decode::<i16>(input),
+		"i32" => decode::<i32>(input),
+		"i64" => decode::<i64>(input),
+		"i128" => decode::<i128>(input),
+		"Vec<u8>" => Vec::<u8>::decode(input).ok().map(|v| format!("0x{}", HexDisplay::from(&v))),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use frame_metadata::{
+		DecodeDifferent, ModuleMetadata, RuntimeMetadataV12, StorageEntryMetadata,
+		StorageEntryModifier, StorageMetadata, ExtrinsicMetadata, DefaultByteGetter,
+		DefaultByte, FnEncode, META_RESERVED,
+	};
+
+	struct NoDefault;
+	impl DefaultByte for NoDefault {
+		fn default_byte(&self) -> Vec<u8> {
+			vec![]
+		}
+	}
+
+	fn entry(name: &'static str, ty: StorageEntryType) -> StorageEntryMetadata {
+		StorageEntryMetadata {
+			name: DecodeDifferent::Encode(name),
+			modifier: StorageEntryModifier::Default,
+			ty,
+			default: DecodeDifferent::Encode(DefaultByteGetter(&NoDefault)),
+			documentation: DecodeDifferent::Encode(&[]),
+		}
+	}
+
+	fn metadata() -> RuntimeMetadataV12 {
+		let entries = vec![
+			entry("Value", StorageEntryType::Plain(DecodeDifferent::Encode("u32"))),
+			entry("Map", StorageEntryType::Map {
+				hasher: StorageHasher::Twox64Concat,
+				key: DecodeDifferent::Encode("u64"),
+				value: DecodeDifferent::Encode("T::Balance"),
+				unused: false,
+			}),
+			entry("DoubleMap", StorageEntryType::DoubleMap {
+				hasher: StorageHasher::Blake2_128Concat,
+				key1: DecodeDifferent::Encode("u32"),
+				key2: DecodeDifferent::Encode("T::AccountId"),
+				value: DecodeDifferent::Encode("bool"),
+				key2_hasher: StorageHasher::Blake2_128,
+			}),
+			entry("NMap", StorageEntryType::NMap {
+				keys: DecodeDifferent::Encode(&["u32", "T::AccountId", "u8"]),
+				hashers: DecodeDifferent::Encode(&[
+					StorageHasher::Twox64Concat,
+					StorageHasher::Blake2_128,
+					StorageHasher::Identity,
+				]),
+				value: DecodeDifferent::Encode("u64"),
+			}),
+		];
+		let storage = StorageMetadata {
+			prefix: DecodeDifferent::Encode("Test"),
+			entries: DecodeDifferent::Decoded(entries),
+		};
+		RuntimeMetadataV12 {
+			modules: DecodeDifferent::Decoded(vec![ModuleMetadata {
+				name: DecodeDifferent::Encode("Test"),
+				storage: Some(DecodeDifferent::Decoded(storage)),
+				calls: None,
+				event: None,
+				constants: DecodeDifferent::Encode(FnEncode(|| &[])),
+				errors: DecodeDifferent::Encode(FnEncode(|| &[])),
+			}]),
+			extrinsic: ExtrinsicMetadata { version: 4, signed_extensions: vec![] },
+		}
+	}
+
+	fn layout() -> StorageLayout {
+		let metadata: RuntimeMetadataPrefixed = metadata().into();
+		StorageLayout::from_metadata(&metadata.encode()).unwrap()
+	}
+
+	#[test]
+	fn should_decode_v11_metadata() {
+		let metadata = RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V11(metadata()));
+		let layout = StorageLayout::from_metadata(&metadata.encode()).unwrap();
+
+		let mut key = storage_prefix("Test", Some("Map"));
+		key.extend_from_slice(&[1; 8]);
+		key.extend_from_slice(&7u64.encode());
+		let map = layout.decode(&key, &[1, 2, 3]);
+		assert_eq!(map.name(), "Test::Map");
+		assert_eq!(map.keys[0].decoded.as_deref(), Some("7"));
+	}
+
+	#[test]
+	fn should_decode_plain_and_map_entries() {
+		let layout = layout();
+
+		let value = layout.decode(&storage_prefix("Test", Some("Value")), &5u32.encode());
+		assert_eq!(value.name(), "Test::Value");
+		assert_eq!(value.value_type.as_deref(), Some("u32"));
+		assert_eq!(value.decoded_value.as_deref(), Some("5"));
+
+		let mut key = storage_prefix("Test", Some("Map"));
+		key.extend_from_slice(&[1; 8]);
+		key.extend_from_slice(&7u64.encode());
+		let map = layout.decode(&key, &[1, 2, 3]);
+		assert_eq!(map.keys.len(), 1);
+		assert_eq!(map.keys[0].hash, Bytes(vec![1; 8]));
+		assert_eq!(map.keys[0].decoded.as_deref(), Some("7"));
+		assert_eq!(map.value_type.as_deref(), Some("T::Balance"));
+		assert_eq!(map.decoded_value, None);
+
+		let mut key = storage_prefix("Test", Some("DoubleMap"));
+		key.extend_from_slice(&[2; 16]);
+		key.extend_from_slice(&3u32.encode());
+		key.extend_from_slice(&[4; 16]);
+		let double_map = layout.decode(&key, &true.encode());
+		assert_eq!(double_map.keys.len(), 2);
+		assert_eq!(double_map.keys[0].decoded.as_deref(), Some("3"));
+		assert_eq!(double_map.keys[1].hash, Bytes(vec![4; 16]));
+		assert_eq!(double_map.keys[1].key, None);
+		assert_eq!(double_map.decoded_value.as_deref(), Some("true"));
+
+		let mut key = storage_prefix("Test", Some("NMap"));
+		key.extend_from_slice(&[5; 8]);
+		key.extend_from_slice(&6u32.encode());
+		key.extend_from_slice(&[7; 16]);
+		key.extend_from_slice(&8u8.encode());
+		let n_map = layout.decode(&key, &9u64.encode());
+		assert_eq!(n_map.keys.len(), 3);
+		assert_eq!(n_map.keys[0].decoded.as_deref(), Some("6"));
+		assert_eq!(n_map.keys[1].hash, Bytes(vec![7; 16]));
+		assert_eq!(n_map.keys[2].decoded.as_deref(), Some("8"));
+		assert_eq!(n_map.decoded_value.as_deref(), Some("9"));
+
+		let unknown = layout.decode(b":code", &[0]);
+		assert_eq!(unknown.name(), "Unknown");
+		assert!(unknown.keys.is_empty());
+	}
+
+	#[test]
+	fn should_compute_storage_diff() {
+		let layout = layout();
+		let key = |item| storage_prefix("Test", Some(item));
+
+		let from = vec![
+			layout.decode(&key("Value"), &1u32.encode()),
+			layout.decode(&key("Map"), &[1]),
+			layout.decode(b":code", &[0]),
+		];
+		let to = vec![
+			layout.decode(&key("Value"), &2u32.encode()),
+			layout.decode(b":code", &[0]),
+			layout.decode(&key("DoubleMap"), &[1]),
+		];
+
+		let diff = StorageDiff::compute(from.clone(), to.clone());
+		assert_eq!(diff.len(), 3);
+		assert!(diff.contains(&StorageDiff::Changed { from: from[0].clone(), to: to[0].clone() }));
+		assert!(diff.contains(&StorageDiff::Removed(from[1].clone())));
+		assert!(diff.contains(&StorageDiff::Added(to[2].clone())));
+	}
+}
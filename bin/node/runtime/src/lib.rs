@@ -27,7 +27,10 @@ use frame_support::{
 		Weight,
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 	},
-	traits::{Currency, Imbalance, KeyOwnerProofSystem, OnUnbalanced, Randomness, LockIdentifier},
+	traits::{
+		Currency, Imbalance, KeyOwnerProofSystem, OnUnbalanced, Randomness, LockIdentifier,
+		InstanceFilter, Filter,
+	},
 };
 use sp_core::{
 	crypto::KeyTypeId,
@@ -39,7 +42,7 @@ use node_primitives::{AccountIndex, Balance, BlockNumber, Hash, Index, Moment};
 use sp_api::impl_runtime_apis;
 use sp_runtime::{
 	Permill, Perbill, Perquintill, Percent, ApplyExtrinsicResult,
-	impl_opaque_keys, generic, create_runtime_str, ModuleId,
+	impl_opaque_keys, generic, create_runtime_str, ModuleId, RuntimeDebug,
 };
 use sp_runtime::curve::PiecewiseLinear;
 use sp_runtime::transaction_validity::{TransactionValidity, TransactionSource, TransactionPriority};
@@ -55,7 +58,9 @@ use pallet_grandpa::fg_primitives;
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
-use pallet_contracts_rpc_runtime_api::ContractExecResult;
+use pallet_contracts_rpc_runtime_api::{
+	ContractExecResult, ContractInstantiateResult, ContractResult,
+};
 use pallet_session::{historical as pallet_session_historical};
 use sp_inherents::{InherentData, CheckInherentsResult};
 
@@ -67,7 +72,7 @@ pub use frame_system::Call as SystemCall;
 pub use pallet_contracts::Gas;
 pub use frame_support::StorageValue;
 pub use pallet_staking::StakerStatus;
-use codec::Encode;
+use codec::{Encode, Decode};
 
 /// Implementations of some helper traits passed into runtime modules as associated types.
 pub mod impls;
@@ -90,10 +95,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 247,
+	spec_version: 258,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// Native version.
@@ -132,7 +137,22 @@ parameter_types! {
 	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
 }
 
+/// The runtime's base call filter, rejecting calls which have been paused through `Pause`.
+pub struct BaseFilter;
+impl Filter<Call> for BaseFilter {
+	fn filter(call: &Call) -> bool {
+		match call {
+			// Inherents, runtime upgrades and the technical committee, which may unpause calls,
+			// can never be paused.
+			Call::System(_) | Call::Timestamp(_) | Call::Authorship(_) | Call::FinalityTracker(_) |
+			Call::TechnicalCommittee(_) => true,
+			_ => pallet_pause::PausedCallFilter::<Runtime>::filter(call),
+		}
+	}
+}
+
 impl frame_system::Trait for Runtime {
+	type BaseCallFilter = BaseFilter;
 	type Origin = Origin;
 	type Call = Call;
 	type Index = Index;
@@ -174,6 +194,116 @@ impl pallet_utility::Trait for Runtime {
 	type MaxSignatories = MaxSignatories;
 }
 
+parameter_types! {
+	// One storage item; key size 32, value size 8.
+	pub const ProxyDepositBase: Balance = 1 * DOLLARS;
+	// Additional storage item size of 33 bytes.
+	pub const ProxyDepositFactor: Balance = 5 * CENTS;
+	pub const MaxProxies: u16 = 32;
+	// One storage item; key size 32, value size 16.
+	pub const AnnouncementDepositBase: Balance = 1 * DOLLARS;
+	// Additional storage item size of 68 bytes.
+	pub const AnnouncementDepositFactor: Balance = 10 * CENTS;
+	pub const MaxPending: u32 = 32;
+}
+
+/// The type used to represent the kinds of proxying allowed.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug)]
+pub enum ProxyType {
+	Any,
+	NonTransfer,
+	Governance,
+	Staking,
+}
+impl Default for ProxyType { fn default() -> Self { Self::Any } }
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			// Only calls which cannot move funds, directly or by dispatching further calls, are
+			// allowed.
+			ProxyType::NonTransfer => matches!(c,
+				Call::System(..) |
+				Call::Babe(..) |
+				Call::Timestamp(..) |
+				Call::Indices(pallet_indices::Call::claim(..)) |
+				Call::Indices(pallet_indices::Call::free(..)) |
+				// Specifically omitting Indices `transfer`, `force_transfer`
+				// Specifically omitting the entire Balances pallet
+				Call::Authorship(..) |
+				Call::Staking(..) |
+				Call::Session(..) |
+				Call::Democracy(..) |
+				Call::Council(..) |
+				Call::TechnicalCommittee(..) |
+				Call::Elections(..) |
+				Call::TechnicalMembership(..) |
+				Call::FinalityTracker(..) |
+				Call::Grandpa(..) |
+				Call::Treasury(..) |
+				Call::Bounties(..) |
+				Call::ImOnline(..) |
+				Call::AuthorityDiscovery(..) |
+				Call::Offences(..) |
+				Call::Identity(..) |
+				Call::Society(..) |
+				// Specifically omitting the entire Recovery pallet
+				Call::Vesting(pallet_vesting::Call::vest(..)) |
+				Call::Vesting(pallet_vesting::Call::vest_other(..)) |
+				Call::Vesting(pallet_vesting::Call::merge_schedules(..)) |
+				// Specifically omitting Vesting `vested_transfer`, `force_vested_transfer`
+				Call::Scheduler(..) |
+				Call::Proxy(pallet_proxy::Call::add_proxy(..)) |
+				Call::Proxy(pallet_proxy::Call::remove_proxy(..)) |
+				Call::Proxy(pallet_proxy::Call::remove_proxies(..)) |
+				Call::Proxy(pallet_proxy::Call::anonymous(..)) |
+				Call::Proxy(pallet_proxy::Call::kill_anonymous(..)) |
+				Call::Proxy(pallet_proxy::Call::announce(..)) |
+				Call::Proxy(pallet_proxy::Call::remove_announcement(..)) |
+				Call::Proxy(pallet_proxy::Call::reject_announcement(..))
+				// Specifically omitting Proxy `proxy`, `proxy_announced`
+			),
+			ProxyType::Governance => matches!(c,
+				Call::Democracy(..) |
+				Call::Council(..) |
+				Call::TechnicalCommittee(..) |
+				Call::Elections(..) |
+				Call::Treasury(..) |
+				Call::Bounties(..)
+			),
+			ProxyType::Staking => matches!(c, Call::Staking(..)),
+		}
+	}
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			(ProxyType::NonTransfer, _) => true,
+			_ => false,
+		}
+	}
+}
+
+impl pallet_pause::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type PauseOrigin = pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, TechnicalCollective>;
+}
+
+impl pallet_proxy::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type MaxPending = MaxPending;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
 parameter_types! {
 	pub const MaximumWeight: Weight = 2_000_000;
 }
@@ -466,6 +596,28 @@ impl pallet_treasury::Trait for Runtime {
 	type SpendPeriod = SpendPeriod;
 	type Burn = Burn;
 	type ModuleId = TreasuryModuleId;
+	type SpendFunds = Bounties;
+}
+
+parameter_types! {
+	pub const DataDepositPerByte: Balance = 1 * CENTS;
+	pub const BountyDepositBase: Balance = 1 * DOLLARS;
+	pub const BountyDepositPayoutDelay: BlockNumber = 8 * DAYS;
+	pub const BountyUpdatePeriod: BlockNumber = 90 * DAYS;
+	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
+	pub const BountyValueMinimum: Balance = 10 * DOLLARS;
+	pub const MaximumReasonLength: u32 = 16384;
+}
+
+impl pallet_bounties::Trait for Runtime {
+	type Event = Event;
+	type BountyDepositBase = BountyDepositBase;
+	type DataDepositPerByte = DataDepositPerByte;
+	type BountyDepositPayoutDelay = BountyDepositPayoutDelay;
+	type BountyUpdatePeriod = BountyUpdatePeriod;
+	type BountyCuratorDeposit = BountyCuratorDeposit;
+	type BountyValueMinimum = BountyValueMinimum;
+	type MaximumReasonLength = MaximumReasonLength;
 }
 
 parameter_types! {
@@ -473,6 +625,11 @@ parameter_types! {
 	pub const RentByteFee: Balance = 1 * DOLLARS;
 	pub const RentDepositOffset: Balance = 1000 * DOLLARS;
 	pub const SurchargeReward: Balance = 150 * DOLLARS;
+	pub const ContractsStorageModel: pallet_contracts::StorageModel =
+		pallet_contracts::StorageModel::Rent;
+	pub const DepositPerByte: Balance = 1 * CENTS;
+	pub const DepositPerItem: Balance = 1 * DOLLARS;
+	pub const CodeDepositPerByte: Balance = 1 * CENTS;
 }
 
 impl pallet_contracts::Trait for Runtime {
@@ -491,6 +648,11 @@ impl pallet_contracts::Trait for Runtime {
 	type SurchargeReward = SurchargeReward;
 	type MaxDepth = pallet_contracts::DefaultMaxDepth;
 	type MaxValueSize = pallet_contracts::DefaultMaxValueSize;
+	type ChainExtension = ();
+	type StorageModel = ContractsStorageModel;
+	type DepositPerByte = DepositPerByte;
+	type DepositPerItem = DepositPerItem;
+	type CodeDepositPerByte = CodeDepositPerByte;
 }
 
 impl pallet_sudo::Trait for Runtime {
@@ -678,6 +840,7 @@ impl pallet_society::Trait for Runtime {
 
 parameter_types! {
 	pub const MinVestedTransfer: Balance = 100 * DOLLARS;
+	pub const MaxVestingSchedules: u32 = 28;
 }
 
 impl pallet_vesting::Trait for Runtime {
@@ -685,6 +848,39 @@ impl pallet_vesting::Trait for Runtime {
 	type Currency = Balances;
 	type BlockNumberToBalance = ConvertInto;
 	type MinVestedTransfer = MinVestedTransfer;
+	type MaxVestingSchedules = MaxVestingSchedules;
+}
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 100 * DOLLARS;
+	pub const AssetApprovalDeposit: Balance = 1 * DOLLARS;
+	pub const MetadataDepositBase: Balance = 10 * DOLLARS;
+	pub const MetadataDepositPerByte: Balance = 1 * DOLLARS;
+	pub const AssetStringLimit: u32 = 50;
+}
+
+impl pallet_assets::Trait for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type StringLimit = AssetStringLimit;
+	type ApprovalDeposit = AssetApprovalDeposit;
+}
+
+parameter_types! {
+	pub const GenericAssetApprovalDeposit: Balance = 1;
+}
+
+impl pallet_generic_asset::Trait for Runtime {
+	type Balance = Balance;
+	type AssetId = u32;
+	type Event = Event;
+	type ApprovalDeposit = GenericAssetApprovalDeposit;
 }
 
 construct_runtime!(
@@ -723,6 +919,11 @@ construct_runtime!(
 		Recovery: pallet_recovery::{Module, Call, Storage, Event<T>},
 		Vesting: pallet_vesting::{Module, Call, Storage, Event<T>, Config<T>},
 		Scheduler: pallet_scheduler::{Module, Call, Storage, Event<T>},
+		Proxy: pallet_proxy::{Module, Call, Storage, Event<T>},
+		Pause: pallet_pause::{Module, Call, Storage, Event},
+		Assets: pallet_assets::{Module, Call, Storage, Event<T>},
+		GenericAsset: pallet_generic_asset::{Module, Call, Storage, Event<T>, Config<T>},
+		Bounties: pallet_bounties::{Module, Call, Storage, Event<T>},
 	}
 );
 
@@ -818,6 +1019,10 @@ impl_runtime_apis! {
 			Grandpa::grandpa_authorities()
 		}
 
+		fn current_set_id() -> fg_primitives::SetId {
+			Grandpa::current_set_id()
+		}
+
 		fn submit_report_equivocation_extrinsic(
 			equivocation_proof: fg_primitives::EquivocationProof<
 				<Block as BlockT>::Hash,
@@ -865,6 +1070,14 @@ impl_runtime_apis! {
 		fn current_epoch_start() -> sp_consensus_babe::SlotNumber {
 			Babe::current_epoch_start()
 		}
+
+		fn current_epoch() -> sp_consensus_babe::Epoch {
+			Babe::current_epoch()
+		}
+
+		fn next_epoch() -> sp_consensus_babe::Epoch {
+			Babe::next_epoch()
+		}
 	}
 
 	impl sp_authority_discovery::AuthorityDiscoveryApi<Block> for Runtime {
@@ -888,15 +1101,44 @@ impl_runtime_apis! {
 			value: Balance,
 			gas_limit: u64,
 			input_data: Vec<u8>,
-		) -> ContractExecResult {
+		) -> ContractResult<ContractExecResult> {
+			let exec_result =
+				Contracts::bare_call(origin, dest.into(), value, gas_limit, input_data, true);
+			ContractResult {
+				gas_consumed: exec_result.gas_consumed,
+				events: exec_result.events,
+				debug_message: exec_result.debug_message,
+				result: match exec_result.result {
+					Ok(v) => ContractExecResult::Success {
+						status: v.status,
+						data: v.data,
+					},
+					Err(_) => ContractExecResult::Error,
+				},
+			}
+		}
+
+		fn instantiate(
+			origin: AccountId,
+			endowment: Balance,
+			gas_limit: u64,
+			code_hash: <Block as BlockT>::Hash,
+			data: Vec<u8>,
+		) -> ContractResult<ContractInstantiateResult<AccountId>> {
 			let exec_result =
-				Contracts::bare_call(origin, dest.into(), value, gas_limit, input_data);
-			match exec_result {
-				Ok(v) => ContractExecResult::Success {
-					status: v.status,
-					data: v.data,
+				Contracts::bare_instantiate(origin, endowment, gas_limit, code_hash, data, true);
+			ContractResult {
+				gas_consumed: exec_result.gas_consumed,
+				events: exec_result.events,
+				debug_message: exec_result.debug_message,
+				result: match exec_result.result {
+					Ok((address, v)) => ContractInstantiateResult::Success {
+						status: v.status,
+						data: v.data,
+						address,
+					},
+					Err(_) => ContractInstantiateResult::Error,
 				},
-				Err(_) => ContractExecResult::Error,
 			}
 		}
 
@@ -924,6 +1166,18 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_assets_rpc_runtime_api::AssetsApi<Block, u32, AccountId, Balance> for Runtime {
+		fn allowance(id: u32, owner: AccountId, delegate: AccountId) -> Balance {
+			Assets::allowance(id, &owner, &delegate)
+		}
+	}
+
+	impl pallet_generic_asset_rpc_runtime_api::GenericAssetApi<Block, u32, AccountId, Balance> for Runtime {
+		fn allowance(id: u32, owner: AccountId, delegate: AccountId) -> Balance {
+			GenericAsset::allowance(&id, &owner, &delegate)
+		}
+	}
+
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
 			SessionKeys::generate(seed)
@@ -962,11 +1216,13 @@ impl_runtime_apis! {
 			let params = (&pallet, &benchmark, &lowest_range_values, &highest_range_values, &steps, repeat);
 
 			add_benchmark!(params, batches, b"balances", Balances);
+			add_benchmark!(params, batches, b"bounties", Bounties);
 			add_benchmark!(params, batches, b"collective", Council);
 			add_benchmark!(params, batches, b"democracy", Democracy);
 			add_benchmark!(params, batches, b"identity", Identity);
 			add_benchmark!(params, batches, b"im-online", ImOnline);
 			add_benchmark!(params, batches, b"offences", OffencesBench::<Runtime>);
+			add_benchmark!(params, batches, b"proxy", Proxy);
 			add_benchmark!(params, batches, b"scheduler", Scheduler);
 			add_benchmark!(params, batches, b"session", SessionBench::<Runtime>);
 			add_benchmark!(params, batches, b"staking", Staking);
@@ -980,6 +1236,13 @@ impl_runtime_apis! {
 			Ok(batches)
 		}
 	}
+
+	#[cfg(feature = "try-runtime")]
+	impl frame_try_runtime::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade() -> Weight {
+			Executive::execute_on_runtime_upgrade()
+		}
+	}
 }
 
 #[cfg(test)]
@@ -995,4 +1258,50 @@ mod tests {
 
 		is_submit_signed_transaction::<Runtime>();
 	}
+
+	#[test]
+	fn non_transfer_proxy_filters_recovery_calls() {
+		let who = AccountId::from([1u8; 32]);
+		let calls = vec![
+			Call::Recovery(pallet_recovery::Call::create_recovery(vec![who.clone()], 1, 10)),
+			Call::Recovery(pallet_recovery::Call::initiate_recovery(who.clone())),
+			Call::Recovery(pallet_recovery::Call::vouch_recovery(who.clone(), who.clone())),
+			Call::Recovery(pallet_recovery::Call::as_recovered(
+				who.clone(),
+				Box::new(Call::System(frame_system::Call::remark(vec![]))),
+			)),
+			Call::Balances(pallet_balances::Call::transfer(who.clone().into(), 1)),
+			Call::Proxy(pallet_proxy::Call::proxy(
+				who.clone(),
+				None,
+				Box::new(Call::System(frame_system::Call::remark(vec![]))),
+			)),
+		];
+		for call in calls {
+			assert!(!ProxyType::NonTransfer.filter(&call));
+			assert!(ProxyType::Any.filter(&call));
+		}
+
+		assert!(ProxyType::NonTransfer.filter(&Call::System(frame_system::Call::remark(vec![]))));
+		assert!(ProxyType::NonTransfer.filter(&Call::Vesting(pallet_vesting::Call::vest())));
+	}
+
+	#[test]
+	fn runtime_upgrade_migrates_vesting_schedules() {
+		use frame_support::storage::{StorageMap, unhashed};
+
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			let who = AccountId::from([1u8; 32]);
+			let schedule = pallet_vesting::VestingInfo {
+				locked: 100 * DOLLARS,
+				per_block: DOLLARS,
+				starting_block: 10,
+			};
+			unhashed::put(&pallet_vesting::Vesting::<Runtime>::hashed_key_for(&who), &schedule);
+
+			Executive::execute_on_runtime_upgrade();
+
+			assert_eq!(Vesting::vesting(&who), Some(vec![schedule]));
+		});
+	}
 }
@@ -218,6 +218,7 @@ impl BenchDb {
 			state_cache_size: 16*1024*1024,
 			state_cache_child_ratio: Some((0, 100)),
 			pruning: PruningMode::ArchiveAll,
+			keep_blocks: sc_client_db::KeepBlocks::All,
 			source: sc_client_db::DatabaseSettingsSrc::RocksDb {
 				path: dir.into(),
 				cache_size: 512,
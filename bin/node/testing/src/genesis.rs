@@ -20,7 +20,7 @@ use crate::keyring::*;
 use sp_keyring::{Ed25519Keyring, Sr25519Keyring};
 use node_runtime::{
 	GenesisConfig, BalancesConfig, SessionConfig, StakingConfig, SystemConfig,
-	GrandpaConfig, IndicesConfig, ContractsConfig, SocietyConfig, WASM_BINARY,
+	GrandpaConfig, IndicesConfig, ContractsConfig, SocietyConfig, GenericAssetConfig, WASM_BINARY,
 	AccountId,
 };
 use node_runtime::constants::currency::*;
@@ -117,5 +117,13 @@ pub fn config_endowed(
 			max_members: 999,
 		}),
 		pallet_vesting: Some(Default::default()),
+		pallet_generic_asset: Some(GenericAssetConfig {
+			assets: vec![],
+			initial_balance: 0,
+			endowed_accounts: vec![],
+			next_asset_id: 1,
+			staking_asset_id: 0,
+			spending_asset_id: 0,
+		}),
 	}
 }
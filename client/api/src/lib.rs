@@ -37,7 +37,9 @@ pub use light::*;
 pub use notifications::*;
 pub use proof_provider::*;
 
-pub use sp_state_machine::{StorageProof, ExecutionStrategy, CloneableSpawn};
+pub use sp_state_machine::{
+	StorageProof, ExecutionStrategy, CloneableSpawn, KeyValueStates, KeyValueStorageLevel,
+};
 
 /// Usage Information Provider interface
 ///
@@ -18,7 +18,7 @@ use sp_runtime::{
 	generic::BlockId,
 	traits::{Block as BlockT},
 };
-use crate::{StorageProof, ChangesProof};
+use crate::{StorageProof, ChangesProof, KeyValueStates};
 use sp_storage::{ChildInfo, StorageKey, PrefixedStorageKey};
 
 /// Interface for providing block proving utilities.
@@ -67,4 +67,27 @@ pub trait ProofProvider<Block: BlockT> {
 		storage_key: Option<&PrefixedStorageKey>,
 		key: &StorageKey,
 	) -> sp_blockchain::Result<ChangesProof<Block::Header>>;
+
+	/// Given a block id and a start key, returns a proof of the key/value pairs of the state
+	/// following `start_key`, including the content of child tries, until the accumulated
+	/// size of the proven entries reaches `size_limit`. Also returns the number of entries.
+	///
+	/// `start_key` is empty to start at the beginning of the state, contains the last read top
+	/// trie key, or the prefixed storage key of a child trie followed by the last read key of
+	/// that child trie.
+	fn read_proof_collection(
+		&self,
+		id: &BlockId<Block>,
+		start_key: &[Vec<u8>],
+		size_limit: usize,
+	) -> sp_blockchain::Result<(StorageProof, u32)>;
+
+	/// Verify a proof generated by `read_proof_collection` against the given state root,
+	/// returning the proven key/value pairs.
+	fn verify_range_proof(
+		&self,
+		root: Block::Hash,
+		proof: StorageProof,
+		start_key: &[Vec<u8>],
+	) -> sp_blockchain::Result<KeyValueStates>;
 }
@@ -0,0 +1,78 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	CliConfiguration, error, params::{PruningParams, SharedParams},
+};
+use log::info;
+use sc_service::{Configuration, GenericChainSpec};
+use sp_runtime::{BuildStorage, traits::Block as BlockT};
+use std::{fmt::Debug, fs, path::PathBuf};
+use structopt::StructOpt;
+
+/// Chain spec with raw state, whose extensions are not needed to import it.
+type RawStateSpec = GenericChainSpec<(), serde_json::Map<String, serde_json::Value>>;
+
+/// The `import-state` command used to initialize the database of a node with
+/// the state of a block exported by `export-state`.
+#[derive(Debug, StructOpt, Clone)]
+pub struct ImportStateCmd {
+	/// Chain spec with the raw state of the block, as generated by `export-state`.
+	#[structopt(parse(from_os_str))]
+	pub input: PathBuf,
+
+	/// File with the JSON encoded header of the block, as returned by `chain_getHeader`.
+	#[structopt(long = "header", value_name = "PATH", parse(from_os_str))]
+	pub header: PathBuf,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+}
+
+impl ImportStateCmd {
+	/// Run the `import-state` command
+	pub fn run<BB>(&self, config: Configuration) -> error::Result<()>
+	where
+		BB: BlockT + Debug,
+	{
+		let header: BB::Header = serde_json::from_reader(fs::File::open(&self.header)?)
+			.map_err(|e| format!("Error parsing block header: {}", e))?;
+
+		info!("Reading raw state...");
+		let input_spec = RawStateSpec::from_json_file(self.input.clone())?;
+		let raw_state = input_spec.build_storage()?;
+
+		sc_service::chain_ops::import_state::<BB>(&config, header, raw_state)?;
+		info!("State imported, the node can now be started from this block.");
+
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ImportStateCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+}
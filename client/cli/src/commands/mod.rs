@@ -19,6 +19,7 @@ mod check_block_cmd;
 mod export_blocks_cmd;
 mod export_state_cmd;
 mod import_blocks_cmd;
+mod import_state_cmd;
 mod purge_chain_cmd;
 mod revert_cmd;
 mod run_cmd;
@@ -31,6 +32,7 @@ pub use self::purge_chain_cmd::PurgeChainCmd;
 pub use self::revert_cmd::RevertCmd;
 pub use self::run_cmd::RunCmd;
 pub use self::export_state_cmd::ExportStateCmd;
+pub use self::import_state_cmd::ImportStateCmd;
 use std::fmt::Debug;
 use structopt::StructOpt;
 
@@ -61,6 +63,9 @@ pub enum Subcommand {
 
 	/// Export state as raw chain spec.
 	ExportState(ExportStateCmd),
+
+	/// Initialize the database with the state of a block exported by `export-state`.
+	ImportState(ImportStateCmd),
 }
 
 // TODO: move to config.rs?
@@ -253,6 +258,12 @@ macro_rules! substrate_cli_subcommands {
 				}
 			}
 
+			fn keep_blocks(&self) -> $crate::Result<::sc_service::config::KeepBlocks> {
+				match self {
+					$($enum::$variant(cmd) => cmd.keep_blocks()),*
+				}
+			}
+
 			fn chain_id(&self, is_dev: bool) -> $crate::Result<String> {
 				match self {
 					$($enum::$variant(cmd) => cmd.chain_id(is_dev)),*
@@ -406,6 +417,7 @@ macro_rules! substrate_cli_subcommands {
 }
 
 substrate_cli_subcommands!(
-	Subcommand => BuildSpec, ExportBlocks, ImportBlocks, CheckBlock, Revert, PurgeChain, ExportState
+	Subcommand => BuildSpec, ExportBlocks, ImportBlocks, CheckBlock, Revert, PurgeChain, ExportState,
+	ImportState
 );
 
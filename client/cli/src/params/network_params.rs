@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
+use crate::arg_enums::SyncMode;
 use crate::params::node_key_params::NodeKeyParams;
 use sc_network::{
 	config::{NetworkConfiguration, NodeKeyConfig, NonReservedPeerMode, TransportConfig},
@@ -105,6 +106,22 @@ pub struct NetworkParams {
 	/// This option will be removed in the future.
 	#[structopt(long)]
 	pub legacy_network_protocol: bool,
+
+	/// Blockchain syncing mode.
+	///
+	/// - `Full`: Download and validate the full blockchain history.
+	///
+	/// - `Warp`: Download the latest finalized state along with a proof of finality, then
+	///   continue with the blocks that follow. Only applies to a node started with an empty
+	///   database.
+	#[structopt(
+		long = "sync",
+		value_name = "SYNC_MODE",
+		possible_values = &SyncMode::variants(),
+		case_insensitive = true,
+		default_value = "Full"
+	)]
+	pub sync: SyncMode,
 }
 
 impl NetworkParams {
@@ -161,6 +178,7 @@ impl NetworkParams {
 			max_parallel_downloads: self.max_parallel_downloads,
 			allow_non_globals_in_dht: self.discover_local || is_dev,
 			use_new_block_requests_protocol: !self.legacy_network_protocol,
+			sync_mode: self.sync.into(),
 		}
 	}
 }
@@ -15,7 +15,7 @@
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::error;
-use sc_service::{PruningMode, Role};
+use sc_service::{KeepBlocks, PruningMode, Role};
 use structopt::StructOpt;
 
 /// Parameters to define the pruning mode
@@ -28,6 +28,11 @@ pub struct PruningParams {
 	/// 256 blocks.
 	#[structopt(long = "pruning", value_name = "PRUNING_MODE")]
 	pub pruning: Option<String>,
+	/// Specify the number of finalized blocks whose bodies are kept in the database.
+	///
+	/// Default is to keep all block bodies. Headers and justifications are always kept.
+	#[structopt(long = "keep-blocks", value_name = "COUNT")]
+	pub keep_blocks: Option<u32>,
 }
 
 impl PruningParams {
@@ -56,4 +61,14 @@ impl PruningParams {
 			}
 		})
 	}
+	/// Get the block body pruning value from the parameters
+	pub fn keep_blocks(&self) -> error::Result<KeepBlocks> {
+		Ok(match self.keep_blocks {
+			Some(0) => return Err(error::Error::Input(
+				"The number of blocks to keep must be greater than zero".to_string()
+			)),
+			Some(n) => KeepBlocks::Some(n),
+			None => KeepBlocks::All,
+		})
+	}
 }
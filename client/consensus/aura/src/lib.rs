@@ -758,6 +758,12 @@ impl<Block: BlockT, C, I, P> BlockImport<Block> for AuraBlockImport<Block, C, I,
 		block: BlockImportParams<Block, Self::Transaction>,
 		new_cache: HashMap<CacheKeyId, Vec<u8>>,
 	) -> Result<ImportResult, Self::Error> {
+		if block.import_state.is_some() {
+			// the parent of a block imported along with its state is not available,
+			// its finality has been proven instead.
+			return self.inner.import_block(block, new_cache).map_err(Into::into);
+		}
+
 		let hash = block.post_hash();
 		let slot_number = find_pre_digest::<Block, P>(&block.header)
 			.expect("valid Aura headers must contain a predigest; \
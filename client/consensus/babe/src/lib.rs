@@ -941,7 +941,7 @@ impl<Block, Client, Inner> BlockImport<Block> for BabeBlockImport<Block, Client,
 	Inner::Error: Into<ConsensusError>,
 	Client: HeaderBackend<Block> + HeaderMetadata<Block, Error = sp_blockchain::Error>
 		+ AuxStore + ProvideRuntimeApi<Block> + ProvideCache<Block> + Send + Sync,
-	Client::Api: BabeApi<Block> + ApiExt<Block>,
+	Client::Api: BabeApi<Block, Error = sp_blockchain::Error> + ApiExt<Block>,
 {
 	type Error = ConsensusError;
 	type Transaction = sp_api::TransactionFor<Client, Block>;
@@ -962,6 +962,23 @@ impl<Block, Client, Inner> BlockImport<Block> for BabeBlockImport<Block, Client,
 			Err(e) => return Err(ConsensusError::ClientImport(e.to_string())),
 		}
 
+		// the epoch data of a block with imported state can't be derived without its
+		// ancestors, so it is read from the runtime once the state is imported.
+		if block.import_state.is_some() {
+			let mut epoch_changes = self.epoch_changes.lock();
+			let import_result = self.inner.import_block(block, new_cache).map_err(Into::into)?;
+
+			reset_epoch_changes_at(
+				&*self.client,
+				&self.config,
+				&mut epoch_changes,
+				hash,
+				number,
+			).map_err(|e| ConsensusError::ClientImport(e.to_string()))?;
+
+			return Ok(import_result);
+		}
+
 		let pre_digest = find_pre_digest::<Block>(&block.header)
 			.expect("valid babe headers must contain a predigest; \
 					 header has been already verified; qed");
@@ -1219,6 +1236,70 @@ fn prune_finalized<Block, Client>(
 	Ok(())
 }
 
+/// Reset the epoch changes to the current and next epoch of the runtime at the given
+/// block and persist them, together with an initial chain weight for the block.
+///
+/// Used when the chain continues from the imported state of the block, since the epoch
+/// changes signaled by its ancestors can't be derived without them.
+fn reset_epoch_changes_at<Block, Client>(
+	client: &Client,
+	config: &Config,
+	epoch_changes: &mut EpochChangesFor<Block, Epoch>,
+	hash: Block::Hash,
+	number: NumberFor<Block>,
+) -> ClientResult<()> where
+	Block: BlockT,
+	Client: AuxStore + ProvideRuntimeApi<Block>,
+	Client::Api: BabeApi<Block, Error = sp_blockchain::Error>,
+{
+	let at = BlockId::Hash(hash);
+	let runtime_api = client.runtime_api();
+	let has_epochs = runtime_api.has_api_with::<dyn BabeApi<Block, Error = sp_blockchain::Error>, _>(
+		&at, |v| v >= 3,
+	)?;
+	if !has_epochs {
+		return Err(ClientError::VersionInvalid(
+			"BabeApi of the runtime doesn't provide the epochs needed to continue \
+			from imported state".to_string()
+		));
+	}
+
+	let epoch_config = BabeEpochConfiguration {
+		c: config.c,
+		allowed_slots: config.allowed_slots,
+	};
+	let to_epoch = |epoch: sp_consensus_babe::Epoch| Epoch {
+		epoch_index: epoch.epoch_index,
+		start_slot: epoch.start_slot,
+		duration: epoch.duration,
+		authorities: epoch.authorities,
+		randomness: epoch.randomness,
+		config: epoch_config.clone(),
+	};
+	let current_epoch = to_epoch(runtime_api.current_epoch(&at)?);
+	let next_epoch = to_epoch(runtime_api.next_epoch(&at)?);
+
+	info!(target: "babe",
+		"👶 Starting from epoch {} at imported block {}, next epoch starts at slot {}",
+		current_epoch.epoch_index,
+		hash,
+		next_epoch.start_slot,
+	);
+
+	epoch_changes.reset(hash, number, current_epoch, next_epoch);
+
+	aux_schema::write_epoch_changes::<Block, _, _>(
+		&*epoch_changes,
+		|insert| client.insert_aux(insert, &[]),
+	)?;
+	// the weight of the ancestors is unknown, but all chains that are still
+	// relevant descend from this block.
+	aux_schema::write_block_weight(hash, &0, |values| {
+		let values = values.iter().map(|(k, v)| (&k[..], *v)).collect::<Vec<_>>();
+		client.insert_aux(&values, &[])
+	})
+}
+
 /// Produce a BABE block-import object to be used later on in the construction of
 /// an import-queue.
 ///
@@ -1229,9 +1310,27 @@ pub fn block_import<Client, Block: BlockT, I>(
 	wrapped_block_import: I,
 	client: Arc<Client>,
 ) -> ClientResult<(BabeBlockImport<Block, Client, I>, BabeLink<Block>)> where
-	Client: AuxStore + HeaderBackend<Block> + HeaderMetadata<Block, Error = sp_blockchain::Error>,
+	Client: AuxStore + HeaderBackend<Block> + HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ ProvideRuntimeApi<Block>,
+	Client::Api: BabeApi<Block, Error = sp_blockchain::Error>,
 {
 	let epoch_changes = aux_schema::load_epoch_changes::<Block, _>(&*client, &config)?;
+
+	// a chain with finalized blocks but no epoch changes continues from imported state.
+	{
+		let mut epoch_changes = epoch_changes.lock();
+		let info = client.info();
+		if epoch_changes.tree().roots().next().is_none() && !info.finalized_number.is_zero() {
+			reset_epoch_changes_at(
+				&*client,
+				&config,
+				&mut epoch_changes,
+				info.finalized_hash,
+				info.finalized_number,
+			)?;
+		}
+	}
+
 	let link = BabeLink {
 		epoch_changes: epoch_changes.clone(),
 		time_source: Default::default(),
@@ -636,6 +636,23 @@ impl<Hash, Number, E: Epoch> EpochChanges<Hash, Number, E> where
 		}
 	}
 
+	/// Reset the epoch changes to the given current and next epoch, both signaled
+	/// at the given block.
+	///
+	/// This is used when the chain continues from a block whose state was imported,
+	/// as the epoch changes signaled by its ancestors are unknown.
+	pub fn reset(&mut self, hash: Hash, number: Number, current: E, next: E) {
+		let epoch = PersistedEpoch::Genesis(current, next);
+		let header = PersistedEpochHeader::<E>::from(&epoch);
+
+		*self = Self::new();
+		// the tree is empty, so there are no other nodes to check the ancestry against.
+		let is_descendent_of = |_: &Hash, _: &Hash| Ok::<_, std::io::Error>(false);
+		self.inner.import(hash, number, header, &is_descendent_of)
+			.expect("importing into an empty fork tree can't fail; qed");
+		self.epochs.insert((hash, number), epoch);
+	}
+
 	/// Return the inner fork tree.
 	pub fn tree(&self) -> &ForkTree<Hash, Number, PersistedEpochHeader<E>> {
 		&self.inner
@@ -967,4 +984,54 @@ mod tests {
 			assert!(epoch_for_x_child_before_genesis.is_none());
 		}
 	}
+
+	#[test]
+	fn reset_starts_epoch_changes_at_given_block() {
+		let is_descendent_of = |_: &Hash, _: &Hash| -> Result<bool, TestError> { Ok(false) };
+		let make_genesis = |slot| Epoch {
+			start_slot: slot,
+			duration: 100,
+		};
+
+		let mut epoch_changes = EpochChanges::<_, _, Epoch>::new();
+		let genesis_epoch = epoch_changes.epoch_descriptor_for_child_of(
+			&is_descendent_of,
+			b"0",
+			0,
+			100,
+		).unwrap().unwrap();
+		let incremented_epoch = epoch_changes
+			.viable_epoch(&genesis_epoch, &make_genesis)
+			.unwrap()
+			.increment(());
+		epoch_changes.import(&is_descendent_of, *b"A", 1, *b"0", incremented_epoch).unwrap();
+
+		epoch_changes.reset(
+			*b"I",
+			50,
+			Epoch { start_slot: 1000, duration: 10 },
+			Epoch { start_slot: 1010, duration: 10 },
+		);
+
+		let roots = epoch_changes.tree().roots().map(|(h, n, _)| (*h, *n)).collect::<Vec<_>>();
+		assert_eq!(roots, vec![(*b"I", 50)]);
+
+		let current_epoch = epoch_changes.epoch_data_for_child_of(
+			&is_descendent_of,
+			b"I",
+			50,
+			1005,
+			&make_genesis,
+		).unwrap().unwrap();
+		assert_eq!(current_epoch, Epoch { start_slot: 1000, duration: 10 });
+
+		let next_epoch = epoch_changes.epoch_data_for_child_of(
+			&is_descendent_of,
+			b"I",
+			50,
+			1012,
+			&make_genesis,
+		).unwrap().unwrap();
+		assert_eq!(next_epoch, Epoch { start_slot: 1010, duration: 10 });
+	}
 }
@@ -46,6 +46,7 @@ mod parity_db;
 mod subdb;
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::path::{Path, PathBuf};
 use std::io;
 use std::collections::HashMap;
@@ -267,10 +268,21 @@ pub struct DatabaseSettings {
 	pub state_cache_child_ratio: Option<(usize, usize)>,
 	/// Pruning mode.
 	pub pruning: PruningMode,
+	/// Block body pruning mode.
+	pub keep_blocks: KeepBlocks,
 	/// Where to find the database.
 	pub source: DatabaseSettingsSrc,
 }
 
+/// Block body pruning settings. Headers and justifications are always kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeepBlocks {
+	/// Keep the bodies of all blocks.
+	All,
+	/// Keep the bodies of the last N finalized blocks.
+	Some(u32),
+}
+
 /// Where to find the database..
 #[derive(Clone)]
 pub enum DatabaseSettingsSrc {
@@ -541,6 +553,12 @@ pub struct BlockImportOperation<Block: BlockT> {
 	finalized_blocks: Vec<(BlockId<Block>, Option<Justification>)>,
 	set_head: Option<BlockId<Block>>,
 	commit_state: bool,
+	/// Whether the state was reset with `reset_storage` rather than built on top of the
+	/// parent state. A non-genesis block with reset state anchors an imported state snapshot.
+	reset_state: bool,
+	/// Genesis header of the chain and the trie nodes of its state, set when the pending
+	/// block is the anchor of an imported state snapshot and the database is empty.
+	state_anchor_genesis: Option<(Block::Header, PrefixedMemoryDB<HashFor<Block>>)>,
 }
 
 impl<Block: BlockT> BlockImportOperation<Block> {
@@ -630,6 +648,7 @@ impl<Block: BlockT> sc_client_api::backend::BlockImportOperation<Block> for Bloc
 		self.db_updates = transaction;
 		self.changes_trie_config_update = Some(changes_trie_config);
 		self.commit_state = true;
+		self.reset_state = true;
 		Ok(root)
 	}
 
@@ -727,6 +746,33 @@ impl<Block: BlockT> sp_state_machine::Storage<HashFor<Block>> for DbGenesisStora
 	}
 }
 
+/// Prefix of the keys of the genesis trie nodes stored by `Backend::import_state`.
+const IMPORTED_GENESIS_STATE_PREFIX: &[u8] = b"genesis_state";
+
+fn imported_genesis_state_key(key: &[u8]) -> Vec<u8> {
+	let mut result = Vec::with_capacity(IMPORTED_GENESIS_STATE_PREFIX.len() + key.len());
+	result.extend_from_slice(IMPORTED_GENESIS_STATE_PREFIX);
+	result.extend_from_slice(key);
+	result
+}
+
+/// Genesis state of a chain that was started from imported state.
+///
+/// The trie nodes are kept outside of the state database, so they are never pruned
+/// and the runtime can always be called at genesis, e.g. to compute the genesis
+/// configuration of the consensus engines.
+struct ImportedGenesisStorage<Block: BlockT> {
+	db: Arc<dyn Database<DbHash>>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<Block: BlockT> sp_state_machine::Storage<HashFor<Block>> for ImportedGenesisStorage<Block> {
+	fn get(&self, key: &Block::Hash, prefix: Prefix) -> Result<Option<DBValue>, String> {
+		let key = prefixed_key::<HashFor<Block>>(key, prefix);
+		Ok(self.db.get(columns::STATE, &imported_genesis_state_key(&key)))
+	}
+}
+
 /// Frozen `value` at time `at`.
 ///
 /// Used as inner structure under lock in `FrozenForDuration`.
@@ -779,8 +825,12 @@ pub struct Backend<Block: BlockT> {
 	shared_cache: SharedCache<Block>,
 	import_lock: Arc<RwLock<()>>,
 	is_archive: bool,
+	keep_blocks: KeepBlocks,
 	io_stats: FrozenForDuration<(kvdb::IoStats, StateUsageInfo)>,
 	state_usage: Arc<StateUsageStats>,
+	/// Whether the chain was started from imported state, so its genesis state is kept
+	/// outside of the state database.
+	imported_state: AtomicBool,
 }
 
 impl<Block: BlockT> Backend<Block> {
@@ -795,18 +845,93 @@ impl<Block: BlockT> Backend<Block> {
 	/// Create new memory-backed client backend for tests.
 	#[cfg(any(test, feature = "test-helpers"))]
 	pub fn new_test(keep_blocks: u32, canonicalization_delay: u64) -> Self {
+		Self::new_test_with_body_pruning(keep_blocks, canonicalization_delay, KeepBlocks::All)
+	}
+
+	/// Create new memory-backed client backend for tests, pruning block bodies.
+	#[cfg(any(test, feature = "test-helpers"))]
+	pub fn new_test_with_body_pruning(
+		keep_blocks: u32,
+		canonicalization_delay: u64,
+		keep_bodies: KeepBlocks,
+	) -> Self {
 		let db = kvdb_memorydb::create(crate::utils::NUM_COLUMNS);
 		let db = sp_database::as_database(db);
 		let db_setting = DatabaseSettings {
 			state_cache_size: 16777216,
 			state_cache_child_ratio: Some((50, 100)),
 			pruning: PruningMode::keep_blocks(keep_blocks),
+			keep_blocks: keep_bodies,
 			source: DatabaseSettingsSrc::Custom(db),
 		};
 
 		Self::new(db_setting, canonicalization_delay).expect("failed to create test-db")
 	}
 
+	/// Seed an empty database with the state of a non-genesis block.
+	///
+	/// The block with the given `header` becomes the best and finalized block of the
+	/// database and `storage` becomes its state, which must match the state root of
+	/// the header. Only the header and the state of `genesis` are stored besides it, so
+	/// the blocks between genesis and the imported block are unknown to the database.
+	pub fn import_state(
+		&self,
+		genesis: Block::Header,
+		genesis_storage: Storage,
+		header: Block::Header,
+		storage: Storage,
+	) -> ClientResult<()> {
+		use sc_client_api::backend::{Backend as _, BlockImportOperation as _};
+
+		if self.blockchain.meta.read().genesis_hash != Default::default() {
+			return Err(ClientError::Backend(
+				"Cannot import state into a non-empty database".into()
+			));
+		}
+		if header.number().is_zero() {
+			return Err(ClientError::Backend(
+				"Cannot import state of genesis, use the genesis chain specification instead".into()
+			));
+		}
+		// Changes tries of the blocks before the imported one are not available, so digest
+		// tries could not be built on top of the imported state.
+		if storage.top.contains_key(well_known_keys::CHANGES_TRIE_CONFIG) {
+			return Err(ClientError::Backend(
+				"Importing state of a chain with changes tries enabled is not supported".into()
+			));
+		}
+
+		let mut genesis_operation = self.begin_operation()?;
+		self.begin_state_operation(&mut genesis_operation, BlockId::Hash(Default::default()))?;
+		let genesis_root = genesis_operation.reset_storage(genesis_storage)?;
+		if genesis_root != *genesis.state_root() {
+			return Err(ClientError::Backend(format!(
+				"Genesis state root {:?} does not match the state root {:?} of genesis {:?}",
+				genesis_root,
+				genesis.state_root(),
+				genesis.hash(),
+			)));
+		}
+
+		let mut operation = self.begin_operation()?;
+		self.begin_state_operation(&mut operation, BlockId::Hash(Default::default()))?;
+		let root = operation.reset_storage(storage)?;
+		if root != *header.state_root() {
+			return Err(ClientError::Backend(format!(
+				"Imported state root {:?} does not match the state root {:?} of block {:?}",
+				root,
+				header.state_root(),
+				header.hash(),
+			)));
+		}
+
+		operation.set_block_data(header, None, None, NewBlockState::Final)?;
+		operation.state_anchor_genesis = Some((genesis, genesis_operation.db_updates));
+		self.commit_operation(operation)?;
+		self.imported_state.store(true, Ordering::Relaxed);
+		Ok(())
+	}
+
 	fn from_database(
 		db: Arc<dyn Database<DbHash>>,
 		canonicalization_delay: u64,
@@ -829,6 +954,7 @@ impl<Block: BlockT> Backend<Block> {
 			prefix_keys: !config.source.supports_ref_counting(),
 		};
 		let offchain_storage = offchain::LocalStorage::new(db.clone());
+		let imported_state = db.get(columns::META, meta_keys::IMPORTED_STATE).is_some();
 		let changes_tries_storage = DbChangesTrieStorage::new(
 			db,
 			blockchain.header_metadata_cache.clone(),
@@ -857,8 +983,10 @@ impl<Block: BlockT> Backend<Block> {
 			),
 			import_lock: Default::default(),
 			is_archive: is_archive_pruning,
+			keep_blocks: config.keep_blocks,
 			io_stats: FrozenForDuration::new(std::time::Duration::from_secs(1)),
 			state_usage: Arc::new(StateUsageStats::new()),
+			imported_state: AtomicBool::new(imported_state),
 		})
 	}
 
@@ -1041,16 +1169,30 @@ impl<Block: BlockT> Backend<Block> {
 			last_finalized_hash = block_hash;
 		}
 
+		let state_anchor_genesis = operation.state_anchor_genesis;
 		let imported = if let Some(pending_block) = operation.pending_block {
 			let hash = pending_block.header.hash();
 			let parent_hash = *pending_block.header.parent_hash();
 			let number = pending_block.header.number().clone();
+			let is_state_anchor = operation.reset_state && !number.is_zero();
 
 			// blocks are keyed by number + hash.
 			let lookup_key = utils::number_and_hash_to_lookup_key(number, hash)?;
 
 			let (enacted, retracted) = if pending_block.leaf_state.is_best() {
-				self.set_head_with_transaction(&mut transaction, parent_hash, (number, hash))?
+				if is_state_anchor {
+					// there is no route from the current best block to the imported state.
+					transaction.set_from_vec(columns::META, meta_keys::BEST_BLOCK, lookup_key.clone());
+					utils::insert_number_to_key_mapping(
+						&mut transaction,
+						columns::KEY_LOOKUP,
+						number,
+						hash,
+					)?;
+					(Default::default(), Default::default())
+				} else {
+					self.set_head_with_transaction(&mut transaction, parent_hash, (number, hash))?
+				}
 			} else {
 				(Default::default(), Default::default())
 			};
@@ -1086,6 +1228,37 @@ impl<Block: BlockT> Backend<Block> {
 				}
 			}
 
+			if let Some((genesis, mut genesis_state)) = state_anchor_genesis {
+				// only genesis is known besides the imported block when the chain starts from
+				// imported state.
+				let genesis_hash = genesis.hash();
+				let genesis_lookup_key = utils::number_and_hash_to_lookup_key(
+					genesis.number().clone(),
+					genesis_hash,
+				)?;
+				utils::insert_hash_to_key_mapping(
+					&mut transaction,
+					columns::KEY_LOOKUP,
+					genesis.number().clone(),
+					genesis_hash,
+				)?;
+				utils::insert_number_to_key_mapping(
+					&mut transaction,
+					columns::KEY_LOOKUP,
+					genesis.number().clone(),
+					genesis_hash,
+				)?;
+				transaction.set_from_vec(columns::HEADER, &genesis_lookup_key, genesis.encode());
+				transaction.set(columns::META, meta_keys::GENESIS_HASH, genesis_hash.as_ref());
+				transaction.set(columns::META, meta_keys::IMPORTED_STATE, &[]);
+				for (key, (val, rc)) in genesis_state.drain() {
+					if rc > 0 {
+						transaction.set_from_vec(columns::STATE, &imported_genesis_state_key(&key), val);
+					}
+				}
+				meta_updates.push((genesis_hash, genesis.number().clone(), false, false));
+			}
+
 			let finalized = if operation.commit_state {
 				let mut changeset: sc_state_db::ChangeSet<Vec<u8>> = sc_state_db::ChangeSet::default();
 				let mut ops: u64 = 0;
@@ -1145,8 +1318,8 @@ impl<Block: BlockT> Backend<Block> {
 				)?;
 				apply_state_commit(&mut transaction, commit);
 
-				// Check if need to finalize. Genesis is always finalized instantly.
-				let finalized = number_u64 == 0 || pending_block.leaf_state.is_final();
+				// Check if need to finalize. Genesis and imported state are always finalized instantly.
+				let finalized = number_u64 == 0 || is_state_anchor || pending_block.leaf_state.is_final();
 				finalized
 			} else {
 				false
@@ -1159,10 +1332,17 @@ impl<Block: BlockT> Backend<Block> {
 			changes_trie_cache_ops = Some(self.changes_tries_storage.commit(
 				&mut transaction,
 				changes_trie_updates,
-				cache::ComplexBlockId::new(
-					*header.parent_hash(),
-					if number.is_zero() { Zero::zero() } else { number - One::one() },
-				),
+				if is_state_anchor {
+					// the parent of imported state is not in the database, so the cache
+					// entries are based on the last finalized block instead.
+					let meta = self.blockchain.meta.read();
+					cache::ComplexBlockId::new(meta.finalized_hash, meta.finalized_number)
+				} else {
+					cache::ComplexBlockId::new(
+						*header.parent_hash(),
+						if number.is_zero() { Zero::zero() } else { number - One::one() },
+					)
+				},
 				cache::ComplexBlockId::new(hash, number),
 				header,
 				finalized,
@@ -1175,7 +1355,9 @@ impl<Block: BlockT> Backend<Block> {
 
 			if finalized {
 				// TODO: ensure best chain contains this block.
-				self.ensure_sequential_finalization(header, Some(last_finalized_hash))?;
+				if !is_state_anchor {
+					self.ensure_sequential_finalization(header, Some(last_finalized_hash))?;
+				}
 				self.note_finalized(
 					&mut transaction,
 					true,
@@ -1313,7 +1495,18 @@ impl<Block: BlockT> Backend<Block> {
 			}
 		}
 
+		if let KeepBlocks::Some(keep_blocks) = self.keep_blocks {
+			// always keep the body of the last finalized block.
+			let keep = NumberFor::<Block>::from(std::cmp::max(keep_blocks, 1));
+			if f_num >= keep {
+				self.prune_block_body(transaction, BlockId::Number(f_num - keep))?;
+			}
+		}
+
 		let new_displaced = self.blockchain.leaves.write().finalize_height(f_num);
+		if let KeepBlocks::Some(_) = self.keep_blocks {
+			self.prune_displaced_block_bodies(transaction, &new_displaced)?;
+		}
 		match displaced {
 			x @ &mut None => *x = Some(new_displaced),
 			&mut Some(ref mut displaced) => displaced.merge(new_displaced),
@@ -1321,6 +1514,42 @@ impl<Block: BlockT> Backend<Block> {
 
 		Ok(())
 	}
+
+	// removes the bodies of the blocks on the branches that were displaced by finalization.
+	fn prune_displaced_block_bodies(
+		&self,
+		transaction: &mut Transaction<DbHash>,
+		displaced: &FinalizationDisplaced<Block::Hash, NumberFor<Block>>,
+	) -> ClientResult<()> {
+		use sc_client_api::blockchain::HeaderBackend as _;
+
+		for leaf in displaced.leaves() {
+			// follow the displaced branch back until the canonical chain is reached.
+			let mut hash = leaf.clone();
+			while let Some(header) = self.blockchain.header(BlockId::Hash(hash))? {
+				if self.blockchain.hash(*header.number())? == Some(hash) {
+					break;
+				}
+				self.prune_block_body(transaction, BlockId::Hash(hash))?;
+				hash = *header.parent_hash();
+			}
+		}
+		Ok(())
+	}
+
+	// removes the body of a block, keeping its header and justification.
+	fn prune_block_body(
+		&self,
+		transaction: &mut Transaction<DbHash>,
+		id: BlockId<Block>,
+	) -> ClientResult<()> {
+		let lookup_key = utils::block_id_to_lookup_key(&*self.storage.db, columns::KEY_LOOKUP, id)?;
+		if let Some(lookup_key) = lookup_key {
+			trace!(target: "db", "Removing body of block {}", id);
+			transaction.remove(columns::BODY, &lookup_key);
+		}
+		Ok(())
+	}
 }
 
 fn apply_state_commit(transaction: &mut Transaction<DbHash>, commit: sc_state_db::CommitSet<Vec<u8>>) {
@@ -1386,6 +1615,8 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 			finalized_blocks: Vec::new(),
 			set_head: None,
 			commit_state: false,
+			reset_state: false,
+			state_anchor_genesis: None,
 		})
 	}
 
@@ -1600,6 +1831,23 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 
 		match self.blockchain.header_metadata(hash) {
 			Ok(ref hdr) => {
+				// the genesis state of a chain started from imported state is kept outside
+				// of the state database.
+				if hdr.number.is_zero() && self.imported_state.load(Ordering::Relaxed) {
+					let genesis_storage = ImportedGenesisStorage::<Block> {
+						db: self.storage.db.clone(),
+						_marker: Default::default(),
+					};
+					let db_state = DbState::<Block>::new(Arc::new(genesis_storage), hdr.state_root);
+					let state = RefTrackingState::new(db_state, self.storage.clone(), None);
+					let caching_state = CachingState::new(state, self.shared_cache.clone(), None);
+					return Ok(SyncingCachingState::new(
+						caching_state,
+						self.state_usage.clone(),
+						self.blockchain.meta.clone(),
+						self.import_lock.clone(),
+					));
+				}
 				if !self.have_state_at(&hash, hdr.number) {
 					return Err(
 						sp_blockchain::Error::UnknownBlock(
@@ -1779,6 +2027,7 @@ pub(crate) mod tests {
 			state_cache_size: 16777216,
 			state_cache_child_ratio: Some((50, 100)),
 			pruning: PruningMode::keep_blocks(1),
+			keep_blocks: KeepBlocks::All,
 			source: DatabaseSettingsSrc::Custom(backing),
 		}, 0).unwrap();
 		assert_eq!(backend.blockchain().info().best_number, 9);
@@ -2303,4 +2552,138 @@ pub(crate) mod tests {
 			backend.commit_operation(op).unwrap_err();
 		}
 	}
+
+	#[test]
+	fn import_state_starts_chain_at_imported_block() {
+		let backend = Backend::<Block>::new_test(10, 10);
+
+		let genesis_pairs = vec![(vec![1, 3, 5], vec![1, 1, 1])];
+		let genesis = Header {
+			number: 0,
+			parent_hash: Default::default(),
+			state_root: BlakeTwo256::trie_root(genesis_pairs.clone()),
+			digest: Default::default(),
+			extrinsics_root: Default::default(),
+		};
+		let genesis_storage = || Storage {
+			top: genesis_pairs.iter().cloned().collect(),
+			children_default: Default::default(),
+		};
+		let storage = vec![
+			(vec![1, 3, 5], vec![2, 4, 6]),
+			(vec![1, 2, 3], vec![9, 9, 9]),
+		];
+		let header = Header {
+			number: 10,
+			parent_hash: H256::from_low_u64_be(9),
+			state_root: BlakeTwo256::trie_root(storage.clone()),
+			digest: Default::default(),
+			extrinsics_root: Default::default(),
+		};
+		let hash = header.hash();
+		let raw_state = || Storage {
+			top: storage.iter().cloned().collect(),
+			children_default: Default::default(),
+		};
+
+		let mut wrong_header = header.clone();
+		wrong_header.state_root = Default::default();
+		assert!(backend.import_state(
+			genesis.clone(),
+			genesis_storage(),
+			wrong_header,
+			raw_state(),
+		).is_err());
+		assert!(backend.import_state(
+			genesis.clone(),
+			raw_state(),
+			header.clone(),
+			raw_state(),
+		).is_err());
+
+		backend.import_state(genesis.clone(), genesis_storage(), header, raw_state()).unwrap();
+
+		let info = backend.blockchain().info();
+		assert_eq!(info.genesis_hash, genesis.hash());
+		assert_eq!((info.best_number, info.best_hash), (10, hash));
+		assert_eq!((info.finalized_number, info.finalized_hash), (10, hash));
+		assert_eq!(backend.blockchain().header(BlockId::Number(0)).unwrap(), Some(genesis.clone()));
+		assert!(backend.blockchain().hash(9).unwrap().is_none());
+
+		let state = backend.state_at(BlockId::Number(10)).unwrap();
+		assert_eq!(state.storage(&[1, 3, 5]).unwrap(), Some(vec![2, 4, 6]));
+		assert_eq!(state.storage(&[1, 2, 3]).unwrap(), Some(vec![9, 9, 9]));
+
+		// the genesis state is kept, so the runtime can still be called at genesis.
+		let genesis_state = backend.state_at(BlockId::Number(0)).unwrap();
+		assert_eq!(genesis_state.storage(&[1, 3, 5]).unwrap(), Some(vec![1, 1, 1]));
+		assert_eq!(genesis_state.storage(&[1, 2, 3]).unwrap(), None);
+
+		let block11 = insert_header(&backend, 11, hash, None, Default::default());
+		assert_eq!(backend.blockchain().info().best_hash, block11);
+
+		// state can only be imported into an empty database.
+		assert!(backend.import_state(
+			genesis,
+			genesis_storage(),
+			Header::new_from_number(20),
+			raw_state(),
+		).is_err());
+	}
+
+	#[test]
+	fn prune_block_bodies() {
+		let backend = Backend::<Block>::new_test_with_body_pruning(10, 0, KeepBlocks::Some(2));
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0..5 {
+			let hash = insert_header(&backend, i, prev_hash, None, Default::default());
+			blocks.push(hash);
+			prev_hash = hash;
+		}
+
+		for i in 1..5 {
+			backend.finalize_block(BlockId::Hash(blocks[i]), None).unwrap();
+		}
+
+		let bc = backend.blockchain();
+		for i in 0..3 {
+			assert_eq!(bc.body(BlockId::hash(blocks[i])).unwrap(), None);
+			assert!(bc.header(BlockId::hash(blocks[i])).unwrap().is_some());
+		}
+		assert_eq!(bc.body(BlockId::hash(blocks[3])).unwrap(), Some(Vec::new()));
+		assert_eq!(bc.body(BlockId::hash(blocks[4])).unwrap(), Some(Vec::new()));
+	}
+
+	#[test]
+	fn prune_block_bodies_of_displaced_forks() {
+		let backend = Backend::<Block>::new_test_with_body_pruning(10, 0, KeepBlocks::Some(10));
+		let block0 = insert_header(&backend, 0, Default::default(), None, Default::default());
+		let block1 = insert_header(&backend, 1, block0, None, Default::default());
+
+		// fork on top of block 1, displaced once the main chain is finalized.
+		let fork_root = H256::from_low_u64_be(42);
+		let fork2 = insert_header(&backend, 2, block1, None, fork_root);
+		let fork3 = insert_header(&backend, 3, fork2, None, fork_root);
+
+		let block2 = insert_header(&backend, 2, block1, None, Default::default());
+		let block3 = insert_header(&backend, 3, block2, None, Default::default());
+		let block4 = insert_header(&backend, 4, block3, None, Default::default());
+
+		let bc = backend.blockchain();
+		assert_eq!(bc.body(BlockId::hash(fork2)).unwrap(), Some(Vec::new()));
+		assert_eq!(bc.body(BlockId::hash(fork3)).unwrap(), Some(Vec::new()));
+
+		for hash in &[block1, block2, block3, block4] {
+			backend.finalize_block(BlockId::Hash(*hash), None).unwrap();
+		}
+
+		for hash in &[fork2, fork3] {
+			assert_eq!(bc.body(BlockId::hash(*hash)).unwrap(), None);
+			assert!(bc.header(BlockId::hash(*hash)).unwrap().is_some());
+		}
+		for hash in &[block1, block2, block3, block4] {
+			assert_eq!(bc.body(BlockId::hash(*hash)).unwrap(), Some(Vec::new()));
+		}
+	}
 }
@@ -57,6 +57,8 @@ pub mod meta_keys {
 	pub const LEAF_PREFIX: &[u8; 4] = b"leaf";
 	/// Children prefix list key.
 	pub const CHILDREN_PREFIX: &[u8; 8] = b"children";
+	/// Set when the chain was started from imported state.
+	pub const IMPORTED_STATE: &[u8; 8] = b"imported";
 }
 
 /// Database metadata.
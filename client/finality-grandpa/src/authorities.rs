@@ -71,6 +71,12 @@ where N: Add<Output=N> + Ord + Clone + Debug,
 		self.inner.read().set_id
 	}
 
+	/// Get the blocks at which previous authority sets were handed off through
+	/// standard changes.
+	pub(crate) fn authority_set_changes(&self) -> AuthoritySetChanges<N> {
+		self.inner.read().authority_set_changes.clone()
+	}
+
 	/// Get the current authorities and their weights (for the current set ID).
 	pub fn current_authorities(&self) -> VoterSet<AuthorityId> {
 		VoterSet::new(self.inner.read().current_authorities.iter().cloned()).expect(
@@ -112,8 +118,17 @@ pub(crate) struct AuthoritySet<H, N> {
 	/// Forced changes are enacted on block depth (not finality), for this reason
 	/// only one forced change should exist per fork.
 	pending_forced_changes: Vec<PendingChange<H, N>>,
+	/// The blocks at which previous sets were handed off through a standard
+	/// change, i.e. the last block finalized by each set, as `(set_id, number)`
+	/// pairs in ascending order. These blocks always have a justification and
+	/// are used to prove authority set handoffs to warp syncing nodes.
+	pub(crate) authority_set_changes: AuthoritySetChanges<N>,
 }
 
+/// List of `(set_id, block_number)` pairs recording the last block finalized
+/// by each authority set that was handed off through a standard change.
+pub(crate) type AuthoritySetChanges<N> = Vec<(u64, N)>;
+
 impl<H, N> AuthoritySet<H, N>
 where H: PartialEq,
 	  N: Ord,
@@ -134,6 +149,7 @@ where H: PartialEq,
 			set_id: 0,
 			pending_standard_changes: ForkTree::new(),
 			pending_forced_changes: Vec::new(),
+			authority_set_changes: Vec::new(),
 		})
 	}
 
@@ -143,6 +159,7 @@ where H: PartialEq,
 		set_id: u64,
 		pending_standard_changes: ForkTree<H, N, PendingChange<H, N>>,
 		pending_forced_changes: Vec<PendingChange<H, N>>,
+		authority_set_changes: AuthoritySetChanges<N>,
 	) -> Option<Self> {
 		if Self::invalid_authority_list(&authorities) {
 			return None;
@@ -153,6 +170,7 @@ where H: PartialEq,
 			set_id,
 			pending_standard_changes,
 			pending_forced_changes,
+			authority_set_changes,
 		})
 	}
 
@@ -375,6 +393,8 @@ where
 					set_id: self.set_id + 1,
 					pending_standard_changes: ForkTree::new(), // new set, new changes.
 					pending_forced_changes: Vec::new(),
+					// forced changes are not justified, so they are not recorded.
+					authority_set_changes: self.authority_set_changes.clone(),
 				}));
 
 				break;
@@ -434,6 +454,7 @@ where
 						"block" => ?change.canon_height
 					);
 
+					self.authority_set_changes.push((self.set_id, finalized_number.clone()));
 					self.current_authorities = change.next_authorities;
 					self.set_id += 1;
 
@@ -558,6 +579,7 @@ mod tests {
 			set_id: 0,
 			pending_standard_changes: ForkTree::new(),
 			pending_forced_changes: Vec::new(),
+			authority_set_changes: Vec::new(),
 		};
 
 		let change = |height| {
@@ -605,6 +627,7 @@ mod tests {
 			set_id: 0,
 			pending_standard_changes: ForkTree::new(),
 			pending_forced_changes: Vec::new(),
+			authority_set_changes: Vec::new(),
 		};
 
 		let change_a = PendingChange {
@@ -672,6 +695,7 @@ mod tests {
 			set_id: 0,
 			pending_standard_changes: ForkTree::new(),
 			pending_forced_changes: Vec::new(),
+			authority_set_changes: Vec::new(),
 		};
 
 		let set_a = vec![(AuthorityId::from_slice(&[1; 32]), 5)];
@@ -738,6 +762,7 @@ mod tests {
 		assert_eq!(authorities.current_authorities, set_a);
 		assert_eq!(authorities.set_id, 1);
 		assert_eq!(authorities.pending_changes().count(), 0);
+		assert_eq!(authorities.authority_set_changes, vec![(0, 15)]);
 	}
 
 	#[test]
@@ -747,6 +772,7 @@ mod tests {
 			set_id: 0,
 			pending_standard_changes: ForkTree::new(),
 			pending_forced_changes: Vec::new(),
+			authority_set_changes: Vec::new(),
 		};
 
 		let set_a = vec![(AuthorityId::from_slice(&[1; 32]), 5)];
@@ -825,6 +851,7 @@ mod tests {
 			set_id: 0,
 			pending_standard_changes: ForkTree::new(),
 			pending_forced_changes: Vec::new(),
+			authority_set_changes: Vec::new(),
 		};
 
 		let set_a = vec![(AuthorityId::from_slice(&[1; 32]), 5)];
@@ -891,6 +918,7 @@ mod tests {
 			set_id: 0,
 			pending_standard_changes: ForkTree::new(),
 			pending_forced_changes: Vec::new(),
+			authority_set_changes: Vec::new(),
 		};
 
 		let set_a = vec![(AuthorityId::from_slice(&[1; 32]), 5)];
@@ -966,6 +994,7 @@ mod tests {
 				set_id: 1,
 				pending_standard_changes: ForkTree::new(),
 				pending_forced_changes: Vec::new(),
+				authority_set_changes: Vec::new(),
 			}),
 		);
 	}
@@ -979,6 +1008,7 @@ mod tests {
 			set_id: 0,
 			pending_standard_changes: ForkTree::new(),
 			pending_forced_changes: Vec::new(),
+			authority_set_changes: Vec::new(),
 		};
 
 		let new_set = current_authorities.clone();
@@ -23,21 +23,21 @@ use parking_lot::RwLockWriteGuard;
 use sp_blockchain::{BlockStatus, well_known_cache_keys};
 use sc_client_api::{backend::Backend, utils::is_descendent_of};
 use sp_utils::mpsc::TracingUnboundedSender;
-use sp_api::{TransactionFor};
+use sp_api::{TransactionFor, ProvideRuntimeApi};
 
 use sp_consensus::{
 	BlockImport, Error as ConsensusError,
 	BlockCheckParams, BlockImportParams, BlockOrigin, ImportResult, JustificationImport,
 	SelectChain,
 };
-use sp_finality_grandpa::{ConsensusLog, ScheduledChange, SetId, GRANDPA_ENGINE_ID};
+use sp_finality_grandpa::{ConsensusLog, GrandpaApi, ScheduledChange, SetId, GRANDPA_ENGINE_ID};
 use sp_runtime::Justification;
 use sp_runtime::generic::{BlockId, OpaqueDigestItemId};
 use sp_runtime::traits::{
 	Block as BlockT, DigestFor, Header as HeaderT, NumberFor, Zero,
 };
 
-use crate::{Error, CommandOrError, NewAuthoritySet, VoterCommand};
+use crate::{Error, CommandOrError, NewAuthoritySet, VoterCommand, aux_schema};
 use crate::authorities::{AuthoritySet, SharedAuthoritySet, DelayKind, PendingChange};
 use crate::consensus_changes::SharedConsensusChanges;
 use crate::environment::finalize_block;
@@ -180,7 +180,7 @@ impl<'a, Block: 'a + BlockT> Drop for PendingSetChanges<'a, Block> {
 	}
 }
 
-fn find_scheduled_change<B: BlockT>(header: &B::Header)
+pub(crate) fn find_scheduled_change<B: BlockT>(header: &B::Header)
 	-> Option<ScheduledChange<NumberFor<B>>>
 {
 	let id = OpaqueDigestItemId::Consensus(&GRANDPA_ENGINE_ID);
@@ -405,6 +405,7 @@ impl<BE, Block: BlockT, Client, SC> BlockImport<Block>
 		DigestFor<Block>: Encode,
 		BE: Backend<Block>,
 		Client: crate::ClientForGrandpa<Block, BE>,
+		Client::Api: GrandpaApi<Block, Error = sp_blockchain::Error>,
 		for<'a> &'a Client:
 			BlockImport<Block, Error = ConsensusError, Transaction = TransactionFor<Client, Block>>,
 {
@@ -427,6 +428,10 @@ impl<BE, Block: BlockT, Client, SC> BlockImport<Block>
 			Err(e) => return Err(ConsensusError::ClientImport(e.to_string()).into()),
 		}
 
+		if block.import_state.is_some() {
+			return self.import_state(block, new_cache);
+		}
+
 		// on initial sync we will restrict logging under info to avoid spam.
 		let initial_sync = block.origin == BlockOrigin::NetworkInitialSync;
 
@@ -550,6 +555,73 @@ impl<BE, Block: BlockT, Client, SC> BlockImport<Block>
 	}
 }
 
+impl<BE, Block: BlockT, Client, SC> GrandpaBlockImport<BE, Block, Client, SC> where
+	BE: Backend<Block>,
+	Client: crate::ClientForGrandpa<Block, BE>,
+	Client::Api: GrandpaApi<Block, Error = sp_blockchain::Error>,
+	for<'a> &'a Client:
+		BlockImport<Block, Error = ConsensusError, Transaction = TransactionFor<Client, Block>>,
+{
+	/// Import a block whose state was downloaded from peers, e.g. after warp sync.
+	///
+	/// Its finality has been proven by the sync, so the authority set is reset to
+	/// the one active at that block as reported by the runtime, and the voter is
+	/// restarted on top of it.
+	fn import_state(
+		&mut self,
+		block: BlockImportParams<Block, TransactionFor<Client, Block>>,
+		new_cache: HashMap<well_known_cache_keys::Id, Vec<u8>>,
+	) -> Result<ImportResult, ConsensusError> {
+		let hash = block.post_hash();
+		let number = block.header.number().clone();
+
+		let imported_aux = match (&*self.inner).import_block(block, new_cache) {
+			Ok(ImportResult::Imported(aux)) => aux,
+			Ok(r) => return Ok(r),
+			Err(e) => return Err(ConsensusError::ClientImport(e.to_string())),
+		};
+
+		let runtime_api = self.inner.runtime_api();
+		let at = BlockId::Hash(hash);
+		let authorities = runtime_api.grandpa_authorities(&at)
+			.map_err(|e| ConsensusError::ClientImport(e.to_string()))?;
+		let set_id = runtime_api.current_set_id(&at)
+			.map_err(|e| ConsensusError::ClientImport(e.to_string()))?;
+
+		let authority_set = AuthoritySet::new(
+			authorities.clone(),
+			set_id,
+			fork_tree::ForkTree::new(),
+			Vec::new(),
+			Vec::new(),
+		).ok_or_else(|| ConsensusError::ClientImport(
+			"Invalid GRANDPA authority set in imported state".into()
+		))?;
+
+		let new_set = NewAuthoritySet {
+			canon_number: number,
+			canon_hash: hash,
+			set_id,
+			authorities,
+		};
+
+		let mut current_set = self.authority_set.inner().write();
+		aux_schema::update_authority_set::<Block, _, _>(
+			&authority_set,
+			Some(&new_set),
+			|insert| self.inner.insert_aux(insert, &[]),
+		).map_err(|e| ConsensusError::ClientImport(e.to_string()))?;
+		*current_set = authority_set;
+		drop(current_set);
+
+		debug!(target: "afg", "Reset authority set to #{} after importing state of block {:?}", set_id, hash);
+
+		let _ = self.send_voter_commands.unbounded_send(VoterCommand::ChangeAuthorities(new_set));
+
+		Ok(ImportResult::Imported(imported_aux))
+	}
+}
+
 impl<Backend, Block: BlockT, Client, SC> GrandpaBlockImport<Backend, Block, Client, SC> {
 	pub(crate) fn new(
 		inner: Arc<Client>,
@@ -117,11 +117,13 @@ mod light_import;
 mod observer;
 mod until_imported;
 mod voting_rule;
+mod warp_proof;
 
 pub use authorities::SharedAuthoritySet;
 pub use finality_proof::{FinalityProofProvider, StorageAndProofProvider};
 pub use justification::GrandpaJustification;
 pub use light_import::light_block_import;
+pub use warp_proof::WarpSyncProofProvider;
 pub use voting_rule::{
 	BeforeBestBlockBy, ThreeQuartersOfTheUnfinalizedChain, VotingRule, VotingRulesBuilder
 };
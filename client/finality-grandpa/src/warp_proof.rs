@@ -0,0 +1,392 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! GRANDPA warp sync proof generation and check.
+//!
+//! A warp sync proof allows a node that knows the authority set active at some
+//! block (e.g. the genesis set) to learn the latest finalized block without
+//! importing the blocks in between. It is made of one fragment per authority
+//! set handoff: the header of the last block finalized by a set, which carries
+//! the standard change signal with the next authorities, and the justification
+//! of that block signed by the outgoing set. The proof may end with the header
+//! and justification of the latest finalized block known to the prover.
+//!
+//! This assumes that standard changes are scheduled with no delay, i.e. that the
+//! block signaling a change is the one enacting it, and stops at the first set
+//! that was replaced through a forced change, since forced changes are not
+//! justified.
+
+use std::sync::Arc;
+
+use finality_grandpa::BlockNumberOps;
+use parity_scale_codec::{Encode, Decode};
+use sc_client_api::{Backend, ExecutorProvider};
+use sc_network::config::{WarpSyncProvider, WarpSyncVerificationResult};
+use sp_blockchain::{Backend as BlockchainBackend, Error as ClientError, Result as ClientResult};
+use sp_finality_grandpa::{AuthorityList, SetId};
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor, One};
+
+use crate::authorities::{AuthoritySetChanges, SharedAuthoritySet};
+use crate::import::find_scheduled_change;
+use crate::justification::GrandpaJustification;
+use crate::{GenesisAuthoritySetProvider, VoterSet};
+
+/// The maximum size in bytes of a warp sync proof. Larger proofs are split and the
+/// rest must be requested from the last proven block.
+const MAX_WARP_SYNC_PROOF_SIZE: usize = 8 * 1024 * 1024;
+
+/// A finalized block along with its justification.
+#[derive(Encode, Decode)]
+struct WarpSyncFragment<Block: BlockT> {
+	/// The header of the finalized block. Except for the last fragment of a finished
+	/// proof, it must contain the standard change signal enacted by the block.
+	header: Block::Header,
+	/// The justification of the block, signed by the set active before it.
+	justification: GrandpaJustification<Block>,
+}
+
+/// A chain of authority set handoffs.
+#[derive(Encode, Decode)]
+pub struct WarpSyncProof<Block: BlockT> {
+	fragments: Vec<WarpSyncFragment<Block>>,
+	/// Whether the proof reaches the latest finalized block known to the prover.
+	is_finished: bool,
+}
+
+impl<Block: BlockT> WarpSyncProof<Block> {
+	/// Generate a proof of the authority set handoffs since the set that was active
+	/// at the given block, up to the latest finalized block.
+	pub(crate) fn generate<B: BlockchainBackend<Block>>(
+		blockchain: &B,
+		begin: Block::Hash,
+		set_changes: &AuthoritySetChanges<NumberFor<Block>>,
+	) -> ClientResult<WarpSyncProof<Block>> {
+		let begin_number = blockchain.block_number_from_id(&BlockId::Hash(begin))?
+			.ok_or_else(|| ClientError::UnknownBlock(format!("{:?}", begin)))?;
+		let finalized_number = blockchain.info().finalized_number;
+
+		if begin_number > finalized_number {
+			return Err(ClientError::Backend(
+				"Warp sync proof can only start at a finalized block".into()
+			));
+		}
+
+		let mut fragments = Vec::new();
+		let mut proof_size = 0;
+		let mut is_finished = true;
+
+		for (_, last_block) in set_changes.iter().filter(|(_, number)| *number > begin_number) {
+			let header = blockchain.header(BlockId::Number(*last_block))?
+				.ok_or_else(|| ClientError::UnknownBlock(format!("{:?}", last_block)))?;
+
+			if find_scheduled_change::<Block>(&header).is_none() {
+				// the next set took over through a forced change, the chain of
+				// justified handoffs is broken here.
+				is_finished = false;
+				break;
+			}
+
+			let justification = fragment_justification(blockchain, *last_block)?
+				.ok_or_else(|| ClientError::Backend(
+					format!("Missing justification of authority set change block #{:?}", last_block)
+				))?;
+
+			let fragment = WarpSyncFragment { header, justification };
+			proof_size += fragment.encoded_size();
+			if proof_size > MAX_WARP_SYNC_PROOF_SIZE {
+				is_finished = false;
+				break;
+			}
+
+			fragments.push(fragment);
+		}
+
+		if is_finished {
+			// prove the latest justified block after the last handoff, justifications
+			// are only stored for some of the finalized blocks.
+			let lower_bound = fragments.last()
+				.map(|fragment| *fragment.header.number())
+				.unwrap_or(begin_number);
+
+			let mut number = finalized_number;
+			while number > lower_bound {
+				if let Some(justification) = fragment_justification(blockchain, number)? {
+					let header = blockchain.header(BlockId::Number(number))?
+						.ok_or_else(|| ClientError::UnknownBlock(format!("{:?}", number)))?;
+
+					fragments.push(WarpSyncFragment { header, justification });
+					break;
+				}
+
+				number = number - One::one();
+			}
+		}
+
+		Ok(WarpSyncProof { fragments, is_finished })
+	}
+
+	/// Verify the proof against the given authority set, returning the authority set
+	/// active after the last fragment.
+	pub(crate) fn verify(
+		&self,
+		set_id: SetId,
+		authorities: AuthorityList,
+	) -> ClientResult<(SetId, AuthorityList)> where
+		NumberFor<Block>: BlockNumberOps,
+	{
+		if self.fragments.is_empty() {
+			return Err(ClientError::BadJustification("Empty warp sync proof".into()));
+		}
+
+		let mut current_set_id = set_id;
+		let mut current_authorities = authorities;
+
+		for (index, fragment) in self.fragments.iter().enumerate() {
+			let voters = VoterSet::new(current_authorities.iter().cloned())
+				.ok_or(ClientError::InvalidAuthoritiesSet)?;
+
+			if fragment.justification.commit.target_hash != fragment.header.hash() {
+				return Err(ClientError::BadJustification(
+					"Warp sync proof justification doesn't match its header".into()
+				));
+			}
+
+			fragment.justification.verify(current_set_id, &voters)?;
+
+			if let Some(change) = find_scheduled_change::<Block>(&fragment.header) {
+				current_set_id += 1;
+				current_authorities = change.next_authorities;
+			} else if index != self.fragments.len() - 1 || !self.is_finished {
+				// only the latest finalized block doesn't need to enact a change.
+				return Err(ClientError::BadJustification(
+					"Warp sync proof header is missing an authority set change".into()
+				));
+			}
+		}
+
+		Ok((current_set_id, current_authorities))
+	}
+}
+
+fn fragment_justification<Block: BlockT, B: BlockchainBackend<Block>>(
+	blockchain: &B,
+	number: NumberFor<Block>,
+) -> ClientResult<Option<GrandpaJustification<Block>>> {
+	match blockchain.justification(BlockId::Number(number))? {
+		Some(justification) => GrandpaJustification::decode(&mut &justification[..])
+			.map(Some)
+			.map_err(|_| ClientError::JustificationDecode),
+		None => Ok(None),
+	}
+}
+
+/// Warp sync proof provider for serving network requests and verifying proofs
+/// received from peers.
+pub struct WarpSyncProofProvider<Block: BlockT, B, C> {
+	backend: Arc<B>,
+	client: Arc<C>,
+	authority_set: SharedAuthoritySet<Block::Hash, NumberFor<Block>>,
+}
+
+impl<Block: BlockT, B, C> WarpSyncProofProvider<Block, B, C> where
+	B: Backend<Block> + Send + Sync + 'static,
+	C: ExecutorProvider<Block> + Send + Sync + 'static,
+{
+	/// Create a new warp sync proof provider using the given backend, the client used
+	/// to read the genesis authorities and the authority set tracked by the GRANDPA
+	/// block import.
+	pub fn new(
+		backend: Arc<B>,
+		client: Arc<C>,
+		authority_set: SharedAuthoritySet<Block::Hash, NumberFor<Block>>,
+	) -> Self {
+		WarpSyncProofProvider { backend, client, authority_set }
+	}
+}
+
+impl<Block, B, C> WarpSyncProvider<Block> for WarpSyncProofProvider<Block, B, C> where
+	Block: BlockT,
+	NumberFor<Block>: BlockNumberOps,
+	B: Backend<Block> + Send + Sync + 'static,
+	C: ExecutorProvider<Block> + Send + Sync + 'static,
+{
+	fn generate(&self, start: Block::Hash) -> ClientResult<Vec<u8>> {
+		let set_changes = self.authority_set.authority_set_changes();
+		WarpSyncProof::<Block>::generate(&*self.backend.blockchain(), start, &set_changes)
+			.map(|proof| proof.encode())
+	}
+
+	fn verify(
+		&self,
+		proof: &[u8],
+		set_id: SetId,
+		authorities: AuthorityList,
+	) -> ClientResult<WarpSyncVerificationResult<Block>> {
+		let proof = WarpSyncProof::<Block>::decode(&mut &proof[..])
+			.map_err(|e| ClientError::Backend(format!("Invalid warp sync proof: {}", e.what())))?;
+		let (next_set_id, next_authorities) = proof.verify(set_id, authorities)?;
+
+		let last_fragment = proof.fragments.last()
+			.expect("verified proof contains at least one fragment; qed");
+		if proof.is_finished {
+			Ok(WarpSyncVerificationResult::Complete(
+				next_set_id,
+				next_authorities,
+				last_fragment.header.clone(),
+				last_fragment.justification.encode(),
+			))
+		} else {
+			Ok(WarpSyncVerificationResult::Partial(
+				next_set_id,
+				next_authorities,
+				last_fragment.header.hash(),
+			))
+		}
+	}
+
+	fn genesis_authorities(&self) -> ClientResult<AuthorityList> {
+		// the local authority set is only the genesis set on a fresh database, so the
+		// genesis authorities are read from the runtime at genesis.
+		let client = self.client.clone() as Arc<dyn ExecutorProvider<Block, Executor = C::Executor>>;
+		GenesisAuthoritySetProvider::get(&client)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+	use parity_scale_codec::Encode;
+	use sc_block_builder::BlockBuilderProvider;
+	use sc_client_api::Backend;
+	use sp_blockchain::HeaderBackend;
+	use sp_consensus::BlockOrigin;
+	use sp_finality_grandpa::{AuthorityList, ConsensusLog, ScheduledChange, GRANDPA_ENGINE_ID};
+	use sp_keyring::Ed25519Keyring;
+	use sp_runtime::generic::{BlockId, DigestItem};
+	use sp_runtime::traits::Header as _;
+	use substrate_test_runtime_client::prelude::*;
+	use substrate_test_runtime_client::runtime::{Block, Hash};
+	use crate::justification::GrandpaJustification;
+	use super::WarpSyncProof;
+
+	fn make_ids(keys: &[Ed25519Keyring]) -> AuthorityList {
+		keys.iter().map(|key| (key.public().into(), 1)).collect()
+	}
+
+	fn make_justification(
+		client: &Arc<TestClient>,
+		set_id: u64,
+		keys: &[Ed25519Keyring],
+		target: (Hash, u64),
+	) -> GrandpaJustification<Block> {
+		let round = 1;
+		let precommits = keys.iter().map(|key| {
+			let precommit = finality_grandpa::Precommit {
+				target_hash: target.0,
+				target_number: target.1,
+			};
+			let message = finality_grandpa::Message::Precommit(precommit.clone());
+			let payload = sp_finality_grandpa::localized_payload(round, set_id, &message);
+
+			finality_grandpa::SignedPrecommit {
+				precommit,
+				signature: key.sign(&payload[..]).into(),
+				id: key.public().into(),
+			}
+		}).collect();
+
+		let commit = finality_grandpa::Commit {
+			target_hash: target.0,
+			target_number: target.1,
+			precommits,
+		};
+
+		GrandpaJustification::from_commit(client, round, commit).unwrap()
+	}
+
+	#[test]
+	fn warp_proof_follows_authority_set_changes() {
+		let set_a = &[Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie];
+		let set_b = &[Ed25519Keyring::Dave, Ed25519Keyring::Eve, Ed25519Keyring::Ferdie];
+		let set_c = &[Ed25519Keyring::Alice, Ed25519Keyring::Eve, Ed25519Keyring::Two];
+
+		let (client, backend) = TestClientBuilder::new().build_with_backend();
+		let mut client = Arc::new(client);
+		let genesis_hash = client.genesis_hash();
+
+		// blocks 5 and 10 enact instant authority set changes, block 12 is finalized
+		// by the last set.
+		let mut finalize = Vec::new();
+		for number in 1..=12u64 {
+			let mut block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+			let (next, signed_by) = match number {
+				5 => (Some(set_b), (0, set_a)),
+				10 => (Some(set_c), (1, set_b)),
+				12 => (None, (2, set_c)),
+				_ => {
+					client.import(BlockOrigin::Own, block).unwrap();
+					continue;
+				},
+			};
+
+			if let Some(next) = next {
+				block.header.digest_mut().push(DigestItem::Consensus(
+					GRANDPA_ENGINE_ID,
+					ConsensusLog::ScheduledChange(ScheduledChange {
+						next_authorities: make_ids(next),
+						delay: 0,
+					}).encode(),
+				));
+			}
+
+			let hash = block.header.hash();
+			client.import(BlockOrigin::Own, block).unwrap();
+			finalize.push((hash, number, signed_by));
+		}
+
+		for (hash, number, (set_id, keys)) in finalize {
+			let justification = make_justification(&client, set_id, keys, (hash, number));
+			client.finalize_block(BlockId::Hash(hash), Some(justification.encode())).unwrap();
+		}
+
+		let set_changes = vec![(0, 5), (1, 10)];
+		let proof = WarpSyncProof::<Block>::generate(
+			&*backend.blockchain(),
+			genesis_hash,
+			&set_changes,
+		).unwrap();
+
+		assert_eq!(proof.fragments.len(), 3);
+		assert!(proof.is_finished);
+		assert_eq!(proof.verify(0, make_ids(set_a)).unwrap(), (2, make_ids(set_c)));
+
+		// the proof doesn't verify against any other set.
+		assert!(proof.verify(1, make_ids(set_a)).is_err());
+		assert!(proof.verify(0, make_ids(set_b)).is_err());
+
+		// starting from the first handoff only proves the following ones.
+		let hash_5 = client.hash(5).unwrap().unwrap();
+		let proof = WarpSyncProof::<Block>::generate(
+			&*backend.blockchain(),
+			hash_5,
+			&set_changes,
+		).unwrap();
+
+		assert_eq!(proof.fragments.len(), 2);
+		assert_eq!(proof.verify(1, make_ids(set_b)).unwrap(), (2, make_ids(set_c)));
+	}
+}
@@ -16,6 +16,7 @@
 
 use crate::{
 	config::{ProtocolId, Role}, block_requests, light_client_handler, finality_requests,
+	state_requests, warp_requests,
 	debug_info, discovery::{DiscoveryBehaviour, DiscoveryConfig, DiscoveryOut},
 	protocol::{message::{self, Roles}, CustomMessageOutcome, Protocol},
 	Event, ObservedRole, DhtEvent, ExHashT,
@@ -46,6 +47,10 @@ pub struct Behaviour<B: BlockT, H: ExHashT> {
 	block_requests: block_requests::BlockRequests<B>,
 	/// Finality proof request handling.
 	finality_proof_requests: finality_requests::FinalityProofRequests<B>,
+	/// Warp sync proof request handling.
+	warp_sync_requests: warp_requests::WarpSyncRequests<B>,
+	/// State request handling.
+	state_requests: state_requests::StateRequests<B>,
 	/// Light client request handling.
 	light_client_handler: light_client_handler::LightClientHandler<B>,
 
@@ -108,6 +113,8 @@ impl<B: BlockT, H: ExHashT> Behaviour<B, H> {
 		local_public_key: PublicKey,
 		block_requests: block_requests::BlockRequests<B>,
 		finality_proof_requests: finality_requests::FinalityProofRequests<B>,
+		warp_sync_requests: warp_requests::WarpSyncRequests<B>,
+		state_requests: state_requests::StateRequests<B>,
 		light_client_handler: light_client_handler::LightClientHandler<B>,
 		disco_config: DiscoveryConfig,
 	) -> Self {
@@ -117,6 +124,8 @@ impl<B: BlockT, H: ExHashT> Behaviour<B, H> {
 			discovery: disco_config.finish(),
 			block_requests,
 			finality_proof_requests,
+			warp_sync_requests,
+			state_requests,
 			light_client_handler,
 			events: VecDeque::new(),
 			role,
@@ -272,6 +281,12 @@ Behaviour<B, H> {
 			CustomMessageOutcome::FinalityProofRequest { target, block_hash, request } => {
 				self.finality_proof_requests.send_request(&target, block_hash, request);
 			},
+			CustomMessageOutcome::WarpProofRequest { target, begin } => {
+				self.warp_sync_requests.send_request(&target, begin);
+			},
+			CustomMessageOutcome::StateRequest { target, block, start } => {
+				self.state_requests.send_request(&target, block, start);
+			},
 			CustomMessageOutcome::NotificationStreamOpened { remote, protocols, roles } => {
 				let role = reported_roles_to_observed_role(&self.role, &remote, roles);
 				for engine_id in protocols {
@@ -364,6 +379,28 @@ impl<B: BlockT, H: ExHashT> NetworkBehaviourEventProcess<finality_requests::Even
 	}
 }
 
+impl<B: BlockT, H: ExHashT> NetworkBehaviourEventProcess<warp_requests::Event<B>> for Behaviour<B, H> {
+	fn inject_event(&mut self, event: warp_requests::Event<B>) {
+		match event {
+			warp_requests::Event::Response { peer, proof, .. } => {
+				let ev = self.substrate.on_warp_proof_response(peer, proof);
+				self.inject_event(ev);
+			}
+		}
+	}
+}
+
+impl<B: BlockT, H: ExHashT> NetworkBehaviourEventProcess<state_requests::Event<B>> for Behaviour<B, H> {
+	fn inject_event(&mut self, event: state_requests::Event<B>) {
+		match event {
+			state_requests::Event::Response { peer, proof, .. } => {
+				let ev = self.substrate.on_state_response(peer, proof);
+				self.inject_event(ev);
+			}
+		}
+	}
+}
+
 impl<B: BlockT, H: ExHashT> NetworkBehaviourEventProcess<debug_info::DebugInfoEvent>
 	for Behaviour<B, H> {
 	fn inject_event(&mut self, event: debug_info::DebugInfoEvent) {
@@ -18,6 +18,8 @@
 
 use sp_blockchain::{Error, HeaderBackend, HeaderMetadata};
 use sc_client_api::{BlockBackend, ProofProvider};
+use sp_finality_grandpa::{AuthorityList, SetId};
+use sp_runtime::Justification;
 use sp_runtime::traits::{Block as BlockT, BlockIdTo};
 
 /// Local client abstraction for the network.
@@ -42,3 +44,33 @@ impl<Block: BlockT> FinalityProofProvider<Block> for () {
 		Ok(None)
 	}
 }
+
+/// Result of verifying a warp sync proof.
+pub enum WarpSyncVerificationResult<Block: BlockT> {
+	/// The proof is valid but doesn't reach the latest finalized block. The next proof
+	/// should be requested starting at the given block, for the given authority set.
+	Partial(SetId, AuthorityList, Block::Hash),
+	/// The proof is valid and proves finality of the given header with the given
+	/// justification.
+	Complete(SetId, AuthorityList, Block::Header, Justification),
+}
+
+/// Warp sync proof provider. Generates and verifies proofs of finality authority set
+/// handoffs, allowing a node to jump to the latest finalized block without importing
+/// the blocks in between.
+pub trait WarpSyncProvider<Block: BlockT>: Send + Sync {
+	/// Generate a proof of the authority set handoffs since the set that was active at
+	/// the given block, up to the latest finalized block.
+	fn generate(&self, start: Block::Hash) -> Result<Vec<u8>, Error>;
+
+	/// Verify a warp sync proof against the given authority set.
+	fn verify(
+		&self,
+		proof: &[u8],
+		set_id: SetId,
+		authorities: AuthorityList,
+	) -> Result<WarpSyncVerificationResult<Block>, Error>;
+
+	/// Get the genesis authority set, against which the first proof is verified.
+	fn genesis_authorities(&self) -> Result<AuthorityList, Error>;
+}
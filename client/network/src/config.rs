@@ -19,7 +19,7 @@
 //! The [`Params`] struct is the struct that must be passed in order to initialize the networking.
 //! See the documentation of [`Params`].
 
-pub use crate::chain::{Client, FinalityProofProvider};
+pub use crate::chain::{Client, FinalityProofProvider, WarpSyncProvider, WarpSyncVerificationResult};
 pub use crate::on_demand_layer::{AlwaysBadChecker, OnDemand};
 pub use libp2p::{identity, core::PublicKey, wasm_ext::ExtTransport, build_multiaddr};
 
@@ -76,6 +76,12 @@ pub struct Params<B: BlockT, H: ExHashT> {
 	/// This object, if `Some`, is used when we need a proof of finality from another node.
 	pub finality_proof_request_builder: Option<BoxFinalityProofRequestBuilder<B>>,
 
+	/// Warp sync proof provider.
+	///
+	/// This object, if `Some`, is used to answer warp sync proof requests from other nodes,
+	/// and to verify the proofs we receive when syncing with [`SyncMode::Warp`].
+	pub warp_sync: Option<Arc<dyn WarpSyncProvider<B>>>,
+
 	/// The `OnDemand` object acts as a "receiver" for block data requests from the client.
 	/// If `Some`, the network worker will process these requests and answer them.
 	/// Normally used only for light clients.
@@ -403,6 +409,8 @@ pub struct NetworkConfiguration {
 	/// If true, uses the `/<chainid>/block-requests/<version>` experimental protocol rather than
 	/// the legacy substream. This option is meant to be hard-wired to `true` in the future.
 	pub use_new_block_requests_protocol: bool,
+	/// How to synchronize the chain when starting from an empty database.
+	pub sync_mode: SyncMode,
 }
 
 impl NetworkConfiguration {
@@ -435,6 +443,7 @@ impl NetworkConfiguration {
 			max_parallel_downloads: 5,
 			allow_non_globals_in_dht: false,
 			use_new_block_requests_protocol: true,
+			sync_mode: SyncMode::Full,
 		}
 	}
 }
@@ -479,6 +488,24 @@ impl NetworkConfiguration {
 	}
 }
 
+/// How the chain is synchronized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncMode {
+	/// Download and import all blocks, starting from genesis.
+	Full,
+	/// Download a proof of finality of the latest finalized block and the state at that
+	/// block, then continue with regular sync from there. Blocks between genesis and the
+	/// warp target are not downloaded. Requires [`Params::warp_sync`] to be set, and is
+	/// only used when our best block is the genesis block.
+	Warp,
+}
+
+impl Default for SyncMode {
+	fn default() -> Self {
+		SyncMode::Full
+	}
+}
+
 /// Configuration for the transport layer.
 #[derive(Clone, Debug)]
 pub enum TransportConfig {
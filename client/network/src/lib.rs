@@ -129,6 +129,16 @@
 //! light-client-related requests for information about the state. Each request is the encoding of
 //! a `light::Request` and each response is the encoding of a `light::Response`, as defined in the
 //! `light.v1.proto` file in this source tree.
+//! - **`/<protocol-id>/sync/warp/1`** is a request-response protocol (see below) that lets one
+//! request a proof of the finality authority set changes since a given block, used to warp sync
+//! to the latest finalized block. Each request is the encoding of a `WarpSyncRequest` and each
+//! response is the encoding of a `WarpSyncResponse`, as defined in the `warp.v1.proto` file in
+//! this source tree.
+//! - **`/<protocol-id>/state/1`** is a request-response protocol (see below) that lets one
+//! download the state of a block, including child tries, by ranges of keys proven against the
+//! state root of the block. Each request is the encoding of a `StateRequest` and each response
+//! is the encoding of a `StateResponse`, as defined in the `state.v1.proto` file in this source
+//! tree.
 //! - **`/<protocol-id>/transactions/1`** is a notifications protocol (see below) where
 //! transactions are pushed to other nodes. The handshake is empty on both sides. The message
 //! format is a SCALE-encoded list of transactions, where each transaction is an opaque list of
@@ -250,8 +260,10 @@ mod on_demand_layer;
 mod protocol;
 mod schema;
 mod service;
+mod state_requests;
 mod transport;
 mod utils;
+mod warp_requests;
 
 pub mod config;
 pub mod error;
@@ -16,7 +16,7 @@
 
 use crate::{
 	ExHashT,
-	chain::{Client, FinalityProofProvider},
+	chain::{Client, FinalityProofProvider, WarpSyncProvider},
 	config::{BoxFinalityProofRequestBuilder, ProtocolId, TransactionPool},
 	error,
 	utils::interval
@@ -343,6 +343,7 @@ impl<B: BlockT, H: ExHashT> Protocol<B, H> {
 		boot_node_ids: Arc<HashSet<PeerId>>,
 		use_new_block_requests_protocol: bool,
 		queue_size_report: Option<HistogramVec>,
+		warp_sync_provider: Option<Arc<dyn WarpSyncProvider<B>>>,
 	) -> error::Result<(Protocol<B, H>, sc_peerset::PeersetHandle)> {
 		let info = chain.info();
 		let sync = ChainSync::new(
@@ -352,6 +353,7 @@ impl<B: BlockT, H: ExHashT> Protocol<B, H> {
 			finality_proof_request_builder,
 			block_announce_validator,
 			config.max_parallel_downloads,
+			warp_sync_provider,
 		);
 
 		let important_peers = {
@@ -1753,6 +1755,37 @@ impl<B: BlockT, H: ExHashT> Protocol<B, H> {
 		}
 	}
 
+	/// Must be called after a [`CustomMessageOutcome::WarpProofRequest`] has been emitted,
+	/// to notify of the response having arrived.
+	pub fn on_warp_proof_response(&mut self, who: PeerId, proof: Vec<u8>) -> CustomMessageOutcome<B> {
+		trace!(target: "sync", "Warp sync proof response from {}", who);
+		if let Err(sync::BadPeer(id, repu)) = self.sync.on_warp_proof_data(who, proof) {
+			self.behaviour.disconnect_peer(&id);
+			self.peerset_handle.report_peer(id, repu);
+		}
+		CustomMessageOutcome::None
+	}
+
+	/// Must be called after a [`CustomMessageOutcome::StateRequest`] has been emitted,
+	/// to notify of the response having arrived.
+	pub fn on_state_response(
+		&mut self,
+		who: PeerId,
+		proof: Vec<u8>,
+	) -> CustomMessageOutcome<B> {
+		trace!(target: "sync", "State response from {} ({} bytes)", who, proof.len());
+		match self.sync.on_state_data(who, proof) {
+			Ok(sync::OnStateData::Continue) => CustomMessageOutcome::None,
+			Ok(sync::OnStateData::Import(origin, block)) =>
+				CustomMessageOutcome::BlockImport(origin, vec![block]),
+			Err(sync::BadPeer(id, repu)) => {
+				self.behaviour.disconnect_peer(&id);
+				self.peerset_handle.report_peer(id, repu);
+				CustomMessageOutcome::None
+			}
+		}
+	}
+
 	fn format_stats(&self) -> String {
 		let mut out = String::new();
 		for (id, stats) in &self.context_data.stats {
@@ -1837,6 +1870,16 @@ pub enum CustomMessageOutcome<B: BlockT> {
 	/// If the request times out, or the peer responds in an invalid way, the peer has to be
 	/// disconnect. This will inform the state machine that the request it has emitted is stale.
 	FinalityProofRequest { target: PeerId, block_hash: B::Hash, request: Vec<u8> },
+	/// A new warp sync proof request must be emitted.
+	/// Once you have the response, you must call `Protocol::on_warp_proof_response`.
+	/// If the request times out, or the peer responds in an invalid way, the peer has to be
+	/// disconnect. This will inform the state machine that the request it has emitted is stale.
+	WarpProofRequest { target: PeerId, begin: B::Hash },
+	/// A new state request must be emitted.
+	/// Once you have the response, you must call `Protocol::on_state_response`.
+	/// If the request times out, or the peer responds in an invalid way, the peer has to be
+	/// disconnect. This will inform the state machine that the request it has emitted is stale.
+	StateRequest { target: PeerId, block: B::Hash, start: Vec<Vec<u8>> },
 	/// Peer has a reported a new head of chain.
 	PeerNewBest(PeerId, NumberFor<B>),
 	None,
@@ -1991,6 +2034,12 @@ impl<B: BlockT, H: ExHashT> NetworkBehaviour for Protocol<B, H> {
 					GenericMessage::FinalityProofRequest(r))
 			}
 		}
+		if let Some((target, begin)) = self.sync.warp_proof_request() {
+			self.pending_messages.push_back(CustomMessageOutcome::WarpProofRequest { target, begin });
+		}
+		if let Some((target, block, start)) = self.sync.state_request() {
+			self.pending_messages.push_back(CustomMessageOutcome::StateRequest { target, block, start });
+		}
 		if let Some(message) = self.pending_messages.pop_front() {
 			return Poll::Ready(NetworkBehaviourAction::GenerateEvent(message));
 		}
@@ -35,12 +35,14 @@ use sp_consensus::{BlockOrigin, BlockStatus,
 	import_queue::{IncomingBlock, BlockImportResult, BlockImportError}
 };
 use crate::{
+	chain::WarpSyncProvider,
 	config::BoxFinalityProofRequestBuilder,
 	protocol::message::{self, generic::FinalityProofRequest, BlockAnnounce, BlockAttributes, BlockRequest, BlockResponse,
 	FinalityProofResponse, Roles},
 };
 use either::Either;
 use extra_requests::ExtraRequests;
+use warp::{StateImportResult, WarpProofImportResult, WarpSync};
 use libp2p::PeerId;
 use log::{debug, trace, warn, info, error};
 use sp_runtime::{
@@ -52,6 +54,8 @@ use std::{fmt, ops::Range, collections::{HashMap, HashSet, VecDeque}, sync::Arc}
 
 mod blocks;
 mod extra_requests;
+mod state;
+mod warp;
 
 /// Maximum blocks to request in a single packet.
 const MAX_BLOCKS_TO_REQUEST: usize = 128;
@@ -105,6 +109,12 @@ mod rep {
 
 	/// Reputation change when a peer sent us invlid ancestry result.
 	pub const UNKNOWN_ANCESTOR:Rep = Rep::new(-(1 << 16), "DB Error");
+
+	/// Reputation change for peers which send us an invalid warp sync proof.
+	pub const BAD_WARP_PROOF: Rep = Rep::new(-(1 << 29), "Bad warp sync proof");
+
+	/// Reputation change for peers which send us an invalid state response.
+	pub const BAD_STATE: Rep = Rep::new(-(1 << 29), "Bad state");
 }
 
 enum PendingRequests {
@@ -191,6 +201,8 @@ pub struct ChainSync<B: BlockT> {
 	max_parallel_downloads: u32,
 	/// Total number of processed blocks (imported or failed).
 	processed_blocks: usize,
+	/// Warp sync in progress, if we started from genesis with a warp sync provider.
+	warp_sync: Option<WarpSync<B>>,
 }
 
 /// All the data we have about a Peer that we are trying to sync with
@@ -249,7 +261,11 @@ pub enum PeerSyncState<B: BlockT> {
 	/// Downloading justification for given block hash.
 	DownloadingJustification(B::Hash),
 	/// Downloading finality proof for given block hash.
-	DownloadingFinalityProof(B::Hash)
+	DownloadingFinalityProof(B::Hash),
+	/// Downloading a warp sync proof.
+	DownloadingWarpProof,
+	/// Downloading state of the warp sync target block.
+	DownloadingState,
 }
 
 impl<B: BlockT> PeerSyncState<B> {
@@ -305,6 +321,15 @@ pub enum OnBlockData<B: BlockT> {
 	Request(PeerId, BlockRequest<B>)
 }
 
+/// Result of [`ChainSync::on_state_data`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OnStateData<B: BlockT> {
+	/// The whole state has been downloaded, the block and its state should be imported.
+	Import(BlockOrigin, IncomingBlock<B>),
+	/// More state needs to be downloaded.
+	Continue,
+}
+
 /// Result of [`ChainSync::on_block_announce`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OnBlockAnnounce {
@@ -344,6 +369,9 @@ pub enum OnBlockFinalityProof<B: BlockT> {
 
 impl<B: BlockT> ChainSync<B> {
 	/// Create a new instance.
+	///
+	/// If `warp_sync_provider` is `Some` and our best block is the genesis block, a full node
+	/// first warp syncs to the latest finalized block.
 	pub fn new(
 		role: Roles,
 		client: Arc<dyn crate::chain::Client<B>>,
@@ -351,6 +379,7 @@ impl<B: BlockT> ChainSync<B> {
 		request_builder: Option<BoxFinalityProofRequestBuilder<B>>,
 		block_announce_validator: Box<dyn BlockAnnounceValidator<B> + Send>,
 		max_parallel_downloads: u32,
+		warp_sync_provider: Option<Arc<dyn WarpSyncProvider<B>>>,
 	) -> Self {
 		let mut required_block_attributes = BlockAttributes::HEADER | BlockAttributes::JUSTIFICATION;
 
@@ -358,6 +387,22 @@ impl<B: BlockT> ChainSync<B> {
 			required_block_attributes |= BlockAttributes::BODY
 		}
 
+		let warp_sync = match warp_sync_provider {
+			Some(provider) if role.is_full() && info.best_number.is_zero() =>
+				match WarpSync::new(info.genesis_hash, client.clone(), provider) {
+					Ok(warp_sync) => Some(warp_sync),
+					Err(e) => {
+						warn!(
+							target: "sync",
+							"💔 Unable to read the genesis authorities, not warp syncing: {:?}",
+							e,
+						);
+						None
+					},
+				},
+			_ => None,
+		};
+
 		ChainSync {
 			client,
 			peers: HashMap::new(),
@@ -376,6 +421,7 @@ impl<B: BlockT> ChainSync<B> {
 			block_announce_validator,
 			max_parallel_downloads,
 			processed_blocks: 0,
+			warp_sync,
 		}
 	}
 
@@ -640,7 +686,7 @@ impl<B: BlockT> ChainSync<B> {
 
 	/// Get an iterator over all block requests of all peers.
 	pub fn block_requests(&mut self) -> impl Iterator<Item = (PeerId, BlockRequest<B>)> + '_ {
-		if self.pending_requests.is_empty() {
+		if self.pending_requests.is_empty() || self.warp_sync.is_some() {
 			return Either::Left(std::iter::empty())
 		}
 		if self.queue_blocks.len() > MAX_IMPORTING_BLOCKS {
@@ -706,6 +752,95 @@ impl<B: BlockT> ChainSync<B> {
 		Either::Right(iter)
 	}
 
+	/// Get the warp sync proof request to emit, if any.
+	///
+	/// Only one warp sync proof request is in flight at any given time.
+	pub fn warp_proof_request(&mut self) -> Option<(PeerId, B::Hash)> {
+		let begin = self.warp_sync.as_ref()?.next_warp_proof_request()?;
+		if self.peers.values().any(|p| p.state == PeerSyncState::DownloadingWarpProof) {
+			return None
+		}
+		let (id, peer) = self.peers.iter_mut()
+			.filter(|(_, p)| p.state.is_available() && !p.best_number.is_zero())
+			.max_by_key(|(_, p)| p.best_number)?;
+		trace!(target: "sync", "New warp sync proof request for {}, starting at {}", id, begin);
+		peer.state = PeerSyncState::DownloadingWarpProof;
+		Some((id.clone(), begin))
+	}
+
+	/// Get the state request to emit, if any.
+	///
+	/// Only one state request is in flight at any given time.
+	pub fn state_request(&mut self) -> Option<(PeerId, B::Hash, Vec<Vec<u8>>)> {
+		let (hash, number, start) = self.warp_sync.as_ref()?.next_state_request()?;
+		if self.peers.values().any(|p| p.state == PeerSyncState::DownloadingState) {
+			return None
+		}
+		let (id, peer) = self.peers.iter_mut()
+			.filter(|(_, p)| p.state.is_available() && p.best_number >= number)
+			.max_by_key(|(_, p)| p.best_number)?;
+		trace!(target: "sync", "New state request for {} at {}", id, hash);
+		peer.state = PeerSyncState::DownloadingState;
+		Some((id.clone(), hash, start))
+	}
+
+	/// Handle a response from the remote to a warp sync proof request that we made.
+	pub fn on_warp_proof_data(&mut self, who: PeerId, proof: Vec<u8>) -> Result<(), BadPeer> {
+		match self.peers.get_mut(&who) {
+			Some(peer) if peer.state == PeerSyncState::DownloadingWarpProof => {
+				peer.state = PeerSyncState::Available;
+			},
+			_ => {
+				trace!(target: "sync", "Unexpected warp sync proof response from {}", who);
+				return Ok(())
+			},
+		}
+
+		let warp_sync = match self.warp_sync.as_mut() {
+			Some(warp_sync) => warp_sync,
+			None => return Ok(()),
+		};
+
+		match warp_sync.import_warp_proof(&proof) {
+			WarpProofImportResult::Success => Ok(()),
+			WarpProofImportResult::BadResponse => Err(BadPeer(who, rep::BAD_WARP_PROOF)),
+		}
+	}
+
+	/// Handle a response from the remote to a state request that we made.
+	///
+	/// If this completes the state of the warp sync target, this outputs the block that
+	/// must be imported in the import queue along with its state.
+	pub fn on_state_data(
+		&mut self,
+		who: PeerId,
+		proof: Vec<u8>,
+	) -> Result<OnStateData<B>, BadPeer> {
+		match self.peers.get_mut(&who) {
+			Some(peer) if peer.state == PeerSyncState::DownloadingState => {
+				peer.state = PeerSyncState::Available;
+			},
+			_ => {
+				trace!(target: "sync", "Unexpected state response from {}", who);
+				return Ok(OnStateData::Continue)
+			},
+		}
+
+		let warp_sync = match self.warp_sync.as_mut() {
+			Some(warp_sync) => warp_sync,
+			None => return Ok(OnStateData::Continue),
+		};
+
+		match warp_sync.import_state(who.clone(), &proof) {
+			StateImportResult::Continue => Ok(OnStateData::Continue),
+			StateImportResult::Import(block) => {
+				self.queue_blocks.insert(block.hash);
+				Ok(OnStateData::Import(BlockOrigin::NetworkInitialSync, block))
+			},
+			StateImportResult::BadResponse => Err(BadPeer(who, rep::BAD_STATE)),
+		}
+	}
+
 	/// Handle a response from the remote to a block request that we made.
 	///
 	/// `request` must be the original request that triggered `response`.
@@ -744,6 +879,7 @@ impl<B: BlockT> ChainSync<B> {
 										origin: block_data.origin,
 										allow_missing_state: true,
 										import_existing: false,
+										state: None,
 									}
 								}).collect()
 						}
@@ -763,6 +899,7 @@ impl<B: BlockT> ChainSync<B> {
 									origin: Some(who.clone()),
 									allow_missing_state: true,
 									import_existing: false,
+									state: None,
 								}
 							}).collect()
 						}
@@ -834,7 +971,9 @@ impl<B: BlockT> ChainSync<B> {
 
 						| PeerSyncState::Available
 						| PeerSyncState::DownloadingJustification(..)
-						| PeerSyncState::DownloadingFinalityProof(..) => Vec::new()
+						| PeerSyncState::DownloadingFinalityProof(..)
+						| PeerSyncState::DownloadingWarpProof
+						| PeerSyncState::DownloadingState => Vec::new()
 					}
 				} else {
 					// When request.is_none() this is a block announcement. Just accept blocks.
@@ -848,6 +987,7 @@ impl<B: BlockT> ChainSync<B> {
 							origin: Some(who.clone()),
 							allow_missing_state: true,
 							import_existing: false,
+							state: None,
 						}
 					}).collect()
 				}
@@ -1003,7 +1143,19 @@ impl<B: BlockT> ChainSync<B> {
 		}
 		self.processed_blocks += results.len();
 
+		let warp_sync_target = self.warp_sync.as_ref().and_then(|w| w.importing_block());
+		let mut warp_sync_done = false;
+
 		for (result, hash) in results {
+			if Some(hash) == warp_sync_target {
+				if result.is_ok() {
+					warp_sync_done = true;
+				} else if let Some(warp_sync) = self.warp_sync.as_mut() {
+					warn!(target: "sync", "💔 Error importing warp sync target {:?}, downloading state again", hash);
+					warp_sync.on_import_failed();
+				}
+			}
+
 			if has_error {
 				continue;
 			}
@@ -1081,6 +1233,13 @@ impl<B: BlockT> ChainSync<B> {
 			};
 		}
 
+		if warp_sync_done {
+			info!("⏩ Warp sync complete, continuing with regular sync");
+			self.warp_sync = None;
+			// peers are now asked for blocks on top of the imported one.
+			output.extend(self.restart());
+		}
+
 		self.pending_requests.set_all();
 		output.into_iter()
 	}
@@ -1569,6 +1728,7 @@ mod test {
 			None,
 			block_announce_validator,
 			1,
+			None,
 		);
 
 		let (a1_hash, a1_number) = {
@@ -0,0 +1,159 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::chain::Client;
+use codec::Decode;
+use libp2p::PeerId;
+use log::debug;
+use sc_client_api::{KeyValueStates, KeyValueStorageLevel, StorageProof};
+use sp_consensus::{ImportedState, import_queue::IncomingBlock};
+use sp_runtime::{Justification, traits::{Block as BlockT, Header, NumberFor}};
+use std::{collections::{BTreeMap, HashMap}, sync::Arc};
+
+/// Result of [`StateSync::import`].
+pub(crate) enum ImportResult<B: BlockT> {
+	/// The proven entries have been recorded, more state must be downloaded.
+	Continue,
+	/// The whole state has been downloaded, the block must be imported.
+	Import(IncomingBlock<B>),
+	/// The response is invalid.
+	BadResponse,
+}
+
+/// State sync state machine. Downloads the state of a block, including its child tries,
+/// by ranges of key/value pairs whose proofs are checked against the block state root.
+pub(crate) struct StateSync<B: BlockT> {
+	target_header: B::Header,
+	target_justification: Option<Justification>,
+	/// Position in the state after which the next range starts.
+	last_key: Vec<Vec<u8>>,
+	/// Downloaded key/value pairs, by prefixed storage key of their child trie (empty for the
+	/// top trie).
+	state: HashMap<Vec<u8>, BTreeMap<Vec<u8>, Vec<u8>>>,
+	complete: bool,
+	imported_bytes: u64,
+	client: Arc<dyn Client<B>>,
+}
+
+impl<B: BlockT> StateSync<B> {
+	/// Start downloading the state of the given block.
+	pub(crate) fn new(
+		client: Arc<dyn Client<B>>,
+		target_header: B::Header,
+		target_justification: Option<Justification>,
+	) -> Self {
+		StateSync {
+			target_header,
+			target_justification,
+			last_key: Vec::new(),
+			state: HashMap::new(),
+			complete: false,
+			imported_bytes: 0,
+			client,
+		}
+	}
+
+	/// Block and start position of the next state request, if the state is not complete.
+	pub(crate) fn next_request(&self) -> Option<(B::Hash, NumberFor<B>, Vec<Vec<u8>>)> {
+		if self.complete {
+			return None;
+		}
+		Some((self.target_header.hash(), *self.target_header.number(), self.last_key.clone()))
+	}
+
+	/// Total size of the downloaded keys and values.
+	pub(crate) fn imported_bytes(&self) -> u64 {
+		self.imported_bytes
+	}
+
+	/// Verify and record a proof of a range of the state, received from a peer.
+	pub(crate) fn import(&mut self, who: PeerId, proof: &[u8]) -> ImportResult<B> {
+		if self.complete {
+			return ImportResult::Continue;
+		}
+
+		let proof = match StorageProof::decode(&mut &proof[..]) {
+			Ok(proof) if !proof.is_empty() => proof,
+			Ok(_) => {
+				debug!(target: "sync", "Empty state proof from {}", who);
+				return ImportResult::BadResponse;
+			},
+			Err(e) => {
+				debug!(target: "sync", "Undecodable state proof from {}: {:?}", who, e);
+				return ImportResult::BadResponse;
+			},
+		};
+
+		let root = *self.target_header.state_root();
+		let states = match self.client.verify_range_proof(root, proof, &self.last_key) {
+			Ok(states) => states,
+			Err(e) => {
+				debug!(target: "sync", "Bad state proof from {}: {:?}", who, e);
+				return ImportResult::BadResponse;
+			},
+		};
+
+		let next = states.next_start(&self.last_key);
+		if next.as_ref() == Some(&self.last_key) {
+			debug!(target: "sync", "State proof from {} doesn't prove any new entry", who);
+			return ImportResult::BadResponse;
+		}
+
+		for level in states.0 {
+			let entries = self.state.entry(level.parent_storage_key).or_default();
+			for (key, value) in level.key_values {
+				self.imported_bytes += (key.len() + value.len()) as u64;
+				entries.insert(key, value);
+			}
+		}
+
+		match next {
+			Some(next) => {
+				self.last_key = next;
+				ImportResult::Continue
+			},
+			None => {
+				self.complete = true;
+				ImportResult::Import(self.imported_block(who))
+			},
+		}
+	}
+
+	/// Block to import, along with the downloaded state.
+	fn imported_block(&mut self, who: PeerId) -> IncomingBlock<B> {
+		let mut levels: Vec<_> = std::mem::take(&mut self.state).into_iter()
+			.map(|(parent_storage_key, key_values)| KeyValueStorageLevel {
+				parent_storage_key,
+				key_values: key_values.into_iter().collect(),
+				complete: true,
+			})
+			.collect();
+		// top trie first.
+		levels.sort_by(|a, b| a.parent_storage_key.cmp(&b.parent_storage_key));
+
+		let hash = self.target_header.hash();
+		IncomingBlock {
+			hash,
+			header: Some(self.target_header.clone()),
+			body: None,
+			justification: self.target_justification.clone(),
+			origin: Some(who),
+			allow_missing_state: true,
+			import_existing: false,
+			state: Some(ImportedState { block: hash, state: KeyValueStates(levels) }),
+		}
+	}
+}
@@ -0,0 +1,190 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::chain::{Client, WarpSyncProvider, WarpSyncVerificationResult};
+use super::state::{ImportResult, StateSync};
+use libp2p::PeerId;
+use log::{debug, info};
+use sp_consensus::import_queue::IncomingBlock;
+use sp_finality_grandpa::{AuthorityList, SetId};
+use sp_runtime::{Justification, traits::{Block as BlockT, Header, NumberFor}};
+use std::sync::Arc;
+
+/// Current phase of the warp sync.
+enum Phase<B: BlockT> {
+	/// Downloading and verifying proofs of the authority set handoffs, starting at
+	/// `last_hash`, where the given set is active.
+	WarpProof {
+		set_id: SetId,
+		authorities: AuthorityList,
+		last_hash: B::Hash,
+	},
+	/// Downloading the state of the proven block.
+	State {
+		header: B::Header,
+		justification: Justification,
+		state_sync: StateSync<B>,
+	},
+	/// The proven block and its state have been sent to the import queue.
+	Importing {
+		header: B::Header,
+		justification: Justification,
+	},
+}
+
+/// Result of [`WarpSync::import_warp_proof`].
+pub(crate) enum WarpProofImportResult {
+	/// The proof is valid.
+	Success,
+	/// The proof is invalid.
+	BadResponse,
+}
+
+/// Result of [`WarpSync::import_state`].
+pub(crate) enum StateImportResult<B: BlockT> {
+	/// The proven entries have been recorded, more state must be downloaded.
+	Continue,
+	/// The whole state has been downloaded, the block must be imported.
+	Import(IncomingBlock<B>),
+	/// The response is invalid.
+	BadResponse,
+}
+
+/// Warp sync state machine. Jumps to the latest finalized block known to peers by
+/// verifying a proof of finality authority set handoffs, then downloads the state
+/// at that block.
+pub(crate) struct WarpSync<B: BlockT> {
+	phase: Phase<B>,
+	provider: Arc<dyn WarpSyncProvider<B>>,
+	client: Arc<dyn Client<B>>,
+}
+
+impl<B: BlockT> WarpSync<B> {
+	/// Start a warp sync from the genesis block, with the genesis authority set.
+	pub(crate) fn new(
+		genesis_hash: B::Hash,
+		client: Arc<dyn Client<B>>,
+		provider: Arc<dyn WarpSyncProvider<B>>,
+	) -> Result<Self, sp_blockchain::Error> {
+		let phase = Phase::WarpProof {
+			set_id: 0,
+			authorities: provider.genesis_authorities()?,
+			last_hash: genesis_hash,
+		};
+		Ok(WarpSync { phase, provider, client })
+	}
+
+	/// Block to request the next warp sync proof from, if we are downloading proofs.
+	pub(crate) fn next_warp_proof_request(&self) -> Option<B::Hash> {
+		match &self.phase {
+			Phase::WarpProof { last_hash, .. } => Some(*last_hash),
+			_ => None,
+		}
+	}
+
+	/// Block and start position of the next state request, if we are downloading state.
+	pub(crate) fn next_state_request(&self) -> Option<(B::Hash, NumberFor<B>, Vec<Vec<u8>>)> {
+		match &self.phase {
+			Phase::State { state_sync, .. } => state_sync.next_request(),
+			_ => None,
+		}
+	}
+
+	/// Hash of the block that has been sent to the import queue, if any.
+	pub(crate) fn importing_block(&self) -> Option<B::Hash> {
+		match &self.phase {
+			Phase::Importing { header, .. } => Some(header.hash()),
+			_ => None,
+		}
+	}
+
+	/// Verify and apply a warp sync proof received from a peer.
+	pub(crate) fn import_warp_proof(&mut self, proof: &[u8]) -> WarpProofImportResult {
+		let (set_id, authorities) = match &self.phase {
+			Phase::WarpProof { set_id, authorities, .. } => (*set_id, authorities.clone()),
+			_ => return WarpProofImportResult::Success,
+		};
+
+		match self.provider.verify(proof, set_id, authorities) {
+			Err(e) => {
+				debug!(target: "sync", "Bad warp sync proof: {:?}", e);
+				WarpProofImportResult::BadResponse
+			},
+			Ok(WarpSyncVerificationResult::Partial(set_id, authorities, last_hash)) => {
+				debug!(target: "sync", "Verified partial warp sync proof, now at set {}", set_id);
+				self.phase = Phase::WarpProof { set_id, authorities, last_hash };
+				WarpProofImportResult::Success
+			},
+			Ok(WarpSyncVerificationResult::Complete(set_id, _, header, justification)) => {
+				info!(
+					"⏩ Warp sync proof verified up to #{} ({}), set {}. Downloading state.",
+					header.number(),
+					header.hash(),
+					set_id,
+				);
+				let state_sync = StateSync::new(
+					self.client.clone(),
+					header.clone(),
+					Some(justification.clone()),
+				);
+				self.phase = Phase::State { header, justification, state_sync };
+				WarpProofImportResult::Success
+			},
+		}
+	}
+
+	/// Verify and record a proof of a range of the state received from a peer.
+	pub(crate) fn import_state(&mut self, who: PeerId, proof: &[u8]) -> StateImportResult<B> {
+		let (header, justification, state_sync) = match &mut self.phase {
+			Phase::State { header, justification, state_sync } =>
+				(header, justification, state_sync),
+			_ => return StateImportResult::Continue,
+		};
+
+		match state_sync.import(who, proof) {
+			ImportResult::Continue => StateImportResult::Continue,
+			ImportResult::BadResponse => StateImportResult::BadResponse,
+			ImportResult::Import(block) => {
+				info!(
+					"⏩ Downloaded state of #{} ({} bytes), importing.",
+					header.number(),
+					state_sync.imported_bytes(),
+				);
+				self.phase = Phase::Importing {
+					header: header.clone(),
+					justification: justification.clone(),
+				};
+				StateImportResult::Import(block)
+			},
+		}
+	}
+
+	/// The import of the block and its state has failed. Downloads the state again.
+	pub(crate) fn on_import_failed(&mut self) {
+		if let Phase::Importing { header, justification } = &self.phase {
+			let state_sync = StateSync::new(
+				self.client.clone(),
+				header.clone(),
+				Some(justification.clone()),
+			);
+			self.phase = Phase::State {
+				header: header.clone(),
+				justification: justification.clone(),
+				state_sync,
+			};
+		}
+	}
+}
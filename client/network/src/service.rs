@@ -28,14 +28,14 @@
 use crate::{
 	ExHashT, NetworkStateInfo,
 	behaviour::{Behaviour, BehaviourOut},
-	config::{parse_addr, parse_str_addr, NonReservedPeerMode, Params, Role, TransportConfig},
+	config::{parse_addr, parse_str_addr, NonReservedPeerMode, Params, Role, SyncMode, TransportConfig},
 	discovery::DiscoveryConfig,
 	error::Error,
 	network_state::{
 		NetworkState, NotConnectedPeer as NetworkStateNotConnectedPeer, Peer as NetworkStatePeer,
 	},
 	on_demand_layer::AlwaysBadChecker,
-	light_client_handler, block_requests, finality_requests,
+	light_client_handler, block_requests, finality_requests, state_requests, warp_requests,
 	protocol::{self, event::Event, LegacyConnectionKillError, sync::SyncState, PeerInfo, Protocol},
 	transport, ReputationChange,
 };
@@ -124,6 +124,13 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 
 		let boot_node_ids = Arc::new(boot_node_ids);
 
+		if params.network_config.sync_mode == SyncMode::Warp && params.warp_sync.is_none() {
+			warn!(
+				target: "sub-libp2p",
+				"Warp sync requested but no warp sync provider is configured, syncing all blocks instead",
+			);
+		}
+
 		// Check for duplicate bootnodes.
 		known_addresses.iter()
 			.try_for_each(|(peer_id, addr)|
@@ -214,6 +221,10 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 			boot_node_ids.clone(),
 			params.network_config.use_new_block_requests_protocol,
 			metrics.as_ref().map(|m| m.notifications_queues_size.clone()),
+			match params.network_config.sync_mode {
+				SyncMode::Full => None,
+				SyncMode::Warp => params.warp_sync.clone(),
+			},
 		)?;
 
 		// Build the swarm.
@@ -231,6 +242,14 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 				let config = finality_requests::Config::new(&params.protocol_id);
 				finality_requests::FinalityProofRequests::new(config, params.finality_proof_provider.clone())
 			};
+			let warp_sync_requests = {
+				let config = warp_requests::Config::new(&params.protocol_id);
+				warp_requests::WarpSyncRequests::new(config, params.warp_sync.clone())
+			};
+			let state_requests = {
+				let config = state_requests::Config::new(&params.protocol_id);
+				state_requests::StateRequests::new(config, params.chain.clone())
+			};
 			let light_client_handler = {
 				let config = light_client_handler::Config::new(&params.protocol_id);
 				light_client_handler::LightClientHandler::new(
@@ -269,6 +288,8 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 				local_public,
 				block_requests,
 				finality_proof_requests,
+				warp_sync_requests,
+				state_requests,
 				light_client_handler,
 				discovery_config
 			);
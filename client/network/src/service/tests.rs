@@ -95,6 +95,7 @@ fn build_test_full_node(config: config::NetworkConfiguration)
 		chain: client.clone(),
 		finality_proof_provider: None,
 		finality_proof_request_builder: None,
+		warp_sync: None,
 		on_demand: None,
 		transaction_pool: Arc::new(crate::config::EmptyTransactionPool),
 		protocol_id: config::ProtocolId::from(&b"/test-protocol-name"[..]),
@@ -635,6 +635,7 @@ pub trait TestNetFactory: Sized {
 				PeersClient::Full(client.clone(), backend.clone()),
 			),
 			finality_proof_request_builder,
+			warp_sync: None,
 			on_demand: None,
 			transaction_pool: Arc::new(EmptyTransactionPool),
 			protocol_id: ProtocolId::from(&b"test-protocol-name"[..]),
@@ -713,6 +714,7 @@ pub trait TestNetFactory: Sized {
 				PeersClient::Light(client.clone(), backend.clone())
 			),
 			finality_proof_request_builder,
+			warp_sync: None,
 			on_demand: None,
 			transaction_pool: Arc::new(EmptyTransactionPool),
 			protocol_id: ProtocolId::from(&b"test-protocol-name"[..]),
@@ -0,0 +1,127 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Transaction pool introspection helpers for author RPC module.
+
+use sp_core::Bytes;
+use sp_transaction_pool::{InPoolStatus, TransactionDetails};
+use serde::{Serialize, Deserialize};
+
+/// Details of a transaction in the pool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolTransaction<Hash> {
+	/// Transaction hash.
+	pub hash: Hash,
+	/// Transaction priority (higher = better).
+	pub priority: u64,
+	/// Block number at which the transaction stops being valid.
+	pub longevity: u64,
+	/// Tags required by the transaction.
+	pub requires: Vec<Bytes>,
+	/// Tags provided by the transaction.
+	pub provides: Vec<Bytes>,
+	/// Required tags that are not provided by any transaction in the pool yet.
+	pub missing_tags: Vec<Bytes>,
+	/// Time the transaction entered the pool, in milliseconds since UNIX epoch.
+	pub imported_at: u64,
+}
+
+impl<Hash> From<TransactionDetails<Hash>> for PoolTransaction<Hash> {
+	fn from(details: TransactionDetails<Hash>) -> Self {
+		let tags = |tags: Vec<Vec<u8>>| tags.into_iter().map(Into::into).collect();
+		PoolTransaction {
+			hash: details.hash,
+			priority: details.priority,
+			longevity: details.longevity,
+			requires: tags(details.requires),
+			provides: tags(details.provides),
+			missing_tags: tags(details.missing_tags),
+			imported_at: details.imported_at,
+		}
+	}
+}
+
+/// Contents of the transaction pool queues.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolContent<Hash> {
+	/// Transactions that are ready to be included in a block.
+	pub ready: Vec<PoolTransaction<Hash>>,
+	/// Transactions waiting for some of their required tags.
+	pub future: Vec<PoolTransaction<Hash>>,
+}
+
+impl<Hash> From<sp_transaction_pool::PoolContent<Hash>> for PoolContent<Hash> {
+	fn from(content: sp_transaction_pool::PoolContent<Hash>) -> Self {
+		PoolContent {
+			ready: content.ready.into_iter().map(Into::into).collect(),
+			future: content.future.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// Status of a transaction in the pool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PoolTransactionStatus<Hash> {
+	/// Transaction is part of the ready queue.
+	Ready(PoolTransaction<Hash>),
+	/// Transaction is part of the future queue.
+	Future(PoolTransaction<Hash>),
+	/// Transaction is not in the pool and has been recently banned from entering it.
+	Banned,
+	/// Transaction is not known to the pool.
+	Unknown,
+}
+
+impl<Hash> From<InPoolStatus<Hash>> for PoolTransactionStatus<Hash> {
+	fn from(status: InPoolStatus<Hash>) -> Self {
+		match status {
+			InPoolStatus::Ready(details) => PoolTransactionStatus::Ready(details.into()),
+			InPoolStatus::Future(details) => PoolTransactionStatus::Future(details.into()),
+			InPoolStatus::Banned => PoolTransactionStatus::Banned,
+			InPoolStatus::Unknown => PoolTransactionStatus::Unknown,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_serialize_pool_status() {
+		let status = PoolTransactionStatus::Future(PoolTransaction {
+			hash: 5u64,
+			priority: 10,
+			longevity: 64,
+			requires: vec![vec![1].into(), vec![2].into()],
+			provides: vec![vec![3].into()],
+			missing_tags: vec![vec![2].into()],
+			imported_at: 1_000,
+		});
+
+		assert_eq!(
+			serde_json::to_string(&status).unwrap(),
+			r#"{"future":{"hash":5,"priority":10,"longevity":64,"requires":["0x01","0x02"],"provides":["0x03"],"missingTags":["0x02"],"importedAt":1000}}"#,
+		);
+		assert_eq!(
+			serde_json::to_string(&PoolTransactionStatus::<u64>::Banned).unwrap(),
+			r#""banned""#,
+		);
+	}
+}
@@ -18,6 +18,7 @@
 
 pub mod error;
 pub mod hash;
+pub mod helpers;
 
 use jsonrpc_derive::rpc;
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
@@ -68,6 +69,19 @@ pub trait AuthorApi<Hash, BlockHash> {
 	#[rpc(name = "author_pendingExtrinsics")]
 	fn pending_extrinsics(&self) -> Result<Vec<Bytes>>;
 
+	/// Returns details of all transactions in the ready and future queues of the pool.
+	///
+	/// Includes priority, longevity, required and provided tags of every transaction
+	/// as well as the tags that transactions in the future queue are still waiting for.
+	#[rpc(name = "author_poolContent")]
+	fn pool_content(&self) -> Result<helpers::PoolContent<Hash>>;
+
+	/// Returns status of the transaction with given hash in the pool.
+	///
+	/// Transactions that are not in the pool, but were recently banned are reported as `banned`.
+	#[rpc(name = "author_poolStatus")]
+	fn pool_status(&self, hash: Hash) -> Result<helpers::PoolTransactionStatus<Hash>>;
+
 	/// Remove given extrinsic from the pool and temporarily ban it to prevent reimporting.
 	#[rpc(name = "author_removeExtrinsic")]
 	fn remove_extrinsic(&self,
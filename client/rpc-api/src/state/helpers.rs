@@ -28,3 +28,53 @@ pub struct ReadProof<Hash> {
 	/// A proof used to prove that storage entries are included in the storage trie
 	pub proof: Vec<Bytes>,
 }
+
+/// Storage accesses recorded while re-executing a block, as returned by `state_traceBlock`.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTrace<Hash> {
+	/// Hash of the traced block
+	pub block_hash: Hash,
+	/// Hash of the parent block whose state the block was executed on
+	pub parent_hash: Hash,
+	/// Storage accesses of each execution phase, in execution order
+	pub phases: Vec<PhaseTrace>,
+}
+
+/// A phase of block execution.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TracePhase {
+	/// Block initialization, running all `on_initialize` hooks.
+	OnInitialize,
+	/// Application of the extrinsic at the given index of the block body.
+	ApplyExtrinsic(u32),
+	/// Block finalization, running all `on_finalize` hooks.
+	OnFinalize,
+}
+
+/// Storage accesses made during a single execution phase.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseTrace {
+	/// The execution phase
+	pub phase: TracePhase,
+	/// The storage accesses, in the order they were made
+	pub accesses: Vec<StorageAccess>,
+}
+
+/// A single storage access.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageAccess {
+	/// The externalities method that accessed the storage, e.g. `Get`, `Put` or `ClearPrefix`
+	pub method: String,
+	/// The child storage key, if a child trie was accessed
+	pub child: Option<Bytes>,
+	/// The accessed key, or the cleared prefix for prefix removals
+	pub key: Bytes,
+	/// Size in bytes of the value that was read or written, if any
+	pub value_size: Option<u64>,
+	/// Target and name of the innermost traced span the access was made in
+	pub span: Option<(String, String)>,
+}
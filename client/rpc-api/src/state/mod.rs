@@ -29,7 +29,7 @@ use sp_version::RuntimeVersion;
 use self::error::FutureResult;
 
 pub use self::gen_client::Client as StateClient;
-pub use self::helpers::ReadProof;
+pub use self::helpers::{ReadProof, BlockTrace, PhaseTrace, TracePhase, StorageAccess};
 
 /// Substrate state API
 #[rpc]
@@ -106,6 +106,26 @@ pub trait StateApi<Hash> {
 	#[rpc(name = "state_getReadProof")]
 	fn read_proof(&self, keys: Vec<StorageKey>, hash: Option<Hash>) -> FutureResult<ReadProof<Hash>>;
 
+	/// Re-executes a block on top of its parent's state and returns the storage
+	/// accesses made during each phase of its execution.
+	///
+	/// `targets` is a comma separated list of tracing targets, with optional levels
+	/// (e.g. `pallet_balances=trace`), of the runtime spans accesses are attributed to.
+	/// If `key_prefix` is given, only accesses to keys starting with it are returned.
+	///
+	/// Runtime spans are only captured when the block is executed natively, i.e. with the
+	/// native runtime matching the on-chain runtime version. Otherwise the accesses are not
+	/// attributed to any span.
+	///
+	/// This method is considered unsafe.
+	#[rpc(name = "state_traceBlock")]
+	fn trace_block(
+		&self,
+		block: Hash,
+		targets: Option<String>,
+		key_prefix: Option<StorageKey>,
+	) -> FutureResult<BlockTrace<Hash>>;
+
 	/// New runtime version subscription
 	#[pubsub(
 		subscription = "state_runtimeVersion",
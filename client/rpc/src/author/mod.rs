@@ -159,6 +159,14 @@ impl<P, Client> AuthorApi<TxHash<P>, BlockHash<P>> for Author<P, Client>
 		Ok(self.pool.ready().map(|tx| tx.data().encode().into()).collect())
 	}
 
+	fn pool_content(&self) -> Result<helpers::PoolContent<TxHash<P>>> {
+		Ok(self.pool.content().into())
+	}
+
+	fn pool_status(&self, hash: TxHash<P>) -> Result<helpers::PoolTransactionStatus<TxHash<P>>> {
+		Ok(self.pool.in_pool_status(&hash).into())
+	}
+
 	fn remove_extrinsic(
 		&self,
 		bytes_or_hash: Vec<hash::ExtrinsicOrHash<TxHash<P>>>,
@@ -182,6 +182,34 @@ fn should_return_pending_extrinsics() {
 	);
 }
 
+#[test]
+fn should_return_pool_content_and_status() {
+	let p = TestSetup::default().author();
+
+	let ex1 = uxt(AccountKeyring::Alice, 1);
+	let hash1 = p.submit_extrinsic(ex1.encode().into()).wait().unwrap();
+	let ex2 = uxt(AccountKeyring::Bob, 0);
+	let hash2 = p.submit_extrinsic(ex2.encode().into()).wait().unwrap();
+
+	let content = p.pool_content().unwrap();
+	assert_eq!(content.ready.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![hash2]);
+	assert_eq!(content.future.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![hash1]);
+	assert_eq!(content.future[0].missing_tags, content.future[0].requires);
+
+	assert_matches!(
+		p.pool_status(hash1),
+		Ok(helpers::PoolTransactionStatus::Future(ref tx)) if tx.hash == hash1
+	);
+	assert_matches!(
+		p.pool_status(hash2),
+		Ok(helpers::PoolTransactionStatus::Ready(ref tx)) if tx.missing_tags.is_empty()
+	);
+
+	p.remove_extrinsic(vec![hash::ExtrinsicOrHash::Hash(hash1)]).unwrap();
+	assert_eq!(p.pool_status(hash1).unwrap(), helpers::PoolTransactionStatus::Banned);
+	assert_eq!(p.pool_status(Default::default()).unwrap(), helpers::PoolTransactionStatus::Unknown);
+}
+
 #[test]
 fn should_remove_extrinsics() {
 	let setup = TestSetup::default();
@@ -24,24 +24,27 @@ use log::warn;
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
 use rpc::{Result as RpcResult, futures::{stream, Future, Sink, Stream, future::result}};
 
-use sc_rpc_api::{Subscriptions, state::ReadProof};
+use sc_rpc_api::{Subscriptions, state::{ReadProof, BlockTrace, PhaseTrace, TracePhase, StorageAccess}};
 use sc_client_api::backend::Backend;
 use sp_blockchain::{Result as ClientResult, Error as ClientError, HeaderMetadata, CachedHeaderMetadata, HeaderBackend};
 use sc_client_api::BlockchainEvents;
 use sp_core::{
-	Bytes, storage::{well_known_keys, StorageKey, StorageData, StorageChangeSet,
+	Bytes, ExecutionContext, storage::{well_known_keys, StorageKey, StorageData, StorageChangeSet,
 	ChildInfo, ChildType, PrefixedStorageKey},
 };
 use sp_version::RuntimeVersion;
 use sp_runtime::{
-	generic::BlockId, traits::{Block as BlockT, NumberFor, SaturatedConversion, CheckedSub},
+	generic::BlockId, traits::{Block as BlockT, Header as HeaderT, NumberFor, SaturatedConversion, CheckedSub},
 };
 
-use sp_api::{Metadata, ProvideRuntimeApi, CallApiAt};
+use sp_api::{Metadata, ProvideRuntimeApi, CallApiAt, Core};
+use sp_state_machine::STORAGE_ACCESS_TARGET;
+use sc_block_builder::BlockBuilderApi;
+use sc_tracing::{TraceCollector, CollectedEvent};
 
 use super::{StateBackend, ChildStateBackend, error::{FutureResult, Error, Result}, client_err};
 use std::marker::PhantomData;
-use sc_client_api::{CallExecutor, StorageProvider, ExecutorProvider, ProofProvider};
+use sc_client_api::{CallExecutor, StorageProvider, ExecutorProvider, ProofProvider, BlockBackend};
 
 /// Ranges to query in state_queryStorage.
 struct QueryStorageRange<Block: BlockT> {
@@ -221,9 +224,10 @@ impl<BE, Block, Client> StateBackend<Block, Client> for FullState<BE, Block, Cli
 	BE: Backend<Block> + 'static,
 	Client: ExecutorProvider<Block> + StorageProvider<Block, BE> + ProofProvider<Block> + HeaderBackend<Block>
 		+ HeaderMetadata<Block, Error = sp_blockchain::Error> + BlockchainEvents<Block>
-		+ CallApiAt<Block, Error = sp_blockchain::Error> + ProvideRuntimeApi<Block>
+		+ BlockBackend<Block> + CallApiAt<Block, Error = sp_blockchain::Error> + ProvideRuntimeApi<Block>
 		+ Send + Sync + 'static,
-	Client::Api: Metadata<Block, Error = sp_blockchain::Error>,
+	Client::Api: Metadata<Block, Error = sp_blockchain::Error>
+		+ BlockBuilderApi<Block, Error = sp_blockchain::Error>,
 {
 	fn call(
 		&self,
@@ -371,6 +375,65 @@ impl<BE, Block, Client> StateBackend<Block, Client> for FullState<BE, Block, Cli
 		))
 	}
 
+	fn trace_block(
+		&self,
+		block: Block::Hash,
+		targets: Option<String>,
+		key_prefix: Option<StorageKey>,
+	) -> FutureResult<BlockTrace<Block::Hash>> {
+		let call_fn = move || {
+			let id = BlockId::Hash(block);
+			let header = self.client.header(id)
+				.and_then(|header| header.ok_or_else(|| ClientError::UnknownBlock(format!("{}", block))))
+				.map_err(client_err)?;
+			let extrinsics = self.client.block_body(&id)
+				.and_then(|body| body.ok_or_else(|| ClientError::UnknownBlock(
+					format!("Body of block {} is not available", block)
+				)))
+				.map_err(client_err)?;
+			let parent_hash = *header.parent_hash();
+			let parent = BlockId::Hash(parent_hash);
+
+			let dispatch = tracing::Dispatch::new(TraceCollector::new(
+				targets.as_deref().unwrap_or_default(),
+				&[STORAGE_ACCESS_TARGET],
+			));
+			let collector = dispatch.downcast_ref::<TraceCollector>()
+				.expect("dispatch was created from a `TraceCollector`; qed");
+			let key_prefix = key_prefix.as_ref().map(|prefix| &prefix.0[..]);
+			let mut phases = Vec::with_capacity(extrinsics.len() + 2);
+			let mut record_phase = |phase| phases.push(PhaseTrace {
+				phase,
+				accesses: storage_accesses(collector.take_events(), key_prefix),
+			});
+
+			// All phases are executed with the same runtime api instance, so that the changes
+			// of every phase are visible to the following ones, just like on block import.
+			let api = self.client.runtime_api();
+			tracing::dispatcher::with_default(&dispatch, || -> ClientResult<()> {
+				api.initialize_block_with_context(&parent, ExecutionContext::Importing, &header)?;
+				record_phase(TracePhase::OnInitialize);
+
+				for (index, extrinsic) in extrinsics.into_iter().enumerate() {
+					// Failing extrinsics still access the storage, so their outcome is ignored.
+					let _ = api.apply_extrinsic_with_context(
+						&parent,
+						ExecutionContext::Importing,
+						extrinsic,
+					)?;
+					record_phase(TracePhase::ApplyExtrinsic(index as u32));
+				}
+
+				api.finalize_block_with_context(&parent, ExecutionContext::Importing)?;
+				record_phase(TracePhase::OnFinalize);
+				Ok(())
+			}).map_err(client_err)?;
+
+			Ok(BlockTrace { block_hash: block, parent_hash, phases })
+		};
+		Box::new(result(call_fn()))
+	}
+
 	fn subscribe_runtime_version(
 		&self,
 		_meta: crate::metadata::Metadata,
@@ -588,6 +651,30 @@ pub(crate) fn split_range(size: usize, middle: Option<usize>) -> (Range<usize>,
 	(range1, range2)
 }
 
+/// Converts the events emitted for `STORAGE_ACCESS_TARGET` into storage accesses,
+/// skipping accesses to keys not starting with `key_prefix`.
+fn storage_accesses(events: Vec<CollectedEvent>, key_prefix: Option<&[u8]>) -> Vec<StorageAccess> {
+	events.into_iter()
+		.filter(|event| event.target == STORAGE_ACCESS_TARGET)
+		.filter_map(|event| {
+			let key: Bytes = event.value("key")?.parse().ok()?;
+			if key_prefix.map_or(false, |prefix| !key.0.starts_with(prefix)) {
+				return None;
+			}
+			let child = event.value("child")
+				.filter(|child| !child.is_empty())
+				.and_then(|child| child.parse().ok());
+			Some(StorageAccess {
+				method: event.value("method")?.to_string(),
+				child,
+				key,
+				value_size: event.value("size").and_then(|size| size.parse().ok()),
+				span: event.span.map(|span| (span.target, span.name)),
+			})
+		})
+		.collect()
+}
+
 fn invalid_block_range<B: BlockT>(
 	from: &CachedHeaderMetadata<B>,
 	to: &CachedHeaderMetadata<B>,
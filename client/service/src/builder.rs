@@ -36,7 +36,7 @@ use futures::{
 };
 use sc_keystore::Store as Keystore;
 use log::{info, warn, error};
-use sc_network::config::{Role, FinalityProofProvider, OnDemand, BoxFinalityProofRequestBuilder};
+use sc_network::config::{Role, FinalityProofProvider, OnDemand, BoxFinalityProofRequestBuilder, WarpSyncProvider};
 use sc_network::{NetworkService, NetworkStateInfo};
 use parking_lot::{Mutex, RwLock};
 use sp_runtime::generic::BlockId;
@@ -94,10 +94,48 @@ pub struct ServiceBuilder<TBl, TRtApi, TCl, TFchr, TSc, TImpQu, TFprb, TFpp,
 	finality_proof_request_builder: Option<TFprb>,
 	finality_proof_provider: Option<TFpp>,
 	transaction_pool: Arc<TExPool>,
-	rpc_extensions: TRpc,
+	rpc_extensions_builder: TRpc,
 	remote_backend: Option<Arc<dyn RemoteBlockchain<TBl>>>,
 	marker: PhantomData<(TBl, TRtApi)>,
 	block_announce_validator_builder: Option<Box<dyn FnOnce(Arc<TCl>) -> Box<dyn BlockAnnounceValidator<TBl> + Send> + Send>>,
+	warp_sync_provider: Option<Arc<dyn WarpSyncProvider<TBl>>>,
+}
+
+/// Builds the RPC extensions exposed on a single RPC interface.
+///
+/// Whether unsafe RPC methods may be called on an interface is only known when the
+/// RPC servers are started, so the extensions are built lazily for each of them.
+pub trait RpcExtensionBuilder {
+	/// The type of the RPC extensions that are built.
+	type Output: sc_rpc::RpcExtension<sc_rpc::Metadata>;
+
+	/// Build the RPC extensions for an interface with the given `DenyUnsafe` policy.
+	fn build(&self, deny_unsafe: sc_rpc::DenyUnsafe) -> Self::Output;
+}
+
+impl<F, R> RpcExtensionBuilder for F where
+	F: Fn(sc_rpc::DenyUnsafe) -> R,
+	R: sc_rpc::RpcExtension<sc_rpc::Metadata>,
+{
+	type Output = R;
+
+	fn build(&self, deny_unsafe: sc_rpc::DenyUnsafe) -> Self::Output {
+		(*self)(deny_unsafe)
+	}
+}
+
+/// An [`RpcExtensionBuilder`] that returns a clone of the same RPC extensions for every
+/// interface, ignoring its `DenyUnsafe` policy.
+pub struct NoopRpcExtensionBuilder<R>(pub R);
+
+impl<R> RpcExtensionBuilder for NoopRpcExtensionBuilder<R> where
+	R: Clone + sc_rpc::RpcExtension<sc_rpc::Metadata>,
+{
+	type Output = R;
+
+	fn build(&self, _deny_unsafe: sc_rpc::DenyUnsafe) -> Self::Output {
+		self.0.clone()
+	}
 }
 
 /// Full client type.
@@ -203,6 +241,7 @@ fn new_full_parts<TBl, TRtApi, TExecDisp>(
 			state_cache_child_ratio:
 			config.state_cache_child_ratio.map(|v| (v, 100)),
 			pruning: config.pruning.clone(),
+			keep_blocks: config.keep_blocks,
 			source: config.database.clone(),
 		};
 
@@ -231,6 +270,9 @@ fn new_full_parts<TBl, TRtApi, TExecDisp>(
 }
 
 
+/// Number of blocks after which non-finalized blocks are canonicalized in the database.
+pub(crate) const CANONICALIZATION_DELAY: u64 = 4096;
+
 /// Create an instance of db-backed client.
 pub fn new_client<E, Block, RA>(
 	settings: DatabaseSettings,
@@ -257,8 +299,6 @@ pub fn new_client<E, Block, RA>(
 		Block: BlockT,
 		E: CodeExecutor + RuntimeInfo,
 {
-	const CANONICALIZATION_DELAY: u64 = 4096;
-
 	let backend = Arc::new(Backend::new(settings, CANONICALIZATION_DELAY)?);
 	let executor = crate::client::LocalCallExecutor::new(backend.clone(), executor, spawn_handle, config.clone());
 	Ok((
@@ -290,7 +330,7 @@ impl ServiceBuilder<(), (), (), (), (), (), (), (), (), (), ()> {
 		BoxFinalityProofRequestBuilder<TBl>,
 		Arc<dyn FinalityProofProvider<TBl>>,
 		(),
-		(),
+		NoopRpcExtensionBuilder<()>,
 		TFullBackend<TBl>,
 	>, Error> {
 		let (client, backend, keystore, task_manager) = new_full_parts(&config)?;
@@ -309,9 +349,10 @@ impl ServiceBuilder<(), (), (), (), (), (), (), (), (), (), ()> {
 			finality_proof_request_builder: None,
 			finality_proof_provider: None,
 			transaction_pool: Arc::new(()),
-			rpc_extensions: Default::default(),
+			rpc_extensions_builder: NoopRpcExtensionBuilder(()),
 			remote_backend: None,
 			block_announce_validator_builder: None,
+			warp_sync_provider: None,
 			marker: PhantomData,
 		})
 	}
@@ -329,7 +370,7 @@ impl ServiceBuilder<(), (), (), (), (), (), (), (), (), (), ()> {
 		BoxFinalityProofRequestBuilder<TBl>,
 		Arc<dyn FinalityProofProvider<TBl>>,
 		(),
-		(),
+		NoopRpcExtensionBuilder<()>,
 		TLightBackend<TBl>,
 	>, Error> {
 		let task_manager = {
@@ -357,6 +398,7 @@ impl ServiceBuilder<(), (), (), (), (), (), (), (), (), (), ()> {
 				state_cache_child_ratio:
 					config.state_cache_child_ratio.map(|v| (v, 100)),
 				pruning: config.pruning.clone(),
+				keep_blocks: config.keep_blocks,
 				source: config.database.clone(),
 			};
 			sc_client_db::light::LightStorage::new(db_settings)?
@@ -392,9 +434,10 @@ impl ServiceBuilder<(), (), (), (), (), (), (), (), (), (), ()> {
 			finality_proof_request_builder: None,
 			finality_proof_provider: None,
 			transaction_pool: Arc::new(()),
-			rpc_extensions: Default::default(),
+			rpc_extensions_builder: NoopRpcExtensionBuilder(()),
 			remote_backend: Some(remote_blockchain),
 			block_announce_validator_builder: None,
+			warp_sync_provider: None,
 			marker: PhantomData,
 		})
 	}
@@ -465,9 +508,10 @@ impl<TBl, TRtApi, TCl, TFchr, TSc, TImpQu, TFprb, TFpp, TExPool, TRpc, Backend>
 			finality_proof_request_builder: self.finality_proof_request_builder,
 			finality_proof_provider: self.finality_proof_provider,
 			transaction_pool: self.transaction_pool,
-			rpc_extensions: self.rpc_extensions,
+			rpc_extensions_builder: self.rpc_extensions_builder,
 			remote_backend: self.remote_backend,
 			block_announce_validator_builder: self.block_announce_validator_builder,
+			warp_sync_provider: self.warp_sync_provider,
 			marker: self.marker,
 		})
 	}
@@ -509,9 +553,10 @@ impl<TBl, TRtApi, TCl, TFchr, TSc, TImpQu, TFprb, TFpp, TExPool, TRpc, Backend>
 			finality_proof_request_builder: self.finality_proof_request_builder,
 			finality_proof_provider: self.finality_proof_provider,
 			transaction_pool: self.transaction_pool,
-			rpc_extensions: self.rpc_extensions,
+			rpc_extensions_builder: self.rpc_extensions_builder,
 			remote_backend: self.remote_backend,
 			block_announce_validator_builder: self.block_announce_validator_builder,
+			warp_sync_provider: self.warp_sync_provider,
 			marker: self.marker,
 		})
 	}
@@ -547,9 +592,10 @@ impl<TBl, TRtApi, TCl, TFchr, TSc, TImpQu, TFprb, TFpp, TExPool, TRpc, Backend>
 			finality_proof_request_builder: self.finality_proof_request_builder,
 			finality_proof_provider,
 			transaction_pool: self.transaction_pool,
-			rpc_extensions: self.rpc_extensions,
+			rpc_extensions_builder: self.rpc_extensions_builder,
 			remote_backend: self.remote_backend,
 			block_announce_validator_builder: self.block_announce_validator_builder,
+			warp_sync_provider: self.warp_sync_provider,
 			marker: self.marker,
 		})
 	}
@@ -611,9 +657,10 @@ impl<TBl, TRtApi, TCl, TFchr, TSc, TImpQu, TFprb, TFpp, TExPool, TRpc, Backend>
 			finality_proof_request_builder: fprb,
 			finality_proof_provider: self.finality_proof_provider,
 			transaction_pool: self.transaction_pool,
-			rpc_extensions: self.rpc_extensions,
+			rpc_extensions_builder: self.rpc_extensions_builder,
 			remote_backend: self.remote_backend,
 			block_announce_validator_builder: self.block_announce_validator_builder,
+			warp_sync_provider: self.warp_sync_provider,
 			marker: self.marker,
 		})
 	}
@@ -674,21 +721,41 @@ impl<TBl, TRtApi, TCl, TFchr, TSc, TImpQu, TFprb, TFpp, TExPool, TRpc, Backend>
 			finality_proof_request_builder: self.finality_proof_request_builder,
 			finality_proof_provider: self.finality_proof_provider,
 			transaction_pool: Arc::new(transaction_pool),
-			rpc_extensions: self.rpc_extensions,
+			rpc_extensions_builder: self.rpc_extensions_builder,
 			remote_backend: self.remote_backend,
 			block_announce_validator_builder: self.block_announce_validator_builder,
+			warp_sync_provider: self.warp_sync_provider,
 			marker: self.marker,
 		})
 	}
 
 	/// Defines the RPC extensions to use.
+	///
+	/// The same extensions are exposed on every interface, regardless of whether
+	/// unsafe RPC methods are allowed on it. Use
+	/// [`with_rpc_extensions_builder`](ServiceBuilder::with_rpc_extensions_builder)
+	/// for extensions exposing unsafe methods.
 	pub fn with_rpc_extensions<URpc>(
 		self,
 		rpc_ext_builder: impl FnOnce(&Self) -> Result<URpc, Error>,
 	) -> Result<ServiceBuilder<TBl, TRtApi, TCl, TFchr, TSc, TImpQu, TFprb, TFpp,
-		TExPool, URpc, Backend>, Error>
+		TExPool, NoopRpcExtensionBuilder<URpc>, Backend>, Error>
 	where TSc: Clone, TFchr: Clone {
 		let rpc_extensions = rpc_ext_builder(&self)?;
+		self.with_rpc_extensions_builder(|_| Ok(NoopRpcExtensionBuilder(rpc_extensions)))
+	}
+
+	/// Defines the builder of the RPC extensions to use.
+	///
+	/// The returned builder is called for every interface the RPC is exposed on,
+	/// with the [`DenyUnsafe`](sc_rpc::DenyUnsafe) policy of that interface.
+	pub fn with_rpc_extensions_builder<URpcBuilder>(
+		self,
+		rpc_ext_builder: impl FnOnce(&Self) -> Result<URpcBuilder, Error>,
+	) -> Result<ServiceBuilder<TBl, TRtApi, TCl, TFchr, TSc, TImpQu, TFprb, TFpp,
+		TExPool, URpcBuilder, Backend>, Error>
+	where TSc: Clone, TFchr: Clone {
+		let rpc_extensions_builder = rpc_ext_builder(&self)?;
 
 		Ok(ServiceBuilder {
 			config: self.config,
@@ -702,9 +769,10 @@ impl<TBl, TRtApi, TCl, TFchr, TSc, TImpQu, TFprb, TFpp, TExPool, TRpc, Backend>
 			finality_proof_request_builder: self.finality_proof_request_builder,
 			finality_proof_provider: self.finality_proof_provider,
 			transaction_pool: self.transaction_pool,
-			rpc_extensions,
+			rpc_extensions_builder,
 			remote_backend: self.remote_backend,
 			block_announce_validator_builder: self.block_announce_validator_builder,
+			warp_sync_provider: self.warp_sync_provider,
 			marker: self.marker,
 		})
 	}
@@ -730,9 +798,39 @@ impl<TBl, TRtApi, TCl, TFchr, TSc, TImpQu, TFprb, TFpp, TExPool, TRpc, Backend>
 			finality_proof_request_builder: self.finality_proof_request_builder,
 			finality_proof_provider: self.finality_proof_provider,
 			transaction_pool: self.transaction_pool,
-			rpc_extensions: self.rpc_extensions,
+			rpc_extensions_builder: self.rpc_extensions_builder,
 			remote_backend: self.remote_backend,
 			block_announce_validator_builder: Some(Box::new(block_announce_validator_builder)),
+			warp_sync_provider: self.warp_sync_provider,
+			marker: self.marker,
+		})
+	}
+
+	/// Defines the warp sync proof provider, used to answer warp sync proof requests from
+	/// other nodes and to warp sync when the network is configured to do so.
+	pub fn with_warp_sync_provider(
+		self,
+		builder: impl FnOnce(Arc<TCl>, Arc<Backend>) -> Result<Arc<dyn WarpSyncProvider<TBl>>, Error>
+	) -> Result<ServiceBuilder<TBl, TRtApi, TCl, TFchr, TSc, TImpQu, TFprb, TFpp,
+		TExPool, TRpc, Backend>, Error> {
+		let warp_sync_provider = builder(self.client.clone(), self.backend.clone())?;
+
+		Ok(ServiceBuilder {
+			config: self.config,
+			client: self.client,
+			backend: self.backend,
+			task_manager: self.task_manager,
+			keystore: self.keystore,
+			fetcher: self.fetcher,
+			select_chain: self.select_chain,
+			import_queue: self.import_queue,
+			finality_proof_request_builder: self.finality_proof_request_builder,
+			finality_proof_provider: self.finality_proof_provider,
+			transaction_pool: self.transaction_pool,
+			rpc_extensions_builder: self.rpc_extensions_builder,
+			remote_backend: self.remote_backend,
+			block_announce_validator_builder: self.block_announce_validator_builder,
+			warp_sync_provider: Some(warp_sync_provider),
 			marker: self.marker,
 		})
 	}
@@ -799,6 +897,7 @@ ServiceBuilder<
 	<Client<TBackend, TExec, TBl, TRtApi> as ProvideRuntimeApi<TBl>>::Api:
 		sp_api::Metadata<TBl> +
 		sc_offchain::OffchainWorkerApi<TBl> +
+		sc_block_builder::BlockBuilderApi<TBl> +
 		sp_transaction_pool::runtime_api::TaggedTransactionQueue<TBl> +
 		sp_session::SessionKeys<TBl> +
 		sp_api::ApiErrorExt<Error = sp_blockchain::Error> +
@@ -810,7 +909,7 @@ ServiceBuilder<
 	TSc: Clone,
 	TImpQu: 'static + ImportQueue<TBl>,
 	TExPool: MaintainedTransactionPool<Block=TBl, Hash = <TBl as BlockT>::Hash> + MallocSizeOfWasm + 'static,
-	TRpc: sc_rpc::RpcExtension<sc_rpc::Metadata> + Clone,
+	TRpc: RpcExtensionBuilder,
 {
 
 	/// Set an ExecutionExtensionsFactory
@@ -848,9 +947,10 @@ ServiceBuilder<
 			finality_proof_request_builder,
 			finality_proof_provider,
 			transaction_pool,
-			rpc_extensions,
+			rpc_extensions_builder,
 			remote_backend,
 			block_announce_validator_builder,
+			warp_sync_provider,
 		} = self;
 
 		sp_session::generate_initial_session_keys(
@@ -917,6 +1017,7 @@ ServiceBuilder<
 			chain: client.clone(),
 			finality_proof_provider,
 			finality_proof_request_builder,
+			warp_sync: warp_sync_provider,
 			on_demand: on_demand.clone(),
 			transaction_pool: transaction_pool_adapter.clone() as _,
 			import_queue,
@@ -1119,14 +1220,19 @@ ServiceBuilder<
 					client.clone(),
 					subscriptions.clone(),
 					remote_backend.clone(),
-					on_demand.clone()
+					on_demand.clone(),
+					deny_unsafe,
 				);
 				(chain, state, child_state)
 
 			} else {
 				// Full nodes
 				let chain = sc_rpc::chain::new_full(client.clone(), subscriptions.clone());
-				let (state, child_state) = sc_rpc::state::new_full(client.clone(), subscriptions.clone());
+				let (state, child_state) = sc_rpc::state::new_full(
+					client.clone(),
+					subscriptions.clone(),
+					deny_unsafe,
+				);
 				(chain, state, child_state)
 			};
 
@@ -1155,7 +1261,7 @@ ServiceBuilder<
 				maybe_offchain_rpc,
 				author::AuthorApi::to_delegate(author),
 				system::SystemApi::to_delegate(system),
-				rpc_extensions.clone(),
+				rpc_extensions_builder.build(deny_unsafe),
 			))
 		};
 		let rpc = start_rpc_servers(&config, gen_handler)?;
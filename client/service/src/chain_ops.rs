@@ -17,13 +17,15 @@
 //! Chain utilities.
 
 use crate::error;
-use crate::builder::{ServiceBuilderCommand, ServiceBuilder};
+use crate::builder::{ServiceBuilderCommand, ServiceBuilder, CANONICALIZATION_DELAY};
+use crate::client::genesis::construct_genesis_block;
+use crate::config::Configuration;
 use crate::error::Error;
 use sc_chain_spec::ChainSpec;
 use log::{warn, info};
 use futures::{future, prelude::*};
 use sp_runtime::traits::{
-	Block as BlockT, NumberFor, One, Zero, Header, SaturatedConversion
+	Block as BlockT, NumberFor, One, Zero, Header, SaturatedConversion, HashFor,
 };
 use sp_runtime::generic::{BlockId, SignedBlock};
 use codec::{Decode, Encode, IoReader};
@@ -35,6 +37,7 @@ use sp_consensus::{
 use sc_executor::{NativeExecutor, NativeExecutionDispatch};
 use sp_core::storage::{StorageKey, well_known_keys, ChildInfo, Storage, StorageChild, StorageMap};
 use sc_client_api::{StorageProvider, BlockBackend, UsageProvider};
+use sp_runtime::BuildStorage;
 
 use std::{io::{Read, Write, Seek}, pin::Pin, collections::HashMap};
 
@@ -43,6 +46,42 @@ pub fn build_spec(spec: &dyn ChainSpec, raw: bool) -> error::Result<String> {
 	spec.as_json(raw).map_err(Into::into)
 }
 
+/// Import the state of a block into the empty database of the node.
+///
+/// `storage` is the state of the block with the given `header`, e.g. as exported by
+/// `export_raw_state`. The chain of the node then starts at this block instead of
+/// genesis, so the blocks before it don't have to be imported and executed.
+pub fn import_state<TBl: BlockT>(
+	config: &Configuration,
+	header: TBl::Header,
+	storage: Storage,
+) -> error::Result<()> {
+	let genesis_storage = config.chain_spec.as_storage_builder().build_storage()?;
+	let genesis_state = sp_state_machine::InMemoryBackend::<HashFor<TBl>>::from(
+		genesis_storage.clone(),
+	);
+	let genesis_header = construct_genesis_block::<TBl>(*genesis_state.root()).deconstruct().0;
+
+	let db_config = sc_client_db::DatabaseSettings {
+		state_cache_size: config.state_cache_size,
+		state_cache_child_ratio: config.state_cache_child_ratio.map(|v| (v, 100)),
+		pruning: config.pruning.clone(),
+		keep_blocks: config.keep_blocks,
+		source: config.database.clone(),
+	};
+	let backend = sc_client_db::Backend::<TBl>::new(db_config, CANONICALIZATION_DELAY)?;
+
+	info!(
+		"Importing state of block #{} ({}), state root: {}",
+		header.number(),
+		header.hash(),
+		header.state_root(),
+	);
+	backend.import_state(genesis_header, genesis_storage, header, storage)?;
+
+	Ok(())
+}
+
 impl<
 	TBl, TRtApi, TBackend,
 	TExecDisp, TFchr, TSc, TImpQu, TFprb, TFpp,
@@ -149,6 +188,7 @@ impl<
 								origin: None,
 								allow_missing_state: false,
 								import_existing: force,
+								state: None,
 							}
 						]);
 					}
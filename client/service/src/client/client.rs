@@ -27,7 +27,10 @@ use codec::{Encode, Decode};
 use hash_db::Prefix;
 use sp_core::{
 	ChangesTrieConfiguration, convert_hash, NativeOrEncoded,
-	storage::{StorageKey, PrefixedStorageKey, StorageData, well_known_keys, ChildInfo},
+	storage::{
+		StorageKey, PrefixedStorageKey, StorageData, well_known_keys, ChildInfo, ChildType, Storage,
+		StorageChild,
+	},
 };
 use sc_telemetry::{telemetry, SUBSTRATE_INFO};
 use sp_runtime::{
@@ -41,12 +44,13 @@ use sp_runtime::{
 use sp_state_machine::{
 	DBValue, Backend as StateBackend, ChangesTrieAnchorBlockId,
 	prove_read, prove_child_read, ChangesTrieRootsStorage, ChangesTrieStorage,
-	ChangesTrieConfigurationRange, key_changes, key_changes_proof,
+	ChangesTrieConfigurationRange, key_changes, key_changes_proof, KeyValueStates,
+	prove_range_read_with_size, read_range_proof_check,
 };
 use sc_executor::RuntimeVersion;
 use sp_consensus::{
 	Error as ConsensusError, BlockStatus, BlockImportParams, BlockCheckParams,
-	ImportResult, BlockOrigin, ForkChoiceStrategy, RecordProof,
+	ImportResult, BlockOrigin, ForkChoiceStrategy, RecordProof, ImportedState,
 };
 use sp_blockchain::{
 	self as blockchain,
@@ -618,6 +622,7 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			fork_choice,
 			intermediates,
 			import_existing,
+			import_state,
 			..
 		} = import_block;
 
@@ -657,6 +662,7 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			auxiliary,
 			fork_choice,
 			import_existing,
+			import_state,
 		);
 
 		if let Ok(ImportResult::Imported(ref aux)) = result {
@@ -695,6 +701,7 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 		aux: Vec<(Vec<u8>, Option<Vec<u8>>)>,
 		fork_choice: ForkChoiceStrategy,
 		import_existing: bool,
+		import_state: Option<ImportedState<Block>>,
 	) -> sp_blockchain::Result<ImportResult> where
 		Self: ProvideRuntimeApi<Block>,
 		<Self as ProvideRuntimeApi<Block>>::Api: CoreApi<Block, Error = Error> +
@@ -726,6 +733,44 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			BlockOrigin::Genesis | BlockOrigin::NetworkInitialSync | BlockOrigin::File => false,
 		};
 
+		let is_state_import = import_state.is_some();
+		if let Some(import_state) = import_state {
+			if !finalized {
+				return Err(Error::Backend("Imported state must belong to a finalized block".into()));
+			}
+			let mut storage = Storage::default();
+			for level in import_state.state.0 {
+				if level.parent_storage_key.is_empty() {
+					// child trie roots are computed from the child tries content.
+					storage.top.extend(level.key_values.into_iter()
+						.filter(|(k, _)| !well_known_keys::is_child_storage_key(k)));
+					continue;
+				}
+				let child_info = match ChildType::from_prefixed_key(
+					PrefixedStorageKey::new_ref(&level.parent_storage_key),
+				) {
+					Some((ChildType::ParentKeyId, storage_key)) => ChildInfo::new_default(storage_key),
+					None => return Err(Error::Backend("Unsupported child trie type".into())),
+				};
+				storage.children_default.entry(child_info.storage_key().to_vec())
+					.or_insert_with(|| StorageChild { data: Default::default(), child_info })
+					.data
+					.extend(level.key_values);
+			}
+			// changes tries of the blocks before the imported one are not available.
+			if storage.top.contains_key(well_known_keys::CHANGES_TRIE_CONFIG) {
+				return Err(Error::Backend(
+					"Importing state of a chain with changes tries enabled is not supported".into()
+				));
+			}
+
+			self.backend.begin_state_operation(&mut operation.op, BlockId::Hash(Default::default()))?;
+			let state_root = operation.op.reset_storage(storage)?;
+			if state_root != *import_headers.post().state_root() {
+				return Err(Error::InvalidStateRoot);
+			}
+		}
+
 		let storage_changes = match storage_changes {
 			Some(storage_changes) => {
 				self.backend.begin_state_operation(&mut operation.op, BlockId::Hash(parent_hash))?;
@@ -786,7 +831,8 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			NewBlockState::Normal
 		};
 
-		let retracted = if is_new_best {
+		// the ancestors of a block with imported state are not known.
+		let retracted = if is_new_best && !is_state_import {
 			let route_from_best = sp_blockchain::tree_route(
 				self.backend.blockchain(),
 				info.best_hash,
@@ -846,6 +892,11 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			<Self as ProvideRuntimeApi<Block>>::Api: CoreApi<Block, Error = Error> +
 				ApiExt<Block, StateBackend = B::State>,
 	{
+		// the state is imported as a whole, the block is not executed.
+		if import_block.import_state.is_some() {
+			return Ok(None);
+		}
+
 		let parent_hash = import_block.header.parent_hash();
 		let at = BlockId::Hash(*parent_hash);
 		let enact_state = match self.block_status(&at)? {
@@ -1237,6 +1288,27 @@ impl<B, E, Block, RA> ProofProvider<Block> for Client<B, E, Block, RA> where
 			cht::size(),
 		)
 	}
+
+	fn read_proof_collection(
+		&self,
+		id: &BlockId<Block>,
+		start_key: &[Vec<u8>],
+		size_limit: usize,
+	) -> sp_blockchain::Result<(StorageProof, u32)> {
+		self.state_at(id)
+			.and_then(|state| prove_range_read_with_size(state, start_key, size_limit)
+				.map_err(Into::into))
+	}
+
+	fn verify_range_proof(
+		&self,
+		root: Block::Hash,
+		proof: StorageProof,
+		start_key: &[Vec<u8>],
+	) -> sp_blockchain::Result<KeyValueStates> {
+		read_range_proof_check::<HashFor<Block>>(root, proof, start_key)
+			.map_err(Into::into)
+	}
 }
 
 
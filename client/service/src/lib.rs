@@ -62,9 +62,11 @@ pub use self::error::Error;
 pub use self::builder::{
 	new_full_client, new_client,
 	ServiceBuilder, ServiceBuilderCommand, TFullClient, TLightClient, TFullBackend, TLightBackend,
-	TFullCallExecutor, TLightCallExecutor,
+	TFullCallExecutor, TLightCallExecutor, RpcExtensionBuilder, NoopRpcExtensionBuilder,
+};
+pub use config::{
+	Configuration, DatabaseConfig, PruningMode, KeepBlocks, Role, RpcMethods, TaskType,
 };
-pub use config::{Configuration, DatabaseConfig, PruningMode, Role, RpcMethods, TaskType};
 pub use sc_chain_spec::{
 	ChainSpec, GenericChainSpec, Properties, RuntimeGenesis, Extension as ChainSpecExtension,
 	NoExtension, ChainType,
@@ -346,7 +348,7 @@ impl<TBl, TCl, TSc, TNetStatus, TNet, TTxPool, TOc> Spawn for
 /// The `status_sink` contain a list of senders to send a periodic network status to.
 fn build_network_future<
 	B: BlockT,
-	C: BlockchainEvents<B>,
+	C: BlockchainEvents<B> + HeaderBackend<B>,
 	H: sc_network::ExHashT
 > (
 	role: Role,
@@ -359,6 +361,7 @@ fn build_network_future<
 ) -> impl Future<Output = ()> {
 	let mut imported_blocks_stream = client.import_notification_stream().fuse();
 	let mut finality_notification_stream = client.finality_notification_stream().fuse();
+	let starting_block = client.info().best_number;
 
 	futures::future::poll_fn(move |cx| {
 		let before_polling = Instant::now();
@@ -446,6 +449,15 @@ fn build_network_future<
 
 					let _ = sender.send(vec![node_role]);
 				}
+				sc_rpc::system::Request::SyncState(sender) => {
+					use sc_rpc::system::SyncState;
+
+					let _ = sender.send(SyncState {
+						starting_block,
+						current_block: client.info().best_number,
+						highest_block: network.best_seen_block(),
+					});
+				}
 			};
 		}
 
@@ -208,8 +208,10 @@ impl<BlockHash: Hash, Key: Hash> NonCanonicalOverlay<BlockHash, Key> {
 	pub fn insert<E: fmt::Debug>(&mut self, hash: &BlockHash, number: u64, parent_hash: &BlockHash, changeset: ChangeSet<Key>) -> Result<CommitSet<Key>, Error<E>> {
 		let mut commit = CommitSet::default();
 		let front_block_number = self.front_block_number();
-		if self.levels.is_empty() && self.last_canonicalized.is_none() && number > 0 {
-			// assume that parent was canonicalized
+		let is_gap = self.last_canonicalized.as_ref().map_or(true, |&(_, n)| n + 1 < number);
+		if self.levels.is_empty() && is_gap && number > 0 {
+			// assume that parent was canonicalized. this is either the first block
+			// or a block whose state was imported without its ancestors.
 			let last_canonicalized = (parent_hash.clone(), number - 1);
 			commit.meta.inserted.push((to_meta_key(LAST_CANONICAL, &()), last_canonicalized.encode()));
 			self.last_canonicalized = Some(last_canonicalized);
@@ -603,6 +605,33 @@ mod tests {
 		assert!(db.data_eq(&make_db(&[1, 3, 4])));
 	}
 
+	#[test]
+	fn insert_after_gap_in_canonical_chain() {
+		let h1 = H256::random();
+		let h2 = H256::random();
+		let h3 = H256::random();
+		let mut db = make_db(&[1, 2]);
+		let mut overlay = NonCanonicalOverlay::<H256, H256>::new(&db).unwrap();
+		db.commit(&overlay.insert::<io::Error>(&h1, 1, &H256::default(), make_changeset(&[3], &[])).unwrap());
+		let mut finalization = CommitSet::default();
+		overlay.canonicalize::<io::Error>(&h1, &mut finalization).unwrap();
+		db.commit(&finalization);
+
+		// block 10 is inserted without its ancestors, e.g. when its state was imported.
+		db.commit(&overlay.insert::<io::Error>(&h2, 10, &H256::random(), make_changeset(&[4], &[])).unwrap());
+		assert_eq!(overlay.last_canonicalized.as_ref().map(|&(_, n)| n), Some(9));
+		assert!(overlay.insert::<io::Error>(&h3, 9, &h2, ChangeSet::default()).is_err());
+
+		let overlay2 = NonCanonicalOverlay::<H256, H256>::new(&db).unwrap();
+		assert_eq!(overlay.levels, overlay2.levels);
+		assert_eq!(overlay.last_canonicalized, overlay2.last_canonicalized);
+
+		let mut finalization = CommitSet::default();
+		overlay.canonicalize::<io::Error>(&h2, &mut finalization).unwrap();
+		db.commit(&finalization);
+		assert!(db.data_eq(&make_db(&[1, 2, 3, 4])));
+	}
+
 	#[test]
 	fn restore_from_journal() {
 		let h1 = H256::random();
@@ -0,0 +1,188 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A subscriber that keeps events in memory instead of sending them to a receiver.
+//!
+//! This is meant to be installed as the thread-local default dispatcher while running
+//! a piece of code whose events should be inspected afterwards, e.g. when re-executing
+//! a block to trace its storage accesses.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+use tracing_core::{
+	event::Event,
+	Level,
+	metadata::Metadata,
+	span::{Attributes, Id, Record},
+	subscriber::{Interest, Subscriber},
+};
+
+use crate::{Visitor, parse_target};
+
+/// Target and name of a span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanInfo {
+	/// Target of the span.
+	pub target: String,
+	/// Name of the span.
+	pub name: String,
+}
+
+/// An event recorded by the [`TraceCollector`].
+#[derive(Debug, Clone)]
+pub struct CollectedEvent {
+	/// Target of the event.
+	pub target: String,
+	/// Recorded fields of the event, in declaration order.
+	pub values: Vec<(String, String)>,
+	/// The innermost span that was entered when the event was emitted.
+	pub span: Option<SpanInfo>,
+}
+
+impl CollectedEvent {
+	/// Returns the value of the field with the given name.
+	pub fn value(&self, name: &str) -> Option<&str> {
+		self.values.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+	}
+}
+
+/// Collects events of the given targets along with the span they were emitted in.
+pub struct TraceCollector {
+	next_id: AtomicU64,
+	span_targets: Vec<(String, Level)>,
+	event_targets: Vec<String>,
+	spans: Mutex<HashMap<u64, SpanInfo>>,
+	entered: Mutex<Vec<u64>>,
+	events: Mutex<Vec<CollectedEvent>>,
+}
+
+impl TraceCollector {
+	/// Create a new collector.
+	///
+	/// `span_targets` is a comma separated list of span targets, with an optional level,
+	/// in the same format accepted by [`ProfilingSubscriber::new`](crate::ProfilingSubscriber::new).
+	/// Only events whose target is exactly one of `event_targets` are collected.
+	pub fn new(span_targets: &str, event_targets: &[&str]) -> Self {
+		TraceCollector {
+			next_id: AtomicU64::new(1),
+			span_targets: span_targets.split(',')
+				.filter(|s| !s.is_empty())
+				.map(|s| parse_target(s))
+				.collect(),
+			event_targets: event_targets.iter().map(|t| t.to_string()).collect(),
+			spans: Mutex::new(HashMap::new()),
+			entered: Mutex::new(Vec::new()),
+			events: Mutex::new(Vec::new()),
+		}
+	}
+
+	/// Take all events collected so far.
+	pub fn take_events(&self) -> Vec<CollectedEvent> {
+		std::mem::replace(&mut *self.events.lock(), Vec::new())
+	}
+}
+
+impl Subscriber for TraceCollector {
+	fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+		// The collector is usually only installed for a short while and the interest is cached
+		// globally, so `enabled` must be asked on every use of a callsite. Otherwise callsites
+		// would keep calling into the dispatcher after the collector is gone.
+		Interest::sometimes()
+	}
+
+	fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+		if metadata.is_event() {
+			self.event_targets.iter().any(|t| metadata.target() == t)
+		} else {
+			self.span_targets.iter()
+				.any(|(target, level)| metadata.target().starts_with(target.as_str()) && metadata.level() <= level)
+		}
+	}
+
+	fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+		let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+		let info = SpanInfo {
+			target: attrs.metadata().target().to_string(),
+			name: attrs.metadata().name().to_string(),
+		};
+		self.spans.lock().insert(id, info);
+		Id::from_u64(id)
+	}
+
+	fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+	fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+	fn event(&self, event: &Event<'_>) {
+		let mut values = Visitor(Vec::new());
+		event.record(&mut values);
+		let span = self.entered.lock().last()
+			.and_then(|id| self.spans.lock().get(id).cloned());
+		self.events.lock().push(CollectedEvent {
+			target: event.metadata().target().to_string(),
+			values: values.0,
+			span,
+		});
+	}
+
+	fn enter(&self, span: &Id) {
+		self.entered.lock().push(span.into_u64());
+	}
+
+	fn exit(&self, span: &Id) {
+		let mut entered = self.entered.lock();
+		if let Some(pos) = entered.iter().rposition(|id| *id == span.into_u64()) {
+			entered.remove(pos);
+		}
+	}
+
+	fn try_close(&self, span: Id) -> bool {
+		self.spans.lock().remove(&span.into_u64());
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn collects_events_with_their_span() {
+		let dispatch = tracing::Dispatch::new(TraceCollector::new("pallet", &["state_access"]));
+		tracing::dispatcher::with_default(&dispatch, || {
+			tracing::trace!(target: "state_access", method = "Get", size = 4u64);
+			let span = tracing::span!(target: "pallet_balances", tracing::Level::TRACE, "transfer");
+			let _guard = span.enter();
+			tracing::trace!(target: "state_access", method = "Put");
+			tracing::trace!(target: "other", method = "Ignored");
+		});
+
+		let collector = dispatch.downcast_ref::<TraceCollector>().unwrap();
+		let events = collector.take_events();
+		assert_eq!(events.len(), 2);
+		assert_eq!(events[0].value("method"), Some("Get"));
+		assert_eq!(events[0].value("size"), Some("4"));
+		assert_eq!(events[0].span, None);
+		assert_eq!(events[1].value("method"), Some("Put"));
+		assert_eq!(
+			events[1].span,
+			Some(SpanInfo { target: "pallet_balances".into(), name: "transfer".into() }),
+		);
+		assert!(collector.take_events().is_empty());
+	}
+}
@@ -23,6 +23,10 @@
 //! See `sp-tracing` for examples on how to use tracing.
 //!
 //! Currently we provide `Log` (default), `Telemetry` variants for `Receiver`
+//!
+//! A [`TraceCollector`] can be used to keep events in memory for later inspection.
+
+mod collector;
 
 use std::collections::HashMap;
 use std::fmt;
@@ -43,6 +47,8 @@ use tracing_core::{
 
 use sc_telemetry::{telemetry, SUBSTRATE_INFO};
 
+pub use collector::{TraceCollector, CollectedEvent, SpanInfo};
+
 /// Used to configure how to receive the metrics
 #[derive(Debug, Clone)]
 pub enum TracingReceiver {
@@ -86,6 +92,10 @@ impl Visit for Visitor {
 		self.record_debug(field, &value)
 	}
 
+	fn record_str(&mut self, field: &Field, value: &str) {
+		self.0.push((field.name().to_string(), value.to_string()));
+	}
+
 	fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
 		self.0.push((field.name().to_string(), format!("{:?}",value)));
 	}
@@ -35,7 +35,10 @@ use sp_runtime::transaction_validity::{
 	TransactionPriority as Priority,
 	TransactionSource as Source,
 };
-use sp_transaction_pool::{error, PoolStatus, InPoolTransaction};
+use sp_transaction_pool::{
+	error, PoolStatus, InPoolTransaction, InPoolStatus, PoolContent, TransactionDetails,
+};
+use wasm_timer::{Instant, SystemTime};
 
 use crate::future::{FutureTransactions, WaitingTransaction};
 use crate::ready::ReadyTransactions;
@@ -376,6 +379,43 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		self.future.all()
 	}
 
+	/// Returns details of all transactions in both ready and future queues.
+	pub fn content(&self) -> PoolContent<Hash> {
+		PoolContent {
+			ready: self.ready
+				.all_with_import_time()
+				.into_iter()
+				.map(|(tx, imported_at)| transaction_details(&tx, vec![], &imported_at))
+				.collect(),
+			future: self.future
+				.all_waiting()
+				.map(|waiting| transaction_details(
+					&waiting.transaction,
+					waiting.missing_tags.iter().cloned().collect(),
+					&waiting.imported_at,
+				))
+				.collect(),
+		}
+	}
+
+	/// Returns status of a transaction given its hash.
+	///
+	/// Only the queues are checked, so `InPoolStatus::Banned` is never returned.
+	pub fn in_pool_status(&self, hash: &Hash) -> InPoolStatus<Hash> {
+		if let Some((tx, imported_at)) = self.ready.by_hash_with_import_time(hash) {
+			return InPoolStatus::Ready(transaction_details(&tx, vec![], &imported_at));
+		}
+
+		match self.future.waiting_by_hash(hash) {
+			Some(waiting) => InPoolStatus::Future(transaction_details(
+				&waiting.transaction,
+				waiting.missing_tags.iter().cloned().collect(),
+				&waiting.imported_at,
+			)),
+			None => InPoolStatus::Unknown,
+		}
+	}
+
 	/// Returns pool transactions given list of hashes.
 	///
 	/// Includes both ready and future pool. For every hash in the `hashes`
@@ -520,6 +560,31 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	}
 }
 
+/// Produce introspection details of given transaction.
+fn transaction_details<Hash: Clone, Ex>(
+	tx: &Transaction<Hash, Ex>,
+	missing_tags: Vec<Tag>,
+	imported_at: &Instant,
+) -> TransactionDetails<Hash> {
+	// `Instant` is opaque, so we go back from the current wall-clock time.
+	let imported_at = SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.ok()
+		.and_then(|now| now.checked_sub(imported_at.elapsed()))
+		.map(|since_epoch| since_epoch.as_millis() as u64)
+		.unwrap_or_default();
+
+	TransactionDetails {
+		hash: tx.hash.clone(),
+		priority: tx.priority,
+		longevity: tx.valid_till,
+		requires: tx.requires.clone(),
+		provides: tx.provides.clone(),
+		missing_tags,
+		imported_at,
+	}
+}
+
 /// Queue limits
 #[derive(Debug, Clone)]
 pub struct Limit {
@@ -640,6 +705,60 @@ mod tests {
 		assert_eq!(pool.ready.len(), 2);
 	}
 
+	#[test]
+	fn should_report_content_and_status_of_both_queues() {
+		// given
+		let mut pool = pool();
+		pool.import(Transaction {
+			data: vec![1u8],
+			bytes: 1,
+			hash: 1,
+			priority: 5u64,
+			valid_till: 64u64,
+			requires: vec![vec![0], vec![2]],
+			provides: vec![vec![1]],
+			propagate: true,
+			source: Source::External,
+		}).unwrap();
+		pool.import(Transaction {
+			data: vec![2u8],
+			bytes: 1,
+			hash: 2,
+			priority: 10u64,
+			valid_till: 32u64,
+			requires: vec![],
+			provides: vec![vec![0]],
+			propagate: true,
+			source: Source::External,
+		}).unwrap();
+
+		// when
+		let content = pool.content();
+
+		// then
+		assert_eq!(content.ready.len(), 1);
+		assert_eq!(content.ready[0].hash, 2);
+		assert_eq!(content.ready[0].priority, 10);
+		assert_eq!(content.ready[0].longevity, 32);
+		assert_eq!(content.ready[0].provides, vec![vec![0]]);
+		assert!(content.ready[0].missing_tags.is_empty());
+		assert!(content.ready[0].imported_at > 0);
+		assert_eq!(content.future.len(), 1);
+		assert_eq!(content.future[0].hash, 1);
+		assert_eq!(content.future[0].requires, vec![vec![0], vec![2]]);
+		assert_eq!(content.future[0].missing_tags, vec![vec![2]]);
+
+		match pool.in_pool_status(&1) {
+			InPoolStatus::Future(details) => assert_eq!(details.missing_tags, vec![vec![2]]),
+			status => panic!("Unexpected status: {:?}", status),
+		}
+		match pool.in_pool_status(&2) {
+			InPoolStatus::Ready(details) => assert_eq!(details.hash, 2),
+			status => panic!("Unexpected status: {:?}", status),
+		}
+		assert_eq!(pool.in_pool_status(&3), InPoolStatus::Unknown);
+	}
+
 	#[test]
 	fn should_promote_a_subgraph() {
 		// given
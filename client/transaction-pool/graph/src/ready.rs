@@ -29,6 +29,7 @@ use sp_runtime::transaction_validity::{
 	TransactionTag as Tag,
 };
 use sp_transaction_pool::error;
+use wasm_timer::Instant;
 
 use crate::future::WaitingTransaction;
 use crate::base_pool::Transaction;
@@ -74,7 +75,8 @@ impl<Hash, Ex> PartialEq for TransactionRef<Hash, Ex> {
 }
 impl<Hash, Ex> Eq for TransactionRef<Hash, Ex> {}
 
-#[derive(Debug, parity_util_mem::MallocSizeOf)]
+#[derive(Debug)]
+#[cfg_attr(not(target_os = "unknown"), derive(parity_util_mem::MallocSizeOf))]
 pub struct ReadyTx<Hash, Ex> {
 	/// A reference to a transaction
 	pub transaction: TransactionRef<Hash, Ex>,
@@ -85,6 +87,8 @@ pub struct ReadyTx<Hash, Ex> {
 	/// Some transactions might be already pruned from the queue,
 	/// so when we compute ready set we may consider this transactions ready earlier.
 	pub requires_offset: usize,
+	/// Time the transaction entered the pool (either queue).
+	pub imported_at: Instant,
 }
 
 impl<Hash: Clone, Ex> Clone for ReadyTx<Hash, Ex> {
@@ -93,6 +97,7 @@ impl<Hash: Clone, Ex> Clone for ReadyTx<Hash, Ex> {
 			transaction: self.transaction.clone(),
 			unlocks: self.unlocks.clone(),
 			requires_offset: self.requires_offset,
+			imported_at: self.imported_at.clone(),
 		}
 	}
 }
@@ -104,7 +109,8 @@ Hence every hash retrieved from `provided_tags` is always present in `ready`;
 qed
 "#;
 
-#[derive(Debug, parity_util_mem::MallocSizeOf)]
+#[derive(Debug)]
+#[cfg_attr(not(target_os = "unknown"), derive(parity_util_mem::MallocSizeOf))]
 pub struct ReadyTransactions<Hash: hash::Hash + Eq, Ex> {
 	/// Insertion id
 	insertion_id: u64,
@@ -170,6 +176,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 		self.insertion_id += 1;
 		let insertion_id = self.insertion_id;
 		let hash = tx.transaction.hash.clone();
+		let imported_at = tx.imported_at;
 		let transaction = tx.transaction;
 
 		let (replaced, unlocks) = self.replace_previous(&transaction)?;
@@ -212,6 +219,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 			transaction,
 			unlocks,
 			requires_offset,
+			imported_at,
 		});
 
 		Ok(replaced)
@@ -243,6 +251,25 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 		}).collect()
 	}
 
+	/// Returns all ready transactions together with the time they entered the pool.
+	///
+	/// The transactions are returned in no particular order.
+	pub fn all_with_import_time(&self) -> Vec<(Arc<Transaction<Hash, Ex>>, Instant)> {
+		self.ready
+			.read()
+			.values()
+			.map(|tx| (tx.transaction.transaction.clone(), tx.imported_at))
+			.collect()
+	}
+
+	/// Retrieve transaction by hash together with the time it entered the pool.
+	pub fn by_hash_with_import_time(&self, hash: &Hash) -> Option<(Arc<Transaction<Hash, Ex>>, Instant)> {
+		self.ready
+			.read()
+			.get(hash)
+			.map(|tx| (tx.transaction.transaction.clone(), tx.imported_at))
+	}
+
 	/// Removes a subtree of transactions from the ready pool.
 	///
 	/// NOTE removing a transaction will also cause a removal of all transactions that depend on that one
@@ -33,7 +33,7 @@ use sp_runtime::{
 	traits::{self, SaturatedConversion},
 	transaction_validity::{TransactionTag as Tag, ValidTransaction, TransactionSource},
 };
-use sp_transaction_pool::{error, PoolStatus};
+use sp_transaction_pool::{error, PoolStatus, PoolContent, InPoolStatus};
 use wasm_timer::Instant;
 use sp_utils::mpsc::{tracing_unbounded, TracingUnboundedSender};
 
@@ -556,6 +556,22 @@ impl<B: ChainApi> ValidatedPool<B> {
 		self.pool.read().status()
 	}
 
+	/// Returns details of all transactions in the pool.
+	pub fn content(&self) -> PoolContent<ExHash<B>> {
+		self.pool.read().content()
+	}
+
+	/// Returns status of the transaction with given hash.
+	///
+	/// Transactions that are not in the pool are reported as `Banned` if the rotator
+	/// has recently banned them.
+	pub fn in_pool_status(&self, hash: &ExHash<B>) -> InPoolStatus<ExHash<B>> {
+		match self.pool.read().in_pool_status(hash) {
+			InPoolStatus::Unknown if self.rotator.is_banned(hash) => InPoolStatus::Banned,
+			status => status,
+		}
+	}
+
 	/// Notify all watchers that transactions in the block with hash have been finalized
 	pub async fn on_block_finalized(&self, block_hash: BlockHash<B>) -> Result<(), B::Error> {
 		debug!(target: "txpool", "Attempting to notify watchers of finalization for {}", block_hash);
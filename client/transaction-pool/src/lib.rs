@@ -43,7 +43,7 @@ use sp_runtime::{
 use sp_transaction_pool::{
 	TransactionPool, PoolStatus, ImportNotificationStream, TxHash, TransactionFor,
 	TransactionStatusStreamFor, MaintainedTransactionPool, PoolFuture, ChainEvent,
-	TransactionSource,
+	TransactionSource, PoolContent, InPoolStatus,
 };
 use wasm_timer::Instant;
 
@@ -319,6 +319,14 @@ impl<PoolApi, Block> TransactionPool for BasicPool<PoolApi, Block>
 		self.pool.validated_pool().ready_by_hash(hash)
 	}
 
+	fn content(&self) -> PoolContent<TxHash<Self>> {
+		self.pool.validated_pool().content()
+	}
+
+	fn in_pool_status(&self, hash: &TxHash<Self>) -> InPoolStatus<TxHash<Self>> {
+		self.pool.validated_pool().in_pool_status(hash)
+	}
+
 	fn ready_at(&self, at: NumberFor<Self::Block>) -> PolledIterator<PoolApi> {
 		if self.ready_poll.lock().updated_at() >= at {
 			let iterator: ReadyIteratorFor<PoolApi> = Box::new(self.pool.validated_pool().ready());
@@ -15,7 +15,7 @@
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::*;
-use sp_transaction_pool::TransactionStatus;
+use sp_transaction_pool::{TransactionStatus, InPoolStatus};
 use futures::executor::block_on;
 use txpool::{self, Pool};
 use sp_runtime::{
@@ -138,6 +138,24 @@ fn should_ban_invalid_transactions() {
 	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt.clone())).unwrap_err();
 }
 
+#[test]
+fn should_report_banned_transactions_status() {
+	let pool = pool();
+	let uxt = uxt(Alice, 209);
+	let hash = block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt.clone())).unwrap();
+	match pool.validated_pool().in_pool_status(&hash) {
+		InPoolStatus::Ready(details) => assert_eq!(details.hash, hash),
+		status => panic!("Unexpected status: {:?}", status),
+	}
+
+	// when
+	pool.validated_pool().remove_invalid(&[hash]);
+
+	// then
+	assert_eq!(pool.validated_pool().in_pool_status(&hash), InPoolStatus::Banned);
+	assert_eq!(pool.validated_pool().in_pool_status(&Default::default()), InPoolStatus::Unknown);
+}
+
 #[test]
 fn should_correctly_prune_transactions_providing_more_than_one_tag() {
 	let api = Arc::new(TestApi::with_alice_nonce(209));
@@ -0,0 +1,337 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Assets pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_system::RawOrigin;
+use frame_support::dispatch::Dispatchable;
+use frame_benchmarking::{benchmarks, account};
+use sp_runtime::traits::Bounded;
+
+use crate::Module as Assets;
+
+const SEED: u32 = 0;
+const MAX_USER_INDEX: u32 = 1000;
+const MAX_ACCOUNTS: u32 = 1000;
+const MAX_APPROVALS: u32 = 1000;
+
+// Create the asset `0` owned by `caller`, with a minimum balance of 1, and return its owner.
+fn create_default_asset<T: Trait>() -> Result<T::AccountId, &'static str> {
+	let caller: T::AccountId = account("caller", 0, SEED);
+	let caller_lookup = T::Lookup::unlookup(caller.clone());
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	Assets::<T>::force_create(
+		T::ForceOrigin::successful_origin(),
+		Default::default(),
+		caller_lookup,
+		1.into(),
+	)?;
+	Ok(caller)
+}
+
+// Create the asset `0`, then mint `amount` units of it to `accounts` accounts, starting with its
+// owner, and return its owner.
+fn create_default_minted_asset<T: Trait>(
+	accounts: u32,
+	amount: T::Balance,
+) -> Result<T::AccountId, &'static str> {
+	let caller = create_default_asset::<T>()?;
+	for i in 0 .. accounts {
+		let who = if i == 0 { caller.clone() } else { account("holder", i, SEED) };
+		Assets::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			Default::default(),
+			T::Lookup::unlookup(who),
+			amount,
+		)?;
+	}
+	Ok(caller)
+}
+
+// Approve `approvals` delegates to transfer 100 units of the asset `0` of `owner`.
+fn add_approvals<T: Trait>(owner: &T::AccountId, approvals: u32) -> Result<(), &'static str> {
+	T::Currency::make_free_balance_be(owner, BalanceOf::<T>::max_value());
+	for i in 0 .. approvals {
+		let delegate: T::AccountId = account("delegate", i, SEED);
+		Assets::<T>::approve_transfer(
+			RawOrigin::Signed(owner.clone()).into(),
+			Default::default(),
+			T::Lookup::unlookup(delegate),
+			100.into(),
+		)?;
+	}
+	Ok(())
+}
+
+benchmarks! {
+	_ {
+		let u in 0 .. MAX_USER_INDEX => ();
+	}
+
+	create {
+		let u in ...;
+		let caller: T::AccountId = account("caller", u, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), caller_lookup, 1.into())
+	verify {
+		assert!(Asset::<T>::contains_key(T::AssetId::default()));
+		assert_eq!(T::Currency::reserved_balance(&caller), T::AssetDeposit::get());
+	}
+
+	force_create {
+		let u in ...;
+		let caller: T::AccountId = account("caller", u, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller);
+		let origin = T::ForceOrigin::successful_origin();
+		let call = Call::<T>::force_create(Default::default(), caller_lookup, 1.into());
+	}: { call.dispatch(origin)? }
+	verify {
+		assert!(Asset::<T>::contains_key(T::AssetId::default()));
+	}
+
+	destroy {
+		let a in 1 .. MAX_ACCOUNTS;
+		let p in 0 .. MAX_APPROVALS;
+		let caller = create_default_minted_asset::<T>(a, 100.into())?;
+		add_approvals::<T>(&caller, p)?;
+		let witness = DestroyWitness { accounts: a, approvals: p };
+	}: _(RawOrigin::Signed(caller), Default::default(), witness)
+	verify {
+		assert!(!Asset::<T>::contains_key(T::AssetId::default()));
+	}
+
+	mint {
+		let u in ...;
+		let caller = create_default_asset::<T>()?;
+		let target: T::AccountId = account("target", u, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		let amount: T::Balance = 100.into();
+	}: _(RawOrigin::Signed(caller), Default::default(), target_lookup, amount)
+	verify {
+		assert_eq!(Assets::<T>::balance(Default::default(), target), amount);
+	}
+
+	// Benchmark `burn` with the worst possible condition: the account is reaped.
+	burn {
+		let u in ...;
+		let caller = create_default_asset::<T>()?;
+		let target: T::AccountId = account("target", u, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		Assets::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			Default::default(),
+			target_lookup.clone(),
+			100.into(),
+		)?;
+	}: _(RawOrigin::Signed(caller), Default::default(), target_lookup, T::Balance::max_value())
+	verify {
+		assert_eq!(Assets::<T>::balance(Default::default(), target), Zero::zero());
+	}
+
+	// Benchmark `transfer` with the worst possible condition:
+	// * The sender account is reaped.
+	// * The recipient account is created.
+	transfer {
+		let u in ...;
+		let amount: T::Balance = 100.into();
+		let caller = create_default_minted_asset::<T>(1, amount)?;
+		let target: T::AccountId = account("target", u, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), target_lookup, amount)
+	verify {
+		assert_eq!(Assets::<T>::balance(Default::default(), caller), Zero::zero());
+		assert_eq!(Assets::<T>::balance(Default::default(), target), amount);
+	}
+
+	// Benchmark `force_transfer` with the worst possible condition:
+	// * The source account is reaped.
+	// * The recipient account is created.
+	force_transfer {
+		let u in ...;
+		let amount: T::Balance = 100.into();
+		let caller = create_default_minted_asset::<T>(1, amount)?;
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let target: T::AccountId = account("target", u, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), caller_lookup, target_lookup, amount)
+	verify {
+		assert_eq!(Assets::<T>::balance(Default::default(), caller), Zero::zero());
+		assert_eq!(Assets::<T>::balance(Default::default(), target), amount);
+	}
+
+	freeze {
+		let u in ...;
+		let caller = create_default_minted_asset::<T>(1, 100.into())?;
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), caller_lookup)
+	verify {
+		assert!(Account::<T>::get(T::AssetId::default(), caller).is_frozen);
+	}
+
+	thaw {
+		let u in ...;
+		let caller = create_default_minted_asset::<T>(1, 100.into())?;
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		Assets::<T>::freeze(
+			RawOrigin::Signed(caller.clone()).into(),
+			Default::default(),
+			caller_lookup.clone(),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), caller_lookup)
+	verify {
+		assert!(!Account::<T>::get(T::AssetId::default(), caller).is_frozen);
+	}
+
+	freeze_asset {
+		let u in ...;
+		let caller = create_default_asset::<T>()?;
+	}: _(RawOrigin::Signed(caller), Default::default())
+	verify {
+		assert!(Asset::<T>::get(T::AssetId::default()).ok_or("asset not found")?.is_frozen);
+	}
+
+	thaw_asset {
+		let u in ...;
+		let caller = create_default_asset::<T>()?;
+		Assets::<T>::freeze_asset(RawOrigin::Signed(caller.clone()).into(), Default::default())?;
+	}: _(RawOrigin::Signed(caller), Default::default())
+	verify {
+		assert!(!Asset::<T>::get(T::AssetId::default()).ok_or("asset not found")?.is_frozen);
+	}
+
+	transfer_ownership {
+		let u in ...;
+		let caller = create_default_asset::<T>()?;
+		let target: T::AccountId = account("target", u, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(RawOrigin::Signed(caller), Default::default(), target_lookup)
+	verify {
+		assert_eq!(Asset::<T>::get(T::AssetId::default()).ok_or("asset not found")?.owner, target);
+	}
+
+	set_team {
+		let u in ...;
+		let caller = create_default_asset::<T>()?;
+		let target: T::AccountId = account("target", u, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(
+		RawOrigin::Signed(caller),
+		Default::default(),
+		target_lookup.clone(),
+		target_lookup.clone(),
+		target_lookup
+	)
+	verify {
+		let details = Asset::<T>::get(T::AssetId::default()).ok_or("asset not found")?;
+		assert_eq!(details.issuer, target);
+		assert_eq!(details.admin, target);
+		assert_eq!(details.freezer, target);
+	}
+
+	set_metadata {
+		let n in 0 .. T::StringLimit::get();
+		let s in 0 .. T::StringLimit::get();
+
+		// at least one byte, so that the metadata is set rather than cleared.
+		let name = vec![0u8; n.max(1) as usize];
+		let symbol = vec![0u8; s as usize];
+		let decimals = 12;
+
+		let caller = create_default_asset::<T>()?;
+	}: _(RawOrigin::Signed(caller), Default::default(), name.clone(), symbol.clone(), decimals)
+	verify {
+		let metadata = Metadata::<T>::get(T::AssetId::default());
+		assert_eq!(metadata.name, name);
+		assert_eq!(metadata.symbol, symbol);
+	}
+
+	approve_transfer {
+		let u in ...;
+		let caller = create_default_minted_asset::<T>(1, 100.into())?;
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let delegate: T::AccountId = account("delegate", u, SEED);
+		let delegate_lookup = T::Lookup::unlookup(delegate.clone());
+		let amount: T::Balance = 100.into();
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), delegate_lookup, amount)
+	verify {
+		assert_eq!(Assets::<T>::allowance(Default::default(), &caller, &delegate), amount);
+	}
+
+	cancel_approval {
+		let u in ...;
+		let caller = create_default_minted_asset::<T>(1, 100.into())?;
+		add_approvals::<T>(&caller, 1)?;
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let delegate_lookup = T::Lookup::unlookup(delegate.clone());
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), delegate_lookup)
+	verify {
+		assert_eq!(Assets::<T>::allowance(Default::default(), &caller, &delegate), Zero::zero());
+	}
+
+	// Benchmark `transfer_approved` with the worst possible condition:
+	// * The whole allowance is used, the approval is removed.
+	// * The owner account is reaped.
+	// * The recipient account is created.
+	transfer_approved {
+		let u in ...;
+		let amount: T::Balance = 100.into();
+		let owner = create_default_minted_asset::<T>(1, amount)?;
+		let owner_lookup = T::Lookup::unlookup(owner.clone());
+		add_approvals::<T>(&owner, 1)?;
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let dest: T::AccountId = account("dest", u, SEED);
+		let dest_lookup = T::Lookup::unlookup(dest.clone());
+	}: _(RawOrigin::Signed(delegate.clone()), Default::default(), owner_lookup, dest_lookup, amount)
+	verify {
+		assert_eq!(Assets::<T>::allowance(Default::default(), &owner, &delegate), Zero::zero());
+		assert_eq!(Assets::<T>::balance(Default::default(), dest), amount);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create::<Test>());
+			assert_ok!(test_benchmark_force_create::<Test>());
+			assert_ok!(test_benchmark_destroy::<Test>());
+			assert_ok!(test_benchmark_mint::<Test>());
+			assert_ok!(test_benchmark_burn::<Test>());
+			assert_ok!(test_benchmark_transfer::<Test>());
+			assert_ok!(test_benchmark_force_transfer::<Test>());
+			assert_ok!(test_benchmark_freeze::<Test>());
+			assert_ok!(test_benchmark_thaw::<Test>());
+			assert_ok!(test_benchmark_freeze_asset::<Test>());
+			assert_ok!(test_benchmark_thaw_asset::<Test>());
+			assert_ok!(test_benchmark_transfer_ownership::<Test>());
+			assert_ok!(test_benchmark_set_team::<Test>());
+			assert_ok!(test_benchmark_set_metadata::<Test>());
+			assert_ok!(test_benchmark_approve_transfer::<Test>());
+			assert_ok!(test_benchmark_cancel_approval::<Test>());
+			assert_ok!(test_benchmark_transfer_approved::<Test>());
+		});
+	}
+}
@@ -21,10 +21,12 @@
 //! ## Overview
 //!
 //! The Assets module provides functionality for asset management of fungible asset classes
-//! with a fixed supply, including:
+//! with a managed supply, including:
 //!
+//! * Asset Creation
 //! * Asset Issuance
 //! * Asset Transfer
+//! * Asset Freezing
 //! * Asset Destruction
 //!
 //! To use it in your runtime, you need to implement the assets [`Trait`](./trait.Trait.html).
@@ -33,10 +35,22 @@
 //!
 //! ### Terminology
 //!
-//! * **Asset issuance:** The creation of a new asset, whose total supply will belong to the
-//!   account that issues the asset.
+//! * **Asset creation:** The creation of a new asset class, with no supply. The creator holds
+//!   a deposit for the asset class, which is returned when it is destroyed.
+//! * **Asset issuance:** The minting of new units of an asset class, by its issuer.
 //! * **Asset transfer:** The action of transferring assets from one account to another.
-//! * **Asset destruction:** The process of an account removing its entire holding of an asset.
+//! * **Asset burning:** The process of removing units of an asset from an account, by its admin.
+//! * **Asset destruction:** The removal of an asset class, along with all of its accounts.
+//! * **Asset owner:** The account which created an asset class, which may destroy it, change its
+//!   team and set its metadata.
+//! * **Asset team:** The accounts holding the privileged roles of an asset class: the issuer may
+//!   mint new units, the admin may burn units, force transfers and thaw accounts, and the freezer
+//!   may freeze accounts.
+//! * **Minimum balance:** The smallest balance of an asset an account may hold. An account whose
+//!   balance would fall below it is reaped, its remaining units being transferred or burned.
+//! * **Freezing:** Preventing an account, or all accounts, from transferring units of an asset.
+//! * **Approval:** An allowance given by an account to a delegate account, to transfer up to some
+//!   amount of its assets. The approving account holds a deposit while the approval exists.
 //! * **Fungible asset:** An asset whose units are interchangeable.
 //! * **Non-fungible asset:** An asset for which each unit has unique characteristics.
 //!
@@ -44,20 +58,44 @@
 //!
 //! The assets system in Substrate is designed to make the following possible:
 //!
-//! * Issue a unique asset to its creator's account.
+//! * Create a new asset class, either by depositing some funds or through a privileged origin.
+//! * Issue units of an asset class, and burn them, under the control of the asset team.
 //! * Move assets between accounts.
-//! * Remove an account's balance of an asset when requested by that account's owner and update
-//!   the asset's total supply.
+//! * Freeze and thaw accounts, or whole asset classes.
+//! * Store a name, a symbol and a number of decimals for each asset class.
+//! * Remove an asset class and all of its accounts when requested by its owner.
 //!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
-//! * `issue` - Issues the total supply of a new fungible asset to the account of the caller of the function.
+//! * `create` - Creates a new asset class, taking the required deposit.
 //! * `transfer` - Transfers an `amount` of units of fungible asset `id` from the balance of
 //! the function caller's account (`origin`) to a `target` account.
-//! * `destroy` - Destroys the entire holding of a fungible asset `id` associated with the account
-//! that called the function.
+//! * `destroy` - Destroys an entire asset class; called by the asset class's Owner.
+//! * `mint` - Increases the asset balance of an account; called by the asset class's Issuer.
+//! * `burn` - Decreases the asset balance of an account; called by the asset class's Admin.
+//! * `force_transfer` - Transfers between arbitrary accounts; called by the asset class's Admin.
+//! * `freeze` - Disallows further `transfer`s from an account; called by the asset class's
+//! Freezer.
+//! * `thaw` - Allows further `transfer`s from an account; called by the asset class's Admin.
+//! * `freeze_asset` - Disallows further `transfer`s of an asset class; called by the asset
+//! class's Freezer.
+//! * `thaw_asset` - Allows further `transfer`s of an asset class; called by the asset class's
+//! Admin.
+//! * `transfer_ownership` - Changes an asset class's Owner; called by the asset class's Owner.
+//! * `set_team` - Changes an asset class's Admin, Freezer and Issuer; called by the asset class's
+//! Owner.
+//! * `set_metadata` - Sets the name, symbol and decimals of an asset class; called by the asset
+//! class's Owner.
+//! * `approve_transfer` - Allows a delegate account to transfer some assets of the caller.
+//! * `cancel_approval` - Revokes the approval of a delegate account given by the caller.
+//! * `transfer_approved` - Transfers assets of an account which approved the caller as delegate.
+//!
+//! The following functions are only callable by the `ForceOrigin`:
+//!
+//! * `force_create` - Creates a new asset class without taking any deposit.
+//! * `destroy` - Destroys an entire asset class.
 //!
 //! Please refer to the [`Call`](./enum.Call.html) enum and its associated variants for documentation on each function.
 //!
@@ -66,6 +104,7 @@
 //!
 //! * `balance` - Get the asset `id` balance of `who`.
 //! * `total_supply` - Get the total supply of an asset `id`.
+//! * `allowance` - Get the amount of asset `id` that `delegate` may still transfer from `owner`.
 //!
 //! Please refer to the [`Module`](./struct.Module.html) struct for details on publicly available functions.
 //!
@@ -73,7 +112,6 @@
 //!
 //! The following example shows how to use the Assets module in your runtime by exposing public functions to:
 //!
-//! * Issue a new fungible asset for a token distribution event (airdrop).
 //! * Query the fungible asset holding balance of an account.
 //! * Query the total supply of a fungible asset that has been issued.
 //!
@@ -92,38 +130,19 @@
 //!
 //! decl_module! {
 //! 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
-//! 		pub fn issue_token_airdrop(origin) -> dispatch::DispatchResult {
+//! 		pub fn ensure_major_holder(origin, id: T::AssetId) -> dispatch::DispatchResult {
 //! 			let sender = ensure_signed(origin).map_err(|e| e.as_str())?;
 //!
-//! 			const ACCOUNT_ALICE: u64 = 1;
-//! 			const ACCOUNT_BOB: u64 = 2;
-//! 			const COUNT_AIRDROP_RECIPIENTS: u64 = 2;
-//! 			const TOKENS_FIXED_SUPPLY: u64 = 100;
-//!
-//! 			ensure!(!COUNT_AIRDROP_RECIPIENTS.is_zero(), "Divide by zero error.");
-//!
-//! 			let asset_id = Self::next_asset_id();
+//! 			let balance = <assets::Module<T>>::balance(id, sender);
+//! 			let total_supply = <assets::Module<T>>::total_supply(id);
 //!
-//! 			<NextAssetId<T>>::mutate(|asset_id| *asset_id += 1);
-//! 			<Balances<T>>::insert((asset_id, &ACCOUNT_ALICE), TOKENS_FIXED_SUPPLY / COUNT_AIRDROP_RECIPIENTS);
-//! 			<Balances<T>>::insert((asset_id, &ACCOUNT_BOB), TOKENS_FIXED_SUPPLY / COUNT_AIRDROP_RECIPIENTS);
-//! 			<TotalSupply<T>>::insert(asset_id, TOKENS_FIXED_SUPPLY);
-//!
-//! 			Self::deposit_event(RawEvent::Issued(asset_id, sender, TOKENS_FIXED_SUPPLY));
+//! 			ensure!(balance * 10.into() >= total_supply, "Not a major holder of the asset.");
 //! 			Ok(())
 //! 		}
 //! 	}
 //! }
 //! ```
 //!
-//! ## Assumptions
-//!
-//! Below are assumptions that must be held when using this module.  If any of
-//! them are violated, the behavior of this module is undefined.
-//!
-//! * The total count of assets should be less than
-//!   `Trait::AssetId::max_value()`.
-//!
 //! ## Related Modules
 //!
 //! * [`System`](../frame_system/index.html)
@@ -132,10 +151,20 @@
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{Parameter, decl_module, decl_event, decl_storage, decl_error, ensure};
-use sp_runtime::traits::{Member, AtLeast32Bit, Zero, StaticLookup};
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use frame_support::{
+	Parameter, decl_module, decl_event, decl_storage, decl_error, ensure, IterableStorageDoubleMap,
+	traits::{Currency, ReservableCurrency, EnsureOrigin, Get, BalanceStatus, MultiAsset, WithdrawReasons},
+	weights::Weight,
+};
+use sp_runtime::{DispatchError, DispatchResult, RuntimeDebug};
+use sp_runtime::traits::{Member, AtLeast32Bit, Zero, StaticLookup, CheckedAdd, CheckedSub, Saturating};
 use frame_system::{self as system, ensure_signed};
-use sp_runtime::traits::One;
+
+mod benchmarking;
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 
 /// The module configuration trait.
 pub trait Trait: frame_system::Trait {
@@ -147,6 +176,95 @@ pub trait Trait: frame_system::Trait {
 
 	/// The arithmetic type of asset identifier.
 	type AssetId: Parameter + AtLeast32Bit + Default + Copy;
+
+	/// The currency mechanism, used for the deposits of asset classes and of their metadata.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The origin which may forcibly create or destroy an asset class.
+	type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The amount held on deposit for a created asset class.
+	type AssetDeposit: Get<BalanceOf<Self>>;
+
+	/// The basic amount held on deposit for the metadata of an asset class.
+	type MetadataDepositBase: Get<BalanceOf<Self>>;
+
+	/// The additional amount held on deposit for each byte of the name and symbol of an asset
+	/// class.
+	type MetadataDepositPerByte: Get<BalanceOf<Self>>;
+
+	/// The maximum length of the name or symbol of an asset class.
+	type StringLimit: Get<u32>;
+
+	/// The amount held on deposit for a stored approval.
+	type ApprovalDeposit: Get<BalanceOf<Self>>;
+}
+
+/// The details of an asset class.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct AssetDetails<Balance, AccountId, DepositBalance> {
+	/// Can change `owner`, `issuer`, `freezer` and `admin` accounts.
+	owner: AccountId,
+	/// Can mint tokens.
+	issuer: AccountId,
+	/// Can thaw tokens, force transfers and burn tokens from any account.
+	admin: AccountId,
+	/// Can freeze tokens.
+	freezer: AccountId,
+	/// The total supply across all accounts.
+	supply: Balance,
+	/// The balance deposited for this asset class. This pays for the data stored here.
+	deposit: DepositBalance,
+	/// The smallest balance an account may hold; accounts falling below it are reaped.
+	min_balance: Balance,
+	/// The number of accounts holding units of this asset class.
+	accounts: u32,
+	/// The number of approvals to transfer units of this asset class.
+	approvals: u32,
+	/// Whether the asset class is frozen for all accounts.
+	is_frozen: bool,
+}
+
+/// The balance of an account for an asset class.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct AssetBalance<Balance> {
+	/// The balance.
+	balance: Balance,
+	/// Whether the account is frozen.
+	is_frozen: bool,
+}
+
+/// An approval to transfer some units of an asset class from the account which gave it.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct Approval<Balance, DepositBalance> {
+	/// The amount which may still be transferred.
+	amount: Balance,
+	/// The balance deposited by the approving account for this approval.
+	deposit: DepositBalance,
+}
+
+/// Witness data for the destruction of an asset class.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct DestroyWitness {
+	/// An upper bound on the number of accounts holding the asset.
+	#[codec(compact)]
+	pub accounts: u32,
+	/// An upper bound on the number of approvals of the asset.
+	#[codec(compact)]
+	pub approvals: u32,
+}
+
+/// The metadata of an asset class.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct AssetMetadata<DepositBalance> {
+	/// The balance deposited for this metadata. This pays for the data stored here.
+	deposit: DepositBalance,
+	/// The user friendly name of this asset. Limited in length by `StringLimit`.
+	name: Vec<u8>,
+	/// The ticker symbol for this asset. Limited in length by `StringLimit`.
+	symbol: Vec<u8>,
+	/// The number of decimals this asset uses to represent one unit.
+	decimals: u8,
 }
 
 decl_module! {
@@ -154,71 +272,723 @@ decl_module! {
 		type Error = Error<T>;
 
 		fn deposit_event() = default;
-		/// Issue a new class of fungible assets. There are, and will only ever be, `total`
-		/// such assets and they'll all belong to the `origin` initially. It will have an
-		/// identifier `AssetId` instance: this will be specified in the `Issued` event.
+
+		/// Issue a new class of fungible assets from a public origin.
+		///
+		/// This new asset class has no assets initially. Its owner is the origin, and
+		/// `AssetDeposit` is reserved from it.
+		///
+		/// The origin must be Signed and the sender must have sufficient funds free.
+		///
+		/// Parameters:
+		/// - `id`: The identifier of the new asset. This must not be currently in use to identify
+		/// an existing asset.
+		/// - `admin`: The admin of this class of assets. The admin is the initial address of each
+		/// member of the asset class's admin team.
+		/// - `min_balance`: The minimum balance of this new asset that any single account must
+		/// have. If an account's balance is reduced below this, then it collapses to zero.
+		///
+		/// Emits `Created` event when successful.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read and write.
+		/// - 1 balance reservation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 50_000_000]
+		fn create(origin,
+			#[compact] id: T::AssetId,
+			admin: <T::Lookup as StaticLookup>::Source,
+			#[compact] min_balance: T::Balance,
+		) {
+			let owner = ensure_signed(origin)?;
+			let admin = T::Lookup::lookup(admin)?;
+
+			ensure!(!Asset::<T>::contains_key(id), Error::<T>::InUse);
+			ensure!(!min_balance.is_zero(), Error::<T>::MinBalanceZero);
+
+			let deposit = T::AssetDeposit::get();
+			T::Currency::reserve(&owner, deposit)?;
+
+			Asset::<T>::insert(id, AssetDetails {
+				owner: owner.clone(),
+				issuer: admin.clone(),
+				admin: admin.clone(),
+				freezer: admin.clone(),
+				supply: Zero::zero(),
+				deposit,
+				min_balance,
+				accounts: 0,
+				approvals: 0,
+				is_frozen: false,
+			});
+			Self::deposit_event(RawEvent::Created(id, owner, admin));
+		}
+
+		/// Issue a new class of fungible assets from a privileged origin.
+		///
+		/// This new asset class has no assets initially and no deposit is taken.
+		///
+		/// The origin must conform to `ForceOrigin`.
+		///
+		/// - `id`: The identifier of the new asset. This must not be currently in use to identify
+		/// an existing asset.
+		/// - `owner`: The owner of this class of assets. The owner has full superuser permissions
+		/// over this asset, but may later change and configure the permissions using
+		/// `transfer_ownership` and `set_team`.
+		/// - `min_balance`: The minimum balance of this new asset that any single account must
+		/// have. If an account's balance is reduced below this, then it collapses to zero.
+		///
+		/// Emits `ForceCreated` event when successful.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read and write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 25_000_000]
+		fn force_create(origin,
+			#[compact] id: T::AssetId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			#[compact] min_balance: T::Balance,
+		) {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			ensure!(!Asset::<T>::contains_key(id), Error::<T>::InUse);
+			ensure!(!min_balance.is_zero(), Error::<T>::MinBalanceZero);
+
+			Asset::<T>::insert(id, AssetDetails {
+				owner: owner.clone(),
+				issuer: owner.clone(),
+				admin: owner.clone(),
+				freezer: owner.clone(),
+				supply: Zero::zero(),
+				deposit: Zero::zero(),
+				min_balance,
+				accounts: 0,
+				approvals: 0,
+				is_frozen: false,
+			});
+			Self::deposit_event(RawEvent::ForceCreated(id, owner));
+		}
+
+		/// Destroy a class of fungible assets, along with all of its accounts and approvals.
+		///
+		/// The origin must conform to `ForceOrigin` or must be Signed and the sender must be the
+		/// owner of the asset `id`. The deposits of the asset class and of its metadata are
+		/// returned to the owner, the deposits of the approvals to the approving accounts.
+		///
+		/// - `id`: The identifier of the asset to be destroyed. This must identify an existing
+		/// asset.
+		/// - `witness`: Upper bounds on the number of accounts holding the asset and on the
+		/// number of its approvals.
+		///
+		/// Emits `Destroyed` event when successful.
+		///
+		/// # <weight>
+		/// - `O(a + p)` where `a` is `witness.accounts` and `p` is `witness.approvals`.
+		/// - `p + 2` storage reads.
+		/// - `a + p + 2` storage deletions.
+		/// - `p + 1` balance unreservations.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(
+				(witness.approvals as Weight).saturating_add(2),
+				(witness.accounts as Weight).saturating_add(witness.approvals as Weight).saturating_add(2),
+			)
+			.saturating_add(50_000_000)
+			.saturating_add((witness.accounts as Weight).saturating_mul(15_000_000))
+			.saturating_add((witness.approvals as Weight).saturating_mul(25_000_000))
+		]
+		fn destroy(origin, #[compact] id: T::AssetId, witness: DestroyWitness) {
+			let maybe_check_owner = match T::ForceOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+			let details = Asset::<T>::get(id).ok_or(Error::<T>::Unknown)?;
+			if let Some(check_owner) = maybe_check_owner {
+				ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+			}
+			ensure!(details.accounts <= witness.accounts, Error::<T>::BadWitness);
+			ensure!(details.approvals <= witness.approvals, Error::<T>::BadWitness);
+
+			let metadata_deposit = Metadata::<T>::take(id).deposit;
+			T::Currency::unreserve(&details.owner, details.deposit.saturating_add(metadata_deposit));
+
+			for ((owner, _), approval) in Approvals::<T>::drain_prefix(&id) {
+				T::Currency::unreserve(&owner, approval.deposit);
+			}
+			Account::<T>::remove_prefix(&id);
+			Asset::<T>::remove(id);
+			Self::deposit_event(RawEvent::Destroyed(id));
+		}
+
+		/// Mint assets of a particular class.
+		///
+		/// The origin must be Signed and the sender must be the Issuer of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset to have some amount minted.
+		/// - `beneficiary`: The account to be credited with the minted assets.
+		/// - `amount`: The amount of the asset to be minted.
+		///
+		/// Emits `Issued` event when successful.
 		///
 		/// # <weight>
 		/// - `O(1)`
-		/// - 1 storage mutation (codec `O(1)`).
-		/// - 2 storage writes (condec `O(1)`).
+		/// - 2 storage reads and writes.
 		/// - 1 event.
 		/// # </weight>
-		#[weight = 0]
-		fn issue(origin, #[compact] total: T::Balance) {
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 45_000_000]
+		fn mint(origin,
+			#[compact] id: T::AssetId,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance
+		) {
+			let origin = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+
+			Asset::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == details.issuer, Error::<T>::NoPermission);
+
+				details.supply = details.supply.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+				Self::increase_balance(id, details, &beneficiary, amount)
+			})?;
+			Self::deposit_event(RawEvent::Issued(id, beneficiary, amount));
+		}
+
+		/// Reduce the balance of `who` by as much as possible up to `amount` assets of `id`.
+		///
+		/// The origin must be Signed and the sender should be the Admin of the asset `id`.
+		///
+		/// Bails with `BalanceZero` if the `who` is already dead. If the remaining balance of
+		/// `who` would fall below the minimum balance of the asset, it is burned as well.
+		///
+		/// - `id`: The identifier of the asset to have some amount burned.
+		/// - `who`: The account to be debited from.
+		/// - `amount`: The maximum amount by which `who`'s balance should be reduced.
+		///
+		/// Emits `Burned` with the actual amount burned.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 storage reads and writes.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 45_000_000]
+		fn burn(origin,
+			#[compact] id: T::AssetId,
+			who: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance
+		) {
 			let origin = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
 
-			let id = Self::next_asset_id();
-			<NextAssetId<T>>::mutate(|id| *id += One::one());
+			let burned = Asset::<T>::try_mutate(id, |maybe_details| -> Result<_, DispatchError> {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == details.admin, Error::<T>::NoPermission);
 
-			<Balances<T>>::insert((id, &origin), total);
-			<TotalSupply<T>>::insert(id, total);
+				let balance = Account::<T>::get(id, &who).balance;
+				ensure!(!balance.is_zero(), Error::<T>::BalanceZero);
 
-			Self::deposit_event(RawEvent::Issued(id, origin, total));
+				let mut burned = amount.min(balance);
+				if balance - burned < details.min_balance {
+					burned = balance;
+				}
+				Self::decrease_balance(id, details, &who, burned);
+				details.supply = details.supply.saturating_sub(burned);
+				Ok(burned)
+			})?;
+			Self::deposit_event(RawEvent::Burned(id, who, burned));
 		}
 
-		/// Move some assets from one holder to another.
+		/// Move some assets from the sender account to another.
+		///
+		/// The origin must be Signed and neither the sender account nor the asset class must be
+		/// frozen.
+		///
+		/// - `id`: The identifier of the asset to have some amount transferred.
+		/// - `target`: The account to be credited.
+		/// - `amount`: The amount by which the sender's balance of assets should be reduced and
+		/// `target`'s balance increased. The amount actually transferred may be slightly greater
+		/// in the case that the transfer would otherwise take the sender balance above zero but
+		/// below the minimum balance. Must be greater than zero.
+		///
+		/// Emits `Transferred` with the actual amount transferred.
 		///
 		/// # <weight>
 		/// - `O(1)`
 		/// - 1 static lookup
-		/// - 2 storage mutations (codec `O(1)`).
+		/// - 3 storage reads and writes.
 		/// - 1 event.
 		/// # </weight>
-		#[weight = 0]
+		#[weight = T::DbWeight::get().reads_writes(3, 3) + 70_000_000]
 		fn transfer(origin,
 			#[compact] id: T::AssetId,
 			target: <T::Lookup as StaticLookup>::Source,
 			#[compact] amount: T::Balance
 		) {
 			let origin = ensure_signed(origin)?;
-			let origin_account = (id, origin.clone());
-			let origin_balance = <Balances<T>>::get(&origin_account);
 			let target = T::Lookup::lookup(target)?;
-			ensure!(!amount.is_zero(), Error::<T>::AmountZero);
-			ensure!(origin_balance >= amount, Error::<T>::BalanceLow);
 
-			Self::deposit_event(RawEvent::Transferred(id, origin, target.clone(), amount));
-			<Balances<T>>::insert(origin_account, origin_balance - amount);
-			<Balances<T>>::mutate((id, target), |balance| *balance += amount);
+			let transferred = Self::do_transfer(id, &origin, &target, amount, None, None)?;
+			Self::deposit_event(RawEvent::Transferred(id, origin, target, transferred));
 		}
 
-		/// Destroy any assets of `id` owned by `origin`.
+		/// Move some assets from one account to another.
+		///
+		/// The origin must be Signed and the sender should be the Admin of the asset `id`. Frozen
+		/// accounts and asset classes are ignored.
+		///
+		/// - `id`: The identifier of the asset to have some amount transferred.
+		/// - `source`: The account to be debited.
+		/// - `dest`: The account to be credited.
+		/// - `amount`: The amount by which the `source`'s balance of assets should be reduced and
+		/// `dest`'s balance increased. The amount actually transferred may be slightly greater in
+		/// the case that the transfer would otherwise take the `source` balance above zero but
+		/// below the minimum balance. Must be greater than zero.
+		///
+		/// Emits `ForceTransferred` with the actual amount transferred.
 		///
 		/// # <weight>
 		/// - `O(1)`
-		/// - 1 storage mutation (codec `O(1)`).
-		/// - 1 storage deletion (codec `O(1)`).
+		/// - 2 static lookups
+		/// - 3 storage reads and writes.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(3, 3) + 70_000_000]
+		fn force_transfer(origin,
+			#[compact] id: T::AssetId,
+			source: <T::Lookup as StaticLookup>::Source,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance,
+		) {
+			let origin = ensure_signed(origin)?;
+			let source = T::Lookup::lookup(source)?;
+			let dest = T::Lookup::lookup(dest)?;
+
+			let transferred = Self::do_transfer(id, &source, &dest, amount, Some(origin), None)?;
+			Self::deposit_event(RawEvent::ForceTransferred(id, source, dest, transferred));
+		}
+
+		/// Disallow further unprivileged transfers from an account.
+		///
+		/// The origin must be Signed and the sender should be the Freezer of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset to be frozen.
+		/// - `who`: The account to be frozen.
+		///
+		/// Emits `Frozen`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 storage reads.
+		/// - 1 storage write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 1) + 30_000_000]
+		fn freeze(origin, #[compact] id: T::AssetId, who: <T::Lookup as StaticLookup>::Source) {
+			let origin = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			let details = Asset::<T>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.freezer, Error::<T>::NoPermission);
+			ensure!(Account::<T>::contains_key(id, &who), Error::<T>::BalanceZero);
+
+			Account::<T>::mutate(id, &who, |account| account.is_frozen = true);
+			Self::deposit_event(RawEvent::Frozen(id, who));
+		}
+
+		/// Allow unprivileged transfers from an account again.
+		///
+		/// The origin must be Signed and the sender should be the Admin of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset to be thawed.
+		/// - `who`: The account to be unfrozen.
+		///
+		/// Emits `Thawed`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 storage reads.
+		/// - 1 storage write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 1) + 30_000_000]
+		fn thaw(origin, #[compact] id: T::AssetId, who: <T::Lookup as StaticLookup>::Source) {
+			let origin = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			let details = Asset::<T>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.admin, Error::<T>::NoPermission);
+			ensure!(Account::<T>::contains_key(id, &who), Error::<T>::BalanceZero);
+
+			Account::<T>::mutate(id, &who, |account| account.is_frozen = false);
+			Self::deposit_event(RawEvent::Thawed(id, who));
+		}
+
+		/// Disallow further unprivileged transfers for the asset class.
+		///
+		/// The origin must be Signed and the sender should be the Freezer of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset to be frozen.
+		///
+		/// Emits `AssetFrozen`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read and write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 25_000_000]
+		fn freeze_asset(origin, #[compact] id: T::AssetId) {
+			let origin = ensure_signed(origin)?;
+
+			Asset::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == details.freezer, Error::<T>::NoPermission);
+
+				details.is_frozen = true;
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::AssetFrozen(id));
+		}
+
+		/// Allow unprivileged transfers for the asset class again.
+		///
+		/// The origin must be Signed and the sender should be the Admin of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset to be thawed.
+		///
+		/// Emits `AssetThawed`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read and write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 25_000_000]
+		fn thaw_asset(origin, #[compact] id: T::AssetId) {
+			let origin = ensure_signed(origin)?;
+
+			Asset::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == details.admin, Error::<T>::NoPermission);
+
+				details.is_frozen = false;
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::AssetThawed(id));
+		}
+
+		/// Change the Owner of an asset.
+		///
+		/// The origin must be Signed and the sender should be the Owner of the asset `id`. The
+		/// deposits of the asset class and of its metadata are moved to the new owner.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `owner`: The new Owner of this asset.
+		///
+		/// Emits `OwnerChanged`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 storage reads.
+		/// - 1 storage write.
+		/// - 1 balance repatriation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 1) + 45_000_000]
+		fn transfer_ownership(origin,
+			#[compact] id: T::AssetId,
+			owner: <T::Lookup as StaticLookup>::Source,
+		) {
+			let origin = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			Asset::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == details.owner, Error::<T>::NoPermission);
+				if details.owner == owner {
+					return Ok(())
+				}
+
+				let deposit = details.deposit.saturating_add(Metadata::<T>::get(id).deposit);
+				T::Currency::repatriate_reserved(&details.owner, &owner, deposit, BalanceStatus::Reserved)?;
+				details.owner = owner.clone();
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::OwnerChanged(id, owner));
+		}
+
+		/// Change the Issuer, Admin and Freezer of an asset.
+		///
+		/// The origin must be Signed and the sender should be the Owner of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset to be frozen.
+		/// - `issuer`: The new Issuer of this asset.
+		/// - `admin`: The new Admin of this asset.
+		/// - `freezer`: The new Freezer of this asset.
+		///
+		/// Emits `TeamChanged`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read and write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 25_000_000]
+		fn set_team(origin,
+			#[compact] id: T::AssetId,
+			issuer: <T::Lookup as StaticLookup>::Source,
+			admin: <T::Lookup as StaticLookup>::Source,
+			freezer: <T::Lookup as StaticLookup>::Source,
+		) {
+			let origin = ensure_signed(origin)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+			let admin = T::Lookup::lookup(admin)?;
+			let freezer = T::Lookup::lookup(freezer)?;
+
+			Asset::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == details.owner, Error::<T>::NoPermission);
+
+				details.issuer = issuer.clone();
+				details.admin = admin.clone();
+				details.freezer = freezer.clone();
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::TeamChanged(id, issuer, admin, freezer));
+		}
+
+		/// Set the metadata for an asset.
+		///
+		/// The origin must be Signed and the sender should be the Owner of the asset `id`.
+		///
+		/// Funds of the sender are reserved according to the formula:
+		/// `MetadataDepositBase + MetadataDepositPerByte * (name.len + symbol.len)` taking into
+		/// account any already reserved funds. Setting an empty name and symbol clears the
+		/// metadata and returns its deposit.
+		///
+		/// - `id`: The identifier of the asset to update.
+		/// - `name`: The user friendly name of this asset. Limited in length by `StringLimit`.
+		/// - `symbol`: The exchange symbol for this asset. Limited in length by `StringLimit`.
+		/// - `decimals`: The number of decimals this asset uses to represent one unit.
+		///
+		/// Emits `MetadataSet` or `MetadataCleared`.
+		///
+		/// # <weight>
+		/// - `O(N)` where `N` is the length of the name and symbol.
+		/// - 2 storage reads.
+		/// - 1 storage write.
+		/// - 1 balance reservation or unreservation.
 		/// - 1 event.
 		/// # </weight>
-		#[weight = 0]
-		fn destroy(origin, #[compact] id: T::AssetId) {
+		#[weight = T::DbWeight::get().reads_writes(2, 1)
+			.saturating_add(50_000_000)
+			.saturating_add(((name.len() + symbol.len()) as Weight).saturating_mul(10_000))
+		]
+		fn set_metadata(origin,
+			#[compact] id: T::AssetId,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+		) {
 			let origin = ensure_signed(origin)?;
-			let balance = <Balances<T>>::take((id, &origin));
-			ensure!(!balance.is_zero(), Error::<T>::BalanceZero);
 
-			<TotalSupply<T>>::mutate(id, |total_supply| *total_supply -= balance);
-			Self::deposit_event(RawEvent::Destroyed(id, origin, balance));
+			ensure!(name.len() <= T::StringLimit::get() as usize, Error::<T>::BadMetadata);
+			ensure!(symbol.len() <= T::StringLimit::get() as usize, Error::<T>::BadMetadata);
+
+			let details = Asset::<T>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.owner, Error::<T>::NoPermission);
+
+			let old_deposit = Metadata::<T>::get(id).deposit;
+			if name.is_empty() && symbol.is_empty() {
+				T::Currency::unreserve(&origin, old_deposit);
+				Metadata::<T>::remove(id);
+				Self::deposit_event(RawEvent::MetadataCleared(id));
+				return Ok(())
+			}
+
+			let new_deposit = T::MetadataDepositPerByte::get()
+				.saturating_mul(((name.len() + symbol.len()) as u32).into())
+				.saturating_add(T::MetadataDepositBase::get());
+			if new_deposit > old_deposit {
+				T::Currency::reserve(&origin, new_deposit - old_deposit)?;
+			} else {
+				T::Currency::unreserve(&origin, old_deposit - new_deposit);
+			}
+
+			Metadata::<T>::insert(id, AssetMetadata {
+				deposit: new_deposit,
+				name: name.clone(),
+				symbol: symbol.clone(),
+				decimals,
+			});
+			Self::deposit_event(RawEvent::MetadataSet(id, name, symbol, decimals));
+		}
+
+		/// Approve an amount of asset for transfer by a delegated third-party account.
+		///
+		/// The origin must be Signed.
+		///
+		/// Ensures that `ApprovalDeposit` worth of `Currency` is reserved from the signing
+		/// account for the purpose of holding the approval. If some non-zero amount of assets is
+		/// already approved from signing account to `delegate`, then it is topped up or
+		/// unreserved to meet the right value.
+		///
+		/// NOTE: The signing account does not need to own `amount` of assets at the point of
+		/// making this call. If there is already an approval in place, then this acts additively.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `delegate`: The account to delegate permission to transfer asset.
+		/// - `amount`: The amount of asset that may be transferred by `delegate`.
+		///
+		/// Emits `ApprovedTransfer` on success.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 storage reads and writes.
+		/// - 1 balance reservation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 50_000_000]
+		fn approve_transfer(origin,
+			#[compact] id: T::AssetId,
+			delegate: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance,
+		) {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+
+			Asset::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				Approvals::<T>::try_mutate(id, (&owner, &delegate), |maybe_approved| -> DispatchResult {
+					let mut approved = match maybe_approved.take() {
+						Some(approved) => approved,
+						None => {
+							details.approvals = details.approvals.checked_add(1)
+								.ok_or(Error::<T>::Overflow)?;
+							Default::default()
+						},
+					};
+					let deposit_required = T::ApprovalDeposit::get();
+					if approved.deposit < deposit_required {
+						T::Currency::reserve(&owner, deposit_required - approved.deposit)?;
+						approved.deposit = deposit_required;
+					}
+					approved.amount = approved.amount.saturating_add(amount);
+					*maybe_approved = Some(approved);
+					Ok(())
+				})
+			})?;
+			Self::deposit_event(RawEvent::ApprovedTransfer(id, owner, delegate, amount));
+		}
+
+		/// Cancel all of some asset approved for delegated transfer by a third-party account.
+		///
+		/// The origin must be Signed and there must be an approval in place between signer and
+		/// `delegate`.
+		///
+		/// Unreserves any deposit previously reserved by `approve_transfer` for the approval.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `delegate`: The account delegated permission to transfer asset.
+		///
+		/// Emits `ApprovalCancelled` on success.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 storage reads and writes.
+		/// - 1 balance unreservation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 45_000_000]
+		fn cancel_approval(origin,
+			#[compact] id: T::AssetId,
+			delegate: <T::Lookup as StaticLookup>::Source,
+		) {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+
+			Asset::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				let approval = Approvals::<T>::take(id, (&owner, &delegate))
+					.ok_or(Error::<T>::Unapproved)?;
+				T::Currency::unreserve(&owner, approval.deposit);
+				details.approvals = details.approvals.saturating_sub(1);
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::ApprovalCancelled(id, owner, delegate));
+		}
+
+		/// Transfer some asset balance from a previously delegated account to some third-party
+		/// account.
+		///
+		/// The origin must be Signed and there must be an approval in place by the `owner` to the
+		/// signer. Neither the `owner` account nor the asset class must be frozen.
+		///
+		/// If the entire amount approved for transfer is transferred, then any deposit previously
+		/// reserved by `approve_transfer` is unreserved.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `owner`: The account which previously approved for a transfer of at least `amount`
+		/// and from which the asset balance will be withdrawn.
+		/// - `destination`: The account to which the asset balance of `amount` will be
+		/// transferred.
+		/// - `amount`: The amount of assets to transfer. As for `transfer`, the amount actually
+		/// transferred may be slightly greater in the case that the transfer would otherwise take
+		/// the `owner` balance above zero but below the minimum balance. The amount actually
+		/// transferred is deducted from the approval, and the transfer fails if it exceeds it.
+		///
+		/// Emits `TransferredApproved` on success.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 static lookups
+		/// - 4 storage reads and writes.
+		/// - 1 balance unreservation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(4, 4) + 90_000_000]
+		fn transfer_approved(origin,
+			#[compact] id: T::AssetId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			destination: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance,
+		) {
+			let delegate = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			let destination = T::Lookup::lookup(destination)?;
+
+			let mut approved = Approvals::<T>::get(id, (&owner, &delegate))
+				.ok_or(Error::<T>::Unapproved)?;
+
+			let transferred = Self::do_transfer(
+				id,
+				&owner,
+				&destination,
+				amount,
+				None,
+				Some(approved.amount),
+			)?;
+			let remaining = approved.amount.saturating_sub(transferred);
+
+			if remaining.is_zero() {
+				T::Currency::unreserve(&owner, approved.deposit);
+				Approvals::<T>::remove(id, (&owner, &delegate));
+				Asset::<T>::mutate(id, |maybe_details| if let Some(details) = maybe_details {
+					details.approvals = details.approvals.saturating_sub(1);
+				});
+			} else {
+				approved.amount = remaining;
+				Approvals::<T>::insert(id, (&owner, &delegate), approved);
+			}
+			Self::deposit_event(RawEvent::TransferredApproved(id, owner, delegate, destination, transferred));
 		}
 	}
 }
@@ -229,12 +999,45 @@ decl_event! {
 		<T as Trait>::Balance,
 		<T as Trait>::AssetId,
 	{
-		/// Some assets were issued.
+		/// Some asset class was created. [asset_id, creator, admin]
+		Created(AssetId, AccountId, AccountId),
+		/// Some assets were issued. [asset_id, beneficiary, amount]
 		Issued(AssetId, AccountId, Balance),
-		/// Some assets were transferred.
+		/// Some assets were transferred. [asset_id, from, to, amount]
 		Transferred(AssetId, AccountId, AccountId, Balance),
-		/// Some assets were destroyed.
-		Destroyed(AssetId, AccountId, Balance),
+		/// Some assets were burned. [asset_id, who, amount]
+		Burned(AssetId, AccountId, Balance),
+		/// The management team changed. [asset_id, issuer, admin, freezer]
+		TeamChanged(AssetId, AccountId, AccountId, AccountId),
+		/// The owner changed. [asset_id, owner]
+		OwnerChanged(AssetId, AccountId),
+		/// Some assets were transferred by the admin. [asset_id, from, to, amount]
+		ForceTransferred(AssetId, AccountId, AccountId, Balance),
+		/// Some account was frozen. [asset_id, who]
+		Frozen(AssetId, AccountId),
+		/// Some account was thawed. [asset_id, who]
+		Thawed(AssetId, AccountId),
+		/// Some asset class was frozen. [asset_id]
+		AssetFrozen(AssetId),
+		/// Some asset class was thawed. [asset_id]
+		AssetThawed(AssetId),
+		/// An asset class was destroyed. [asset_id]
+		Destroyed(AssetId),
+		/// Some asset class was force-created. [asset_id, owner]
+		ForceCreated(AssetId, AccountId),
+		/// New metadata has been set for an asset. [asset_id, name, symbol, decimals]
+		MetadataSet(AssetId, Vec<u8>, Vec<u8>, u8),
+		/// The metadata of an asset has been cleared. [asset_id]
+		MetadataCleared(AssetId),
+		/// An approval for a delegate account to transfer some assets of the owner was made.
+		/// [asset_id, owner, delegate, amount]
+		ApprovedTransfer(AssetId, AccountId, AccountId, Balance),
+		/// An approval for a delegate account to transfer assets of the owner was cancelled.
+		/// [asset_id, owner, delegate]
+		ApprovalCancelled(AssetId, AccountId, AccountId),
+		/// A delegate account transferred some assets of the owner, which approved it.
+		/// [asset_id, owner, delegate, destination, amount]
+		TransferredApproved(AssetId, AccountId, AccountId, AccountId, Balance),
 	}
 }
 
@@ -246,17 +1049,41 @@ decl_error! {
 		BalanceLow,
 		/// Balance should be non-zero
 		BalanceZero,
+		/// The signing account has no permission to do the operation.
+		NoPermission,
+		/// The given asset ID is unknown.
+		Unknown,
+		/// The origin account is frozen.
+		Frozen,
+		/// The asset ID is already taken.
+		InUse,
+		/// Invalid witness data given.
+		BadWitness,
+		/// Minimum balance should be non-zero.
+		MinBalanceZero,
+		/// Invalid metadata given.
+		BadMetadata,
+		/// An operation would overflow a balance or counter.
+		Overflow,
+		/// No approval exists that would allow the transfer.
+		Unapproved,
 	}
 }
 
 decl_storage! {
 	trait Store for Module<T: Trait> as Assets {
+		/// Details of an asset.
+		Asset: map hasher(blake2_128_concat) T::AssetId
+			=> Option<AssetDetails<T::Balance, T::AccountId, BalanceOf<T>>>;
 		/// The number of units of assets held by any given account.
-		Balances: map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => T::Balance;
-		/// The next asset identifier up for grabs.
-		NextAssetId get(fn next_asset_id): T::AssetId;
-		/// The total unit supply of an asset.
-		TotalSupply: map hasher(twox_64_concat) T::AssetId => T::Balance;
+		Account: double_map hasher(blake2_128_concat) T::AssetId, hasher(blake2_128_concat) T::AccountId
+			=> AssetBalance<T::Balance>;
+		/// Metadata of an asset.
+		Metadata: map hasher(blake2_128_concat) T::AssetId => AssetMetadata<BalanceOf<T>>;
+		/// Approved transfers of an asset, by account which gave the approval and delegate.
+		Approvals: double_map hasher(blake2_128_concat) T::AssetId,
+			hasher(blake2_128_concat) (T::AccountId, T::AccountId)
+			=> Option<Approval<T::Balance, BalanceOf<T>>>;
 	}
 }
 
@@ -266,12 +1093,142 @@ impl<T: Trait> Module<T> {
 
 	/// Get the asset `id` balance of `who`.
 	pub fn balance(id: T::AssetId, who: T::AccountId) -> T::Balance {
-		<Balances<T>>::get((id, who))
+		Account::<T>::get(id, who).balance
 	}
 
 	/// Get the total supply of an asset `id`.
 	pub fn total_supply(id: T::AssetId) -> T::Balance {
-		<TotalSupply<T>>::get(id)
+		Asset::<T>::get(id).map(|details| details.supply).unwrap_or_else(Zero::zero)
+	}
+
+	/// Get the amount of asset `id` that `delegate` may still transfer from the account of
+	/// `owner`.
+	pub fn allowance(id: T::AssetId, owner: &T::AccountId, delegate: &T::AccountId) -> T::Balance {
+		Approvals::<T>::get(id, (owner, delegate))
+			.map(|approval| approval.amount)
+			.unwrap_or_else(Zero::zero)
+	}
+
+	/// Credit `amount` to `who`, creating its account if needed. Fails if the resulting balance
+	/// is below the minimum balance of the asset.
+	fn increase_balance(
+		id: T::AssetId,
+		details: &mut AssetDetails<T::Balance, T::AccountId, BalanceOf<T>>,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		Account::<T>::try_mutate(id, who, |account| -> DispatchResult {
+			let new_balance = account.balance.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+			ensure!(new_balance >= details.min_balance, Error::<T>::BalanceLow);
+			if account.balance.is_zero() {
+				details.accounts = details.accounts.checked_add(1).ok_or(Error::<T>::Overflow)?;
+			}
+			account.balance = new_balance;
+			Ok(())
+		})
+	}
+
+	/// Debit `amount` from `who`, reaping its account if nothing is left. The caller must ensure
+	/// that the balance is sufficient and that the remaining balance is either zero or at least
+	/// the minimum balance.
+	fn decrease_balance(
+		id: T::AssetId,
+		details: &mut AssetDetails<T::Balance, T::AccountId, BalanceOf<T>>,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) {
+		let mut account = Account::<T>::get(id, who);
+		account.balance = account.balance.saturating_sub(amount);
+		if account.balance.is_zero() {
+			Account::<T>::remove(id, who);
+			details.accounts = details.accounts.saturating_sub(1);
+		} else {
+			Account::<T>::insert(id, who, account);
+		}
+	}
+
+	/// Move `amount` of asset `id` from `source` to `dest`, returning the amount actually
+	/// transferred. If `maybe_admin` is given, it must be the admin of the asset and frozen
+	/// accounts are ignored. If `max_debit` is given, the transfer fails with `Unapproved` when
+	/// it would take more than that from `source`.
+	fn do_transfer(
+		id: T::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: T::Balance,
+		maybe_admin: Option<T::AccountId>,
+		max_debit: Option<T::Balance>,
+	) -> Result<T::Balance, DispatchError> {
+		ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+
+		Asset::<T>::try_mutate(id, |maybe_details| -> Result<_, DispatchError> {
+			let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+			let source_account = Account::<T>::get(id, source);
+			match maybe_admin {
+				Some(admin) => ensure!(admin == details.admin, Error::<T>::NoPermission),
+				None => ensure!(!details.is_frozen && !source_account.is_frozen, Error::<T>::Frozen),
+			}
+			ensure!(source_account.balance >= amount, Error::<T>::BalanceLow);
+
+			// the dust left below the minimum balance goes with the transfer.
+			let mut amount = amount;
+			if source != dest && source_account.balance - amount < details.min_balance {
+				amount = source_account.balance;
+			}
+			if let Some(max_debit) = max_debit {
+				ensure!(amount <= max_debit, Error::<T>::Unapproved);
+			}
+
+			if source == dest {
+				return Ok(amount)
+			}
+
+			Self::increase_balance(id, details, dest, amount)?;
+			Self::decrease_balance(id, details, source, amount);
+			Ok(amount)
+		})
+	}
+}
+
+impl<T: Trait> MultiAsset<T::AccountId> for Module<T> {
+	type AssetId = T::AssetId;
+	type Balance = T::Balance;
+
+	fn free_balance(asset_id: T::AssetId, who: &T::AccountId) -> T::Balance {
+		Self::balance(asset_id, who.clone())
+	}
+
+	fn withdraw(
+		asset_id: T::AssetId,
+		who: &T::AccountId,
+		value: T::Balance,
+		_reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Asset::<T>::try_mutate(asset_id, |maybe_details| -> DispatchResult {
+			let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+			let account = Account::<T>::get(asset_id, who);
+			ensure!(!details.is_frozen && !account.is_frozen, Error::<T>::Frozen);
+			if value.is_zero() {
+				// Nothing to withdraw; don't touch the account, which may not exist.
+				return Ok(())
+			}
+			let remaining = account.balance.checked_sub(&value).ok_or(Error::<T>::BalanceLow)?;
+			ensure!(remaining.is_zero() || remaining >= details.min_balance, Error::<T>::BalanceLow);
+
+			Self::decrease_balance(asset_id, details, who, value);
+			details.supply = details.supply.saturating_sub(value);
+			Ok(())
+		})
+	}
+
+	fn deposit_into_existing(asset_id: T::AssetId, who: &T::AccountId, value: T::Balance) -> DispatchResult {
+		Asset::<T>::try_mutate(asset_id, |maybe_details| -> DispatchResult {
+			let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+			ensure!(!Account::<T>::get(asset_id, who).balance.is_zero(), Error::<T>::BalanceZero);
+
+			details.supply = details.supply.checked_add(&value).ok_or(Error::<T>::Overflow)?;
+			Self::increase_balance(asset_id, details, who, value)
+		})
 	}
 }
 
@@ -283,7 +1240,8 @@ mod tests {
 	use sp_core::H256;
 	// The testing primitives are very useful for avoiding having to work with signatures
 	// or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
-	use sp_runtime::{Perbill, traits::{BlakeTwo256, IdentityLookup}, testing::Header};
+	use sp_runtime::{Perbill, traits::{BadOrigin, BlakeTwo256, IdentityLookup}, testing::Header};
+	use frame_system::EnsureRoot;
 
 	impl_outer_origin! {
 		pub enum Origin for Test  where system = frame_system {}
@@ -301,6 +1259,7 @@ mod tests {
 		pub const AvailableBlockRatio: Perbill = Perbill::one();
 	}
 	impl frame_system::Trait for Test {
+		type BaseCallFilter = ();
 		type Origin = Origin;
 		type Index = u64;
 		type Call = ();
@@ -320,35 +1279,140 @@ mod tests {
 		type MaximumBlockLength = MaximumBlockLength;
 		type Version = ();
 		type ModuleToIndex = ();
-		type AccountData = ();
+		type AccountData = pallet_balances::AccountData<u64>;
 		type OnNewAccount = ();
 		type OnKilledAccount = ();
 	}
+	parameter_types! {
+		pub const ExistentialDeposit: u64 = 1;
+	}
+	impl pallet_balances::Trait for Test {
+		type Balance = u64;
+		type Event = ();
+		type DustRemoval = ();
+		type ExistentialDeposit = ExistentialDeposit;
+		type AccountStore = System;
+	}
+	parameter_types! {
+		pub const AssetDeposit: u64 = 1;
+		pub const MetadataDepositBase: u64 = 1;
+		pub const MetadataDepositPerByte: u64 = 1;
+		pub const StringLimit: u32 = 50;
+		pub const ApprovalDeposit: u64 = 1;
+	}
 	impl Trait for Test {
 		type Event = ();
 		type Balance = u64;
 		type AssetId = u32;
+		type Currency = Balances;
+		type ForceOrigin = EnsureRoot<u64>;
+		type AssetDeposit = AssetDeposit;
+		type MetadataDepositBase = MetadataDepositBase;
+		type MetadataDepositPerByte = MetadataDepositPerByte;
+		type StringLimit = StringLimit;
+		type ApprovalDeposit = ApprovalDeposit;
 	}
+	type System = frame_system::Module<Test>;
+	type Balances = pallet_balances::Module<Test>;
 	type Assets = Module<Test>;
 
 	// This function basically just builds a genesis storage key/value store according to
 	// our desired mockup.
-	fn new_test_ext() -> sp_io::TestExternalities {
+	pub fn new_test_ext() -> sp_io::TestExternalities {
 		frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
 	}
 
+	fn witness(accounts: u32, approvals: u32) -> DestroyWitness {
+		DestroyWitness { accounts, approvals }
+	}
+
 	#[test]
-	fn issuing_asset_units_to_issuer_should_work() {
+	fn basic_minting_should_work() {
 		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
 			assert_eq!(Assets::balance(0, 1), 100);
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 100));
+			assert_eq!(Assets::balance(0, 2), 100);
+			assert_eq!(Assets::total_supply(0), 200);
+		});
+	}
+
+	#[test]
+	fn creating_asset_should_reserve_deposit() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				Assets::create(Origin::signed(1), 0, 1, 1),
+				pallet_balances::Error::<Test, _>::InsufficientBalance,
+			);
+			Balances::make_free_balance_be(&1, 100);
+			assert_ok!(Assets::create(Origin::signed(1), 0, 1, 1));
+			assert_eq!(Balances::reserved_balance(&1), 1);
+			assert_noop!(Assets::create(Origin::signed(1), 0, 1, 1), Error::<Test>::InUse);
+			assert_noop!(Assets::create(Origin::signed(1), 1, 1, 0), Error::<Test>::MinBalanceZero);
+
+			assert_ok!(Assets::destroy(Origin::signed(1), 0, witness(0, 0)));
+			assert_eq!(Balances::reserved_balance(&1), 0);
+		});
+	}
+
+	#[test]
+	fn force_create_requires_force_origin() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(Assets::force_create(Origin::signed(1), 0, 1, 1), BadOrigin);
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_noop!(Assets::force_create(Origin::root(), 0, 1, 1), Error::<Test>::InUse);
+		});
+	}
+
+	#[test]
+	fn only_team_members_should_manage_asset() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+			assert_ok!(Assets::set_team(Origin::signed(1), 0, 2, 3, 4));
+
+			assert_noop!(Assets::mint(Origin::signed(1), 0, 1, 100), Error::<Test>::NoPermission);
+			assert_ok!(Assets::mint(Origin::signed(2), 0, 1, 100));
+
+			assert_noop!(Assets::burn(Origin::signed(2), 0, 1, 10), Error::<Test>::NoPermission);
+			assert_ok!(Assets::burn(Origin::signed(3), 0, 1, 10));
+
+			assert_noop!(Assets::freeze(Origin::signed(3), 0, 1), Error::<Test>::NoPermission);
+			assert_ok!(Assets::freeze(Origin::signed(4), 0, 1));
+			assert_noop!(Assets::thaw(Origin::signed(4), 0, 1), Error::<Test>::NoPermission);
+			assert_ok!(Assets::thaw(Origin::signed(3), 0, 1));
+
+			assert_noop!(Assets::set_team(Origin::signed(2), 0, 2, 2, 2), Error::<Test>::NoPermission);
+			assert_noop!(Assets::mint(Origin::signed(2), 1, 1, 100), Error::<Test>::Unknown);
+		});
+	}
+
+	#[test]
+	fn destroying_asset_should_remove_accounts() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 100));
+
+			assert_noop!(Assets::destroy(Origin::signed(2), 0, witness(2, 0)), Error::<Test>::NoPermission);
+			assert_noop!(Assets::destroy(Origin::signed(1), 0, witness(1, 0)), Error::<Test>::BadWitness);
+			assert_ok!(Assets::destroy(Origin::signed(1), 0, witness(2, 0)));
+			assert_eq!(Assets::balance(0, 1), 0);
+			assert_eq!(Assets::balance(0, 2), 0);
+			assert_eq!(Assets::total_supply(0), 0);
+			assert!(Asset::<Test>::get(0).is_none());
+
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::destroy(Origin::root(), 0, witness(0, 0)));
 		});
 	}
 
 	#[test]
 	fn querying_total_supply_should_work() {
 		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
 			assert_eq!(Assets::balance(0, 1), 100);
 			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
 			assert_eq!(Assets::balance(0, 1), 50);
@@ -357,15 +1421,16 @@ mod tests {
 			assert_eq!(Assets::balance(0, 1), 50);
 			assert_eq!(Assets::balance(0, 2), 19);
 			assert_eq!(Assets::balance(0, 3), 31);
-			assert_ok!(Assets::destroy(Origin::signed(3), 0));
+			assert_ok!(Assets::burn(Origin::signed(1), 0, 3, u64::max_value()));
 			assert_eq!(Assets::total_supply(0), 69);
 		});
 	}
 
 	#[test]
-	fn transferring_amount_above_available_balance_should_work() {
+	fn transferring_amount_below_available_balance_should_work() {
 		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
 			assert_eq!(Assets::balance(0, 1), 100);
 			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
 			assert_eq!(Assets::balance(0, 1), 50);
@@ -373,15 +1438,83 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn transferring_dust_should_reap_source_account() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 10));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+			assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 1);
+
+			// the 5 units left would be below the minimum balance.
+			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 95));
+			assert_eq!(Assets::balance(0, 1), 0);
+			assert_eq!(Assets::balance(0, 2), 100);
+			assert!(!Account::<Test>::contains_key(0, 1));
+			assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 1);
+
+			// the target account would be below the minimum balance.
+			assert_noop!(Assets::transfer(Origin::signed(2), 0, 3, 5), Error::<Test>::BalanceLow);
+			assert_noop!(Assets::mint(Origin::signed(1), 0, 3, 5), Error::<Test>::BalanceLow);
+		});
+	}
+
+	#[test]
+	fn burning_dust_should_reap_account() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 10));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 100));
+			assert_ok!(Assets::burn(Origin::signed(1), 0, 2, 95));
+			assert_eq!(Assets::balance(0, 2), 0);
+			assert_eq!(Assets::total_supply(0), 0);
+			assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 0);
+			assert_noop!(Assets::burn(Origin::signed(1), 0, 2, 1), Error::<Test>::BalanceZero);
+		});
+	}
+
+	#[test]
+	fn transferring_frozen_balance_should_not_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+			assert_ok!(Assets::freeze(Origin::signed(1), 0, 1));
+			assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 50), Error::<Test>::Frozen);
+			assert_ok!(Assets::thaw(Origin::signed(1), 0, 1));
+			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+
+			assert_ok!(Assets::freeze_asset(Origin::signed(1), 0));
+			assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 50), Error::<Test>::Frozen);
+			assert_ok!(Assets::thaw_asset(Origin::signed(1), 0));
+			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+		});
+	}
+
+	#[test]
+	fn force_transfer_should_ignore_freezing() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 100));
+			assert_ok!(Assets::freeze(Origin::signed(1), 0, 2));
+			assert_noop!(Assets::force_transfer(Origin::signed(2), 0, 2, 3, 50), Error::<Test>::NoPermission);
+			assert_ok!(Assets::force_transfer(Origin::signed(1), 0, 2, 3, 50));
+			assert_eq!(Assets::balance(0, 2), 50);
+			assert_eq!(Assets::balance(0, 3), 50);
+			assert_noop!(
+				Assets::force_transfer(Origin::signed(1), 0, 2, 3, 51),
+				Error::<Test>::BalanceLow,
+			);
+		});
+	}
+
 	#[test]
 	fn transferring_amount_more_than_available_balance_should_not_work() {
 		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
 			assert_eq!(Assets::balance(0, 1), 100);
 			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
 			assert_eq!(Assets::balance(0, 1), 50);
 			assert_eq!(Assets::balance(0, 2), 50);
-			assert_ok!(Assets::destroy(Origin::signed(1), 0));
+			assert_ok!(Assets::burn(Origin::signed(1), 0, 1, u64::max_value()));
 			assert_eq!(Assets::balance(0, 1), 0);
 			assert_noop!(Assets::transfer(Origin::signed(1), 0, 1, 50), Error::<Test>::BalanceLow);
 		});
@@ -390,7 +1523,8 @@ mod tests {
 	#[test]
 	fn transferring_less_than_one_unit_should_not_work() {
 		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
 			assert_eq!(Assets::balance(0, 1), 100);
 			assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 0), Error::<Test>::AmountZero);
 		});
@@ -399,27 +1533,231 @@ mod tests {
 	#[test]
 	fn transferring_more_units_than_total_supply_should_not_work() {
 		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
 			assert_eq!(Assets::balance(0, 1), 100);
 			assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 101), Error::<Test>::BalanceLow);
 		});
 	}
 
 	#[test]
-	fn destroying_asset_balance_with_positive_balance_should_work() {
+	fn transferring_ownership_should_move_deposits() {
 		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
-			assert_eq!(Assets::balance(0, 1), 100);
-			assert_ok!(Assets::destroy(Origin::signed(1), 0));
+			Balances::make_free_balance_be(&1, 100);
+			Balances::make_free_balance_be(&2, 100);
+			assert_ok!(Assets::create(Origin::signed(1), 0, 1, 1));
+			assert_ok!(Assets::set_metadata(Origin::signed(1), 0, vec![0u8; 10], vec![0u8; 10], 12));
+			assert_eq!(Balances::reserved_balance(&1), 22);
+
+			assert_noop!(Assets::transfer_ownership(Origin::signed(2), 0, 2), Error::<Test>::NoPermission);
+			assert_ok!(Assets::transfer_ownership(Origin::signed(1), 0, 2));
+			assert_eq!(Balances::reserved_balance(&1), 0);
+			assert_eq!(Balances::reserved_balance(&2), 22);
+
+			assert_noop!(Assets::set_team(Origin::signed(1), 0, 1, 1, 1), Error::<Test>::NoPermission);
+			assert_ok!(Assets::destroy(Origin::signed(2), 0, witness(0, 0)));
+			assert_eq!(Balances::reserved_balance(&2), 0);
+			assert_eq!(Balances::free_balance(&2), 122);
 		});
 	}
 
 	#[test]
-	fn destroying_asset_balance_with_zero_balance_should_not_work() {
+	fn set_metadata_should_work() {
 		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
-			assert_eq!(Assets::balance(0, 2), 0);
-			assert_noop!(Assets::destroy(Origin::signed(2), 0), Error::<Test>::BalanceZero);
+			Balances::make_free_balance_be(&1, 30);
+			assert_noop!(
+				Assets::set_metadata(Origin::signed(1), 0, vec![0u8; 10], vec![0u8; 10], 12),
+				Error::<Test>::Unknown,
+			);
+			assert_ok!(Assets::create(Origin::signed(1), 0, 1, 1));
+			assert_noop!(
+				Assets::set_metadata(Origin::signed(2), 0, vec![0u8; 10], vec![0u8; 10], 12),
+				Error::<Test>::NoPermission,
+			);
+			assert_noop!(
+				Assets::set_metadata(Origin::signed(1), 0, vec![0u8; 100], vec![0u8; 10], 12),
+				Error::<Test>::BadMetadata,
+			);
+			assert_noop!(
+				Assets::set_metadata(Origin::signed(1), 0, vec![0u8; 10], vec![0u8; 100], 12),
+				Error::<Test>::BadMetadata,
+			);
+
+			// the deposit follows the length of the name and symbol.
+			assert_ok!(Assets::set_metadata(Origin::signed(1), 0, vec![0u8; 10], vec![0u8; 10], 12));
+			assert_eq!(Balances::reserved_balance(&1), 22);
+			assert_ok!(Assets::set_metadata(Origin::signed(1), 0, vec![0u8; 10], vec![0u8; 5], 12));
+			assert_eq!(Balances::reserved_balance(&1), 17);
+			assert_noop!(
+				Assets::set_metadata(Origin::signed(1), 0, vec![0u8; 20], vec![0u8; 20], 12),
+				pallet_balances::Error::<Test, _>::InsufficientBalance,
+			);
+			assert_eq!(Metadata::<Test>::get(0).decimals, 12);
+
+			// an empty name and symbol clear the metadata.
+			assert_ok!(Assets::set_metadata(Origin::signed(1), 0, vec![], vec![], 0));
+			assert_eq!(Balances::reserved_balance(&1), 1);
+			assert!(!Metadata::<Test>::contains_key(0));
+		});
+	}
+
+	#[test]
+	fn approval_lifecycle_works() {
+		new_test_ext().execute_with(|| {
+			Balances::make_free_balance_be(&1, 1);
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+			assert_noop!(
+				Assets::approve_transfer(Origin::signed(1), 1, 2, 50),
+				Error::<Test>::Unknown,
+			);
+			assert_noop!(
+				Assets::approve_transfer(Origin::signed(1), 0, 2, 0),
+				Error::<Test>::AmountZero,
+			);
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 50));
+			assert_eq!(Balances::reserved_balance(&1), 1);
+			assert_eq!(Assets::allowance(0, &1, &2), 50);
+
+			// approvals are additive, and no further deposit is taken.
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 20));
+			assert_eq!(Assets::allowance(0, &1, &2), 70);
+			assert_eq!(Asset::<Test>::get(0).unwrap().approvals, 1);
+
+			assert_noop!(
+				Assets::transfer_approved(Origin::signed(3), 0, 1, 3, 10),
+				Error::<Test>::Unapproved,
+			);
+			assert_noop!(
+				Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 71),
+				Error::<Test>::Unapproved,
+			);
+			assert_ok!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 40));
+			assert_eq!(Assets::allowance(0, &1, &2), 30);
+			assert_eq!(Assets::balance(0, 1), 60);
+			assert_eq!(Assets::balance(0, 3), 40);
+
+			// the deposit is returned once the whole allowance is used.
+			assert_ok!(Assets::transfer_approved(Origin::signed(2), 0, 1, 2, 30));
+			assert_eq!(Assets::allowance(0, &1, &2), 0);
+			assert_eq!(Balances::reserved_balance(&1), 0);
+			assert_eq!(Asset::<Test>::get(0).unwrap().approvals, 0);
+			assert_eq!(Assets::balance(0, 2), 30);
+		});
+	}
+
+	#[test]
+	fn transfer_approved_charges_the_swept_dust() {
+		new_test_ext().execute_with(|| {
+			Balances::make_free_balance_be(&1, 1);
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 10));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 95));
+
+			// the remaining 5 would be swept along, exceeding the allowance.
+			assert_noop!(
+				Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 95),
+				Error::<Test>::Unapproved,
+			);
+
+			assert_ok!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 85));
+			assert_eq!(Assets::allowance(0, &1, &2), 10);
+			assert_noop!(
+				Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 10),
+				Error::<Test>::Unapproved,
+			);
+
+			// the dust is swept along and charged to the allowance.
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 5));
+			assert_ok!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 10));
+			assert_eq!(Assets::balance(0, 1), 0);
+			assert_eq!(Assets::balance(0, 3), 100);
+			assert_eq!(Assets::allowance(0, &1, &2), 0);
+			assert_eq!(Balances::reserved_balance(&1), 0);
+		});
+	}
+
+	#[test]
+	fn approval_deposit_is_required() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+			assert_noop!(
+				Assets::approve_transfer(Origin::signed(1), 0, 2, 50),
+				pallet_balances::Error::<Test, _>::InsufficientBalance,
+			);
+		});
+	}
+
+	#[test]
+	fn cancel_approval_works() {
+		new_test_ext().execute_with(|| {
+			Balances::make_free_balance_be(&1, 1);
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 50));
+			assert_noop!(Assets::cancel_approval(Origin::signed(1), 1, 2), Error::<Test>::Unknown);
+			assert_noop!(Assets::cancel_approval(Origin::signed(2), 0, 2), Error::<Test>::Unapproved);
+			assert_noop!(Assets::cancel_approval(Origin::signed(1), 0, 3), Error::<Test>::Unapproved);
+			assert_ok!(Assets::cancel_approval(Origin::signed(1), 0, 2));
+			assert_eq!(Balances::reserved_balance(&1), 0);
+			assert_eq!(Assets::allowance(0, &1, &2), 0);
+			assert_noop!(
+				Assets::transfer_approved(Origin::signed(2), 0, 1, 2, 10),
+				Error::<Test>::Unapproved,
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_approved_respects_freezing() {
+		new_test_ext().execute_with(|| {
+			Balances::make_free_balance_be(&1, 1);
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 50));
+			assert_ok!(Assets::freeze(Origin::signed(1), 0, 1));
+			assert_noop!(
+				Assets::transfer_approved(Origin::signed(2), 0, 1, 2, 10),
+				Error::<Test>::Frozen,
+			);
+		});
+	}
+
+	#[test]
+	fn destroying_asset_should_return_approval_deposits() {
+		new_test_ext().execute_with(|| {
+			Balances::make_free_balance_be(&1, 1);
+			Balances::make_free_balance_be(&2, 1);
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 100));
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 3, 50));
+			assert_ok!(Assets::approve_transfer(Origin::signed(2), 0, 3, 50));
+
+			assert_noop!(Assets::destroy(Origin::signed(1), 0, witness(2, 1)), Error::<Test>::BadWitness);
+			assert_ok!(Assets::destroy(Origin::signed(1), 0, witness(2, 2)));
+			assert_eq!(Balances::reserved_balance(&1), 0);
+			assert_eq!(Balances::reserved_balance(&2), 0);
+			assert_eq!(Assets::allowance(0, &1, &3), 0);
+		});
+	}
+
+	#[test]
+	fn multi_asset_withdraw_of_nothing_keeps_accounts() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+			assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 1);
+
+			assert_ok!(<Assets as MultiAsset<_>>::withdraw(0, &2, 0, WithdrawReasons::all()));
+			assert_ok!(<Assets as MultiAsset<_>>::withdraw(0, &1, 0, WithdrawReasons::all()));
+			assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 1);
+			assert_eq!(Assets::balance(0, 1), 100);
+
+			assert_ok!(<Assets as MultiAsset<_>>::withdraw(0, &1, 100, WithdrawReasons::all()));
+			assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 0);
+			assert_eq!(Assets::total_supply(0), 0);
 		});
 	}
 }
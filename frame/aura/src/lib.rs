@@ -85,7 +85,7 @@ decl_storage! {
 }
 
 decl_module! {
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin { }
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin, system = frame_system { }
 }
 
 impl<T: Trait> Module<T> {
@@ -18,7 +18,7 @@
 
 use super::*;
 use mock::*;
-use frame_support::traits::OnFinalize;
+use frame_support::{StorageValue, traits::{OnFinalize, OnRuntimeUpgrade}};
 use pallet_session::ShouldEndSession;
 use sp_core::crypto::IsWrappedBy;
 use sp_consensus_vrf::schnorrkel::{VRFOutput, VRFProof};
@@ -149,3 +149,47 @@ fn can_predict_next_epoch_change() {
 		assert_eq!(Babe::next_expected_epoch_change(System::block_number()), Some(5 + 2));
 	})
 }
+
+#[test]
+fn can_fetch_current_and_next_epoch_data() {
+	new_test_ext(5).1.execute_with(|| {
+		// genesis authorities should be used for the first and second epoch
+		assert_eq!(
+			Babe::current_epoch().authorities,
+			Babe::next_epoch().authorities,
+		);
+
+		// 1 epoch = 3 slots, so block 10 at slot 10 starts epoch 3.
+		go_to_block(1, 1);
+		progress_to_block(10);
+
+		let current_epoch = Babe::current_epoch();
+		assert_eq!(current_epoch.epoch_index, 3);
+		assert_eq!(current_epoch.start_slot, 10);
+		assert_eq!(current_epoch.duration, 3);
+		assert_eq!(current_epoch.randomness, Babe::randomness());
+
+		let next_epoch = Babe::next_epoch();
+		assert_eq!(next_epoch.epoch_index, 4);
+		assert_eq!(next_epoch.start_slot, 13);
+		assert_eq!(next_epoch.randomness, NextRandomness::get());
+	});
+}
+
+#[test]
+fn next_authorities_are_set_on_runtime_upgrade() {
+	new_test_ext(4).1.execute_with(|| {
+		// chains upgrading from a runtime that didn't store the next authorities.
+		NextAuthorities::kill();
+		assert!(Babe::next_epoch().authorities.is_empty());
+
+		Babe::on_runtime_upgrade();
+		assert_eq!(Babe::next_epoch().authorities, Babe::authorities());
+		assert_eq!(Babe::next_epoch().authorities.len(), 4);
+
+		// announced next authorities are kept.
+		NextAuthorities::put(Vec::<(AuthorityId, BabeAuthorityWeight)>::new());
+		Babe::on_runtime_upgrade();
+		assert!(Babe::next_epoch().authorities.is_empty());
+	});
+}
@@ -0,0 +1,218 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Bounties pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, account};
+use pallet_treasury::SpendFunds;
+
+use crate::Module as Bounties;
+use pallet_treasury::Module as Treasury;
+
+const SEED: u32 = 0;
+
+// Create the pre-requisite information needed to create a `propose_bounty`.
+fn setup_bounty<T: Trait>(u: u32, d: u32) -> (
+	T::AccountId,
+	T::AccountId,
+	BalanceOf<T>,
+	BalanceOf<T>,
+	Vec<u8>,
+) {
+	let caller = account("caller", u, SEED);
+	let value: BalanceOf<T> = T::BountyValueMinimum::get().saturating_mul(100.into());
+	let fee = value / 2.into();
+	let deposit = T::BountyDepositBase::get()
+		+ T::DataDepositPerByte::get() * MAX_BYTES.into()
+		+ T::Currency::minimum_balance();
+	let _ = T::Currency::make_free_balance_be(&caller, deposit);
+	let curator = account("curator", u, SEED);
+	let _ = T::Currency::make_free_balance_be(&curator, fee + T::Currency::minimum_balance());
+	let reason = vec![0; d as usize];
+	(caller, curator, fee, value, reason)
+}
+
+// Make sure the treasury pot is funded.
+fn setup_pot_account<T: Trait>() {
+	let pot_account = Treasury::<T>::account_id();
+	let value = T::Currency::minimum_balance().saturating_mul(1_000_000_000.into());
+	let _ = T::Currency::make_free_balance_be(&pot_account, value);
+}
+
+// Fund the approved bounties from the treasury pot.
+fn fund_bounties<T: Trait>() {
+	let mut budget_remaining = Treasury::<T>::pot();
+	let mut imbalance = PositiveImbalanceOf::<T>::zero();
+	let mut total_weight: Weight = 0;
+	let mut missed_any = false;
+	<Bounties<T> as SpendFunds<T>>::spend_funds(
+		&mut budget_remaining,
+		&mut imbalance,
+		&mut total_weight,
+		&mut missed_any,
+	);
+}
+
+// Create a funded bounty with an active curator, returning the curator and the bounty index.
+fn create_bounty<T: Trait>() -> Result<(<T::Lookup as StaticLookup>::Source, BountyIndex), &'static str> {
+	let (caller, curator, fee, value, reason) = setup_bounty::<T>(0, MAX_BYTES);
+	let curator_lookup = T::Lookup::unlookup(curator.clone());
+	Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+	let bounty_id = BountyCount::get() - 1;
+	Bounties::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
+	fund_bounties::<T>();
+	Bounties::<T>::propose_curator(RawOrigin::Root.into(), bounty_id, curator_lookup.clone(), fee)?;
+	Bounties::<T>::accept_curator(RawOrigin::Signed(curator).into(), bounty_id)?;
+	Ok((curator_lookup, bounty_id))
+}
+
+// Create bounties that are approved for use in `spend_funds`.
+fn create_approved_bounties<T: Trait>(n: u32) -> Result<(), &'static str> {
+	for i in 0 .. n {
+		let (caller, _curator, _fee, value, reason) = setup_bounty::<T>(i, MAX_BYTES);
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+		Bounties::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
+	}
+	ensure!(BountyApprovals::get().len() == n as usize, "Not all approved");
+	Ok(())
+}
+
+const MAX_BYTES: u32 = 16384;
+
+benchmarks! {
+	_ { }
+
+	propose_bounty {
+		let d in 0 .. MAX_BYTES;
+
+		let (caller, _curator, _fee, value, description) = setup_bounty::<T>(0, d);
+	}: _(RawOrigin::Signed(caller), value, description)
+
+	approve_bounty {
+		let u in 0 .. 1000;
+		let (caller, _curator, _fee, value, reason) = setup_bounty::<T>(u, MAX_BYTES);
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+	}: _(RawOrigin::Root, bounty_id)
+
+	propose_curator {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (caller, curator, fee, value, reason) = setup_bounty::<T>(u, MAX_BYTES);
+		let curator_lookup = T::Lookup::unlookup(curator.clone());
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+		Bounties::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
+		fund_bounties::<T>();
+	}: _(RawOrigin::Root, bounty_id, curator_lookup, fee)
+
+	// Worst case when curator is inactive and any sender unassigns the curator.
+	unassign_curator {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (_curator_lookup, bounty_id) = create_bounty::<T>()?;
+		frame_system::Module::<T>::set_block_number(T::BountyUpdatePeriod::get() + 1.into());
+		let caller = account("caller", u, SEED);
+	}: _(RawOrigin::Signed(caller), bounty_id)
+
+	accept_curator {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (caller, curator, fee, value, reason) = setup_bounty::<T>(u, MAX_BYTES);
+		let curator_lookup = T::Lookup::unlookup(curator.clone());
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+		Bounties::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
+		fund_bounties::<T>();
+		Bounties::<T>::propose_curator(RawOrigin::Root.into(), bounty_id, curator_lookup, fee)?;
+	}: _(RawOrigin::Signed(curator), bounty_id)
+
+	award_bounty {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (curator_lookup, bounty_id) = create_bounty::<T>()?;
+		let curator = T::Lookup::lookup(curator_lookup)?;
+		let beneficiary = T::Lookup::unlookup(account("beneficiary", u, SEED));
+	}: _(RawOrigin::Signed(curator), bounty_id, beneficiary)
+
+	claim_bounty {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (curator_lookup, bounty_id) = create_bounty::<T>()?;
+		let curator = T::Lookup::lookup(curator_lookup)?;
+		let beneficiary = T::Lookup::unlookup(account("beneficiary", u, SEED));
+		Bounties::<T>::award_bounty(RawOrigin::Signed(curator.clone()).into(), bounty_id, beneficiary)?;
+		frame_system::Module::<T>::set_block_number(T::BountyDepositPayoutDelay::get());
+	}: _(RawOrigin::Signed(curator), bounty_id)
+
+	close_bounty_proposed {
+		let u in 0 .. 1000;
+		let (caller, _curator, _fee, value, reason) = setup_bounty::<T>(u, MAX_BYTES);
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+	}: close_bounty(RawOrigin::Root, bounty_id)
+
+	close_bounty_active {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (_curator_lookup, bounty_id) = create_bounty::<T>()?;
+	}: close_bounty(RawOrigin::Root, bounty_id)
+
+	extend_bounty_expiry {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (curator_lookup, bounty_id) = create_bounty::<T>()?;
+		let curator = T::Lookup::lookup(curator_lookup)?;
+	}: _(RawOrigin::Signed(curator), bounty_id, Vec::new())
+
+	spend_funds {
+		let b in 0 .. 100;
+		setup_pot_account::<T>();
+		create_approved_bounties::<T>(b)?;
+	}: {
+		fund_bounties::<T>();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_propose_bounty::<Test>());
+			assert_ok!(test_benchmark_approve_bounty::<Test>());
+			assert_ok!(test_benchmark_propose_curator::<Test>());
+			assert_ok!(test_benchmark_unassign_curator::<Test>());
+			assert_ok!(test_benchmark_accept_curator::<Test>());
+			assert_ok!(test_benchmark_award_bounty::<Test>());
+			assert_ok!(test_benchmark_claim_bounty::<Test>());
+			assert_ok!(test_benchmark_close_bounty_proposed::<Test>());
+			assert_ok!(test_benchmark_close_bounty_active::<Test>());
+			assert_ok!(test_benchmark_extend_bounty_expiry::<Test>());
+			assert_ok!(test_benchmark_spend_funds::<Test>());
+		});
+	}
+}
@@ -0,0 +1,500 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Bounties pallet tests.
+
+#![cfg(test)]
+
+use super::*;
+use std::cell::RefCell;
+use frame_support::{
+	assert_noop, assert_ok, impl_outer_origin, impl_outer_event, parameter_types, weights::Weight,
+	traits::{Contains, ContainsLengthBound, OnInitialize}
+};
+use sp_core::H256;
+use sp_runtime::{
+	Perbill, ModuleId, Percent,
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup, BadOrigin},
+};
+
+impl_outer_origin! {
+	pub enum Origin for Test  where system = frame_system {}
+}
+
+mod bounties {
+	// Re-export needed for `impl_outer_event!`.
+	pub use super::super::*;
+}
+
+impl_outer_event! {
+	pub enum Event for Test {
+		system<T>,
+		pallet_balances<T>,
+		pallet_treasury<T>,
+		bounties<T>,
+	}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = ();
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u128; // u64 is not enough to hold bytes used to generate bounty account
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type MaximumBlockLength = MaximumBlockLength;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+}
+thread_local! {
+	static TEN_TO_FOURTEEN: RefCell<Vec<u128>> = RefCell::new(vec![10,11,12,13,14]);
+}
+pub struct TenToFourteen;
+impl Contains<u128> for TenToFourteen {
+	fn sorted_members() -> Vec<u128> {
+		TEN_TO_FOURTEEN.with(|v| {
+			v.borrow().clone()
+		})
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add(new: &u128) {
+		TEN_TO_FOURTEEN.with(|v| {
+			let mut members = v.borrow_mut();
+			members.push(*new);
+			members.sort();
+		})
+	}
+}
+impl ContainsLengthBound for TenToFourteen {
+	fn max_len() -> usize {
+		TEN_TO_FOURTEEN.with(|v| v.borrow().len())
+	}
+	fn min_len() -> usize { 0 }
+}
+parameter_types! {
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: u64 = 1;
+	pub const SpendPeriod: u64 = 2;
+	pub const Burn: Permill = Permill::from_percent(50);
+	pub const TipCountdown: u64 = 1;
+	pub const TipFindersFee: Percent = Percent::from_percent(20);
+	pub const TipReportDepositBase: u64 = 1;
+	pub const TipReportDepositPerByte: u64 = 1;
+	pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
+}
+impl pallet_treasury::Trait for Test {
+	type ModuleId = TreasuryModuleId;
+	type Currency = pallet_balances::Module<Test>;
+	type ApproveOrigin = frame_system::EnsureRoot<u128>;
+	type RejectOrigin = frame_system::EnsureRoot<u128>;
+	type Tippers = TenToFourteen;
+	type TipCountdown = TipCountdown;
+	type TipFindersFee = TipFindersFee;
+	type TipReportDepositBase = TipReportDepositBase;
+	type TipReportDepositPerByte = TipReportDepositPerByte;
+	type Event = Event;
+	type ProposalRejection = ();
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type SpendFunds = Bounties;
+}
+parameter_types! {
+	pub const DataDepositPerByte: u64 = 1;
+	pub const BountyDepositBase: u64 = 80;
+	pub const BountyDepositPayoutDelay: u64 = 3;
+	pub const BountyUpdatePeriod: u64 = 20;
+	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
+	pub const BountyValueMinimum: u64 = 1;
+	pub const MaximumReasonLength: u32 = 16384;
+}
+impl Trait for Test {
+	type BountyDepositBase = BountyDepositBase;
+	type DataDepositPerByte = DataDepositPerByte;
+	type BountyDepositPayoutDelay = BountyDepositPayoutDelay;
+	type BountyUpdatePeriod = BountyUpdatePeriod;
+	type BountyCuratorDeposit = BountyCuratorDeposit;
+	type BountyValueMinimum = BountyValueMinimum;
+	type MaximumReasonLength = MaximumReasonLength;
+	type Event = Event;
+}
+type System = frame_system::Module<Test>;
+type Balances = pallet_balances::Module<Test>;
+type Treasury = pallet_treasury::Module<Test>;
+type Bounties = Module<Test>;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test>{
+		// Total issuance will be 200 with treasury account initialized at ED.
+		balances: vec![(0, 100), (1, 98), (2, 1)],
+	}.assimilate_storage(&mut t).unwrap();
+	pallet_treasury::GenesisConfig::default().assimilate_storage::<Test>(&mut t).unwrap();
+	t.into()
+}
+
+fn last_event() -> RawEvent<u64, u128> {
+	System::events().into_iter().map(|r| r.event)
+		.filter_map(|e| {
+			if let Event::bounties(inner) = e { Some(inner) } else { None }
+		})
+		.last()
+		.unwrap()
+}
+
+// Propose and approve a bounty of `value` from account 0, then fund it from a pot of 100.
+fn funded_bounty(value: u64) {
+	Balances::make_free_balance_be(&Treasury::account_id(), 101);
+	assert_ok!(Bounties::propose_bounty(Origin::signed(0), value, b"12345".to_vec()));
+	assert_ok!(Bounties::approve_bounty(Origin::ROOT, 0));
+	<Treasury as OnInitialize<u64>>::on_initialize(2);
+}
+
+// Fund a bounty of 50 and make account 4 its active curator, with a fee of 4.
+fn active_bounty() {
+	funded_bounty(50);
+	assert_ok!(Bounties::propose_curator(Origin::ROOT, 0, 4, 4));
+	Balances::make_free_balance_be(&4, 10);
+	assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+}
+
+#[test]
+fn genesis_config_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Treasury::pot(), 0);
+		assert_eq!(Bounties::bounty_count(), 0);
+	});
+}
+
+#[test]
+fn propose_bounty_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 10, b"1234567890".to_vec()));
+		assert_eq!(last_event(), RawEvent::BountyProposed(0));
+
+		let deposit: u64 = 80 + 10;
+		assert_eq!(Balances::reserved_balance(0), deposit);
+		assert_eq!(Balances::free_balance(0), 100 - deposit);
+
+		assert_eq!(Bounties::bounties(0).unwrap(), Bounty {
+			proposer: 0,
+			fee: 0,
+			curator_deposit: 0,
+			value: 10,
+			bond: deposit,
+			status: BountyStatus::Proposed,
+		});
+		assert_eq!(Bounties::bounty_descriptions(0).unwrap(), b"1234567890".to_vec());
+		assert_eq!(Bounties::bounty_count(), 1);
+	});
+}
+
+#[test]
+fn propose_bounty_validation_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Bounties::propose_bounty(Origin::signed(1), 0, [0; 17_000].to_vec()),
+			Error::<Test>::ReasonTooBig
+		);
+		assert_noop!(
+			Bounties::propose_bounty(Origin::signed(1), 10, b"12345678901234567890".to_vec()),
+			Error::<Test>::InsufficientProposersBalance
+		);
+		assert_noop!(
+			Bounties::propose_bounty(Origin::signed(1), 0, b"12345678901234567890".to_vec()),
+			Error::<Test>::InvalidValue
+		);
+	});
+}
+
+#[test]
+fn close_bounty_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(Bounties::close_bounty(Origin::ROOT, 0), Error::<Test>::InvalidIndex);
+
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 10, b"12345".to_vec()));
+		assert_noop!(Bounties::close_bounty(Origin::signed(0), 0), BadOrigin);
+		assert_ok!(Bounties::close_bounty(Origin::ROOT, 0));
+
+		let deposit: u64 = 80 + 5;
+		assert_eq!(last_event(), RawEvent::BountyRejected(0, deposit));
+
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 100 - deposit);
+
+		assert_eq!(Bounties::bounties(0), None);
+		assert_eq!(Bounties::bounty_descriptions(0), None);
+	});
+}
+
+#[test]
+fn approve_bounty_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_noop!(Bounties::approve_bounty(Origin::ROOT, 0), Error::<Test>::InvalidIndex);
+
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+		assert_ok!(Bounties::approve_bounty(Origin::ROOT, 0));
+		assert_noop!(Bounties::approve_bounty(Origin::ROOT, 0), Error::<Test>::UnexpectedStatus);
+
+		let deposit: u64 = 80 + 5;
+		assert_eq!(Bounties::bounties(0).unwrap(), Bounty {
+			proposer: 0,
+			fee: 0,
+			value: 50,
+			curator_deposit: 0,
+			bond: deposit,
+			status: BountyStatus::Approved,
+		});
+		assert_eq!(Bounties::bounty_approvals(), vec![0]);
+
+		// cancelling is only possible once the bounty is funded.
+		assert_noop!(Bounties::close_bounty(Origin::ROOT, 0), Error::<Test>::UnexpectedStatus);
+
+		// deposit not returned yet
+		assert_eq!(Balances::reserved_balance(0), deposit);
+		assert_eq!(Balances::free_balance(0), 100 - deposit);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		// return deposit
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 100);
+
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Funded);
+		assert!(Bounties::bounty_approvals().is_empty());
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 50);
+		assert_eq!(Treasury::pot(), 100 - 50 - 25); // burn 25
+	});
+}
+
+#[test]
+fn bounty_waits_for_enough_funds() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 150, b"12345".to_vec()));
+		assert_ok!(Bounties::approve_bounty(Origin::ROOT, 0));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Approved);
+		assert_eq!(Treasury::pot(), 100); // Pot hasn't changed, nothing is burnt
+
+		let _ = Balances::deposit_into_existing(&Treasury::account_id(), 100).unwrap();
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 150);
+		assert_eq!(Treasury::pot(), 25);
+	});
+}
+
+#[test]
+fn assign_curator_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+		// no curator before the bounty is funded.
+		assert_noop!(
+			Bounties::propose_curator(Origin::ROOT, 0, 4, 4),
+			Error::<Test>::UnexpectedStatus
+		);
+		assert_ok!(Bounties::approve_bounty(Origin::ROOT, 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		assert_noop!(Bounties::propose_curator(Origin::ROOT, 0, 4, 50), Error::<Test>::InvalidFee);
+		assert_ok!(Bounties::propose_curator(Origin::ROOT, 0, 4, 4));
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::CuratorProposed { curator: 4 });
+		assert_eq!(Bounties::bounties(0).unwrap().fee, 4);
+
+		assert_noop!(Bounties::accept_curator(Origin::signed(1), 0), Error::<Test>::RequireCurator);
+
+		Balances::make_free_balance_be(&4, 10);
+		assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Active {
+			curator: 4,
+			update_due: 21,
+		});
+		assert_eq!(Bounties::bounties(0).unwrap().curator_deposit, 2);
+		assert_eq!(Balances::free_balance(&4), 8);
+		assert_eq!(Balances::reserved_balance(&4), 2);
+
+		assert_noop!(Bounties::accept_curator(Origin::signed(4), 0), Error::<Test>::UnexpectedStatus);
+	});
+}
+
+#[test]
+fn unassign_curator_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		funded_bounty(50);
+		assert_noop!(Bounties::unassign_curator(Origin::ROOT, 0), Error::<Test>::UnexpectedStatus);
+
+		// a proposed curator can be unassigned by themselves or the reject origin.
+		assert_ok!(Bounties::propose_curator(Origin::ROOT, 0, 4, 4));
+		assert_noop!(Bounties::unassign_curator(Origin::signed(1), 0), BadOrigin);
+		assert_ok!(Bounties::unassign_curator(Origin::signed(4), 0));
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Funded);
+
+		assert_ok!(Bounties::propose_curator(Origin::ROOT, 0, 4, 4));
+		assert_ok!(Bounties::unassign_curator(Origin::ROOT, 0));
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Funded);
+
+		// an active curator giving up gets their deposit back.
+		assert_ok!(Bounties::propose_curator(Origin::ROOT, 0, 4, 4));
+		Balances::make_free_balance_be(&4, 10);
+		assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+		assert_ok!(Bounties::unassign_curator(Origin::signed(4), 0));
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::free_balance(&4), 10);
+		assert_eq!(Balances::reserved_balance(&4), 0);
+
+		// an active curator unassigned by the reject origin gets slashed.
+		assert_ok!(Bounties::propose_curator(Origin::ROOT, 0, 4, 4));
+		assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+		assert_ok!(Bounties::unassign_curator(Origin::ROOT, 0));
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Bounties::bounties(0).unwrap().curator_deposit, 0);
+		assert_eq!(Balances::free_balance(&4), 8);
+		assert_eq!(Balances::reserved_balance(&4), 0);
+	});
+}
+
+#[test]
+fn award_and_claim_bounty_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		active_bounty();
+
+		assert_noop!(Bounties::award_bounty(Origin::signed(1), 0, 3), Error::<Test>::RequireCurator);
+		assert_ok!(Bounties::award_bounty(Origin::signed(4), 0, 3));
+		assert_eq!(last_event(), RawEvent::BountyAwarded(0, 3));
+
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::PendingPayout {
+			curator: 4,
+			beneficiary: 3,
+			unlock_at: 4,
+		});
+
+		// the payout is only possible after the delay, and can't be cancelled in the meantime.
+		assert_noop!(Bounties::claim_bounty(Origin::signed(1), 0), Error::<Test>::Premature);
+		assert_noop!(Bounties::close_bounty(Origin::ROOT, 0), Error::<Test>::PendingPayout);
+
+		System::set_block_number(4);
+		assert_ok!(Bounties::claim_bounty(Origin::signed(1), 0));
+		assert_eq!(last_event(), RawEvent::BountyClaimed(0, 46, 3));
+
+		// curator gets the deposit back and the fee.
+		assert_eq!(Balances::free_balance(&4), 14);
+		assert_eq!(Balances::reserved_balance(&4), 0);
+		assert_eq!(Balances::free_balance(&3), 46);
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 0);
+
+		assert_eq!(Bounties::bounties(0), None);
+		assert_eq!(Bounties::bounty_descriptions(0), None);
+	});
+}
+
+#[test]
+fn cancel_active_bounty_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		active_bounty();
+		assert_eq!(Treasury::pot(), 25);
+
+		assert_ok!(Bounties::close_bounty(Origin::ROOT, 0));
+		assert_eq!(last_event(), RawEvent::BountyCanceled(0));
+
+		// curator gets the deposit back and the treasury the bounty funds.
+		assert_eq!(Balances::free_balance(&4), 10);
+		assert_eq!(Balances::reserved_balance(&4), 0);
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 0);
+		assert_eq!(Treasury::pot(), 75);
+
+		assert_eq!(Bounties::bounties(0), None);
+		assert_eq!(Bounties::bounty_descriptions(0), None);
+	});
+}
+
+#[test]
+fn extend_expiry_and_unassign_inactive_curator_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		active_bounty();
+
+		// the curator still has time to give an update.
+		System::set_block_number(21);
+		assert_noop!(Bounties::unassign_curator(Origin::signed(0), 0), Error::<Test>::Premature);
+
+		assert_noop!(
+			Bounties::extend_bounty_expiry(Origin::signed(0), 0, Vec::new()),
+			Error::<Test>::RequireCurator
+		);
+		assert_ok!(Bounties::extend_bounty_expiry(Origin::signed(4), 0, Vec::new()));
+		assert_eq!(last_event(), RawEvent::BountyExtended(0));
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Active {
+			curator: 4,
+			update_due: 41,
+		});
+
+		// once the update is overdue, anyone can unassign the curator, who gets slashed.
+		System::set_block_number(42);
+		assert_ok!(Bounties::unassign_curator(Origin::signed(0), 0));
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::free_balance(&4), 8);
+		assert_eq!(Balances::reserved_balance(&4), 0);
+	});
+}
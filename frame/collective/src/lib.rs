@@ -541,6 +541,7 @@ mod tests {
 		pub const MotionDuration: u64 = 3;
 	}
 	impl frame_system::Trait for Test {
+		type BaseCallFilter = ();
 		type Origin = Origin;
 		type Index = u64;
 		type BlockNumber = u64;
@@ -45,3 +45,21 @@ pub enum RentProjection<BlockNumber> {
 	/// E.g. because the contract accumulated enough funds to offset the rent storage costs.
 	NoEviction,
 }
+
+/// Result type of a dry-run of a contract call or instantiation.
+///
+/// It contains the execution result together with some auxiliary information.
+#[derive(Eq, PartialEq, codec::Encode, codec::Decode, sp_runtime::RuntimeDebug)]
+pub struct ContractResult<R> {
+	/// How much gas was consumed during execution.
+	pub gas_consumed: u64,
+	/// The SCALE encoded events of the runtime which were deposited during execution.
+	pub events: Vec<Vec<u8>>,
+	/// The UTF-8 encoded messages printed by the contracts with `ext_println` during execution.
+	///
+	/// Messages of all contracts which were called during execution are collected, including
+	/// the ones of calls that were reverted.
+	pub debug_message: Vec<u8>,
+	/// The execution result.
+	pub result: R,
+}
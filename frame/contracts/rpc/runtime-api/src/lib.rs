@@ -24,9 +24,11 @@
 
 use codec::{Codec, Decode, Encode};
 use pallet_contracts_primitives::{GetStorageResult, RentProjectionResult};
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{RuntimeDebug, traits::Block as BlockT};
 use sp_std::vec::Vec;
 
+pub use pallet_contracts_primitives::ContractResult;
+
 /// A result of execution of a contract.
 #[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug)]
 pub enum ContractExecResult {
@@ -45,15 +47,49 @@ pub enum ContractExecResult {
 	Error,
 }
 
+/// A result of the instantiation of a contract.
+#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub enum ContractInstantiateResult<AccountId> {
+	/// The constructor of the contract returned successfully.
+	///
+	/// There is a status code and, optionally, some data returned by the constructor.
+	Success {
+		/// Status code returned by the constructor.
+		status: u8,
+		/// Output data returned by the constructor.
+		///
+		/// Can be empty.
+		data: Vec<u8>,
+		/// The address of the new contract.
+		address: AccountId,
+	},
+	/// The instantiation either trapped or returned an error.
+	Error,
+}
+
 sp_api::decl_runtime_apis! {
 	/// The API to interact with contracts without using executive.
+	#[api_version(2)]
 	pub trait ContractsApi<AccountId, Balance, BlockNumber> where
 		AccountId: Codec,
 		Balance: Codec,
 		BlockNumber: Codec,
 	{
+		/// Perform a call from a specified account to a given contract.
+		#[changed_in(2)]
+		fn call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: u64,
+			input_data: Vec<u8>,
+		) -> ContractExecResult;
+
 		/// Perform a call from a specified account to a given contract.
 		///
+		/// The result is returned together with the gas consumed, the deposited events and the
+		/// debug messages of the called contracts.
+		///
 		/// See the contracts' `call` dispatchable function for more details.
 		fn call(
 			origin: AccountId,
@@ -61,7 +97,18 @@ sp_api::decl_runtime_apis! {
 			value: Balance,
 			gas_limit: u64,
 			input_data: Vec<u8>,
-		) -> ContractExecResult;
+		) -> ContractResult<ContractExecResult>;
+
+		/// Instantiate a new contract from the code stored under `code_hash`.
+		///
+		/// See the contracts' `instantiate` dispatchable function for more details.
+		fn instantiate(
+			origin: AccountId,
+			endowment: Balance,
+			gas_limit: u64,
+			code_hash: <Block as BlockT>::Hash,
+			data: Vec<u8>,
+		) -> ContractResult<ContractInstantiateResult<AccountId>>;
 
 		/// Query a given storage key in a given contract.
 		///
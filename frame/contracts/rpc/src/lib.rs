@@ -23,7 +23,7 @@ use jsonrpc_core::{Error, ErrorCode, Result};
 use jsonrpc_derive::rpc;
 use pallet_contracts_primitives::RentProjection;
 use serde::{Deserialize, Serialize};
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::{Bytes, H256};
 use sp_rpc::number;
@@ -34,7 +34,8 @@ use sp_runtime::{
 
 pub use self::gen_client::Client as ContractsClient;
 pub use pallet_contracts_rpc_runtime_api::{
-	self as runtime_api, ContractExecResult, ContractsApi as ContractsRuntimeApi,
+	self as runtime_api, ContractExecResult, ContractInstantiateResult, ContractResult,
+	ContractsApi as ContractsRuntimeApi,
 };
 
 const RUNTIME_ERROR: i64 = 1;
@@ -83,6 +84,45 @@ pub struct CallRequest<AccountId, Balance> {
 	input_data: Bytes,
 }
 
+/// A struct that encodes RPC parameters required to instantiate a new smart-contract.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct InstantiateRequest<AccountId, Balance, Hash> {
+	origin: AccountId,
+	endowment: Balance,
+	gas_limit: number::NumberOrHex<u64>,
+	code_hash: Hash,
+	data: Bytes,
+}
+
+/// An RPC serializable result of a contract dry-run together with the auxiliary information
+/// collected during execution.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcContractResult<R> {
+	/// How much gas was consumed during execution.
+	gas_consumed: u64,
+	/// The SCALE encoded events of the runtime which were deposited during execution.
+	events: Vec<Bytes>,
+	/// The messages printed by the contracts during execution.
+	debug_message: String,
+	/// The result of the execution.
+	result: R,
+}
+
+impl<R, S: From<R>> From<ContractResult<R>> for RpcContractResult<S> {
+	fn from(r: ContractResult<R>) -> Self {
+		RpcContractResult {
+			gas_consumed: r.gas_consumed,
+			events: r.events.into_iter().map(Into::into).collect(),
+			debug_message: String::from_utf8_lossy(&r.debug_message).into_owned(),
+			result: r.result.into(),
+		}
+	}
+}
+
 /// An RPC serializable result of contract execution
 #[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -111,6 +151,40 @@ impl From<ContractExecResult> for RpcContractExecResult {
 	}
 }
 
+/// An RPC serializable result of contract instantiation
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcContractInstantiateResult<AccountId> {
+	/// Successful instantiation
+	Success {
+		/// Status code
+		status: u8,
+		/// Output data
+		data: Bytes,
+		/// Address of the new contract
+		address: AccountId,
+	},
+	/// Error instantiation
+	Error(()),
+}
+
+impl<AccountId> From<ContractInstantiateResult<AccountId>>
+	for RpcContractInstantiateResult<AccountId>
+{
+	fn from(r: ContractInstantiateResult<AccountId>) -> Self {
+		match r {
+			ContractInstantiateResult::Success { status, data, address } =>
+				RpcContractInstantiateResult::Success {
+					status,
+					data: data.into(),
+					address,
+				},
+			ContractInstantiateResult::Error => RpcContractInstantiateResult::Error(()),
+		}
+	}
+}
+
 /// Contracts RPC methods.
 #[rpc]
 pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance> {
@@ -120,12 +194,34 @@ pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance> {
 	/// won't change any state. Nonetheless, the calling state-changing contracts is still possible.
 	///
 	/// This method is useful for calling getter-like methods on contracts.
+	///
+	/// The messages printed by the called contracts are returned instead of being printed to
+	/// the log of the node.
+	///
+	/// Note that the result of the execution is not returned at the top level anymore, but under
+	/// the `result` field, next to `gasConsumed`, `events` and `debugMessage`. When the runtime
+	/// only implements the first version of the runtime API, these fields are left empty.
 	#[rpc(name = "contracts_call")]
 	fn call(
 		&self,
 		call_request: CallRequest<AccountId, Balance>,
 		at: Option<BlockHash>,
-	) -> Result<RpcContractExecResult>;
+	) -> Result<RpcContractResult<RpcContractExecResult>>;
+
+	/// Instantiate a new contract.
+	///
+	/// This call is performed locally without submitting any transactions. Thus executing this
+	/// won't change any state. The code of the contract must already be stored on chain.
+	///
+	/// This method is useful for determining the gas required by an instantiation as well as
+	/// the address of the new contract. The messages printed by the contracts are returned
+	/// instead of being printed to the log of the node.
+	#[rpc(name = "contracts_instantiate")]
+	fn instantiate(
+		&self,
+		instantiate_request: InstantiateRequest<AccountId, Balance, BlockHash>,
+		at: Option<BlockHash>,
+	) -> Result<RpcContractResult<RpcContractInstantiateResult<AccountId>>>;
 
 	/// Returns the value under a specified storage `key` in a contract given by `address` param,
 	/// or `None` if it is not set.
@@ -189,7 +285,7 @@ where
 		&self,
 		call_request: CallRequest<AccountId, Balance>,
 		at: Option<<Block as BlockT>::Hash>,
-	) -> Result<RpcContractExecResult> {
+	) -> Result<RpcContractResult<RpcContractExecResult>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
@@ -202,26 +298,55 @@ where
 			gas_limit,
 			input_data,
 		} = call_request;
-		let gas_limit = gas_limit.to_number().map_err(|e| Error {
-			code: ErrorCode::InvalidParams,
-			message: e,
-			data: None,
-		})?;
-
-		let max_gas_limit = 5 * GAS_PER_SECOND;
-		if gas_limit > max_gas_limit {
-			return Err(Error {
-				code: ErrorCode::InvalidParams,
-				message: format!(
-					"Requested gas limit is greater than maximum allowed: {} > {}",
-					gas_limit, max_gas_limit
-				),
-				data: None,
-			});
-		}
+		let gas_limit = limit_gas(gas_limit)?;
+
+		let has_v2 = api
+			.has_api_with::<dyn ContractsRuntimeApi<
+				Block,
+				AccountId,
+				Balance,
+				<<Block as BlockT>::Header as HeaderT>::Number,
+				Error = (),
+			>, _>(&at, |v| v >= 2)
+			.map_err(|e| runtime_error_into_rpc_err(e))?;
+
+		let exec_result = if has_v2 {
+			api.call(&at, origin, dest, value, gas_limit, input_data.to_vec())
+		} else {
+			#[allow(deprecated)] // old call without the auxiliary information
+			api.call_before_version_2(&at, origin, dest, value, gas_limit, input_data.to_vec())
+				.map(|result| ContractResult {
+					gas_consumed: 0,
+					events: Vec::new(),
+					debug_message: Vec::new(),
+					result,
+				})
+		}.map_err(|e| runtime_error_into_rpc_err(e))?;
+
+		Ok(exec_result.into())
+	}
+
+	fn instantiate(
+		&self,
+		instantiate_request: InstantiateRequest<AccountId, Balance, <Block as BlockT>::Hash>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<RpcContractResult<RpcContractInstantiateResult<AccountId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let InstantiateRequest {
+			origin,
+			endowment,
+			gas_limit,
+			code_hash,
+			data,
+		} = instantiate_request;
+		let gas_limit = limit_gas(gas_limit)?;
 
 		let exec_result = api
-			.call(&at, origin, dest, value, gas_limit, input_data.to_vec())
+			.instantiate(&at, origin, endowment, gas_limit, code_hash, data.to_vec())
 			.map_err(|e| runtime_error_into_rpc_err(e))?;
 
 		Ok(exec_result.into())
@@ -269,6 +394,30 @@ where
 	}
 }
 
+/// Converts the gas limit of a request into a number and ensures it doesn't exceed the maximum
+/// gas limit allowed for dry-runs.
+fn limit_gas(gas_limit: number::NumberOrHex<u64>) -> Result<u64> {
+	let gas_limit = gas_limit.to_number().map_err(|e| Error {
+		code: ErrorCode::InvalidParams,
+		message: e,
+		data: None,
+	})?;
+
+	let max_gas_limit = 5 * GAS_PER_SECOND;
+	if gas_limit > max_gas_limit {
+		return Err(Error {
+			code: ErrorCode::InvalidParams,
+			message: format!(
+				"Requested gas limit is greater than maximum allowed: {} > {}",
+				gas_limit, max_gas_limit
+			),
+			data: None,
+		});
+	}
+
+	Ok(gas_limit)
+}
+
 /// Converts a runtime trap into an RPC error.
 fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> Error {
 	Error {
@@ -293,4 +442,36 @@ mod tests {
 		test(r#"{"success":{"status":5,"data":"0x1234"}}"#);
 		test(r#"{"error":null}"#);
 	}
+
+	#[test]
+	fn call_result_should_serialize_deserialize_properly() {
+		fn test(expected: &str) {
+			let res: RpcContractResult<RpcContractExecResult> =
+				serde_json::from_str(expected).unwrap();
+			let actual = serde_json::to_string(&res).unwrap();
+			assert_eq!(actual, expected);
+		}
+
+		test(concat!(
+			r#"{"gasConsumed":5000,"events":[],"debugMessage":"Hello World!","#,
+			r#""result":{"success":{"status":5,"data":"0x1234"}}}"#,
+		));
+		test(r#"{"gasConsumed":0,"events":[],"debugMessage":"","result":{"error":null}}"#);
+	}
+
+	#[test]
+	fn instantiate_result_should_serialize_deserialize_properly() {
+		fn test(expected: &str) {
+			let res: RpcContractResult<RpcContractInstantiateResult<String>> =
+				serde_json::from_str(expected).unwrap();
+			let actual = serde_json::to_string(&res).unwrap();
+			assert_eq!(actual, expected);
+		}
+
+		test(concat!(
+			r#"{"gasConsumed":5000,"events":["0x0102"],"debugMessage":"Hello World!","#,
+			r#""result":{"success":{"status":0,"data":"0x1234","address":"5GrwvaEF"}}}"#,
+		));
+		test(r#"{"gasConsumed":0,"events":[],"debugMessage":"","result":{"error":null}}"#);
+	}
 }
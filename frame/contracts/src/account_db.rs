@@ -21,6 +21,7 @@ use super::{
 	TrieIdGenerator,
 };
 use crate::exec::StorageKey;
+use crate::wasm::{decrement_code_refcount, increment_code_refcount};
 use sp_std::cell::RefCell;
 use sp_std::collections::btree_map::{BTreeMap, Entry};
 use sp_std::prelude::*;
@@ -43,6 +44,9 @@ pub struct ChangeEntry<T: Trait> {
 	/// If Some(_), then the rent allowance is set to the value. If None and `reset` is false, then
 	/// the rent allowance is unmodified. If None and `reset` is true, the contract is deleted.
 	rent_allowance: Option<BalanceOf<T>>,
+	/// If Some(_), then the storage deposit held by the contract is set to the value. Otherwise
+	/// it is unmodified.
+	storage_deposit: Option<BalanceOf<T>>,
 	storage: BTreeMap<StorageKey, Option<Vec<u8>>>,
 	/// If true, indicates that the existing contract and all its storage entries should be removed
 	/// and replaced with the fields on this change entry. Otherwise, the fields on this change
@@ -92,6 +96,7 @@ impl<T: Trait> Default for ChangeEntry<T> {
 	fn default() -> Self {
 		ChangeEntry {
 			rent_allowance: Default::default(),
+			storage_deposit: Default::default(),
 			balance: Default::default(),
 			code_hash: Default::default(),
 			storage: Default::default(),
@@ -102,6 +107,18 @@ impl<T: Trait> Default for ChangeEntry<T> {
 
 pub type ChangeSet<T> = BTreeMap<<T as frame_system::Trait>::AccountId, ChangeEntry<T>>;
 
+/// The storage occupied by a contract after a change set is committed.
+pub struct StorageFootprint<T: Trait> {
+	/// The contract occupying the storage.
+	pub account: T::AccountId,
+	/// The size of the storage in octets, including `Trait::StorageSizeOffset`.
+	pub storage_size: u32,
+	/// The number of items in the storage.
+	pub storage_items: u32,
+	/// The storage deposit currently held by the contract.
+	pub storage_deposit: BalanceOf<T>,
+}
+
 pub trait AccountDb<T: Trait> {
 	/// Account is used when overlayed otherwise trie_id must be provided.
 	/// This is for performance reason.
@@ -113,6 +130,8 @@ pub trait AccountDb<T: Trait> {
 	fn get_code_hash(&self, account: &T::AccountId) -> Option<CodeHash<T>>;
 	/// If account has an alive contract then return the rent allowance associated.
 	fn get_rent_allowance(&self, account: &T::AccountId) -> Option<BalanceOf<T>>;
+	/// Returns the storage deposit held by the account, or zero if it has no alive contract.
+	fn get_storage_deposit(&self, account: &T::AccountId) -> BalanceOf<T>;
 	/// Returns false iff account has no alive contract nor tombstone.
 	fn contract_exists(&self, account: &T::AccountId) -> bool;
 	fn get_balance(&self, account: &T::AccountId) -> BalanceOf<T>;
@@ -136,6 +155,11 @@ impl<T: Trait> AccountDb<T> for DirectAccountDb {
 	fn get_rent_allowance(&self, account: &T::AccountId) -> Option<BalanceOf<T>> {
 		<ContractInfoOf<T>>::get(account).and_then(|i| i.as_alive().map(|i| i.rent_allowance))
 	}
+	fn get_storage_deposit(&self, account: &T::AccountId) -> BalanceOf<T> {
+		<ContractInfoOf<T>>::get(account)
+			.and_then(|i| i.as_alive().map(|i| i.storage_deposit))
+			.unwrap_or_else(Zero::zero)
+	}
 	fn contract_exists(&self, account: &T::AccountId) -> bool {
 		<ContractInfoOf<T>>::contains_key(account)
 	}
@@ -152,6 +176,7 @@ impl<T: Trait> AccountDb<T> for DirectAccountDb {
 
 			if changed.code_hash().is_some()
 				|| changed.rent_allowance().is_some()
+				|| changed.storage_deposit.is_some()
 				|| !changed.storage.is_empty()
 				|| changed.reset
 			{
@@ -168,15 +193,20 @@ impl<T: Trait> AccountDb<T> for DirectAccountDb {
 					// Existing contract is being removed.
 					(true, Some(info), None) => {
 						child::kill_storage(&info.child_trie_info());
+						decrement_code_refcount::<T>(&info.code_hash);
 						<ContractInfoOf<T>>::remove(&address);
 						continue;
 					}
 					// Existing contract is being replaced by a new one.
 					(true, Some(info), Some(code_hash)) => {
 						child::kill_storage(&info.child_trie_info());
+						decrement_code_refcount::<T>(&info.code_hash);
+						increment_code_refcount::<T>(&code_hash);
 						AliveContractInfo::<T> {
 							code_hash,
 							storage_size: T::StorageSizeOffset::get(),
+							storage_items: 0,
+							storage_deposit: <BalanceOf<T>>::zero(),
 							trie_id: <T as Trait>::TrieIdGenerator::trie_id(&address),
 							deduct_block: <frame_system::Module<T>>::block_number(),
 							rent_allowance: <BalanceOf<T>>::max_value(),
@@ -185,9 +215,12 @@ impl<T: Trait> AccountDb<T> for DirectAccountDb {
 					}
 					// New contract is being instantiated.
 					(_, None, Some(code_hash)) => {
+						increment_code_refcount::<T>(&code_hash);
 						AliveContractInfo::<T> {
 							code_hash,
 							storage_size: T::StorageSizeOffset::get(),
+							storage_items: 0,
+							storage_deposit: <BalanceOf<T>>::zero(),
 							trie_id: <T as Trait>::TrieIdGenerator::trie_id(&address),
 							deduct_block: <frame_system::Module<T>>::block_number(),
 							rent_allowance: <BalanceOf<T>>::max_value(),
@@ -203,9 +236,18 @@ impl<T: Trait> AccountDb<T> for DirectAccountDb {
 				}
 
 				if let Some(code_hash) = changed.code_hash {
+					// The code of an existing contract is being replaced.
+					if code_hash != new_info.code_hash {
+						decrement_code_refcount::<T>(&new_info.code_hash);
+						increment_code_refcount::<T>(&code_hash);
+					}
 					new_info.code_hash = code_hash;
 				}
 
+				if let Some(storage_deposit) = changed.storage_deposit {
+					new_info.storage_deposit = storage_deposit;
+				}
+
 				if !changed.storage.is_empty() {
 					new_info.last_write = Some(<frame_system::Module<T>>::block_number());
 				}
@@ -216,9 +258,12 @@ impl<T: Trait> AccountDb<T> for DirectAccountDb {
 						&blake2_256(&k),
 					) {
 						new_info.storage_size -= value.len() as u32;
+						// Items stored before the item count was tracked are not counted.
+						new_info.storage_items = new_info.storage_items.saturating_sub(1);
 					}
 					if let Some(value) = v {
 						new_info.storage_size += value.len() as u32;
+						new_info.storage_items += 1;
 						child::put_raw(&new_info.child_trie_info(), &blake2_256(&k), &value[..]);
 					} else {
 						child::kill(&new_info.child_trie_info(), &blake2_256(&k));
@@ -320,6 +365,94 @@ impl<'a, T: Trait> OverlayAccountDb<'a, T> {
 			.or_insert(Default::default())
 			.balance = Some(balance);
 	}
+
+	/// Assume contract exists
+	pub fn set_code_hash(&mut self, account: &T::AccountId, code_hash: CodeHash<T>) {
+		self.local
+			.borrow_mut()
+			.entry(account.clone())
+			.or_insert(Default::default())
+			.code_hash = Some(code_hash);
+	}
+
+	/// Assume contract exists
+	pub fn set_storage_deposit(&mut self, account: &T::AccountId, storage_deposit: BalanceOf<T>) {
+		self.local
+			.borrow_mut()
+			.entry(account.clone())
+			.or_insert(Default::default())
+			.storage_deposit = Some(storage_deposit);
+	}
+
+	/// Returns the storage footprint of every alive contract whose storage is changed by this
+	/// overlay, as it would be after committing the overlay.
+	///
+	/// The footprint is computed against the persistent storage, so this must only be called on
+	/// an overlay on top of `DirectAccountDb`. Contracts which are removed by this overlay are
+	/// not included.
+	pub fn storage_footprints(&self) -> Vec<StorageFootprint<T>> {
+		let local = self.local.borrow();
+		let mut footprints = Vec::new();
+		for (address, changed) in local.iter() {
+			if changed.storage.is_empty() && changed.code_hash.is_none() {
+				continue;
+			}
+
+			let old_info = match <ContractInfoOf<T>>::get(address) {
+				Some(ContractInfo::Alive(alive)) => Some(alive),
+				None => None,
+				Some(ContractInfo::Tombstone(_)) => continue,
+			};
+
+			let (existing, mut footprint) = match (changed.reset, old_info, changed.code_hash) {
+				// Existing contract is being modified.
+				(false, Some(info), _) => {
+					let footprint = StorageFootprint {
+						account: address.clone(),
+						storage_size: info.storage_size,
+						storage_items: info.storage_items,
+						storage_deposit: info.storage_deposit,
+					};
+					(Some(info), footprint)
+				}
+				// New contract is being instantiated, possibly replacing an existing one.
+				(_, _, Some(_)) => {
+					let footprint = StorageFootprint {
+						account: address.clone(),
+						storage_size: T::StorageSizeOffset::get(),
+						storage_items: 0,
+						storage_deposit: <BalanceOf<T>>::zero(),
+					};
+					(None, footprint)
+				}
+				// Contract is being removed or there is no contract at all.
+				(_, _, None) => continue,
+			};
+
+			if let Some(storage_deposit) = changed.storage_deposit {
+				footprint.storage_deposit = storage_deposit;
+			}
+
+			for (k, v) in changed.storage.iter() {
+				if let Some(value) = existing.as_ref().and_then(|info| child::get_raw(
+					&info.child_trie_info(),
+					&blake2_256(k),
+				)) {
+					footprint.storage_size =
+						footprint.storage_size.saturating_sub(value.len() as u32);
+					footprint.storage_items = footprint.storage_items.saturating_sub(1);
+				}
+				if let Some(value) = v {
+					footprint.storage_size =
+						footprint.storage_size.saturating_add(value.len() as u32);
+					footprint.storage_items = footprint.storage_items.saturating_add(1);
+				}
+			}
+
+			footprints.push(footprint);
+		}
+		footprints
+	}
 }
 
 impl<'a, T: Trait> AccountDb<T> for OverlayAccountDb<'a, T> {
@@ -349,6 +482,19 @@ impl<'a, T: Trait> AccountDb<T> for OverlayAccountDb<'a, T> {
 			.and_then(|changes| changes.rent_allowance())
 			.unwrap_or_else(|| self.underlying.get_rent_allowance(account))
 	}
+	fn get_storage_deposit(&self, account: &T::AccountId) -> BalanceOf<T> {
+		self.local
+			.borrow()
+			.get(account)
+			.and_then(|changes| changes.storage_deposit.or_else(|| {
+				if changes.reset {
+					Some(<BalanceOf<T>>::zero())
+				} else {
+					None
+				}
+			}))
+			.unwrap_or_else(|| self.underlying.get_storage_deposit(account))
+	}
 	fn contract_exists(&self, account: &T::AccountId) -> bool {
 		self.local
 			.borrow()
@@ -376,6 +522,7 @@ impl<'a, T: Trait> AccountDb<T> for OverlayAccountDb<'a, T> {
 						value.balance = changed.balance.or(value.balance);
 						value.code_hash = changed.code_hash.or(value.code_hash);
 						value.rent_allowance = changed.rent_allowance.or(value.rent_allowance);
+						value.storage_deposit = changed.storage_deposit.or(value.storage_deposit);
 						value.storage.extend(changed.storage.into_iter());
 					}
 				}
@@ -0,0 +1,174 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate. If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime defined extensions of the contract execution environment.
+//!
+//! A runtime can make functionality that is not covered by the built-in host functions
+//! available to contracts by implementing [`ChainExtension`] and setting it as
+//! [`Trait::ChainExtension`]. Contracts access it through the single `ext_call_chain_extension`
+//! host function, which passes a function id chosen by the contract together with an input
+//! buffer:
+//!
+//! ```ignore
+//! ext_call_chain_extension(
+//! 	func_id: u32,
+//! 	input_ptr: u32,
+//! 	input_len: u32,
+//! 	output_ptr: u32,
+//! 	output_len_ptr: u32,
+//! ) -> u32
+//! ```
+//!
+//! The extension decides what the function id means, reads its input and writes its output
+//! through the [`Environment`] and returns a `u32` which is passed on to the contract as the
+//! return value of the host function. Any error returned by the extension traps the contract.
+//!
+//! The extension is responsible for charging the appropriate amount of gas for the work it does
+//! with [`Environment::charge_weight`]. Accessing the contract memory through the
+//! [`Environment`] is charged in the same way as for the built-in host functions.
+//!
+//! [`Trait::ChainExtension`]: ../trait.Trait.html#associatedtype.ChainExtension
+
+use crate::wasm::Runtime;
+use codec::{Decode, Encode};
+use frame_support::weights::Weight;
+use sp_runtime::DispatchError;
+use sp_std::prelude::*;
+
+pub use crate::exec::Ext;
+
+/// A result of a chain extension call which is passed to the contract as the return value of
+/// `ext_call_chain_extension`, or an error which traps the contract.
+pub type Result<T> = sp_std::result::Result<T, DispatchError>;
+
+/// A runtime defined extension of the contract execution environment.
+pub trait ChainExtension {
+	/// Call the chain extension logic.
+	///
+	/// `func_id` is the id passed by the contract and lets the extension distinguish between
+	/// the different functions it offers. The input, output and gas meter of the calling
+	/// contract are accessed through `env`.
+	///
+	/// The returned value is passed to the contract as the return value of
+	/// `ext_call_chain_extension`. Returning an error traps the contract.
+	fn call<E: Ext>(func_id: u32, env: Environment<E>) -> Result<u32>;
+
+	/// Determines whether chain extensions are enabled for this chain.
+	///
+	/// Contracts importing `ext_call_chain_extension` are rejected on upload when this returns
+	/// `false`.
+	fn enabled() -> bool {
+		true
+	}
+}
+
+/// Implementation that indicates that no chain extension is available.
+impl ChainExtension for () {
+	fn call<E: Ext>(_func_id: u32, _env: Environment<E>) -> Result<u32> {
+		// Never called since `enabled()` returns `false` and contracts importing
+		// `ext_call_chain_extension` are rejected by the code preparation.
+		Err(DispatchError::Other("chain extensions are disabled"))
+	}
+
+	fn enabled() -> bool {
+		false
+	}
+}
+
+/// The access of a chain extension to the calling contract.
+///
+/// Gives access to the input and output buffers passed by the contract, its gas meter and the
+/// [`Ext`] of the current execution.
+pub struct Environment<'a, 'b, E: Ext + 'b> {
+	runtime: &'a mut Runtime<'b, E>,
+	input_ptr: u32,
+	input_len: u32,
+	output_ptr: u32,
+	output_len_ptr: u32,
+}
+
+impl<'a, 'b, E: Ext + 'b> Environment<'a, 'b, E> {
+	pub(crate) fn new(
+		runtime: &'a mut Runtime<'b, E>,
+		input_ptr: u32,
+		input_len: u32,
+		output_ptr: u32,
+		output_len_ptr: u32,
+	) -> Self {
+		Environment {
+			runtime,
+			input_ptr,
+			input_len,
+			output_ptr,
+			output_len_ptr,
+		}
+	}
+
+	/// Charge the given `weight` to the gas meter of the calling contract.
+	///
+	/// Returns an error if the contract ran out of gas. The extension should return this error
+	/// in order to trap the contract.
+	pub fn charge_weight(&mut self, weight: Weight) -> Result<()> {
+		self.runtime.charge_weight(weight)
+			.map_err(|_| DispatchError::Other("ran out of gas in chain extension"))
+	}
+
+	/// Grants access to the execution environment of the calling contract.
+	pub fn ext(&mut self) -> &mut E {
+		self.runtime.ext()
+	}
+
+	/// The length of the input buffer passed by the contract.
+	pub fn in_len(&self) -> u32 {
+		self.input_len
+	}
+
+	/// Read the input buffer passed by the contract.
+	///
+	/// Reads at most `max_len` bytes. Reading the memory is charged to the gas meter.
+	pub fn read(&mut self, max_len: u32) -> Result<Vec<u8>> {
+		self.runtime.read_sandbox_memory(self.input_ptr, self.input_len.min(max_len))
+			.map_err(|_| DispatchError::Other("failed to read chain extension input"))
+	}
+
+	/// Read the input buffer passed by the contract and decode it as `T`.
+	pub fn read_as<T: Decode>(&mut self) -> Result<T> {
+		let buf = self.read(self.input_len)?;
+		T::decode(&mut &buf[..])
+			.map_err(|_| DispatchError::Other("failed to decode chain extension input"))
+	}
+
+	/// Write `buffer` into the output buffer passed by the contract.
+	///
+	/// Returns an error if `buffer` is longer than the output buffer. On success the length of
+	/// the output buffer is set to the length of `buffer`. Writing the memory is charged to the
+	/// gas meter.
+	pub fn write(&mut self, buffer: &[u8]) -> Result<()> {
+		let buf_len = self.runtime.read_sandbox_memory(self.output_len_ptr, 4)
+			.ok()
+			.and_then(|len| u32::decode(&mut &len[..]).ok())
+			.ok_or(DispatchError::Other("failed to read chain extension output length"))?;
+		if (buf_len as usize) < buffer.len() {
+			return Err(DispatchError::Other("chain extension output buffer too small"));
+		}
+		self.runtime.write_sandbox_memory(self.output_ptr, buffer)
+			.and_then(|_| self.runtime.write_sandbox_memory(
+				self.output_len_ptr,
+				&(buffer.len() as u32).encode(),
+			))
+			.map_err(|_| DispatchError::Other("failed to write chain extension output"))
+	}
+}
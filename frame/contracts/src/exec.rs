@@ -14,16 +14,16 @@
 // You should have received a copy of the GNU General Public License
 // along with Substrate. If not, see <http://www.gnu.org/licenses/>.
 
-use super::{CodeHash, Config, ContractAddressFor, Event, RawEvent, Trait,
+use super::{CodeHash, CodeStorage, Config, ContractAddressFor, Event, RawEvent, Trait,
 	TrieId, BalanceOf, ContractInfo};
 use crate::account_db::{AccountDb, DirectAccountDb, OverlayAccountDb};
 use crate::gas::{Gas, GasMeter, Token};
 use crate::rent;
 
-use sp_std::prelude::*;
-use sp_runtime::traits::{Bounded, CheckedAdd, CheckedSub, Zero};
+use sp_std::{prelude::*, cell::RefCell};
+use sp_runtime::traits::{Bounded, CheckedAdd, CheckedSub, Saturating, Zero};
 use frame_support::{
-	storage::unhashed, dispatch::DispatchError,
+	StorageMap, storage::unhashed, dispatch::DispatchError,
 	traits::{WithdrawReason, Currency, Time, Randomness},
 };
 
@@ -193,6 +193,12 @@ pub trait Ext {
 	/// Rent allowance of the contract
 	fn rent_allowance(&self) -> BalanceOf<Self::T>;
 
+	/// Replace the code of the current contract with the code identified by `hash`.
+	///
+	/// The new code is used for all executions of the contract after the current one has
+	/// finished. Returns an error if no code is stored under `hash`.
+	fn set_code_hash(&mut self, hash: CodeHash<Self::T>) -> Result<(), DispatchError>;
+
 	/// Returns the current block number.
 	fn block_number(&self) -> BlockNumberOf<Self::T>;
 
@@ -206,6 +212,12 @@ pub trait Ext {
 
 	/// Returns the price of one weight unit.
 	fn get_weight_price(&self) -> BalanceOf<Self::T>;
+
+	/// Append a string to the debug buffer.
+	///
+	/// Returns `false` if no debug buffer is collected for this execution. The caller is then
+	/// free to handle the message in another way.
+	fn append_debug_buffer(&mut self, msg: &str) -> bool;
 }
 
 /// Loader is a companion of the `Vm` trait. It loads an appropriate abstract
@@ -303,6 +315,8 @@ pub struct ExecutionContext<'a, T: Trait + 'a, V, L> {
 	pub loader: &'a L,
 	pub timestamp: MomentOf<T>,
 	pub block_number: T::BlockNumber,
+	/// The buffer into which the messages printed by contracts are collected, if any.
+	pub debug_message: Option<&'a RefCell<Vec<u8>>>,
 }
 
 impl<'a, T, E, V, L> ExecutionContext<'a, T, V, L>
@@ -328,6 +342,7 @@ where
 			loader: &loader,
 			timestamp: T::Time::now(),
 			block_number: <frame_system::Module<T>>::block_number(),
+			debug_message: None,
 		}
 	}
 
@@ -346,6 +361,7 @@ where
 			loader: self.loader,
 			timestamp: self.timestamp.clone(),
 			block_number: self.block_number.clone(),
+			debug_message: self.debug_message,
 		}
 	}
 
@@ -688,9 +704,11 @@ fn transfer<'a, T: Trait, V: Vm<T>, L: Loader<T>>(
 	}
 
 	// Only ext_terminate is allowed to bring the sender below the existential deposit
+	// or to spend its storage deposit.
 	let required_balance = match cause {
 		Terminate => 0.into(),
 		_ => ctx.config.existential_deposit
+			.saturating_add(ctx.overlay.get_storage_deposit(transactor)),
 	};
 
 	T::Currency::ensure_can_withdraw(
@@ -859,6 +877,14 @@ where
 			.unwrap_or(<BalanceOf<T>>::max_value()) // Must never be triggered actually
 	}
 
+	fn set_code_hash(&mut self, hash: CodeHash<T>) -> Result<(), DispatchError> {
+		if !<CodeStorage<T>>::contains_key(&hash) {
+			Err("code is not found")?
+		}
+		self.ctx.overlay.set_code_hash(&self.ctx.self_account, hash);
+		Ok(())
+	}
+
 	fn block_number(&self) -> T::BlockNumber { self.block_number }
 
 	fn max_value_size(&self) -> u32 {
@@ -875,6 +901,16 @@ where
 		let price = Payment::<T>::weight_to_fee_with_adjustment::<u128>(1);
 		price.saturated_into()
 	}
+
+	fn append_debug_buffer(&mut self, msg: &str) -> bool {
+		match self.ctx.debug_message {
+			Some(buffer) => {
+				buffer.borrow_mut().extend_from_slice(msg.as_bytes());
+				true
+			},
+			None => false,
+		}
+	}
 }
 
 /// These tests exercise the executive layer.
@@ -48,6 +48,17 @@
 //! then all of B's calls are reverted. Assuming correct error handling by contract A, A's other calls and state
 //! changes still persist.
 //!
+//! ### Storage
+//!
+//! Contracts pay for the storage they occupy in one of two ways, selected by [`Trait::StorageModel`]:
+//!
+//! - [`StorageModel::Rent`]: The contract pays rent from its own balance for every block. A contract
+//! which can't afford its rent is evicted and leaves a tombstone, from which it can be restored.
+//! - [`StorageModel::Deposit`]: The origin of a `call` or `instantiate` pays a deposit for every byte
+//! and storage item added by the execution. The deposit is held by the contract and refunded to the
+//! origin of the execution which frees the storage again. The origin can limit the deposit it is
+//! willing to pay. Contracts are never evicted in this mode.
+//!
 //! ### Notable Scenarios
 //!
 //! Contract call failures are not always cascading. When failures occur in a sub-call, they do not "bubble up",
@@ -63,6 +74,8 @@
 //! This instantiates a new smart contract account and calls its contract deploy handler to
 //! initialize the contract.
 //! * `call` - Makes a call to an account, optionally transferring some balance.
+//! * `remove_code` - Removes code which is no longer used by any contract and refunds the deposit
+//! paid for storing it.
 //!
 //! ## Usage
 //!
@@ -86,6 +99,8 @@ mod account_db;
 mod exec;
 mod wasm;
 mod rent;
+mod storage_deposit;
+pub mod chain_extension;
 
 #[cfg(test)]
 mod tests;
@@ -100,7 +115,7 @@ pub use crate::exec::{ExecResult, ExecReturnValue, ExecError, StatusCode};
 #[cfg(feature = "std")]
 use serde::{Serialize, Deserialize};
 use sp_core::crypto::UncheckedFrom;
-use sp_std::{prelude::*, marker::PhantomData, fmt::Debug};
+use sp_std::{prelude::*, marker::PhantomData, fmt::Debug, cell::RefCell};
 use codec::{Codec, Encode, Decode};
 use sp_io::hashing::blake2_256;
 use sp_runtime::{
@@ -114,12 +129,14 @@ use frame_support::dispatch::{
 };
 use frame_support::{
 	Parameter, decl_module, decl_event, decl_storage, decl_error,
-	parameter_types, IsSubType, storage::child::{self, ChildInfo},
+	parameter_types, IsSubType, IterableStorageMap, storage::child::{self, ChildInfo},
+};
+use frame_support::traits::{
+	OnUnbalanced, Currency, Get, Time, Randomness, GetPalletVersion, PalletVersion,
 };
-use frame_support::traits::{OnUnbalanced, Currency, Get, Time, Randomness};
 use frame_support::weights::{FunctionOf, DispatchClass, Weight, GetDispatchInfo, Pays};
 use frame_system::{self as system, ensure_signed, RawOrigin, ensure_root};
-use pallet_contracts_primitives::{RentProjection, ContractAccessError};
+use pallet_contracts_primitives::{RentProjection, ContractAccessError, ContractResult};
 
 pub type CodeHash<T> = <T as frame_system::Trait>::Hash;
 pub type TrieId = Vec<u8>;
@@ -205,6 +222,12 @@ pub struct RawAliveContractInfo<CodeHash, Balance, BlockNumber> {
 	pub trie_id: TrieId,
 	/// The size of stored value in octet.
 	pub storage_size: u32,
+	/// The number of items in the storage of the contract.
+	pub storage_items: u32,
+	/// The storage deposit held by the contract for its storage.
+	///
+	/// Always zero if the storage is paid with rent.
+	pub storage_deposit: Balance,
 	/// The code associated with a given account.
 	pub code_hash: CodeHash,
 	/// Pay rent at most up to this value.
@@ -227,6 +250,22 @@ pub(crate) fn child_trie_info(trie_id: &[u8]) -> ChildInfo {
 	ChildInfo::new_default(trie_id)
 }
 
+pub type CodeInfo<T> = RawCodeInfo<<T as frame_system::Trait>::AccountId, BalanceOf<T>>;
+
+/// Information about stored code.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct RawCodeInfo<AccountId, Balance> {
+	/// The account which stored the code and paid the deposit for it.
+	///
+	/// `None` for code which was stored before the code was reference counted. Such code can't
+	/// be removed.
+	pub owner: Option<AccountId>,
+	/// The deposit paid by the owner for storing the code.
+	pub deposit: Balance,
+	/// The number of alive contracts using the code.
+	pub refcount: u64,
+}
+
 pub type TombstoneContractInfo<T> =
 	RawTombstoneContractInfo<<T as frame_system::Trait>::Hash, <T as frame_system::Trait>::Hashing>;
 
@@ -288,6 +327,15 @@ pub type BalanceOf<T> = <<T as pallet_transaction_payment::Trait>::Currency as C
 pub type NegativeImbalanceOf<T> =
 	<<T as pallet_transaction_payment::Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
 
+/// The way contracts pay for the storage they occupy.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum StorageModel {
+	/// Contracts pay rent for their storage and are evicted if they can't afford it.
+	Rent,
+	/// The origin of an execution pays a refundable deposit for the storage it adds.
+	Deposit,
+}
+
 parameter_types! {
 	/// A reasonable default value for [`Trait::SignedClaimedHandicap`].
 	pub const DefaultSignedClaimHandicap: u32 = 2;
@@ -363,6 +411,31 @@ pub trait Trait: frame_system::Trait + pallet_transaction_payment::Trait {
 
 	/// The maximum size of a storage value in bytes.
 	type MaxValueSize: Get<u32>;
+
+	/// Type that allows the runtime authors to add new host functions for a contract to call.
+	///
+	/// Use `()` if the runtime does not provide any chain extension.
+	type ChainExtension: chain_extension::ChainExtension;
+
+	/// The way contracts pay for their storage.
+	///
+	/// With [`StorageModel::Deposit`] the rent related configuration is ignored.
+	type StorageModel: Get<StorageModel>;
+
+	/// The deposit charged for every byte added to the storage of a contract.
+	///
+	/// Only used with [`StorageModel::Deposit`].
+	type DepositPerByte: Get<BalanceOf<Self>>;
+
+	/// The deposit charged for every item added to the storage of a contract.
+	///
+	/// Only used with [`StorageModel::Deposit`].
+	type DepositPerItem: Get<BalanceOf<Self>>;
+
+	/// The deposit charged for every byte of code stored with `put_code`.
+	///
+	/// The deposit is refunded when the code is removed with `remove_code`.
+	type CodeDepositPerByte: Get<BalanceOf<Self>>;
 }
 
 /// Simple contract address determiner.
@@ -402,7 +475,17 @@ decl_error! {
 		/// Tombstones don't match.
 		InvalidTombstone,
 		/// An origin TrieId written in the current block.
-		InvalidContractOrigin
+		InvalidContractOrigin,
+		/// The storage deposit required by the execution exceeds the given limit.
+		StorageDepositLimitExhausted,
+		/// The origin can't afford the storage deposit required by the execution.
+		StorageDepositNotAffordable,
+		/// No code is stored under the given code hash.
+		CodeNotFound,
+		/// The code was stored by another account.
+		NotCodeOwner,
+		/// The code is still used by a contract.
+		CodeInUse,
 	}
 }
 
@@ -449,6 +532,17 @@ decl_module! {
 
 		fn deposit_event() = default;
 
+		fn on_runtime_upgrade() -> Weight {
+			// Storage deposits and code reference counting were introduced with version 3.
+			if Self::on_chain_storage_version() >= Some(PalletVersion::new(3, 0, 0)) {
+				return T::DbWeight::get().reads(1)
+			}
+
+			Self::migrate_to_storage_deposit()
+				.saturating_add(Self::migrate_to_code_refcount())
+				.saturating_add(T::DbWeight::get().reads(1))
+		}
+
 		/// Updates the schedule for metering contracts.
 		///
 		/// The schedule must have a greater version than the stored schedule.
@@ -476,15 +570,27 @@ decl_module! {
 			origin,
 			code: Vec<u8>
 		) -> DispatchResult {
-			ensure_signed(origin)?;
+			let origin = ensure_signed(origin)?;
 			let schedule = <Module<T>>::current_schedule();
-			let result = wasm::save_code::<T>(code, &schedule);
+			let result = wasm::save_code::<T>(code, &schedule, &origin);
 			if let Ok(code_hash) = result {
 				Self::deposit_event(RawEvent::CodeStored(code_hash));
 			}
 			result.map(|_| ()).map_err(Into::into)
 		}
 
+		/// Removes the code stored under `code_hash` and refunds the deposit paid for it.
+		///
+		/// Only the account which stored the code can remove it and only once no contract uses
+		/// the code anymore.
+		#[weight = T::DbWeight::get().reads_writes(1, 4)]
+		pub fn remove_code(origin, code_hash: CodeHash<T>) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			wasm::remove_code::<T>(&origin, &code_hash)?;
+			Self::deposit_event(RawEvent::CodeRemoved(code_hash));
+			Ok(())
+		}
+
 		/// Makes a call to an account, optionally transferring some balance.
 		///
 		/// * If the account is a smart-contract account, the associated code will be
@@ -492,8 +598,18 @@ decl_module! {
 		/// * If the account is a regular account, any value will be transferred.
 		/// * If no account exists and the call value is not less than `existential_deposit`,
 		/// a regular account will be created and any value will be transferred.
+		///
+		/// The `storage_deposit_limit` is the maximum storage deposit the origin is willing to
+		/// pay for this call. `None` means no limit. It is ignored unless the storage is paid with
+		/// deposits.
 		#[weight = FunctionOf(
-			|args: (&<T::Lookup as StaticLookup>::Source, &BalanceOf<T>, &Weight, &Vec<u8>)| *args.2,
+			|args: (
+				&<T::Lookup as StaticLookup>::Source,
+				&BalanceOf<T>,
+				&Weight,
+				&Option<BalanceOf<T>>,
+				&Vec<u8>,
+			)| *args.2,
 			DispatchClass::Normal,
 			Pays::Yes
 		)]
@@ -502,15 +618,20 @@ decl_module! {
 			dest: <T::Lookup as StaticLookup>::Source,
 			#[compact] value: BalanceOf<T>,
 			#[compact] gas_limit: Gas,
+			storage_deposit_limit: Option<BalanceOf<T>>,
 			data: Vec<u8>
 		) -> DispatchResultWithPostInfo {
 			let origin = ensure_signed(origin)?;
 			let dest = T::Lookup::lookup(dest)?;
 			let mut gas_meter = GasMeter::new(gas_limit);
 
-			let result = Self::execute_wasm(origin, &mut gas_meter, |ctx, gas_meter| {
-				ctx.call(dest, value, gas_meter, data)
-			});
+			let result = Self::execute_wasm(
+				origin,
+				&mut gas_meter,
+				storage_deposit_limit,
+				None,
+				|ctx, gas_meter| ctx.call(dest, value, gas_meter, data),
+			);
 			gas_meter.into_dispatch_result(result.map_err(|e| e.reason))
 		}
 
@@ -524,8 +645,10 @@ decl_module! {
 		///   after the execution is saved as the `code` of the account. That code will be invoked
 		///   upon any call received by this account.
 		/// - The contract is initialized.
+		///
+		/// See `call` for the meaning of `storage_deposit_limit`.
 		#[weight = FunctionOf(
-			|args: (&BalanceOf<T>, &Weight, &CodeHash<T>, &Vec<u8>)| *args.1,
+			|args: (&BalanceOf<T>, &Weight, &Option<BalanceOf<T>>, &CodeHash<T>, &Vec<u8>)| *args.1,
 			DispatchClass::Normal,
 			Pays::Yes
 		)]
@@ -533,16 +656,21 @@ decl_module! {
 			origin,
 			#[compact] endowment: BalanceOf<T>,
 			#[compact] gas_limit: Gas,
+			storage_deposit_limit: Option<BalanceOf<T>>,
 			code_hash: CodeHash<T>,
 			data: Vec<u8>
 		) -> DispatchResultWithPostInfo {
 			let origin = ensure_signed(origin)?;
 			let mut gas_meter = GasMeter::new(gas_limit);
 
-			let result = Self::execute_wasm(origin, &mut gas_meter, |ctx, gas_meter| {
-				ctx.instantiate(endowment, gas_meter, &code_hash, data)
-					.map(|(_address, output)| output)
-			});
+			let result = Self::execute_wasm(
+				origin,
+				&mut gas_meter,
+				storage_deposit_limit,
+				None,
+				|ctx, gas_meter| ctx.instantiate(endowment, gas_meter, &code_hash, data)
+					.map(|(_address, output)| output),
+			);
 			gas_meter.into_dispatch_result(result.map_err(|e| e.reason))
 		}
 
@@ -587,19 +715,57 @@ impl<T: Trait> Module<T> {
 	///
 	/// This function is similar to `Self::call`, but doesn't perform any address lookups and better
 	/// suitable for calling directly from Rust.
+	///
+	/// If `debug` is `true` the messages printed by contracts are collected into the debug
+	/// message of the result instead of being printed to the log. This is meant to be used
+	/// by RPC dry-runs only.
 	pub fn bare_call(
 		origin: T::AccountId,
 		dest: T::AccountId,
 		value: BalanceOf<T>,
 		gas_limit: Gas,
 		input_data: Vec<u8>,
-	) -> ExecResult {
-		let mut gas_meter = GasMeter::new(gas_limit);
-		Self::execute_wasm(origin, &mut gas_meter, |ctx, gas_meter| {
+		debug: bool,
+	) -> ContractResult<ExecResult> {
+		Self::dry_run(origin, gas_limit, debug, |ctx, gas_meter| {
 			ctx.call(dest, value, gas_meter, input_data)
 		})
 	}
 
+	/// Instantiate a new contract.
+	///
+	/// This function is similar to `Self::instantiate`, but returns the address of the new
+	/// contract and the output of its constructor. It is better suitable for calling directly
+	/// from Rust.
+	///
+	/// See [`Self::bare_call`] for the meaning of `debug`.
+	pub fn bare_instantiate(
+		origin: T::AccountId,
+		endowment: BalanceOf<T>,
+		gas_limit: Gas,
+		code_hash: CodeHash<T>,
+		data: Vec<u8>,
+		debug: bool,
+	) -> ContractResult<Result<(T::AccountId, ExecReturnValue), ExecError>> {
+		let mut address = None;
+		let result = Self::dry_run(origin, gas_limit, debug, |ctx, gas_meter| {
+			ctx.instantiate(endowment, gas_meter, &code_hash, data)
+				.map(|(account_id, output)| {
+					address = Some(account_id);
+					output
+				})
+		});
+		ContractResult {
+			gas_consumed: result.gas_consumed,
+			events: result.events,
+			debug_message: result.debug_message,
+			result: result.result.map(|output| (
+				address.expect("`address` is set whenever the instantiation succeeds; qed"),
+				output,
+			)),
+		}
+	}
+
 	/// Query storage of a specified contract under a specified key.
 	pub fn get_storage(
 		address: T::AccountId,
@@ -631,21 +797,67 @@ impl<T: Trait> Module<T> {
 		<Module<T>>::current_schedule().put_code_per_byte_cost.saturating_mul(code.len() as Gas)
 	}
 
+	/// Execute `func` like `execute_wasm` and collect the auxiliary information returned by the
+	/// dry-run RPCs.
+	fn dry_run(
+		origin: T::AccountId,
+		gas_limit: Gas,
+		debug: bool,
+		func: impl FnOnce(&mut ExecutionContext<T, WasmVm, WasmLoader>, &mut GasMeter<T>) -> ExecResult
+	) -> ContractResult<ExecResult> {
+		let mut gas_meter = GasMeter::new(gas_limit);
+		let debug_message = RefCell::new(Vec::new());
+		let event_count = <frame_system::Module<T>>::event_count();
+		let result = Self::execute_wasm(
+			origin,
+			&mut gas_meter,
+			None,
+			if debug { Some(&debug_message) } else { None },
+			func,
+		);
+		let events = <frame_system::Module<T>>::events()
+			.into_iter()
+			.skip(event_count as usize)
+			.map(|record| record.event.encode())
+			.collect();
+		ContractResult {
+			gas_consumed: gas_limit.saturating_sub(gas_meter.gas_left()),
+			events,
+			debug_message: debug_message.into_inner(),
+			result,
+		}
+	}
+
 	fn execute_wasm(
 		origin: T::AccountId,
 		gas_meter: &mut GasMeter<T>,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+		debug_message: Option<&RefCell<Vec<u8>>>,
 		func: impl FnOnce(&mut ExecutionContext<T, WasmVm, WasmLoader>, &mut GasMeter<T>) -> ExecResult
 	) -> ExecResult {
 		let cfg = Config::preload();
 		let vm = WasmVm::new(&cfg.schedule);
 		let loader = WasmLoader::new(&cfg.schedule);
 		let mut ctx = ExecutionContext::top_level(origin.clone(), &cfg, &vm, &loader);
+		ctx.debug_message = debug_message;
 
-		let result = func(&mut ctx, gas_meter);
+		let mut result = func(&mut ctx, gas_meter);
 
 		if result.as_ref().map(|output| output.is_success()).unwrap_or(false) {
-			// Commit all changes that made it thus far into the persistent storage.
-			DirectAccountDb.commit(ctx.overlay.into_change_set());
+			match storage_deposit::charge::<T>(
+				&origin,
+				&mut ctx.overlay,
+				&mut ctx.deferred,
+				storage_deposit_limit,
+			) {
+				// Commit all changes that made it thus far into the persistent storage.
+				Ok(()) => DirectAccountDb.commit(ctx.overlay.into_change_set()),
+				// The whole execution is reverted if the storage deposit can't be paid.
+				Err(reason) => {
+					ctx.deferred.clear();
+					result = Err(ExecError { reason, buffer: Vec::new() });
+				}
+			}
 		}
 
 		// Execute deferred actions.
@@ -761,9 +973,14 @@ impl<T: Trait> Module<T> {
 			.sum::<u32>();
 
 		<ContractInfoOf<T>>::remove(&origin);
+		wasm::decrement_code_refcount::<T>(&origin_contract.code_hash);
+		wasm::increment_code_refcount::<T>(&code_hash);
 		<ContractInfoOf<T>>::insert(&dest, ContractInfo::Alive(RawAliveContractInfo {
 			trie_id: origin_contract.trie_id,
 			storage_size: origin_contract.storage_size,
+			storage_items: origin_contract.storage_items
+				.saturating_sub(key_values_taken.len() as u32),
+			storage_deposit: origin_contract.storage_deposit,
 			code_hash,
 			rent_allowance,
 			deduct_block: current_block,
@@ -776,6 +993,75 @@ impl<T: Trait> Module<T> {
 
 		Ok(())
 	}
+
+	/// Migrate the contract infos to the layout which tracks the storage items and deposit.
+	///
+	/// No deposit is held for the storage of a contract before the migration, and the items stored
+	/// before are not counted. The next execution changing the storage of the contract is charged
+	/// for all of its bytes.
+	fn migrate_to_storage_deposit() -> Weight {
+		#[derive(Decode)]
+		struct OldAliveContractInfo<CodeHash, Balance, BlockNumber> {
+			trie_id: TrieId,
+			storage_size: u32,
+			code_hash: CodeHash,
+			rent_allowance: Balance,
+			deduct_block: BlockNumber,
+			last_write: Option<BlockNumber>,
+		}
+
+		#[derive(Decode)]
+		enum OldContractInfo<T: Trait> {
+			Alive(OldAliveContractInfo<CodeHash<T>, BalanceOf<T>, T::BlockNumber>),
+			Tombstone(TombstoneContractInfo<T>),
+		}
+
+		let translated = sp_std::cell::Cell::new(0 as Weight);
+		<ContractInfoOf<T>>::translate::<OldContractInfo<T>, _>(|_, info| {
+			translated.set(translated.get() + 1);
+			Some(match info {
+				OldContractInfo::Alive(alive) => ContractInfo::Alive(RawAliveContractInfo {
+					trie_id: alive.trie_id,
+					storage_size: alive.storage_size,
+					storage_items: 0,
+					storage_deposit: Zero::zero(),
+					code_hash: alive.code_hash,
+					rent_allowance: alive.rent_allowance,
+					deduct_block: alive.deduct_block,
+					last_write: alive.last_write,
+				}),
+				OldContractInfo::Tombstone(tombstone) => ContractInfo::Tombstone(tombstone),
+			})
+		});
+
+		T::DbWeight::get().reads_writes(translated.get(), translated.get())
+	}
+
+	/// Create the info of every stored code and count the alive contracts using it.
+	///
+	/// The code stored before has no owner afterwards and can't be removed.
+	fn migrate_to_code_refcount() -> Weight {
+		let mut reads: Weight = 0;
+		let mut writes: Weight = 0;
+		for (code_hash, _) in <PristineCode<T>>::iter() {
+			reads += 1;
+			writes += 1;
+			<CodeInfoOf<T>>::insert(code_hash, CodeInfo::<T> {
+				owner: None,
+				deposit: Zero::zero(),
+				refcount: 0,
+			});
+		}
+		for (_, info) in <ContractInfoOf<T>>::iter() {
+			reads += 1;
+			if let ContractInfo::Alive(alive) = info {
+				writes += 1;
+				wasm::increment_code_refcount::<T>(&alive.code_hash);
+			}
+		}
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
 }
 
 decl_event! {
@@ -813,6 +1099,9 @@ decl_event! {
 		/// Code with the specified hash has been stored.
 		CodeStored(Hash),
 
+		/// Code with the specified hash has been removed.
+		CodeRemoved(Hash),
+
 		/// Triggered when the current schedule is updated.
 		ScheduleUpdated(u32),
 
@@ -822,6 +1111,24 @@ decl_event! {
 
 		/// An event deposited upon execution of a contract from the account.
 		ContractExecution(AccountId, Vec<u8>),
+
+		/// A storage deposit was paid for the storage added to a contract.
+		///
+		/// # Params
+		///
+		/// - `payer`: `AccountId`: The origin of the execution which paid the deposit.
+		/// - `contract`: `AccountId`: The contract which holds the deposit.
+		/// - `amount`: `Balance`: The paid deposit.
+		StorageDepositCharged(AccountId, AccountId, Balance),
+
+		/// A storage deposit was refunded for the storage freed from a contract.
+		///
+		/// # Params
+		///
+		/// - `contract`: `AccountId`: The contract which held the deposit.
+		/// - `payee`: `AccountId`: The origin of the execution which received the refund.
+		/// - `amount`: `Balance`: The refunded deposit.
+		StorageDepositRefunded(AccountId, AccountId, Balance),
 	}
 }
 
@@ -837,6 +1144,8 @@ decl_storage! {
 		pub AccountCounter: u64 = 0;
 		/// The code associated with a given account.
 		pub ContractInfoOf: map hasher(twox_64_concat) T::AccountId => Option<ContractInfo<T>>;
+		/// The owner, deposit and reference count of stored code.
+		pub CodeInfoOf: map hasher(identity) CodeHash<T> => Option<CodeInfo<T>>;
 	}
 }
 
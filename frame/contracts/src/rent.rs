@@ -18,8 +18,9 @@
 
 use crate::{
 	AliveContractInfo, BalanceOf, ContractInfo, ContractInfoOf, Module, RawEvent,
-	TombstoneContractInfo, Trait,
+	StorageModel, TombstoneContractInfo, Trait,
 };
+use crate::wasm::decrement_code_refcount;
 use frame_support::storage::child;
 use frame_support::traits::{Currency, ExistenceRequirement, Get, OnUnbalanced, WithdrawReason};
 use frame_support::StorageMap;
@@ -140,6 +141,11 @@ fn consider_case<T: Trait>(
 	handicap: T::BlockNumber,
 	contract: &AliveContractInfo<T>,
 ) -> Verdict<T> {
+	if T::StorageModel::get() == StorageModel::Deposit {
+		// The storage is paid with deposits, no rent is due.
+		return Verdict::Exempt;
+	}
+
 	// How much block has passed since the last deduction for the contract.
 	let blocks_passed = {
 		// Calculate an effective block number, i.e. after adjusting for handicap.
@@ -225,6 +231,7 @@ fn enact_verdict<T: Trait>(
 			child::kill_storage(
 				&alive_contract_info.child_trie_info(),
 			);
+			decrement_code_refcount::<T>(&alive_contract_info.code_hash);
 			<Module<T>>::deposit_event(RawEvent::Evicted(account.clone(), false));
 			None
 		}
@@ -248,6 +255,9 @@ fn enact_verdict<T: Trait>(
 			child::kill_storage(
 				&alive_contract_info.child_trie_info(),
 			);
+			// Tombstones don't use the code. It has to be stored again in order to restore
+			// the contract if it was removed in the meantime.
+			decrement_code_refcount::<T>(&alive_contract_info.code_hash);
 
 			<Module<T>>::deposit_event(RawEvent::Evicted(account.clone(), true));
 			Some(tombstone_info)
@@ -345,6 +355,10 @@ pub fn compute_rent_projection<T: Trait>(
 		None | Some(ContractInfo::Tombstone(_)) => return Err(ContractAccessError::IsTombstone),
 		Some(ContractInfo::Alive(contract)) => contract,
 	};
+	if T::StorageModel::get() == StorageModel::Deposit {
+		// Contracts are never evicted when the storage is paid with deposits.
+		return Ok(RentProjection::NoEviction);
+	}
 	let current_block_number = <frame_system::Module<T>>::block_number();
 	let verdict = consider_case::<T>(
 		account,
@@ -0,0 +1,108 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate. If not, see <http://www.gnu.org/licenses/>.
+
+//! A module responsible for charging and refunding storage deposits.
+//!
+//! Only used with [`StorageModel::Deposit`]. After a successful execution the deposit required
+//! by every contract whose storage was changed is computed from its storage footprint. The
+//! difference to the deposit already held by the contract is transferred from the origin of the
+//! execution to the contract or refunded from the contract to the origin.
+
+use crate::{BalanceOf, Error, RawEvent, StorageModel, Trait};
+use crate::account_db::{AccountDb, OverlayAccountDb};
+use crate::exec::DeferredAction;
+use frame_support::traits::{Currency, Get};
+use sp_runtime::DispatchError;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::prelude::*;
+
+/// The deposit required for a storage of `storage_size` octets holding `storage_items` items.
+fn required_deposit<T: Trait>(storage_size: u32, storage_items: u32) -> BalanceOf<T> {
+	T::DepositPerByte::get().saturating_mul(storage_size.into())
+		.saturating_add(T::DepositPerItem::get().saturating_mul(storage_items.into()))
+}
+
+/// Charge or refund the storage deposits for all changes made in `overlay` by an execution of
+/// `origin`.
+///
+/// The transfers are performed in the `overlay` and the events are pushed to `deferred`, so
+/// nothing happens unless the `overlay` is committed afterwards. Returns an error if the net
+/// deposit charged exceeds `limit` or if `origin` can't afford it.
+///
+/// Does nothing if the storage is not paid with deposits.
+pub fn charge<T: Trait>(
+	origin: &T::AccountId,
+	overlay: &mut OverlayAccountDb<T>,
+	deferred: &mut Vec<DeferredAction<T>>,
+	limit: Option<BalanceOf<T>>,
+) -> Result<(), DispatchError> {
+	if T::StorageModel::get() != StorageModel::Deposit {
+		return Ok(());
+	}
+
+	let mut charged = <BalanceOf<T>>::zero();
+	let mut refunded = <BalanceOf<T>>::zero();
+	let mut changes = Vec::new();
+	for footprint in overlay.storage_footprints() {
+		let required = required_deposit::<T>(footprint.storage_size, footprint.storage_items);
+		if required > footprint.storage_deposit {
+			charged = charged.saturating_add(required - footprint.storage_deposit);
+		} else if required < footprint.storage_deposit {
+			let refund = (footprint.storage_deposit - required)
+				.min(overlay.get_balance(&footprint.account));
+			refunded = refunded.saturating_add(refund);
+		} else {
+			continue;
+		}
+		changes.push((footprint.account, footprint.storage_deposit, required));
+	}
+
+	if limit.map(|limit| charged.saturating_sub(refunded) > limit).unwrap_or(false) {
+		return Err(Error::<T>::StorageDepositLimitExhausted.into());
+	}
+
+	// The origin must stay alive after paying the deposit.
+	if !charged.is_zero() &&
+		overlay.get_balance(origin).saturating_add(refunded) <
+			charged.saturating_add(T::Currency::minimum_balance())
+	{
+		return Err(Error::<T>::StorageDepositNotAffordable.into());
+	}
+
+	for (contract, old_deposit, new_deposit) in changes {
+		let origin_balance = overlay.get_balance(origin);
+		let contract_balance = overlay.get_balance(&contract);
+		let event = if new_deposit > old_deposit {
+			let amount = new_deposit - old_deposit;
+			overlay.set_balance(origin, origin_balance.saturating_sub(amount));
+			overlay.set_balance(&contract, contract_balance.saturating_add(amount));
+			RawEvent::StorageDepositCharged(origin.clone(), contract.clone(), amount)
+		} else {
+			// The contract can't refund more than it holds.
+			let amount = (old_deposit - new_deposit).min(contract_balance);
+			overlay.set_balance(&contract, contract_balance - amount);
+			overlay.set_balance(origin, origin_balance.saturating_add(amount));
+			RawEvent::StorageDepositRefunded(contract.clone(), origin.clone(), amount)
+		};
+		overlay.set_storage_deposit(&contract, new_deposit);
+		deferred.push(DeferredAction::DepositEvent {
+			event,
+			topics: Vec::new(),
+		});
+	}
+
+	Ok(())
+}
@@ -22,21 +22,23 @@
 use crate::{
 	BalanceOf, ComputeDispatchFee, ContractAddressFor, ContractInfo, ContractInfoOf, GenesisConfig,
 	Module, RawAliveContractInfo, RawEvent, Trait, TrieId, TrieIdFromParentCounter, Schedule,
-	TrieIdGenerator, account_db::{AccountDb, DirectAccountDb, OverlayAccountDb},
-	gas::Gas,
+	TrieIdGenerator, StorageModel, Error, CodeInfoOf, RawCodeInfo, CodeStorage, PristineCode,
+	account_db::{AccountDb, DirectAccountDb, OverlayAccountDb},
+	gas::Gas, chain_extension::{self, ChainExtension, Environment, Ext},
 };
 use assert_matches::assert_matches;
 use hex_literal::*;
 use codec::{Decode, Encode, KeyedVec};
 use sp_runtime::{
-	Perbill, BuildStorage, transaction_validity::{InvalidTransaction, ValidTransaction},
+	Perbill, BuildStorage, DispatchError, transaction_validity::{InvalidTransaction, ValidTransaction},
 	traits::{BlakeTwo256, Hash, IdentityLookup, SignedExtension, Convert},
 	testing::{Digest, DigestItem, Header, UintAuthorityId, H256},
 };
 use frame_support::{
-	assert_ok, assert_err, assert_err_ignore_postinfo, impl_outer_dispatch, impl_outer_event,
-	impl_outer_origin, parameter_types,
-	storage::child, StorageMap, StorageValue, traits::{Currency, Get},
+	assert_ok, assert_err, assert_err_ignore_postinfo, assert_noop, impl_outer_dispatch,
+	impl_outer_event, impl_outer_origin, parameter_types,
+	storage::child, StorageMap, StorageValue,
+	traits::{Currency, Get, OnRuntimeUpgrade, PalletVersion},
 	weights::{DispatchInfo, DispatchClass, Weight, PostDispatchInfo, Pays},
 };
 use std::{cell::RefCell, sync::atomic::{AtomicUsize, Ordering}};
@@ -72,6 +74,8 @@ impl_outer_dispatch! {
 
 thread_local! {
 	static EXISTENTIAL_DEPOSIT: RefCell<u64> = RefCell::new(0);
+	static STORAGE_MODEL: RefCell<StorageModel> = RefCell::new(StorageModel::Rent);
+	static CODE_DEPOSIT_PER_BYTE: RefCell<u64> = RefCell::new(0);
 }
 
 pub struct ExistentialDeposit;
@@ -79,6 +83,16 @@ impl Get<u64> for ExistentialDeposit {
 	fn get() -> u64 { EXISTENTIAL_DEPOSIT.with(|v| *v.borrow()) }
 }
 
+pub struct TestStorageModel;
+impl Get<StorageModel> for TestStorageModel {
+	fn get() -> StorageModel { STORAGE_MODEL.with(|v| *v.borrow()) }
+}
+
+pub struct CodeDepositPerByte;
+impl Get<u64> for CodeDepositPerByte {
+	fn get() -> u64 { CODE_DEPOSIT_PER_BYTE.with(|v| *v.borrow()) }
+}
+
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct Test;
 parameter_types! {
@@ -88,6 +102,7 @@ parameter_types! {
 	pub const AvailableBlockRatio: Perbill = Perbill::one();
 }
 impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
 	type Origin = Origin;
 	type Index = u64;
 	type BlockNumber = u64;
@@ -135,6 +150,8 @@ parameter_types! {
 	pub const SurchargeReward: u64 = 150;
 	pub const MaxDepth: u32 = 100;
 	pub const MaxValueSize: u32 = 16_384;
+	pub const DepositPerByte: u64 = 2;
+	pub const DepositPerItem: u64 = 100;
 }
 
 parameter_types! {
@@ -171,6 +188,11 @@ impl Trait for Test {
 	type SurchargeReward = SurchargeReward;
 	type MaxDepth = MaxDepth;
 	type MaxValueSize = MaxValueSize;
+	type ChainExtension = TestExtension;
+	type StorageModel = TestStorageModel;
+	type DepositPerByte = DepositPerByte;
+	type DepositPerItem = DepositPerItem;
+	type CodeDepositPerByte = CodeDepositPerByte;
 }
 
 type Balances = pallet_balances::Module<Test>;
@@ -203,6 +225,31 @@ impl TrieIdGenerator<u64> for DummyTrieIdGenerator {
 	}
 }
 
+/// A chain extension which offers two functions to the contracts under test:
+///
+/// - `0`: copies its input to the output and returns the length of the input.
+/// - `1`: charges the weight given as input and returns `0`.
+///
+/// Calling any other function fails.
+pub struct TestExtension;
+impl ChainExtension for TestExtension {
+	fn call<E: Ext>(func_id: u32, mut env: Environment<E>) -> chain_extension::Result<u32> {
+		match func_id {
+			0 => {
+				let input = env.read(env.in_len())?;
+				env.write(&input)?;
+				Ok(input.len() as u32)
+			},
+			1 => {
+				let weight: Weight = env.read_as()?;
+				env.charge_weight(weight)?;
+				Ok(0)
+			},
+			_ => Err(DispatchError::Other("unknown chain extension function")),
+		}
+	}
+}
+
 pub struct DummyComputeDispatchFee;
 impl ComputeDispatchFee<Call, u64> for DummyComputeDispatchFee {
 	fn compute_dispatch_fee(call: &Call) -> u64 {
@@ -219,11 +266,15 @@ const GAS_LIMIT: Gas = 10_000_000_000;
 
 pub struct ExtBuilder {
 	existential_deposit: u64,
+	storage_model: StorageModel,
+	code_deposit_per_byte: u64,
 }
 impl Default for ExtBuilder {
 	fn default() -> Self {
 		Self {
 			existential_deposit: 1,
+			storage_model: StorageModel::Rent,
+			code_deposit_per_byte: 0,
 		}
 	}
 }
@@ -232,8 +283,18 @@ impl ExtBuilder {
 		self.existential_deposit = existential_deposit;
 		self
 	}
+	pub fn storage_model(mut self, storage_model: StorageModel) -> Self {
+		self.storage_model = storage_model;
+		self
+	}
+	pub fn code_deposit_per_byte(mut self, code_deposit_per_byte: u64) -> Self {
+		self.code_deposit_per_byte = code_deposit_per_byte;
+		self
+	}
 	pub fn set_associated_consts(&self) {
 		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = self.existential_deposit);
+		STORAGE_MODEL.with(|v| *v.borrow_mut() = self.storage_model);
+		CODE_DEPOSIT_PER_BYTE.with(|v| *v.borrow_mut() = self.code_deposit_per_byte);
 	}
 	pub fn build(self) -> sp_io::TestExternalities {
 		self.set_associated_consts();
@@ -271,7 +332,7 @@ fn returns_base_call_cost() {
 		Balances::deposit_creating(&ALICE, 100_000_000);
 
 		assert_eq!(
-			Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, Vec::new()),
+			Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, Vec::new()),
 			Ok(
 				PostDispatchInfo {
 					actual_weight: Some(67500000),
@@ -295,6 +356,8 @@ fn account_removal_does_not_remove_storage() {
 			ContractInfoOf::<Test>::insert(1, &ContractInfo::Alive(RawAliveContractInfo {
 				trie_id: trie_id1.clone(),
 				storage_size: <Test as Trait>::StorageSizeOffset::get(),
+				storage_items: 0,
+				storage_deposit: 0,
 				deduct_block: System::block_number(),
 				code_hash: H256::repeat_byte(1),
 				rent_allowance: 40,
@@ -310,6 +373,8 @@ fn account_removal_does_not_remove_storage() {
 			ContractInfoOf::<Test>::insert(2, &ContractInfo::Alive(RawAliveContractInfo {
 				trie_id: trie_id2.clone(),
 				storage_size: <Test as Trait>::StorageSizeOffset::get(),
+				storage_items: 0,
+				storage_deposit: 0,
 				deduct_block: System::block_number(),
 				code_hash: H256::repeat_byte(2),
 				rent_allowance: 40,
@@ -369,6 +434,7 @@ fn instantiate_and_call_and_deposit_event() {
 			Origin::signed(ALICE),
 			100,
 			GAS_LIMIT,
+			None,
 			code_hash.into(),
 			vec![],
 		);
@@ -462,6 +528,7 @@ fn dispatch_call() {
 			Origin::signed(ALICE),
 			100,
 			GAS_LIMIT,
+			None,
 			code_hash.into(),
 			vec![],
 		));
@@ -471,6 +538,7 @@ fn dispatch_call() {
 			BOB, // newly created account
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 		));
 
@@ -583,6 +651,7 @@ fn dispatch_call_not_dispatched_after_top_level_transaction_failure() {
 			Origin::signed(ALICE),
 			100,
 			GAS_LIMIT,
+			None,
 			code_hash.into(),
 			vec![],
 		));
@@ -595,6 +664,7 @@ fn dispatch_call_not_dispatched_after_top_level_transaction_failure() {
 				BOB, // newly created account
 				0,
 				GAS_LIMIT,
+				None,
 				vec![],
 			),
 			"contract trapped during execution"
@@ -659,6 +729,7 @@ fn run_out_of_gas() {
 				Origin::signed(ALICE),
 				100,
 				GAS_LIMIT,
+				None,
 				code_hash.into(),
 				vec![],
 			));
@@ -671,6 +742,7 @@ fn run_out_of_gas() {
 					BOB, // newly created account
 					0,
 					67_500_000,
+					None,
 					vec![],
 				),
 				"ran out of gas during contract execution"
@@ -735,19 +807,23 @@ fn storage_size() {
 		assert_ok!(Contracts::instantiate(
 			Origin::signed(ALICE),
 			30_000,
-			GAS_LIMIT, code_hash.into(),
+			GAS_LIMIT, None, code_hash.into(),
 			<Test as pallet_balances::Trait>::Balance::from(1_000u32).encode() // rent allowance
 		));
 		let bob_contract = ContractInfoOf::<Test>::get(BOB).unwrap().get_alive().unwrap();
 		assert_eq!(bob_contract.storage_size, <Test as Trait>::StorageSizeOffset::get() + 4);
+		assert_eq!(bob_contract.storage_items, 1);
 
-		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, call::set_storage_4_byte()));
+		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, call::set_storage_4_byte()));
 		let bob_contract = ContractInfoOf::<Test>::get(BOB).unwrap().get_alive().unwrap();
 		assert_eq!(bob_contract.storage_size, <Test as Trait>::StorageSizeOffset::get() + 4 + 4);
+		assert_eq!(bob_contract.storage_items, 2);
 
-		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, call::remove_storage_4_byte()));
+		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, call::remove_storage_4_byte()));
 		let bob_contract = ContractInfoOf::<Test>::get(BOB).unwrap().get_alive().unwrap();
 		assert_eq!(bob_contract.storage_size, <Test as Trait>::StorageSizeOffset::get() + 4);
+		assert_eq!(bob_contract.storage_items, 1);
+		assert_eq!(bob_contract.storage_deposit, 0);
 	});
 }
 
@@ -772,7 +848,7 @@ fn deduct_blocks() {
 		assert_ok!(Contracts::instantiate(
 			Origin::signed(ALICE),
 			30_000,
-			GAS_LIMIT, code_hash.into(),
+			GAS_LIMIT, None, code_hash.into(),
 			<Test as pallet_balances::Trait>::Balance::from(1_000u32).encode() // rent allowance
 		));
 
@@ -784,7 +860,7 @@ fn deduct_blocks() {
 		initialize_block(5);
 
 		// Trigger rent through call
-		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, call::null()));
+		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, call::null()));
 
 		// Check result
 		let rent = (8 + 4 - 3) // storage size = size_offset + deploy_set_storage - deposit_offset
@@ -799,7 +875,7 @@ fn deduct_blocks() {
 		initialize_block(12);
 
 		// Trigger rent through call
-		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, call::null()));
+		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, call::null()));
 
 		// Check result
 		let rent_2 = (8 + 4 - 2) // storage size = size_offset + deploy_set_storage - deposit_offset
@@ -811,7 +887,7 @@ fn deduct_blocks() {
 		assert_eq!(Balances::free_balance(BOB), 30_000 - rent - rent_2);
 
 		// Second call on same block should have no effect on rent
-		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, call::null()));
+		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, call::null()));
 
 		let bob_contract = ContractInfoOf::<Test>::get(BOB).unwrap().get_alive().unwrap();
 		assert_eq!(bob_contract.rent_allowance, 1_000 - rent - rent_2);
@@ -824,7 +900,7 @@ fn deduct_blocks() {
 fn call_contract_removals() {
 	removals(|| {
 		// Call on already-removed account might fail, and this is fine.
-		Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, call::null());
+		Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, call::null());
 		true
 	});
 }
@@ -866,7 +942,7 @@ fn claim_surcharge(blocks: u64, trigger_call: impl Fn() -> bool, removes: bool)
 		assert_ok!(Contracts::instantiate(
 			Origin::signed(ALICE),
 			100,
-			GAS_LIMIT, code_hash.into(),
+			GAS_LIMIT, None, code_hash.into(),
 			<Test as pallet_balances::Trait>::Balance::from(1_000u32).encode() // rent allowance
 		));
 
@@ -899,7 +975,7 @@ fn removals(trigger_call: impl Fn() -> bool) {
 		assert_ok!(Contracts::instantiate(
 			Origin::signed(ALICE),
 			100,
-			GAS_LIMIT, code_hash.into(),
+			GAS_LIMIT, None, code_hash.into(),
 			<Test as pallet_balances::Trait>::Balance::from(1_000u32).encode() // rent allowance
 		));
 
@@ -935,7 +1011,7 @@ fn removals(trigger_call: impl Fn() -> bool) {
 		assert_ok!(Contracts::instantiate(
 			Origin::signed(ALICE),
 			1_000,
-			GAS_LIMIT, code_hash.into(),
+			GAS_LIMIT, None, code_hash.into(),
 			<Test as pallet_balances::Trait>::Balance::from(100u32).encode() // rent allowance
 		));
 
@@ -970,7 +1046,7 @@ fn removals(trigger_call: impl Fn() -> bool) {
 		assert_ok!(Contracts::instantiate(
 			Origin::signed(ALICE),
 			50+Balances::minimum_balance(),
-			GAS_LIMIT, code_hash.into(),
+			GAS_LIMIT, None, code_hash.into(),
 			<Test as pallet_balances::Trait>::Balance::from(1_000u32).encode() // rent allowance
 		));
 
@@ -980,7 +1056,7 @@ fn removals(trigger_call: impl Fn() -> bool) {
 		assert_eq!(Balances::free_balance(BOB), 50 + Balances::minimum_balance());
 
 		// Transfer funds
-		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, call::transfer()));
+		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, call::transfer()));
 		assert_eq!(ContractInfoOf::<Test>::get(BOB).unwrap().get_alive().unwrap().rent_allowance, 1_000);
 		assert_eq!(Balances::free_balance(BOB), Balances::minimum_balance());
 
@@ -1014,19 +1090,19 @@ fn call_removed_contract() {
 		assert_ok!(Contracts::instantiate(
 			Origin::signed(ALICE),
 			100,
-			GAS_LIMIT, code_hash.into(),
+			GAS_LIMIT, None, code_hash.into(),
 			<Test as pallet_balances::Trait>::Balance::from(1_000u32).encode() // rent allowance
 		));
 
 		// Calling contract should succeed.
-		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, call::null()));
+		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, call::null()));
 
 		// Advance blocks
 		initialize_block(10);
 
 		// Calling contract should remove contract and fail.
 		assert_err_ignore_postinfo!(
-			Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, call::null()),
+			Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, call::null()),
 			"contract has been evicted"
 		);
 		// Calling a contract that is about to evict shall emit an event.
@@ -1040,7 +1116,7 @@ fn call_removed_contract() {
 
 		// Subsequent contract calls should also fail.
 		assert_err_ignore_postinfo!(
-			Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, call::null()),
+			Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, call::null()),
 			"contract has been evicted"
 		);
 	})
@@ -1059,6 +1135,7 @@ fn default_rent_allowance_on_instantiate() {
 			Origin::signed(ALICE),
 			30_000,
 			GAS_LIMIT,
+			None,
 			code_hash.into(),
 			vec![],
 		));
@@ -1071,7 +1148,7 @@ fn default_rent_allowance_on_instantiate() {
 		initialize_block(5);
 
 		// Trigger rent through call
-		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, call::null()));
+		assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, call::null()));
 
 		// Check contract is still alive
 		let bob_contract = ContractInfoOf::<Test>::get(BOB).unwrap().get_alive();
@@ -1141,6 +1218,7 @@ fn restoration(test_different_storage: bool, test_restore_to_with_dirty_storage:
 			Origin::signed(ALICE),
 			30_000,
 			GAS_LIMIT,
+			None,
 			set_rent_code_hash.into(),
 			<Test as pallet_balances::Trait>::Balance::from(0u32).encode()
 		));
@@ -1154,6 +1232,7 @@ fn restoration(test_different_storage: bool, test_restore_to_with_dirty_storage:
 			assert_ok!(Contracts::call(
 				Origin::signed(ALICE),
 				BOB, 0, GAS_LIMIT,
+				None,
 				call::set_storage_4_byte())
 			);
 		}
@@ -1167,7 +1246,7 @@ fn restoration(test_different_storage: bool, test_restore_to_with_dirty_storage:
 		// Call `BOB`, which makes it pay rent. Since the rent allowance is set to 0
 		// we expect that it will get removed leaving tombstone.
 		assert_err_ignore_postinfo!(
-			Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, call::null()),
+			Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, call::null()),
 			"contract has been evicted"
 		);
 		assert!(ContractInfoOf::<Test>::get(BOB).unwrap().get_tombstone().is_some());
@@ -1190,6 +1269,7 @@ fn restoration(test_different_storage: bool, test_restore_to_with_dirty_storage:
 			Origin::signed(CHARLIE),
 			30_000,
 			GAS_LIMIT,
+			None,
 			restoration_code_hash.into(),
 			<Test as pallet_balances::Trait>::Balance::from(0u32).encode()
 		));
@@ -1210,6 +1290,7 @@ fn restoration(test_different_storage: bool, test_restore_to_with_dirty_storage:
 			DJANGO,
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 		));
 
@@ -1328,6 +1409,7 @@ fn storage_max_value_limit() {
 			Origin::signed(ALICE),
 			30_000,
 			GAS_LIMIT,
+			None,
 			code_hash.into(),
 			vec![],
 		));
@@ -1342,6 +1424,7 @@ fn storage_max_value_limit() {
 			BOB,
 			0,
 			GAS_LIMIT,
+			None,
 			Encode::encode(&self::MaxValueSize::get()),
 		));
 
@@ -1352,6 +1435,7 @@ fn storage_max_value_limit() {
 				BOB,
 				0,
 				GAS_LIMIT,
+				None,
 				Encode::encode(&(self::MaxValueSize::get() + 1)),
 			),
 			"contract trapped during execution"
@@ -1376,6 +1460,7 @@ fn deploy_and_call_other_contract() {
 			Origin::signed(ALICE),
 			100_000,
 			GAS_LIMIT,
+			None,
 			caller_code_hash.into(),
 			vec![],
 		));
@@ -1387,6 +1472,7 @@ fn deploy_and_call_other_contract() {
 			BOB,
 			0,
 			GAS_LIMIT,
+			None,
 			callee_code_hash.as_ref().to_vec(),
 		));
 	});
@@ -1404,6 +1490,7 @@ fn cannot_self_destruct_through_draning() {
 			Origin::signed(ALICE),
 			100_000,
 			GAS_LIMIT,
+			None,
 			code_hash.into(),
 			vec![],
 		));
@@ -1422,6 +1509,7 @@ fn cannot_self_destruct_through_draning() {
 				BOB,
 				0,
 				GAS_LIMIT,
+				None,
 				vec![],
 			),
 			"contract trapped during execution"
@@ -1442,6 +1530,7 @@ fn cannot_self_destruct_while_live() {
 			Origin::signed(ALICE),
 			100_000,
 			GAS_LIMIT,
+			None,
 			code_hash.into(),
 			vec![],
 		));
@@ -1460,6 +1549,7 @@ fn cannot_self_destruct_while_live() {
 				BOB,
 				0,
 				GAS_LIMIT,
+				None,
 				vec![0],
 			),
 			"contract trapped during execution"
@@ -1486,6 +1576,7 @@ fn self_destruct_works() {
 			Origin::signed(ALICE),
 			100_000,
 			GAS_LIMIT,
+			None,
 			code_hash.into(),
 			vec![],
 		));
@@ -1503,6 +1594,7 @@ fn self_destruct_works() {
 				BOB,
 				0,
 				GAS_LIMIT,
+				None,
 				vec![],
 			),
 			Ok(_)
@@ -1537,6 +1629,7 @@ fn destroy_contract_and_transfer_funds() {
 			Origin::signed(ALICE),
 			200_000,
 			GAS_LIMIT,
+			None,
 			caller_code_hash.into(),
 			callee_code_hash.as_ref().to_vec(),
 		));
@@ -1553,6 +1646,7 @@ fn destroy_contract_and_transfer_funds() {
 			BOB,
 			0,
 			GAS_LIMIT,
+			None,
 			CHARLIE.encode(),
 		));
 
@@ -1576,6 +1670,7 @@ fn cannot_self_destruct_in_constructor() {
 				Origin::signed(ALICE),
 				100_000,
 				GAS_LIMIT,
+				None,
 				code_hash.into(),
 				vec![],
 			),
@@ -1600,6 +1695,7 @@ fn get_runtime_storage() {
 			Origin::signed(ALICE),
 			100,
 			GAS_LIMIT,
+			None,
 			code_hash.into(),
 			vec![],
 		));
@@ -1608,6 +1704,7 @@ fn get_runtime_storage() {
 			BOB,
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 		));
 	});
@@ -1626,6 +1723,7 @@ fn crypto_hashes() {
 			Origin::signed(ALICE),
 			100_000,
 			GAS_LIMIT,
+			None,
 			code_hash.into(),
 			vec![],
 		));
@@ -1656,7 +1754,8 @@ fn crypto_hashes() {
 				0,
 				GAS_LIMIT,
 				params,
-			).unwrap();
+				false,
+			).result.unwrap();
 			assert_eq!(result.status, 0);
 			let expected = hash_fn(input.as_ref());
 			assert_eq!(&result.data[..*expected_size], &*expected);
@@ -1664,6 +1763,547 @@ fn crypto_hashes() {
 	})
 }
 
+#[test]
+fn chain_extension_works() {
+	let (wasm, code_hash) = compile_module::<Test>(&load_wasm("chain_extension.wat")).unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		Balances::deposit_creating(&ALICE, 1_000_000);
+		assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+		assert_ok!(Contracts::instantiate(
+			Origin::signed(ALICE),
+			100_000,
+			GAS_LIMIT,
+			None,
+			code_hash.into(),
+			vec![],
+		));
+
+		// Function 0 echoes its input and returns the input length.
+		let mut params = 0u32.encode();
+		params.extend_from_slice(b"_DEAD_BEEF");
+		let result = Contracts::bare_call(ALICE, BOB, 0, GAS_LIMIT, params, false).result.unwrap();
+		assert_eq!(result.status, 10);
+		assert_eq!(result.data, b"_DEAD_BEEF".to_vec());
+
+		// Function 1 charges the given weight.
+		let mut params = 1u32.encode();
+		params.extend_from_slice(&1_000u64.encode());
+		let result = Contracts::bare_call(ALICE, BOB, 0, GAS_LIMIT, params, false).result.unwrap();
+		assert_eq!(result.status, 0);
+
+		let mut params = 1u32.encode();
+		params.extend_from_slice(&Weight::max_value().encode());
+		let result = Contracts::bare_call(ALICE, BOB, 0, GAS_LIMIT, params, false).result;
+		assert_eq!(
+			result.unwrap_err().reason,
+			DispatchError::Other("ran out of gas during contract execution"),
+		);
+
+		// An error returned by the chain extension traps the contract.
+		let result = Contracts::bare_call(ALICE, BOB, 0, GAS_LIMIT, 2u32.encode(), false).result;
+		assert_eq!(
+			result.unwrap_err().reason,
+			DispatchError::Other("contract trapped during execution"),
+		);
+	});
+}
+
+#[test]
+fn dry_run_collects_debug_message_and_events() {
+	let (wasm, code_hash) = compile_module::<Test>(&load_wasm("debug_message.wat")).unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		Balances::deposit_creating(&ALICE, 1_000_000);
+		Balances::deposit_creating(&CHARLIE, 1_000_000);
+		assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+
+		// Without debugging the message is not collected.
+		let result = Contracts::bare_instantiate(
+			CHARLIE,
+			100_000,
+			GAS_LIMIT,
+			code_hash.into(),
+			vec![],
+			false,
+		);
+		assert_eq!(result.result.unwrap().0, DJANGO);
+		assert!(result.debug_message.is_empty());
+
+		let result = Contracts::bare_instantiate(
+			ALICE,
+			100_000,
+			GAS_LIMIT,
+			code_hash.into(),
+			vec![],
+			true,
+		);
+		let (address, output) = result.result.unwrap();
+		assert_eq!(address, BOB);
+		assert_eq!(output.status, 0);
+		assert_eq!(result.debug_message, b"Hello World!".to_vec());
+		assert!(result.gas_consumed > 0);
+		assert!(result.events.contains(
+			&MetaEvent::contracts(RawEvent::Instantiated(ALICE, BOB)).encode()
+		));
+
+		// The message is collected even though the call traps.
+		let result = Contracts::bare_call(ALICE, BOB, 0, GAS_LIMIT, vec![], true);
+		assert_eq!(
+			result.result.unwrap_err().reason,
+			DispatchError::Other("contract trapped during execution"),
+		);
+		assert_eq!(result.debug_message, b"Hello World!".to_vec());
+		assert!(result.events.is_empty());
+	});
+}
+
+/// The input of `storage_deposit.wat` which stores a value of `len` bytes under the key selected
+/// by `key`, or clears it if `len` is zero.
+fn storage_deposit_input(key: u32, len: u32) -> Vec<u8> {
+	(key, len).encode()
+}
+
+#[test]
+fn storage_deposit_charged_and_refunded() {
+	let (wasm, code_hash) = compile_module::<Test>(&load_wasm("storage_deposit.wat")).unwrap();
+
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.storage_model(StorageModel::Deposit)
+		.build()
+		.execute_with(|| {
+			Balances::deposit_creating(&ALICE, 1_000_000);
+			Balances::deposit_creating(&CHARLIE, 1_000);
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+
+			// The base deposit for `StorageSizeOffset` is charged on instantiation.
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(ALICE),
+				30_000,
+				GAS_LIMIT,
+				None,
+				code_hash.into(),
+				vec![],
+			));
+			let bob_contract = ContractInfoOf::<Test>::get(BOB).unwrap().get_alive().unwrap();
+			assert_eq!(bob_contract.storage_items, 0);
+			assert_eq!(bob_contract.storage_deposit, 16);
+			assert_eq!(Balances::free_balance(ALICE), 1_000_000 - 30_000 - 16);
+			assert_eq!(Balances::free_balance(BOB), 30_000 + 16);
+
+			// 10 bytes and 1 item are added.
+			assert_ok!(Contracts::call(
+				Origin::signed(ALICE),
+				BOB,
+				0,
+				GAS_LIMIT,
+				Some(120),
+				storage_deposit_input(1, 10),
+			));
+			let bob_contract = ContractInfoOf::<Test>::get(BOB).unwrap().get_alive().unwrap();
+			assert_eq!(bob_contract.storage_items, 1);
+			assert_eq!(bob_contract.storage_deposit, 136);
+			assert_eq!(Balances::free_balance(ALICE), 1_000_000 - 30_000 - 136);
+			assert_eq!(Balances::free_balance(BOB), 30_000 + 136);
+			assert!(System::events().iter().any(|record| record.event ==
+				MetaEvent::contracts(RawEvent::StorageDepositCharged(ALICE, BOB, 120))
+			));
+
+			// The storage deposit can't be transferred away by the contract.
+			assert_eq!(
+				Contracts::bare_call(BOB, CHARLIE, 30_000 + 136 - 50, GAS_LIMIT, vec![], false)
+					.result
+					.unwrap_err()
+					.reason,
+				DispatchError::Other("brings sender below existential deposit"),
+			);
+
+			// Freeing the storage refunds whoever frees it.
+			assert_ok!(Contracts::call(
+				Origin::signed(CHARLIE),
+				BOB,
+				0,
+				GAS_LIMIT,
+				None,
+				storage_deposit_input(1, 0),
+			));
+			let bob_contract = ContractInfoOf::<Test>::get(BOB).unwrap().get_alive().unwrap();
+			assert_eq!(bob_contract.storage_items, 0);
+			assert_eq!(bob_contract.storage_deposit, 16);
+			assert_eq!(Balances::free_balance(CHARLIE), 1_000 + 120);
+			assert_eq!(Balances::free_balance(BOB), 30_000 + 16);
+			assert!(System::events().iter().any(|record| record.event ==
+				MetaEvent::contracts(RawEvent::StorageDepositRefunded(BOB, CHARLIE, 120))
+			));
+		});
+}
+
+#[test]
+fn storage_deposit_limit_and_funds_are_enforced() {
+	let (wasm, code_hash) = compile_module::<Test>(&load_wasm("storage_deposit.wat")).unwrap();
+
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.storage_model(StorageModel::Deposit)
+		.build()
+		.execute_with(|| {
+			Balances::deposit_creating(&ALICE, 1_000_000);
+			Balances::deposit_creating(&CHARLIE, 150);
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(ALICE),
+				30_000,
+				GAS_LIMIT,
+				None,
+				code_hash.into(),
+				vec![],
+			));
+
+			// The call requires a deposit of 120 which exceeds the limit.
+			assert_err_ignore_postinfo!(
+				Contracts::call(
+					Origin::signed(ALICE),
+					BOB,
+					0,
+					GAS_LIMIT,
+					Some(119),
+					storage_deposit_input(1, 10),
+				),
+				Error::<Test>::StorageDepositLimitExhausted,
+			);
+
+			// Paying the deposit would bring CHARLIE below the existential deposit.
+			assert_err_ignore_postinfo!(
+				Contracts::call(
+					Origin::signed(CHARLIE),
+					BOB,
+					0,
+					GAS_LIMIT,
+					None,
+					storage_deposit_input(1, 10),
+				),
+				Error::<Test>::StorageDepositNotAffordable,
+			);
+
+			// Nothing was changed by the failed calls.
+			let bob_contract = ContractInfoOf::<Test>::get(BOB).unwrap().get_alive().unwrap();
+			assert_eq!(bob_contract.storage_items, 0);
+			assert_eq!(bob_contract.storage_deposit, 16);
+			assert_eq!(Balances::free_balance(CHARLIE), 150);
+		});
+}
+
+#[test]
+fn storage_deposit_contracts_are_not_evicted() {
+	let (wasm, code_hash) = compile_module::<Test>(&load_wasm("storage_deposit.wat")).unwrap();
+
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.storage_model(StorageModel::Deposit)
+		.build()
+		.execute_with(|| {
+			Balances::deposit_creating(&ALICE, 1_000_000);
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(ALICE),
+				100,
+				GAS_LIMIT,
+				None,
+				code_hash.into(),
+				vec![],
+			));
+			assert_ok!(Contracts::call(
+				Origin::signed(ALICE),
+				BOB,
+				0,
+				GAS_LIMIT,
+				None,
+				storage_deposit_input(1, 1_000),
+			));
+
+			// No rent is due, so the contract can't be evicted however long it exists.
+			initialize_block(1_000);
+			assert_eq!(Contracts::rent_projection(BOB), Ok(pallet_contracts_primitives::RentProjection::NoEviction));
+			assert_ok!(Contracts::claim_surcharge(Origin::NONE, BOB, Some(ALICE)));
+			let bob_contract = ContractInfoOf::<Test>::get(BOB).unwrap().get_alive().unwrap();
+			assert_eq!(bob_contract.deduct_block, 1);
+		});
+}
+
+#[test]
+fn storage_deposit_refund_is_limited_to_contract_balance() {
+	let (wasm, code_hash) = compile_module::<Test>(&load_wasm("storage_deposit.wat")).unwrap();
+
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.storage_model(StorageModel::Deposit)
+		.build()
+		.execute_with(|| {
+			Balances::deposit_creating(&ALICE, 1_000_000);
+			Balances::deposit_creating(&CHARLIE, 1_000);
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(ALICE),
+				30_000,
+				GAS_LIMIT,
+				None,
+				code_hash.into(),
+				vec![],
+			));
+			assert_ok!(Contracts::call(
+				Origin::signed(ALICE),
+				BOB,
+				0,
+				GAS_LIMIT,
+				None,
+				storage_deposit_input(1, 10),
+			));
+
+			// The contract holds less than the deposit it is due to refund.
+			Balances::make_free_balance_be(&BOB, 100);
+			let issuance = Balances::total_issuance();
+			assert_ok!(Contracts::call(
+				Origin::signed(CHARLIE),
+				BOB,
+				0,
+				GAS_LIMIT,
+				None,
+				storage_deposit_input(1, 0),
+			));
+			assert_eq!(Balances::free_balance(CHARLIE), 1_000 + 100);
+			assert_eq!(Balances::total_issuance(), issuance);
+			assert!(System::events().iter().any(|record| record.event ==
+				MetaEvent::contracts(RawEvent::StorageDepositRefunded(BOB, CHARLIE, 100))
+			));
+		});
+}
+
+#[test]
+fn storage_deposit_migration_holds_no_deposit() {
+	ExtBuilder::default()
+		.storage_model(StorageModel::Deposit)
+		.build()
+		.execute_with(|| {
+			// An alive contract in the layout used before storage deposits were introduced.
+			let old_info = (
+				0u8,
+				vec![1u8; 8],
+				1_000u32,
+				H256::repeat_byte(1),
+				500u64,
+				1u64,
+				None::<u64>,
+			);
+			frame_support::storage::unhashed::put_raw(
+				&ContractInfoOf::<Test>::hashed_key_for(BOB),
+				&old_info.encode(),
+			);
+			// Chains store the versions of their modules since genesis.
+			PalletVersion::new(2, 0, 0)
+				.put_into_storage::<<Test as frame_system::Trait>::ModuleToIndex, Contracts>();
+
+			Contracts::on_runtime_upgrade();
+
+			let bob_contract = ContractInfoOf::<Test>::get(BOB).unwrap().get_alive().unwrap();
+			assert_eq!(bob_contract.storage_size, 1_000);
+			assert_eq!(bob_contract.storage_items, 0);
+			assert_eq!(bob_contract.storage_deposit, 0);
+			assert_eq!(bob_contract.rent_allowance, 500);
+
+			// Nothing is migrated once the version introducing storage deposits is stored.
+			frame_support::storage::unhashed::put_raw(
+				&ContractInfoOf::<Test>::hashed_key_for(BOB),
+				&old_info.encode(),
+			);
+			PalletVersion::new(3, 0, 0)
+				.put_into_storage::<<Test as frame_system::Trait>::ModuleToIndex, Contracts>();
+
+			Contracts::on_runtime_upgrade();
+
+			assert_eq!(
+				frame_support::storage::unhashed::get_raw(&ContractInfoOf::<Test>::hashed_key_for(BOB)),
+				Some(old_info.encode()),
+			);
+		});
+}
+
+#[test]
+fn set_code_hash_and_remove_code() {
+	let (wasm, code_hash) = compile_module::<Test>(&load_wasm("set_code_hash.wat")).unwrap();
+	let (new_wasm, new_code_hash) =
+		compile_module::<Test>(&load_wasm("return_with_data.wat")).unwrap();
+
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.code_deposit_per_byte(1)
+		.build()
+		.execute_with(|| {
+			Balances::deposit_creating(&ALICE, 1_000_000);
+			Balances::deposit_creating(&CHARLIE, 1_000_000);
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm.clone()));
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), new_wasm.clone()));
+
+			// The deposit is charged once per code.
+			assert_ok!(Contracts::put_code(Origin::signed(CHARLIE), new_wasm.clone()));
+			assert_eq!(Balances::free_balance(CHARLIE), 1_000_000);
+			assert_eq!(
+				Balances::free_balance(ALICE),
+				1_000_000 - wasm.len() as u64 - new_wasm.len() as u64,
+			);
+			assert_eq!(Balances::reserved_balance(ALICE), wasm.len() as u64 + new_wasm.len() as u64);
+			assert_eq!(CodeInfoOf::<Test>::get(code_hash), Some(RawCodeInfo {
+				owner: Some(ALICE),
+				deposit: wasm.len() as u64,
+				refcount: 0,
+			}));
+
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(ALICE),
+				30_000,
+				GAS_LIMIT,
+				None,
+				code_hash.into(),
+				vec![],
+			));
+			assert_eq!(CodeInfoOf::<Test>::get(code_hash).unwrap().refcount, 1);
+
+			// Code can only be removed by its owner once no contract uses it.
+			assert_noop!(
+				Contracts::remove_code(Origin::signed(ALICE), code_hash),
+				Error::<Test>::CodeInUse,
+			);
+			assert_noop!(
+				Contracts::remove_code(Origin::signed(CHARLIE), new_code_hash),
+				Error::<Test>::NotCodeOwner,
+			);
+
+			// Setting a code hash which doesn't refer to stored code traps.
+			assert_err_ignore_postinfo!(
+				Contracts::call(
+					Origin::signed(ALICE),
+					BOB,
+					0,
+					GAS_LIMIT,
+					None,
+					H256::repeat_byte(0x11).encode(),
+				),
+				"contract trapped during execution"
+			);
+
+			// Upgrade the code of BOB.
+			assert_ok!(Contracts::call(
+				Origin::signed(ALICE),
+				BOB,
+				0,
+				GAS_LIMIT,
+				None,
+				new_code_hash.encode(),
+			));
+			let bob_contract = ContractInfoOf::<Test>::get(BOB).unwrap().get_alive().unwrap();
+			assert_eq!(bob_contract.code_hash, new_code_hash);
+			assert_eq!(CodeInfoOf::<Test>::get(code_hash).unwrap().refcount, 0);
+			assert_eq!(CodeInfoOf::<Test>::get(new_code_hash).unwrap().refcount, 1);
+
+			// The new code is executed from now on.
+			let input = vec![0, 0, 0, 0, 1, 2, 3];
+			let output = Contracts::bare_call(ALICE, BOB, 0, GAS_LIMIT, input, false).result.unwrap();
+			assert_eq!(output.data, vec![1, 2, 3]);
+
+			// The old code can be removed now and the deposit is refunded.
+			let balance = Balances::free_balance(ALICE);
+			assert_ok!(Contracts::remove_code(Origin::signed(ALICE), code_hash));
+			assert_eq!(Balances::free_balance(ALICE), balance + wasm.len() as u64);
+			assert_eq!(Balances::reserved_balance(ALICE), new_wasm.len() as u64);
+			assert!(!CodeInfoOf::<Test>::contains_key(code_hash));
+			assert!(!CodeStorage::<Test>::contains_key(code_hash));
+			assert!(!PristineCode::<Test>::contains_key(code_hash));
+			assert!(System::events().iter().any(|record| record.event ==
+				MetaEvent::contracts(RawEvent::CodeRemoved(code_hash))
+			));
+			assert_noop!(
+				Contracts::remove_code(Origin::signed(ALICE), code_hash),
+				Error::<Test>::CodeNotFound,
+			);
+		});
+}
+
+#[test]
+fn code_refcount_migration_creates_info_for_stored_code() {
+	let (wasm, code_hash) = compile_module::<Test>(&load_wasm("set_code_hash.wat")).unwrap();
+	let (unused_wasm, unused_code_hash) =
+		compile_module::<Test>(&load_wasm("return_with_data.wat")).unwrap();
+
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.build()
+		.execute_with(|| {
+			Balances::deposit_creating(&ALICE, 1_000_000);
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), unused_wasm));
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(ALICE),
+				30_000,
+				GAS_LIMIT,
+				None,
+				code_hash.into(),
+				vec![],
+			));
+
+			// Code stored before code was reference counted has no info.
+			CodeInfoOf::<Test>::remove(code_hash);
+			CodeInfoOf::<Test>::remove(unused_code_hash);
+
+			Contracts::migrate_to_code_refcount();
+
+			assert_eq!(CodeInfoOf::<Test>::get(code_hash), Some(RawCodeInfo {
+				owner: None,
+				deposit: 0,
+				refcount: 1,
+			}));
+			assert_eq!(CodeInfoOf::<Test>::get(unused_code_hash), Some(RawCodeInfo {
+				owner: None,
+				deposit: 0,
+				refcount: 0,
+			}));
+
+			// Code without an owner can't be removed.
+			assert_noop!(
+				Contracts::remove_code(Origin::signed(ALICE), unused_code_hash),
+				Error::<Test>::NotCodeOwner,
+			);
+		});
+}
+
+#[test]
+fn code_info_is_created_when_code_without_info_is_used() {
+	let (wasm, code_hash) = compile_module::<Test>(&load_wasm("set_code_hash.wat")).unwrap();
+
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.build()
+		.execute_with(|| {
+			Balances::deposit_creating(&ALICE, 1_000_000);
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			CodeInfoOf::<Test>::remove(code_hash);
+
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(ALICE),
+				30_000,
+				GAS_LIMIT,
+				None,
+				code_hash.into(),
+				vec![],
+			));
+
+			assert_eq!(CodeInfoOf::<Test>::get(code_hash), Some(RawCodeInfo {
+				owner: None,
+				deposit: 0,
+				refcount: 1,
+			}));
+		});
+}
+
 fn load_wasm(file_name: &str) -> String {
 	let path = ["tests/", file_name].concat();
 	std::fs::read_to_string(&path).expect(&format!("Unable to read {} file", path))
@@ -25,30 +25,100 @@
 //! - When we update the schedule we want it to have strictly greater version than the current saved one:
 //! this guarantees that every instrumented contract code in cache cannot have the version equal to the current one.
 //! Thus, before executing a contract it should be reinstrument with new schedule.
+//! - Stored code is reference counted by the alive contracts using it. The account which stored
+//! the code pays a deposit for it which is refunded when the code is removed. Code can only be
+//! removed by that account and only once no contract uses it anymore.
 
 use crate::wasm::{prepare, runtime::Env, PrefabWasmModule};
-use crate::{CodeHash, CodeStorage, PristineCode, Schedule, Trait};
+use crate::{
+	BalanceOf, CodeHash, CodeInfo, CodeInfoOf, CodeStorage, Error, PristineCode, Schedule, Trait,
+};
 use sp_std::prelude::*;
-use sp_runtime::traits::Hash;
+use sp_runtime::traits::{Hash, Saturating, Zero};
 use frame_support::StorageMap;
+use frame_support::traits::{Get, ReservableCurrency};
 
 /// Put code in the storage. The hash of code is used as a key and is returned
 /// as a result of this function.
 ///
-/// This function instruments the given code and caches it in the storage.
+/// This function instruments the given code and caches it in the storage. If the code is not
+/// stored yet, `owner` becomes its owner and pays `Trait::CodeDepositPerByte` for every byte
+/// of the original code.
 pub fn save<T: Trait>(
 	original_code: Vec<u8>,
 	schedule: &Schedule,
+	owner: &T::AccountId,
 ) -> Result<CodeHash<T>, &'static str> {
-	let prefab_module = prepare::prepare_contract::<Env>(&original_code, schedule)?;
+	let prefab_module = prepare::prepare_contract::<Env, T>(&original_code, schedule)?;
 	let code_hash = T::Hashing::hash(&original_code);
 
+	if !<CodeInfoOf<T>>::contains_key(&code_hash) {
+		let deposit = T::CodeDepositPerByte::get()
+			.saturating_mul((original_code.len() as u32).into());
+		// The deposit is reserved here and unreserved again when the code is removed.
+		T::Currency::reserve(owner, deposit)
+			.map_err(|_| "not enough funds to pay the code deposit")?;
+		<CodeInfoOf<T>>::insert(code_hash, CodeInfo::<T> {
+			owner: Some(owner.clone()),
+			deposit,
+			refcount: 0,
+		});
+	}
+
 	<CodeStorage<T>>::insert(code_hash, prefab_module);
 	<PristineCode<T>>::insert(code_hash, original_code);
 
 	Ok(code_hash)
 }
 
+/// Remove the code with the given code hash from the storage and refund its deposit.
+///
+/// Returns the refunded deposit. Fails if the code is not found, if `origin` is not the owner of
+/// the code or if the code is still used by a contract.
+pub fn remove<T: Trait>(
+	origin: &T::AccountId,
+	code_hash: &CodeHash<T>,
+) -> Result<BalanceOf<T>, Error<T>> {
+	let info = <CodeInfoOf<T>>::get(code_hash).ok_or(Error::<T>::CodeNotFound)?;
+	if info.owner.as_ref() != Some(origin) {
+		return Err(Error::<T>::NotCodeOwner);
+	}
+	if info.refcount != 0 {
+		return Err(Error::<T>::CodeInUse);
+	}
+
+	<CodeInfoOf<T>>::remove(code_hash);
+	<CodeStorage<T>>::remove(code_hash);
+	<PristineCode<T>>::remove(code_hash);
+	let remaining = T::Currency::unreserve(origin, info.deposit);
+
+	Ok(info.deposit.saturating_sub(remaining))
+}
+
+/// Increment the number of alive contracts using the code with the given code hash.
+///
+/// Code without an info, e.g. because it was stored before code was reference counted, gets an
+/// info without an owner, so that it can never be removed.
+pub fn increment_refcount<T: Trait>(code_hash: &CodeHash<T>) {
+	<CodeInfoOf<T>>::mutate(code_hash, |info| {
+		let info = info.get_or_insert_with(|| CodeInfo::<T> {
+			owner: None,
+			deposit: Zero::zero(),
+			refcount: 0,
+		});
+		info.refcount = info.refcount.saturating_add(1);
+	});
+}
+
+/// Decrement the number of alive contracts using the code with the given code hash.
+pub fn decrement_refcount<T: Trait>(code_hash: &CodeHash<T>) {
+	<CodeInfoOf<T>>::mutate(code_hash, |info| {
+		if let Some(info) = info {
+			info.refcount = info.refcount.saturating_sub(1);
+		}
+	});
+}
+
 /// Load code with the given code hash.
 ///
 /// If the module was instrumented with a lower version of schedule than
@@ -68,7 +138,7 @@ pub fn load<T: Trait>(
 		// We need to re-instrument the code with the latest schedule here.
 		let original_code =
 			<PristineCode<T>>::get(code_hash).ok_or_else(|| "pristine code is not found")?;
-		prefab_module = prepare::prepare_contract::<Env>(&original_code, schedule)?;
+		prefab_module = prepare::prepare_contract::<Env, T>(&original_code, schedule)?;
 		<CodeStorage<T>>::insert(&code_hash, &prefab_module);
 	}
 	Ok(prefab_module)
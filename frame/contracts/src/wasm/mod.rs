@@ -32,10 +32,15 @@ mod code_cache;
 mod prepare;
 mod runtime;
 
-use self::runtime::{to_execution_result, Runtime};
+use self::runtime::to_execution_result;
 use self::code_cache::load as load_code;
 
 pub use self::code_cache::save as save_code;
+pub use self::code_cache::remove as remove_code;
+pub use self::code_cache::{
+	increment_refcount as increment_code_refcount, decrement_refcount as decrement_code_refcount,
+};
+pub(crate) use self::runtime::Runtime;
 
 /// A prepared wasm module ready for execution.
 #[derive(Clone, Encode, Decode)]
@@ -207,9 +212,11 @@ mod tests {
 		transfers: Vec<TransferEntry>,
 		dispatches: Vec<DispatchEntry>,
 		restores: Vec<RestoreEntry>,
+		code_hashes: Vec<H256>,
 		// (topics, data)
 		events: Vec<(Vec<H256>, Vec<u8>)>,
 		next_account_id: u64,
+		debug_buffer: Vec<u8>,
 
 		/// Runtime storage keys works the following way.
 		///
@@ -359,6 +366,11 @@ mod tests {
 			self.rent_allowance
 		}
 
+		fn set_code_hash(&mut self, hash: H256) -> Result<(), DispatchError> {
+			self.code_hashes.push(hash);
+			Ok(())
+		}
+
 		fn block_number(&self) -> u64 { 121 }
 
 		fn max_value_size(&self) -> u32 { 16_384 }
@@ -378,6 +390,10 @@ mod tests {
 		fn get_weight_price(&self) -> BalanceOf<Self::T> {
 			1312_u32.into()
 		}
+		fn append_debug_buffer(&mut self, msg: &str) -> bool {
+			self.debug_buffer.extend_from_slice(msg.as_bytes());
+			true
+		}
 	}
 
 	impl Ext for &mut MockExt {
@@ -474,6 +490,9 @@ mod tests {
 		fn rent_allowance(&self) -> u64 {
 			(**self).rent_allowance()
 		}
+		fn set_code_hash(&mut self, hash: H256) -> Result<(), DispatchError> {
+			(**self).set_code_hash(hash)
+		}
 		fn block_number(&self) -> u64 {
 			(**self).block_number()
 		}
@@ -486,6 +505,9 @@ mod tests {
 		fn get_weight_price(&self) -> BalanceOf<Self::T> {
 			(**self).get_weight_price()
 		}
+		fn append_debug_buffer(&mut self, msg: &str) -> bool {
+			(**self).append_debug_buffer(msg)
+		}
 	}
 
 	fn execute<E: Ext>(
@@ -499,7 +521,7 @@ mod tests {
 		let wasm = wabt::wat2wasm(wat).unwrap();
 		let schedule = crate::Schedule::default();
 		let prefab_module =
-			prepare_contract::<super::runtime::Env>(&wasm, &schedule).unwrap();
+			prepare_contract::<super::runtime::Env, E::T>(&wasm, &schedule).unwrap();
 
 		let exec = WasmExecutable {
 			// Use a "call" convention.
@@ -729,6 +751,43 @@ mod tests {
 		);
 	}
 
+	const CODE_SET_CODE_HASH: &str = r#"
+(module
+	;; ext_set_code_hash(
+	;;     code_hash_ptr: u32,
+	;;     code_hash_len: u32,
+	;; )
+	(import "env" "ext_set_code_hash" (func $ext_set_code_hash (param i32 i32)))
+	(import "env" "memory" (memory 1 1))
+	(func (export "call")
+		(call $ext_set_code_hash
+			(i32.const 4)  ;; Pointer to the code hash.
+			(i32.const 32)  ;; Length of the code hash.
+		)
+	)
+	(func (export "deploy"))
+
+	;; Hash of code.
+	(data (i32.const 4)
+		"\11\11\11\11\11\11\11\11\11\11\11\11\11\11\11\11"
+		"\11\11\11\11\11\11\11\11\11\11\11\11\11\11\11\11"
+	)
+)
+"#;
+
+	#[test]
+	fn contract_set_code_hash() {
+		let mut mock_ext = MockExt::default();
+		execute(
+			CODE_SET_CODE_HASH,
+			vec![],
+			&mut mock_ext,
+			&mut GasMeter::new(GAS_LIMIT),
+		).unwrap();
+
+		assert_eq!(&mock_ext.code_hashes, &[[0x11; 32].into()]);
+	}
+
 	const CODE_TRANSFER_LIMITED_GAS: &str = r#"
 (module
 	;; ext_call(
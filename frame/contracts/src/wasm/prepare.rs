@@ -20,7 +20,8 @@
 
 use crate::wasm::env_def::ImportSatisfyCheck;
 use crate::wasm::PrefabWasmModule;
-use crate::Schedule;
+use crate::{Schedule, Trait};
+use crate::chain_extension::ChainExtension;
 
 use parity_wasm::elements::{self, Internal, External, MemoryType, Type, ValueType};
 use pwasm_utils;
@@ -262,7 +263,9 @@ impl<'a> ContractModule<'a> {
 	/// - checks any imported function against defined host functions set, incl.
 	///   their signatures.
 	/// - if there is a memory import, returns it's descriptor
-	fn scan_imports<C: ImportSatisfyCheck>(&self) -> Result<Option<&MemoryType>, &'static str> {
+	fn scan_imports<C: ImportSatisfyCheck, T: Trait>(
+		&self,
+	) -> Result<Option<&MemoryType>, &'static str> {
 		let module = &self.module;
 
 		let types = module.type_section().map(|ts| ts.types()).unwrap_or(&[]);
@@ -306,6 +309,14 @@ impl<'a> ContractModule<'a> {
 				return Err("module imports `ext_println` but debug features disabled");
 			}
 
+			// We disallow importing `ext_call_chain_extension` unless the runtime provides
+			// a chain extension.
+			if !T::ChainExtension::enabled()
+				&& import.field().as_bytes() == b"ext_call_chain_extension"
+			{
+				return Err("module uses chain extensions but chain extensions are disabled");
+			}
+
 			// We disallow importing `gas` function here since it is treated as implementation detail.
 			if import.field().as_bytes() == b"gas"
 				|| !C::can_satisfy(import.field().as_bytes(), func_ty)
@@ -333,7 +344,7 @@ impl<'a> ContractModule<'a> {
 /// - all imported functions from the external environment matches defined by `env` module,
 ///
 /// The preprocessing includes injecting code for gas metering and metering the height of stack.
-pub fn prepare_contract<C: ImportSatisfyCheck>(
+pub fn prepare_contract<C: ImportSatisfyCheck, T: Trait>(
 	original_code: &[u8],
 	schedule: &Schedule,
 ) -> Result<PrefabWasmModule, &'static str> {
@@ -348,7 +359,7 @@ pub fn prepare_contract<C: ImportSatisfyCheck>(
 		maximum: u32,
 	}
 
-	let memory_def = if let Some(memory_type) = contract_module.scan_imports::<C>()? {
+	let memory_def = if let Some(memory_type) = contract_module.scan_imports::<C, T>()? {
 		// Inspect the module to extract the initial and maximum page count.
 		let limits = memory_type.limits();
 		match (limits.initial(), limits.maximum()) {
@@ -423,7 +434,7 @@ mod tests {
 			fn $name() {
 				let wasm = wabt::Wat2Wasm::new().validate(false).convert($wat).unwrap();
 				let schedule = Schedule::default();
-				let r = prepare_contract::<TestEnv>(wasm.as_ref(), &schedule);
+				let r = prepare_contract::<TestEnv, crate::tests::Test>(wasm.as_ref(), &schedule);
 				assert_matches!(r, $($expected)*);
 			}
 		};
@@ -710,7 +721,7 @@ mod tests {
 			).unwrap();
 			let mut schedule = Schedule::default();
 			schedule.enable_println = true;
-			let r = prepare_contract::<TestEnv>(wasm.as_ref(), &schedule);
+			let r = prepare_contract::<TestEnv, crate::tests::Test>(wasm.as_ref(), &schedule);
 			assert_matches!(r, Ok(_));
 		}
 	}
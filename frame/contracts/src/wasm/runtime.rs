@@ -32,7 +32,7 @@ use sp_io::hashing::{
 	blake2_128,
 	sha2_256,
 };
-use frame_support::weights::GetDispatchInfo;
+use frame_support::{traits::Get, weights::GetDispatchInfo};
 
 /// The value returned from ext_call and ext_instantiate contract external functions if the call or
 /// instantiation traps. This value is chosen as if the execution does not trap, the return value
@@ -80,6 +80,46 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 			special_trap: None,
 		}
 	}
+
+	/// Grants access to the execution environment of the running contract.
+	pub(crate) fn ext(&mut self) -> &mut E {
+		self.ext
+	}
+
+	/// Charge the gas meter with the weight consumed by a chain extension.
+	pub(crate) fn charge_weight(&mut self, weight: Gas) -> Result<(), sp_sandbox::HostError> {
+		charge_gas(
+			self.gas_meter,
+			self.schedule,
+			&mut self.special_trap,
+			RuntimeToken::ChainExtension(weight),
+		)
+	}
+
+	/// Read designated chunk from the sandbox memory. See [`read_sandbox_memory`].
+	pub(crate) fn read_sandbox_memory(
+		&mut self,
+		ptr: u32,
+		len: u32,
+	) -> Result<Vec<u8>, sp_sandbox::HostError> {
+		read_sandbox_memory(self, ptr, len)
+	}
+
+	/// Write the given buffer to the sandbox memory. See [`write_sandbox_memory`].
+	pub(crate) fn write_sandbox_memory(
+		&mut self,
+		ptr: u32,
+		buf: &[u8],
+	) -> Result<(), sp_sandbox::HostError> {
+		write_sandbox_memory(
+			self.schedule,
+			&mut self.special_trap,
+			self.gas_meter,
+			&self.memory,
+			ptr,
+			buf,
+		)
+	}
 }
 
 pub(crate) fn to_execution_result<E: Ext>(
@@ -159,6 +199,10 @@ pub enum RuntimeToken {
 	/// (topic_count, data_bytes): A buffer of the given size is posted as an event indexed with the
 	/// given number of topics.
 	DepositEvent(u32, u32),
+	/// A chain extension consumed the given weight.
+	ChainExtension(Gas),
+	/// The code storage is read to check that the code to set exists.
+	SetCodeHash,
 }
 
 impl<T: Trait> Token<T> for RuntimeToken {
@@ -197,6 +241,8 @@ impl<T: Trait> Token<T> for RuntimeToken {
 					)
 			},
 			DispatchWithWeight(gas) => gas.checked_add(metadata.dispatch_base_cost),
+			ChainExtension(gas) => Some(gas),
+			SetCodeHash => Some(T::DbWeight::get().reads(1)),
 		};
 
 		value.unwrap_or_else(|| Bounded::max_value())
@@ -965,6 +1011,28 @@ define_env!(Env, <E: Ext>,
 		Ok(())
 	},
 
+	// Replace the code of the calling contract with the code identified by the given code hash.
+	//
+	// The currently running code is not affected. The new code is used for all calls into the
+	// contract after the current execution has finished. The storage of the contract is kept.
+	//
+	// Traps if no code is stored under the given code hash.
+	//
+	// - code_hash_ptr: a pointer to the buffer that contains the new code hash.
+	//   Should be decodable as a `T::Hash`. Traps otherwise.
+	// - code_hash_len: length of the code hash buffer.
+	ext_set_code_hash(ctx, code_hash_ptr: u32, code_hash_len: u32) => {
+		let code_hash: CodeHash<<E as Ext>::T> =
+			read_sandbox_memory_as(ctx, code_hash_ptr, code_hash_len)?;
+		charge_gas(
+			ctx.gas_meter,
+			ctx.schedule,
+			&mut ctx.special_trap,
+			RuntimeToken::SetCodeHash,
+		)?;
+		ctx.ext.set_code_hash(code_hash).map_err(|_| sp_sandbox::HostError)
+	},
+
 	// Stores the rent allowance into the scratch buffer.
 	//
 	// The data is encoded as T::Balance. The current contents of the scratch buffer are overwritten.
@@ -977,11 +1045,16 @@ define_env!(Env, <E: Ext>,
 
 	// Prints utf8 encoded string from the data buffer.
 	// Only available on `--dev` chains.
+	//
+	// The string is appended to the debug buffer if the contract is executed by an RPC dry-run
+	// and printed to the log of the node otherwise.
 	// This function may be removed at any time, superseded by a more general contract debugging feature.
 	ext_println(ctx, str_ptr: u32, str_len: u32) => {
 		let data = read_sandbox_memory(ctx, str_ptr, str_len)?;
 		if let Ok(utf8) = core::str::from_utf8(&data) {
-			sp_runtime::print(utf8);
+			if !ctx.ext.append_debug_buffer(utf8) {
+				sp_runtime::print(utf8);
+			}
 		}
 		Ok(())
 	},
@@ -1114,6 +1187,41 @@ define_env!(Env, <E: Ext>,
 	ext_hash_blake2_128(ctx, input_ptr: u32, input_len: u32, output_ptr: u32) => {
 		compute_hash_on_intermediate_buffer(ctx, blake2_128, input_ptr, input_len, output_ptr)
 	},
+
+	// Call into the chain extension provided by the runtime.
+	//
+	// The meaning of `func_id`, the input and the output as well as the returned value are
+	// defined by the chain extension. Traps if the chain extension returns an error.
+	//
+	// This function is only available if the runtime enabled a chain extension. Contracts
+	// importing it are rejected on upload otherwise.
+	//
+	// # Parameters
+	//
+	// - `func_id`: the id of the chain extension function to call.
+	// - `input_ptr`: the pointer into the linear memory where the input data is placed.
+	// - `input_len`: the length of the input data in bytes.
+	// - `output_ptr`: the pointer into the linear memory where the chain extension
+	//                 writes its output.
+	// - `output_len_ptr`: the pointer into the linear memory where the length of the
+	//                     output buffer is placed as a little endian u32. It is overwritten
+	//                     with the length of the output written by the chain extension.
+	ext_call_chain_extension(
+		ctx,
+		func_id: u32,
+		input_ptr: u32,
+		input_len: u32,
+		output_ptr: u32,
+		output_len_ptr: u32
+	) -> u32 => {
+		use crate::chain_extension::{ChainExtension, Environment};
+		if !<<E as Ext>::T as Trait>::ChainExtension::enabled() {
+			return Err(sp_sandbox::HostError);
+		}
+		let env = Environment::new(ctx, input_ptr, input_len, output_ptr, output_len_ptr);
+		<<E as Ext>::T as Trait>::ChainExtension::call(func_id, env)
+			.map_err(|_| sp_sandbox::HostError)
+	},
 );
 
 /// Computes the given hash function on the scratch buffer.
@@ -0,0 +1,59 @@
+;; Call the chain extension with the function id and input given in the scratch buffer.
+(module
+	(import "env" "ext_scratch_size" (func $ext_scratch_size (result i32)))
+	(import "env" "ext_scratch_read" (func $ext_scratch_read (param i32 i32 i32)))
+	(import "env" "ext_scratch_write" (func $ext_scratch_write (param i32 i32)))
+	(import "env" "ext_call_chain_extension"
+		(func $ext_call_chain_extension (param i32 i32 i32 i32 i32) (result i32))
+	)
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) length of the output buffer
+	(data (i32.const 0) "\20")
+
+	;; [4, 32) unused
+
+	;; [32, 64) output buffer
+
+	;; [64, inf) input buffer
+
+	(func (export "deploy"))
+
+	;; The scratch buffer is expected to contain the function id of the chain extension as
+	;; a little endian u32 followed by the input passed to the chain extension.
+	;;
+	;; The output of the chain extension is copied into the scratch buffer and its return
+	;; value is returned as the status code.
+	(func (export "call") (result i32)
+		(local $input_len i32)
+		(local $ret_val i32)
+
+		(local.set $input_len (call $ext_scratch_size))
+		(if (i32.lt_u (local.get $input_len) (i32.const 4))
+			;; A function id must be given.
+			(unreachable)
+		)
+		(call $ext_scratch_read
+			(i32.const 64)				;; Pointer where to store the input
+			(i32.const 0)				;; Offset from the start of the scratch buffer.
+			(local.get $input_len)		;; Count of bytes to copy.
+		)
+
+		(local.set $ret_val
+			(call $ext_call_chain_extension
+				(i32.load (i32.const 64))	;; The function id
+				(i32.const 68)				;; Pointer to the input
+				(i32.sub (local.get $input_len) (i32.const 4))	;; Length of the input
+				(i32.const 32)				;; Pointer to the output buffer
+				(i32.const 0)				;; Pointer to the length of the output buffer
+			)
+		)
+
+		(call $ext_scratch_write
+			(i32.const 32)				;; Pointer to the output written by the chain extension
+			(i32.load (i32.const 0))	;; Length of the output
+		)
+
+		(local.get $ret_val)
+	)
+)
@@ -0,0 +1,22 @@
+;; Print a message from both the constructor and the call and trap afterwards when called.
+(module
+	(import "env" "ext_println" (func $ext_println (param i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	(data (i32.const 0) "Hello World!")
+
+	(func (export "deploy")
+		(call $ext_println
+			(i32.const 0)	;; Pointer to the message
+			(i32.const 12)	;; Length of the message
+		)
+	)
+
+	(func (export "call")
+		(call $ext_println
+			(i32.const 0)	;; Pointer to the message
+			(i32.const 12)	;; Length of the message
+		)
+		(unreachable)
+	)
+)
@@ -0,0 +1,39 @@
+;; Replaces the code of the contract with the code identified by the code hash passed as input.
+(module
+	(import "env" "ext_set_code_hash" (func $ext_set_code_hash (param i32 i32)))
+	(import "env" "ext_scratch_size" (func $ext_scratch_size (result i32)))
+	(import "env" "ext_scratch_read" (func $ext_scratch_read (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	(func $assert (param i32)
+		(block $ok
+			(br_if $ok
+				(get_local 0)
+			)
+			(unreachable)
+		)
+	)
+
+	(func (export "call")
+		(call $assert
+			(i32.eq
+				(call $ext_scratch_size)
+				(i32.const 32)
+			)
+		)
+
+		;; copy the code hash from the scratch buffer into the contract's memory.
+		(call $ext_scratch_read
+			(i32.const 0)		;; Pointer in memory to the place where to copy.
+			(i32.const 0)		;; Offset from the start of the scratch buffer.
+			(i32.const 32)		;; Count of bytes to copy.
+		)
+
+		(call $ext_set_code_hash
+			(i32.const 0)		;; Pointer to the code hash.
+			(i32.const 32)		;; Length of the code hash.
+		)
+	)
+
+	(func (export "deploy"))
+)
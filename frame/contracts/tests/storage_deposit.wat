@@ -0,0 +1,59 @@
+;; Sets or clears a storage item.
+;;
+;; The input is a `u32` which selects the storage key followed by a `u32` length. A value of
+;; that length is stored under the key, or the key is cleared if the length is zero.
+(module
+	(import "env" "ext_set_storage" (func $ext_set_storage (param i32 i32 i32)))
+	(import "env" "ext_clear_storage" (func $ext_clear_storage (param i32)))
+	(import "env" "ext_scratch_size" (func $ext_scratch_size (result i32)))
+	(import "env" "ext_scratch_read" (func $ext_scratch_read (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 32) storage key
+	;; [32, 36) value length
+	;; [64, ...) value
+
+	(func $assert (param i32)
+		(block $ok
+			(br_if $ok
+				(get_local 0)
+			)
+			(unreachable)
+		)
+	)
+
+	(func (export "call")
+		(call $assert
+			(i32.eq
+				(call $ext_scratch_size)
+				(i32.const 8)
+			)
+		)
+
+		;; copy the key selector into the first bytes of the key and the length after the key.
+		(call $ext_scratch_read
+			(i32.const 0)		;; Pointer in memory to the place where to copy.
+			(i32.const 0)		;; Offset from the start of the scratch buffer.
+			(i32.const 8)		;; Count of bytes to copy.
+		)
+		(i32.store (i32.const 32) (i32.load (i32.const 4)))
+		(i32.store (i32.const 4) (i32.const 0))
+
+		(if (i32.eqz (i32.load (i32.const 32)))
+			(then
+				(call $ext_clear_storage
+					(i32.const 0)		;; Pointer to storage key
+				)
+			)
+			(else
+				(call $ext_set_storage
+					(i32.const 0)		;; Pointer to storage key
+					(i32.const 64)		;; Pointer to value
+					(i32.load (i32.const 32))	;; Size of value
+				)
+			)
+		)
+	)
+
+	(func (export "deploy"))
+)
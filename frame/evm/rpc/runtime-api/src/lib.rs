@@ -0,0 +1,80 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition required by EVM RPC extensions.
+//!
+//! This API should be imported and implemented by the runtime,
+//! of a node that wants to serve the Ethereum JSON-RPC
+//! on top of the EVM module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{DispatchError, traits::Block as BlockT};
+use sp_std::vec::Vec;
+
+pub use pallet_evm::{Account, EthereumTransaction, Log, TransactionAction, TransactionStatus};
+
+sp_api::decl_runtime_apis! {
+	/// The API to serve the Ethereum JSON-RPC from the EVM module.
+	pub trait EvmApi<BlockNumber> where
+		BlockNumber: Codec,
+	{
+		/// Returns the chain id Ethereum transactions are signed for.
+		fn chain_id() -> u64;
+
+		/// Returns the nonce and the balance of the given address.
+		fn account_basic(address: H160) -> Account;
+
+		/// Returns the code stored at the given address.
+		fn account_code_at(address: H160) -> Vec<u8>;
+
+		/// Returns the value stored under `index` in the storage of the given address.
+		fn storage_at(address: H160, index: H256) -> H256;
+
+		/// Execute a message call, or a contract creation if `to` is `None`, without applying it.
+		///
+		/// Returns the output of the call together with the gas used. The gas price defaults to
+		/// zero and no fee is paid. See the EVM module's `dry_run` for more details.
+		fn call(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: u32,
+			gas_price: Option<U256>,
+		) -> Result<(Vec<u8>, U256), DispatchError>;
+
+		/// Wrap a signed Ethereum transaction into an extrinsic calling the EVM module's
+		/// `transact`.
+		fn convert_transaction(transaction: EthereumTransaction) -> <Block as BlockT>::Extrinsic;
+
+		/// Returns the Ethereum-style hash of the block with the given number.
+		fn ethereum_block_hash(number: BlockNumber) -> H256;
+
+		/// Returns the number of the block with the given Ethereum-style hash.
+		fn block_number_by_ethereum_hash(hash: H256) -> Option<BlockNumber>;
+
+		/// Returns the statuses of the Ethereum transactions included in the block the API is
+		/// called at.
+		///
+		/// The statuses are derived from the events of the block, see the EVM module's
+		/// `transaction_statuses`.
+		#[skip_initialize_block]
+		fn transaction_statuses() -> Vec<TransactionStatus>;
+	}
+}
@@ -0,0 +1,789 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Ethereum compatible RPC methods for interaction with the EVM module.
+//!
+//! Implements the subset of the Ethereum JSON-RPC needed by wallets like MetaMask and by the
+//! usual Ethereum tooling. Substrate block numbers are used as Ethereum block numbers, while
+//! blocks are identified by the Ethereum-style hashes recorded by the EVM module. Receipts and
+//! logs are derived from the events of the blocks that included the transactions.
+
+use std::sync::Arc;
+
+use futures::TryFutureExt;
+use jsonrpc_core::{BoxFuture, Error, ErrorCode, Result, futures::{Future, future}};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H160, H256, U256};
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor, UniqueSaturatedInto, Zero, One},
+};
+use sp_transaction_pool::{TransactionPool, TransactionSource};
+
+pub use self::gen_client::Client as EthClient;
+pub use pallet_evm_rpc_runtime_api::{
+	self as runtime_api, EthereumTransaction, EvmApi as EvmRuntimeApi, Log, TransactionStatus,
+};
+
+const RUNTIME_ERROR: i64 = 1;
+const EXECUTION_ERROR: i64 = 2;
+const TRANSACTION_REJECTED: i64 = 3;
+
+/// The maximal gas limit of `eth_call` and `eth_estimateGas`, which is also used if the request
+/// doesn't specify a gas limit.
+///
+/// Limits how long the RPC may be blocked by a single request.
+const MAX_GAS_LIMIT: u32 = 25_000_000;
+
+/// The maximal number of blocks `eth_getLogs` searches.
+const MAX_BLOCK_RANGE: u32 = 1_000;
+
+/// The number of most recent blocks `eth_getTransactionReceipt` searches.
+///
+/// Transactions are not indexed, so the receipts of older transactions are not found.
+const MAX_RECEIPT_SEARCH_DEPTH: u32 = 256;
+
+/// A block number or one of the tags accepted in place of a block number.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(untagged)]
+pub enum BlockNumber {
+	/// A block tag.
+	Tag(BlockTag),
+	/// A block number.
+	Number(U256),
+}
+
+/// Tags referring to a block relative to the current chain.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockTag {
+	/// The best block.
+	Latest,
+	/// The genesis block.
+	Earliest,
+	/// The block being built, which is treated like the best block.
+	Pending,
+}
+
+/// A struct that encodes RPC parameters of `eth_call` and `eth_estimateGas`.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CallRequest {
+	/// The sender, the zero address if missing.
+	from: Option<H160>,
+	/// The callee, a contract is created if missing.
+	to: Option<H160>,
+	/// The gas limit, [`MAX_GAS_LIMIT`] if missing.
+	gas: Option<U256>,
+	/// The gas price, zero if missing. No fee is paid for the execution.
+	gas_price: Option<U256>,
+	/// The value transferred to the callee.
+	value: Option<U256>,
+	/// The call data or the init code.
+	data: Option<Bytes>,
+}
+
+/// A single value or a list of alternatives a filter matches.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum ValueOrArray<T> {
+	/// A single value.
+	Value(T),
+	/// A list of values.
+	Array(Vec<T>),
+}
+
+impl<T: PartialEq> ValueOrArray<T> {
+	fn contains(&self, value: &T) -> bool {
+		match self {
+			ValueOrArray::Value(v) => v == value,
+			ValueOrArray::Array(values) => values.contains(value),
+		}
+	}
+}
+
+/// A struct that encodes RPC parameters of `eth_getLogs`.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Filter {
+	/// The first block searched, the best block if missing.
+	from_block: Option<BlockNumber>,
+	/// The last block searched, the best block if missing.
+	to_block: Option<BlockNumber>,
+	/// The only block searched. Can't be combined with a block range.
+	block_hash: Option<H256>,
+	/// The addresses of the contracts that emitted the logs.
+	address: Option<ValueOrArray<H160>>,
+	/// The topics of the logs by position, `None` matches any topic.
+	topics: Option<Vec<Option<ValueOrArray<H256>>>>,
+}
+
+impl Filter {
+	fn matches(&self, log: &Log) -> bool {
+		let address_matches = self.address.as_ref()
+			.map_or(true, |address| address.contains(&log.address));
+		let topics_match = self.topics.as_ref().map_or(true, |topics| {
+			topics.iter().enumerate().all(|(i, topic)| match topic {
+				Some(topic) => log.topics.get(i).map_or(false, |t| topic.contains(t)),
+				None => true,
+			})
+		});
+
+		address_matches && topics_match
+	}
+}
+
+/// An RPC serializable log together with its position in the chain.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLog {
+	/// Address of the contract that emitted the log.
+	address: H160,
+	/// Topics of the log.
+	topics: Vec<H256>,
+	/// Data of the log.
+	data: Bytes,
+	/// Ethereum-style hash of the block containing the log.
+	block_hash: H256,
+	/// Number of the block containing the log.
+	block_number: U256,
+	/// Hash of the transaction that emitted the log.
+	transaction_hash: H256,
+	/// Index of the transaction that emitted the log.
+	transaction_index: U256,
+	/// Index of the log in the block.
+	log_index: U256,
+	/// Whether the log was removed by a chain reorganization, always `false`.
+	removed: bool,
+}
+
+/// An RPC serializable receipt of a transaction.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Receipt {
+	/// Hash of the transaction.
+	transaction_hash: H256,
+	/// Index of the transaction in the block.
+	transaction_index: U256,
+	/// Ethereum-style hash of the block containing the transaction.
+	block_hash: H256,
+	/// Number of the block containing the transaction.
+	block_number: U256,
+	/// Sender of the transaction.
+	from: H160,
+	/// Callee of the transaction, `None` for a contract creation.
+	to: Option<H160>,
+	/// Gas used by the transaction and all transactions before it in the block.
+	cumulative_gas_used: U256,
+	/// Gas used by the transaction.
+	gas_used: U256,
+	/// Address of the created contract, `None` for a message call.
+	contract_address: Option<H160>,
+	/// Logs emitted by the transaction.
+	logs: Vec<RpcLog>,
+	/// `1` if the transaction succeeded and `0` otherwise.
+	status: U256,
+}
+
+/// The logs of the given block in the order of emission, each with its index in the block.
+fn block_logs(
+	statuses: &[TransactionStatus],
+	block_hash: H256,
+	block_number: U256,
+) -> impl Iterator<Item = (&TransactionStatus, &Log, RpcLog)> {
+	statuses.iter()
+		.flat_map(|status| status.logs.iter().map(move |log| (status, log)))
+		.enumerate()
+		.map(move |(log_index, (status, log))| (status, log, RpcLog {
+			address: log.address,
+			topics: log.topics.clone(),
+			data: log.data.clone().into(),
+			block_hash,
+			block_number,
+			transaction_hash: status.transaction_hash,
+			transaction_index: status.transaction_index.into(),
+			log_index: log_index.into(),
+			removed: false,
+		}))
+}
+
+/// Ethereum compatible RPC methods.
+#[rpc]
+pub trait EthApi {
+	/// Returns the chain id transactions must be signed for.
+	#[rpc(name = "eth_chainId")]
+	fn chain_id(&self) -> Result<U256>;
+
+	/// Returns the number of the best block.
+	#[rpc(name = "eth_blockNumber")]
+	fn block_number(&self) -> Result<U256>;
+
+	/// Returns the balance of the given address.
+	#[rpc(name = "eth_getBalance")]
+	fn balance(&self, address: H160, number: Option<BlockNumber>) -> Result<U256>;
+
+	/// Returns the code stored at the given address.
+	#[rpc(name = "eth_getCode")]
+	fn code_at(&self, address: H160, number: Option<BlockNumber>) -> Result<Bytes>;
+
+	/// Returns the value stored at the given position in the storage of the given address.
+	#[rpc(name = "eth_getStorageAt")]
+	fn storage_at(
+		&self,
+		address: H160,
+		index: U256,
+		number: Option<BlockNumber>,
+	) -> Result<H256>;
+
+	/// Executes a message call or contract creation and returns its output.
+	///
+	/// This call is performed locally without submitting any transactions. Thus executing this
+	/// won't change any state.
+	#[rpc(name = "eth_call")]
+	fn call(&self, request: CallRequest, number: Option<BlockNumber>) -> Result<Bytes>;
+
+	/// Executes a message call or contract creation on the best block and returns the gas used.
+	///
+	/// This call is performed locally without submitting any transactions. Thus executing this
+	/// won't change any state.
+	#[rpc(name = "eth_estimateGas")]
+	fn estimate_gas(&self, request: CallRequest) -> Result<U256>;
+
+	/// Submits an RLP encoded signed transaction to the transaction pool.
+	///
+	/// Returns the hash of the transaction.
+	#[rpc(name = "eth_sendRawTransaction")]
+	fn send_raw_transaction(&self, bytes: Bytes) -> BoxFuture<H256>;
+
+	/// Returns the receipt of the given transaction, or `None` if it isn't included in one of
+	/// the most recent blocks of the best chain.
+	#[rpc(name = "eth_getTransactionReceipt")]
+	fn transaction_receipt(&self, hash: H256) -> Result<Option<Receipt>>;
+
+	/// Returns the logs matching the given filter.
+	#[rpc(name = "eth_getLogs")]
+	fn logs(&self, filter: Filter) -> Result<Vec<RpcLog>>;
+}
+
+/// An implementation of Ethereum compatible RPC methods.
+pub struct Eth<C, P, B> {
+	client: Arc<C>,
+	pool: Arc<P>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, P, B> Eth<C, P, B> {
+	/// Create new `Eth` with the given reference to the client and the transaction pool.
+	pub fn new(client: Arc<C>, pool: Arc<P>) -> Self {
+		Eth {
+			client,
+			pool,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<C, P, Block> Eth<C, P, Block> where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+{
+	/// The best block, at which requests not referring to a specific block are served.
+	fn best(&self) -> BlockId<Block> {
+		BlockId::hash(self.client.info().best_hash)
+	}
+
+	/// Resolve an optional block number of a request, defaulting to the best block.
+	fn resolve(&self, number: Option<BlockNumber>) -> Result<NumberFor<Block>> {
+		match number.unwrap_or(BlockNumber::Tag(BlockTag::Latest)) {
+			BlockNumber::Tag(BlockTag::Latest) | BlockNumber::Tag(BlockTag::Pending) =>
+				Ok(self.client.info().best_number),
+			BlockNumber::Tag(BlockTag::Earliest) => Ok(Zero::zero()),
+			BlockNumber::Number(number) => NumberOrHex::Hex(number).to_number()
+				.map_err(|e| Error {
+					code: ErrorCode::InvalidParams,
+					message: e,
+					data: None,
+				}),
+		}
+	}
+}
+
+impl<C, P, Block> EthApi for Eth<C, P, Block> where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: EvmRuntimeApi<Block, NumberFor<Block>>,
+	P: TransactionPool<Block = Block> + 'static,
+{
+	fn chain_id(&self) -> Result<U256> {
+		let chain_id = self.client.runtime_api()
+			.chain_id(&self.best())
+			.map_err(|e| runtime_error_into_rpc_err(e))?;
+
+		Ok(chain_id.into())
+	}
+
+	fn block_number(&self) -> Result<U256> {
+		Ok(number_into_u256::<Block>(self.client.info().best_number))
+	}
+
+	fn balance(&self, address: H160, number: Option<BlockNumber>) -> Result<U256> {
+		let at = BlockId::number(self.resolve(number)?);
+		let account = self.client.runtime_api()
+			.account_basic(&at, address)
+			.map_err(|e| runtime_error_into_rpc_err(e))?;
+
+		Ok(account.balance)
+	}
+
+	fn code_at(&self, address: H160, number: Option<BlockNumber>) -> Result<Bytes> {
+		let at = BlockId::number(self.resolve(number)?);
+		let code = self.client.runtime_api()
+			.account_code_at(&at, address)
+			.map_err(|e| runtime_error_into_rpc_err(e))?;
+
+		Ok(code.into())
+	}
+
+	fn storage_at(
+		&self,
+		address: H160,
+		index: U256,
+		number: Option<BlockNumber>,
+	) -> Result<H256> {
+		let at = BlockId::number(self.resolve(number)?);
+		let mut key = [0u8; 32];
+		index.to_big_endian(&mut key);
+
+		self.client.runtime_api()
+			.storage_at(&at, address, key.into())
+			.map_err(|e| runtime_error_into_rpc_err(e))
+	}
+
+	fn call(&self, request: CallRequest, number: Option<BlockNumber>) -> Result<Bytes> {
+		let at = BlockId::number(self.resolve(number)?);
+		let (output, _) = dry_run(&*self.client, &at, request)?;
+
+		Ok(output.into())
+	}
+
+	fn estimate_gas(&self, request: CallRequest) -> Result<U256> {
+		let (_, used_gas) = dry_run(&*self.client, &self.best(), request)?;
+
+		Ok(used_gas)
+	}
+
+	fn send_raw_transaction(&self, bytes: Bytes) -> BoxFuture<H256> {
+		let transaction = match EthereumTransaction::from_rlp(&bytes) {
+			Some(transaction) => transaction,
+			None => return Box::new(future::err(Error {
+				code: ErrorCode::InvalidParams,
+				message: "The transaction is not a valid RLP encoded transaction.".into(),
+				data: None,
+			})),
+		};
+		let hash = transaction.hash();
+
+		let best = self.best();
+		let extrinsic = match self.client.runtime_api().convert_transaction(&best, transaction) {
+			Ok(extrinsic) => extrinsic,
+			Err(e) => return Box::new(future::err(runtime_error_into_rpc_err(e))),
+		};
+
+		Box::new(self.pool
+			.submit_one(&best, TransactionSource::External, extrinsic)
+			.compat()
+			.map(move |_| hash)
+			.map_err(|e| Error {
+				code: ErrorCode::ServerError(TRANSACTION_REJECTED),
+				message: "The transaction was rejected by the transaction pool.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+		)
+	}
+
+	fn transaction_receipt(&self, hash: H256) -> Result<Option<Receipt>> {
+		let api = self.client.runtime_api();
+
+		let mut number = self.client.info().best_number;
+		for _ in 0..MAX_RECEIPT_SEARCH_DEPTH {
+			let at = BlockId::number(number);
+			let statuses = api.transaction_statuses(&at)
+				.map_err(|e| runtime_error_into_rpc_err(e))?;
+			if let Some(status) = statuses.iter().find(|status| status.transaction_hash == hash) {
+				let block_hash = api.ethereum_block_hash(&at, number)
+					.map_err(|e| runtime_error_into_rpc_err(e))?;
+				let block_number = number_into_u256::<Block>(number);
+				let index = status.transaction_index;
+
+				let cumulative_gas_used = statuses.iter()
+					.take_while(|s| s.transaction_index <= index)
+					.fold(U256::zero(), |gas, status| gas.saturating_add(status.gas_used));
+				let logs = block_logs(&statuses, block_hash, block_number)
+					.filter(|(s, _, _)| s.transaction_index == index)
+					.map(|(_, _, log)| log)
+					.collect();
+
+				return Ok(Some(Receipt {
+					transaction_hash: status.transaction_hash,
+					transaction_index: index.into(),
+					block_hash,
+					block_number,
+					from: status.from,
+					to: status.to,
+					cumulative_gas_used,
+					gas_used: status.gas_used,
+					contract_address: status.contract_address,
+					logs,
+					status: if status.succeeded { U256::one() } else { U256::zero() },
+				}));
+			}
+
+			if number.is_zero() {
+				break;
+			}
+			number = number - One::one();
+		}
+
+		Ok(None)
+	}
+
+	fn logs(&self, filter: Filter) -> Result<Vec<RpcLog>> {
+		let api = self.client.runtime_api();
+		let best = self.best();
+
+		let (from, to) = match filter.block_hash {
+			Some(hash) => {
+				if filter.from_block.is_some() || filter.to_block.is_some() {
+					return Err(Error {
+						code: ErrorCode::InvalidParams,
+						message: "`blockHash` can't be combined with `fromBlock` or `toBlock`."
+							.into(),
+						data: None,
+					});
+				}
+				match api.block_number_by_ethereum_hash(&best, hash)
+					.map_err(|e| runtime_error_into_rpc_err(e))?
+				{
+					Some(number) => (number, number),
+					None => return Ok(Vec::new()),
+				}
+			},
+			None => (self.resolve(filter.from_block)?, self.resolve(filter.to_block)?),
+		};
+
+		if from > to {
+			return Ok(Vec::new());
+		}
+		let range: u32 = (to - from).unique_saturated_into();
+		if range >= MAX_BLOCK_RANGE {
+			return Err(Error {
+				code: ErrorCode::InvalidParams,
+				message: format!(
+					"Requested block range is greater than maximum allowed: {} > {}",
+					range + 1, MAX_BLOCK_RANGE,
+				),
+				data: None,
+			});
+		}
+
+		let mut logs = Vec::new();
+		for offset in 0..=range {
+			let number = from + offset.into();
+			let at = BlockId::number(number);
+			let statuses = api.transaction_statuses(&at)
+				.map_err(|e| runtime_error_into_rpc_err(e))?;
+			if !statuses.is_empty() {
+				let block_hash = api.ethereum_block_hash(&at, number)
+					.map_err(|e| runtime_error_into_rpc_err(e))?;
+				let block_number = number_into_u256::<Block>(number);
+				logs.extend(
+					block_logs(&statuses, block_hash, block_number)
+						.filter(|(_, log, _)| filter.matches(log))
+						.map(|(_, _, log)| log)
+				);
+			}
+		}
+
+		Ok(logs)
+	}
+}
+
+/// Execute the call of an `eth_call` or `eth_estimateGas` request without applying it.
+///
+/// Returns the output of the call and the gas used.
+fn dry_run<C, Block>(
+	client: &C,
+	at: &BlockId<Block>,
+	request: CallRequest,
+) -> Result<(Vec<u8>, U256)> where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block>,
+	C::Api: EvmRuntimeApi<Block, NumberFor<Block>>,
+{
+	let CallRequest {
+		from,
+		to,
+		gas,
+		gas_price,
+		value,
+		data,
+	} = request;
+	let gas_limit = limit_gas(gas)?;
+
+	client.runtime_api()
+		.call(
+			at,
+			from.unwrap_or_default(),
+			to,
+			data.map(|data| data.to_vec()).unwrap_or_default(),
+			value.unwrap_or_default(),
+			gas_limit,
+			gas_price,
+		)
+		.map_err(|e| runtime_error_into_rpc_err(e))?
+		.map_err(|e| Error {
+			code: ErrorCode::ServerError(EXECUTION_ERROR),
+			message: "Execution failed".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+}
+
+/// Converts the gas limit of a request into a number and ensures it doesn't exceed the maximum
+/// gas limit allowed for dry-runs.
+fn limit_gas(gas_limit: Option<U256>) -> Result<u32> {
+	let gas_limit = gas_limit.unwrap_or_else(|| MAX_GAS_LIMIT.into());
+	if gas_limit > MAX_GAS_LIMIT.into() {
+		return Err(Error {
+			code: ErrorCode::InvalidParams,
+			message: format!(
+				"Requested gas limit is greater than maximum allowed: {} > {}",
+				gas_limit, MAX_GAS_LIMIT
+			),
+			data: None,
+		});
+	}
+
+	Ok(gas_limit.low_u32())
+}
+
+/// Converts a block number into its RPC representation.
+fn number_into_u256<Block: BlockT>(number: NumberFor<Block>) -> U256 {
+	U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(number))
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> Error {
+	Error {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime trapped".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::Mutex;
+	use sp_api::ApiRef;
+	use sp_runtime::DispatchError;
+	use sp_test_primitives::Block;
+	use runtime_api::Account;
+
+	/// The arguments of a dry-run passed to the runtime.
+	type CallArgs = (H160, Option<H160>, Vec<u8>, U256, u32, Option<U256>);
+
+	/// A client whose runtime records the dry-runs it is asked for.
+	#[derive(Clone, Default)]
+	struct TestApi {
+		calls: Arc<Mutex<Vec<CallArgs>>>,
+	}
+
+	struct RuntimeApi {
+		inner: TestApi,
+	}
+
+	impl ProvideRuntimeApi<Block> for TestApi {
+		type Api = RuntimeApi;
+
+		fn runtime_api<'a>(&'a self) -> ApiRef<'a, Self::Api> {
+			RuntimeApi { inner: self.clone() }.into()
+		}
+	}
+
+	sp_api::mock_impl_runtime_apis! {
+		impl EvmRuntimeApi<Block, u64> for RuntimeApi {
+			type Error = sp_blockchain::Error;
+
+			fn chain_id() -> u64 {
+				42
+			}
+
+			fn account_basic(_address: H160) -> Account {
+				unimplemented!("Not required in tests")
+			}
+
+			fn account_code_at(_address: H160) -> Vec<u8> {
+				unimplemented!("Not required in tests")
+			}
+
+			fn storage_at(_address: H160, _index: H256) -> H256 {
+				unimplemented!("Not required in tests")
+			}
+
+			fn call(
+				&self,
+				from: H160,
+				to: Option<H160>,
+				data: Vec<u8>,
+				value: U256,
+				gas_limit: u32,
+				gas_price: Option<U256>,
+			) -> std::result::Result<(Vec<u8>, U256), DispatchError> {
+				self.inner.calls.lock().unwrap()
+					.push((from, to, data.clone(), value, gas_limit, gas_price));
+				if value > 1_000.into() {
+					Err(DispatchError::Other("BalanceLow"))
+				} else {
+					Ok((data, 21_000.into()))
+				}
+			}
+
+			fn convert_transaction(
+				_transaction: EthereumTransaction,
+			) -> <Block as BlockT>::Extrinsic {
+				unimplemented!("Not required in tests")
+			}
+
+			fn ethereum_block_hash(_number: u64) -> H256 {
+				unimplemented!("Not required in tests")
+			}
+
+			fn block_number_by_ethereum_hash(_hash: H256) -> Option<u64> {
+				unimplemented!("Not required in tests")
+			}
+
+			fn transaction_statuses() -> Vec<TransactionStatus> {
+				unimplemented!("Not required in tests")
+			}
+		}
+	}
+
+	#[test]
+	fn dry_run_should_forward_the_request_to_the_runtime() {
+		let client = TestApi::default();
+		let at = BlockId::number(0);
+		let request = |json: &str| serde_json::from_str::<CallRequest>(json).unwrap();
+		let to = format!("{:?}", H160::repeat_byte(2));
+
+		// Missing fields are defaulted, except for the gas price which is left to the runtime.
+		assert_eq!(
+			dry_run(&client, &at, request(&format!(r#"{{"to":"{}","data":"0x0102"}}"#, to)))
+				.unwrap(),
+			(vec![1, 2], 21_000.into()),
+		);
+		assert_eq!(client.calls.lock().unwrap().pop(), Some((
+			H160::zero(),
+			Some(H160::repeat_byte(2)),
+			vec![1, 2],
+			U256::zero(),
+			MAX_GAS_LIMIT,
+			None,
+		)));
+
+		let from = format!("{:?}", H160::repeat_byte(1));
+		let json = format!(
+			r#"{{"from":"{}","gas":"0x5208","gasPrice":"0x1","value":"0x1"}}"#,
+			from,
+		);
+		assert_eq!(dry_run(&client, &at, request(&json)).unwrap(), (vec![], 21_000.into()));
+		assert_eq!(client.calls.lock().unwrap().pop(), Some((
+			H160::repeat_byte(1),
+			None,
+			vec![],
+			U256::one(),
+			21_000,
+			Some(U256::one()),
+		)));
+
+		// Execution errors are reported as such and the gas limit is capped.
+		let err = dry_run(&client, &at, request(r#"{"value":"0x1000"}"#)).unwrap_err();
+		assert_eq!(err.code, ErrorCode::ServerError(EXECUTION_ERROR));
+		let err = dry_run(&client, &at, request(r#"{"gas":"0x2000000"}"#)).unwrap_err();
+		assert_eq!(err.code, ErrorCode::InvalidParams);
+		assert_eq!(client.calls.lock().unwrap().len(), 1);
+	}
+
+	#[test]
+	fn block_number_should_serialize_deserialize_properly() {
+		fn test(expected: &str, number: BlockNumber) {
+			let res: BlockNumber = serde_json::from_str(expected).unwrap();
+			assert_eq!(res, number);
+			let actual = serde_json::to_string(&res).unwrap();
+			assert_eq!(actual, expected);
+		}
+
+		test(r#""latest""#, BlockNumber::Tag(BlockTag::Latest));
+		test(r#""earliest""#, BlockNumber::Tag(BlockTag::Earliest));
+		test(r#""pending""#, BlockNumber::Tag(BlockTag::Pending));
+		test(r#""0x2a""#, BlockNumber::Number(42.into()));
+	}
+
+	#[test]
+	fn filter_should_match_address_and_topics() {
+		let log = Log {
+			address: H160::repeat_byte(1),
+			topics: vec![H256::repeat_byte(2), H256::repeat_byte(3)],
+			data: vec![],
+		};
+		let filter = |json: &str| serde_json::from_str::<Filter>(json).unwrap();
+		let address = format!("{:?}", H160::repeat_byte(1));
+		let first = format!("{:?}", H256::repeat_byte(2));
+		let second = format!("{:?}", H256::repeat_byte(3));
+
+		assert!(filter("{}").matches(&log));
+		assert!(filter(&format!(r#"{{"address":"{}"}}"#, address)).matches(&log));
+		assert!(filter(&format!(r#"{{"address":["{}"]}}"#, address)).matches(&log));
+		assert!(!filter(&format!(r#"{{"address":"{:?}"}}"#, H160::zero())).matches(&log));
+		assert!(filter(&format!(r#"{{"topics":["{}"]}}"#, first)).matches(&log));
+		assert!(filter(&format!(r#"{{"topics":[null,["{}","{}"]]}}"#, first, second))
+			.matches(&log));
+		assert!(!filter(&format!(r#"{{"topics":["{}"]}}"#, second)).matches(&log));
+		assert!(!filter(&format!(r#"{{"topics":[null,null,"{}"]}}"#, first)).matches(&log));
+	}
+
+	#[test]
+	fn receipt_should_serialize_deserialize_properly() {
+		let expected = concat!(
+			r#"{"transactionHash":"0x0101010101010101010101010101010101010101010101010101010101010101","#,
+			r#""transactionIndex":"0x0","#,
+			r#""blockHash":"0x0202020202020202020202020202020202020202020202020202020202020202","#,
+			r#""blockNumber":"0x5","#,
+			r#""from":"0x0303030303030303030303030303030303030303","#,
+			r#""to":null,"#,
+			r#""cumulativeGasUsed":"0x5208","#,
+			r#""gasUsed":"0x5208","#,
+			r#""contractAddress":"0x0404040404040404040404040404040404040404","#,
+			r#""logs":[],"status":"0x1"}"#,
+		);
+		let res: Receipt = serde_json::from_str(expected).unwrap();
+		let actual = serde_json::to_string(&res).unwrap();
+		assert_eq!(actual, expected);
+	}
+}
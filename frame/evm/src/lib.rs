@@ -15,23 +15,45 @@
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
 //! EVM execution module for Substrate
+//!
+//! Besides the `call`, `create` and `create2` dispatchables, which are signed by Substrate
+//! accounts, the module accepts signed Ethereum transactions through the unsigned `transact`
+//! dispatchable. The module must be declared with its `ValidateUnsigned` part in the runtime
+//! for them to be accepted by the transaction pool.
+//!
+//! For every block the module records an Ethereum-style block hash, of which only the most recent
+//! `BlockHashCount` are kept, and deposits an `Executed` event with the status of each Ethereum
+//! transaction it included. This allows to serve the Ethereum JSON-RPC from the chain state and
+//! the events of recent blocks, see the `pallet-evm-rpc` crate.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod backend;
+mod transaction;
+#[cfg(test)]
+mod tests;
 
 pub use crate::backend::{Account, Log, Vicinity, Backend};
+pub use crate::transaction::{
+	EthereumTransaction, TransactionAction, TransactionSignature, TransactionStatus,
+};
 
-use sp_std::{vec::Vec, marker::PhantomData};
+use sp_std::{vec::Vec, marker::PhantomData, convert::TryInto};
+use codec::Encode;
 use frame_support::{ensure, decl_module, decl_storage, decl_event, decl_error};
 use frame_support::weights::{Weight, DispatchClass, FunctionOf, Pays};
 use frame_support::traits::{Currency, WithdrawReason, ExistenceRequirement, Get};
-use frame_system::{self as system, ensure_signed};
+use frame_system::{self as system, ensure_signed, ensure_none};
 use sp_runtime::ModuleId;
 use sp_core::{U256, H256, H160, Hasher};
 use sp_runtime::{
-	DispatchResult, traits::{UniqueSaturatedInto, AccountIdConversion, SaturatedConversion},
+	DispatchResult, DispatchError,
+	traits::{UniqueSaturatedInto, AccountIdConversion, SaturatedConversion, Saturating, One},
+	transaction_validity::{
+		TransactionValidity, TransactionValidityError, ValidTransaction, InvalidTransaction,
+		TransactionSource,
+	},
 };
 use sha3::{Digest, Keccak256};
 use evm::{ExitReason, ExitSucceed, ExitError, Config};
@@ -51,6 +73,17 @@ impl FeeCalculator for () {
 	fn min_gas_price() -> U256 { U256::zero() }
 }
 
+/// Trait for converting the gas limit of an Ethereum transaction into the weight of its
+/// execution.
+pub trait GasToWeight {
+	/// Return the weight of an execution using at most `gas` gas.
+	fn gas_to_weight(gas: u32) -> Weight;
+}
+
+impl GasToWeight for () {
+	fn gas_to_weight(gas: u32) -> Weight { gas as Weight }
+}
+
 /// Trait for converting account ids of `balances` module into
 /// `H160` for EVM module.
 ///
@@ -129,6 +162,10 @@ pub trait Trait: frame_system::Trait + pallet_timestamp::Trait {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 	/// Precompiles associated with this EVM engine.
 	type Precompiles: Precompiles;
+	/// Chain id as specified by EIP-155, which Ethereum transactions are signed for.
+	type ChainId: Get<u64>;
+	/// Convert the gas limit of Ethereum transactions into the weight of `transact`.
+	type GasToWeight: GasToWeight;
 
 	/// EVM config used in the module.
 	fn config() -> &'static Config {
@@ -139,8 +176,18 @@ pub trait Trait: frame_system::Trait + pallet_timestamp::Trait {
 decl_storage! {
 	trait Store for Module<T: Trait> as EVM {
 		Accounts get(fn accounts) config(): map hasher(blake2_128_concat) H160 => Account;
-		AccountCodes: map hasher(blake2_128_concat) H160 => Vec<u8>;
-		AccountStorages: double_map hasher(blake2_128_concat) H160, hasher(blake2_128_concat) H256 => H256;
+		AccountCodes get(fn account_codes): map hasher(blake2_128_concat) H160 => Vec<u8>;
+		AccountStorages get(fn account_storages):
+			double_map hasher(blake2_128_concat) H160, hasher(blake2_128_concat) H256 => H256;
+
+		/// Hashes of the Ethereum transactions executed in the current block.
+		CurrentTransactionHashes: Vec<H256>;
+		/// The Ethereum-style hash of each of the most recent `BlockHashCount` blocks.
+		BlockHashes get(fn ethereum_block_hash):
+			map hasher(twox_64_concat) T::BlockNumber => H256;
+		/// The block number of each Ethereum-style block hash in `BlockHashes`.
+		BlockNumbers get(fn block_number_by_ethereum_hash):
+			map hasher(identity) H256 => Option<T::BlockNumber>;
 	}
 }
 
@@ -157,6 +204,8 @@ decl_event! {
 		BalanceDeposit(AccountId, H160, U256),
 		/// A withdrawal has been made from a given address.
 		BalanceWithdraw(AccountId, H160, U256),
+		/// An Ethereum transaction has been executed.
+		Executed(TransactionStatus),
 	}
 }
 
@@ -180,6 +229,12 @@ decl_error! {
 		ExitReasonFatal,
 		/// Nonce is invalid
 		InvalidNonce,
+		/// Signature of the Ethereum transaction is invalid
+		InvalidSignature,
+		/// Ethereum transaction is signed for another chain
+		InvalidChainId,
+		/// Gas limit of the Ethereum transaction is too high
+		GasLimitTooHigh,
 	}
 }
 
@@ -191,6 +246,34 @@ decl_module! {
 
 		const ModuleId: ModuleId = T::ModuleId::get();
 
+		/// Chain id which Ethereum transactions are signed for.
+		const ChainId: u64 = T::ChainId::get();
+
+		fn on_initialize() -> Weight {
+			// The block hash is stored and the oldest one pruned in `on_finalize`.
+			T::DbWeight::get().reads_writes(3, 5)
+		}
+
+		fn on_finalize(n: T::BlockNumber) {
+			let transaction_hashes = CurrentTransactionHashes::take();
+			let parent_hash = <BlockHashes<T>>::get(n.saturating_sub(One::one()));
+			let hash = H256::from_slice(
+				Keccak256::digest(&(parent_hash, n, transaction_hashes).encode()).as_slice()
+			);
+
+			<BlockHashes<T>>::insert(n, hash);
+			<BlockNumbers<T>>::insert(hash, n);
+
+			// move the block hash pruning window by one block
+			let block_hash_count = <T as frame_system::Trait>::BlockHashCount::get();
+			if n >= block_hash_count {
+				let to_remove = n - block_hash_count;
+				if <BlockHashes<T>>::contains_key(to_remove) {
+					<BlockNumbers<T>>::remove(<BlockHashes<T>>::take(to_remove));
+				}
+			}
+		}
+
 		/// Deposit balance from currency/balances module into EVM.
 		#[weight = 0]
 		fn deposit_balance(origin, value: BalanceOf<T>) {
@@ -261,13 +344,16 @@ decl_module! {
 				gas_limit,
 				gas_price,
 				nonce,
-				|executor| ((), executor.transact_call(
-					source,
-					target,
-					value,
-					input,
-					gas_limit as usize,
-				)),
+				|executor| {
+					let (reason, _) = executor.transact_call(
+						source,
+						target,
+						value,
+						input,
+						gas_limit as usize,
+					);
+					((), reason)
+				},
 			).map_err(Into::into)
 		}
 
@@ -354,9 +440,111 @@ decl_module! {
 			Module::<T>::deposit_event(Event::<T>::Created(create_address));
 			Ok(())
 		}
+
+		/// Execute a signed Ethereum transaction.
+		///
+		/// The sender is recovered from the signature of the transaction, which is checked in
+		/// `validate_unsigned` before the transaction enters the pool. The fee is paid in gas
+		/// like in Ethereum.
+		#[weight = FunctionOf(
+			|(transaction,): (&EthereumTransaction,)|
+				T::GasToWeight::gas_to_weight(transaction.gas_limit.saturated_into::<u32>()),
+			DispatchClass::Normal,
+			Pays::No,
+		)]
+		fn transact(origin, transaction: EthereumTransaction) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let source = transaction.sender().ok_or(Error::<T>::InvalidSignature)?;
+			ensure!(
+				transaction.signature.chain_id().map_or(true, |id| id == T::ChainId::get()),
+				Error::<T>::InvalidChainId,
+			);
+			ensure!(
+				transaction.gas_limit <= U256::from(u32::max_value()),
+				Error::<T>::GasLimitTooHigh,
+			);
+
+			let transaction_hash = transaction.hash();
+			let gas_limit = transaction.gas_limit.low_u32();
+			let value = transaction.value;
+			let input = transaction.input;
+
+			let (to, info) = match transaction.action {
+				TransactionAction::Call(target) => (Some(target), Self::execute(
+					source,
+					value,
+					gas_limit,
+					transaction.gas_price,
+					Some(transaction.nonce),
+					true,
+					|executor| {
+						let (reason, _) = executor.transact_call(
+							source,
+							target,
+							value,
+							input,
+							gas_limit as usize,
+						);
+						(None, reason)
+					},
+				)?),
+				TransactionAction::Create => (None, Self::execute(
+					source,
+					value,
+					gas_limit,
+					transaction.gas_price,
+					Some(transaction.nonce),
+					true,
+					|executor| {
+						(Some(executor.create_address(
+							evm::CreateScheme::Legacy { caller: source },
+						)), executor.transact_create(
+							source,
+							value,
+							input,
+							gas_limit as usize,
+						))
+					},
+				)?),
+			};
+
+			let result = Self::exit_reason_into_result(info.exit_reason);
+			let contract_address = info.value.filter(|_| result.is_ok());
+			if let Some(address) = contract_address {
+				Module::<T>::deposit_event(Event::<T>::Created(address));
+			}
+
+			let transaction_index = CurrentTransactionHashes::decode_len().unwrap_or(0) as u32;
+			CurrentTransactionHashes::append(transaction_hash);
+			Module::<T>::deposit_event(Event::<T>::Executed(TransactionStatus {
+				transaction_hash,
+				transaction_index,
+				from: source,
+				to,
+				contract_address,
+				gas_used: info.used_gas,
+				succeeded: result.is_ok(),
+				logs: info.logs,
+			}));
+
+			result.map_err(Into::into)
+		}
 	}
 }
 
+/// The outcome of an EVM execution.
+struct ExecutionInfo<R> {
+	/// The reason the execution exited with.
+	exit_reason: ExitReason,
+	/// The value returned by the executed operation.
+	value: R,
+	/// The gas used by the execution.
+	used_gas: U256,
+	/// The logs emitted by the execution.
+	logs: Vec<Log>,
+}
+
 impl<T: Trait> Module<T> {
 	/// The account ID of the EVM module.
 	///
@@ -390,6 +578,103 @@ impl<T: Trait> Module<T> {
 		AccountStorages::remove_prefix(address);
 	}
 
+	/// The statuses of the Ethereum transactions executed in the current block.
+	///
+	/// The statuses are read from the events of the block, so this should only be called from
+	/// outside of the block execution, e.g. by a runtime API called on top of the block. Used to
+	/// serve the receipts and logs of the Ethereum JSON-RPC.
+	pub fn transaction_statuses() -> Vec<TransactionStatus> where
+		<T as frame_system::Trait>::Event: TryInto<Event<T>>,
+	{
+		<frame_system::Module<T>>::events().into_iter()
+			.filter_map(|record| match record.event.try_into() {
+				Ok(RawEvent::Executed(status)) => Some(status),
+				_ => None,
+			})
+			.collect()
+	}
+
+	/// Check the signature, the chain id, the gas limit and the gas price of an Ethereum
+	/// transaction and whether its sender can pay for it.
+	///
+	/// Returns the sender and its account.
+	fn check_transaction(
+		transaction: &EthereumTransaction,
+	) -> Result<(H160, Account), TransactionValidityError> {
+		let sender = transaction.sender().ok_or(InvalidTransaction::BadProof)?;
+		if transaction.signature.chain_id().map_or(false, |id| id != T::ChainId::get()) {
+			return Err(InvalidTransaction::BadProof.into());
+		}
+		if transaction.gas_limit > U256::from(u32::max_value()) {
+			return Err(InvalidTransaction::ExhaustsResources.into());
+		}
+		if transaction.gas_price < T::FeeCalculator::min_gas_price() {
+			return Err(InvalidTransaction::Payment.into());
+		}
+
+		let account = Accounts::get(&sender);
+		let total_payment = transaction.gas_price.saturating_mul(transaction.gas_limit)
+			.saturating_add(transaction.value);
+		if account.balance < total_payment {
+			return Err(InvalidTransaction::Payment.into());
+		}
+
+		Ok((sender, account))
+	}
+
+	/// Execute a message call or a contract creation without applying its changes.
+	///
+	/// Returns the output of the call, which is empty for a contract creation, and the gas used.
+	/// The gas price defaults to zero and no fee is paid. Used to serve `eth_call` and
+	/// `eth_estimateGas`.
+	pub fn dry_run(
+		from: H160,
+		to: Option<H160>,
+		data: Vec<u8>,
+		value: U256,
+		gas_limit: u32,
+		gas_price: Option<U256>,
+	) -> Result<(Vec<u8>, U256), DispatchError> {
+		let gas_price = gas_price.unwrap_or_default();
+		let info = match to {
+			Some(target) => Self::execute(
+				from,
+				value,
+				gas_limit,
+				gas_price,
+				None,
+				false,
+				|executor| {
+					let (reason, output) = executor.transact_call(
+						from,
+						target,
+						value,
+						data,
+						gas_limit as usize,
+					);
+					(output, reason)
+				},
+			)?,
+			None => Self::execute(
+				from,
+				value,
+				gas_limit,
+				gas_price,
+				None,
+				false,
+				|executor| (Vec::new(), executor.transact_create(
+					from,
+					value,
+					data,
+					gas_limit as usize,
+				)),
+			)?,
+		};
+
+		Self::exit_reason_into_result(info.exit_reason)?;
+		Ok((info.value, info.used_gas))
+	}
+
 	/// Execute an EVM operation.
 	fn execute_evm<F, R>(
 		source: H160,
@@ -401,8 +686,36 @@ impl<T: Trait> Module<T> {
 	) -> Result<R, Error<T>> where
 		F: FnOnce(&mut StackExecutor<Backend<T>>) -> (R, ExitReason),
 	{
-		ensure!(gas_price >= T::FeeCalculator::min_gas_price(), Error::<T>::GasPriceTooLow);
+		let info = Self::execute(source, value, gas_limit, gas_price, nonce, true, f)?;
+		Self::exit_reason_into_result(info.exit_reason).map(|()| info.value)
+	}
+
+	/// Convert the exit reason of an execution into the module's result.
+	fn exit_reason_into_result(reason: ExitReason) -> Result<(), Error<T>> {
+		match reason {
+			ExitReason::Succeed(_) => Ok(()),
+			ExitReason::Error(_) => Err(Error::<T>::ExitReasonFailed),
+			ExitReason::Revert(_) => Err(Error::<T>::ExitReasonRevert),
+			ExitReason::Fatal(_) => Err(Error::<T>::ExitReasonFatal),
+		}
+	}
 
+	/// Execute an EVM operation, paying the fee from `source`.
+	///
+	/// The changes are only written to storage and the fee is only paid if `apply` is set. An
+	/// error is only returned if the operation can't be executed at all, the outcome of the
+	/// execution is reported in the returned `ExecutionInfo`.
+	fn execute<F, R>(
+		source: H160,
+		value: U256,
+		gas_limit: u32,
+		gas_price: U256,
+		nonce: Option<U256>,
+		apply: bool,
+		f: F,
+	) -> Result<ExecutionInfo<R>, Error<T>> where
+		F: FnOnce(&mut StackExecutor<Backend<T>>) -> (R, ExitReason),
+	{
 		let vicinity = Vicinity {
 			gas_price,
 			origin: source,
@@ -416,8 +729,12 @@ impl<T: Trait> Module<T> {
 			T::Precompiles::execute,
 		);
 
-		let total_fee = gas_price.checked_mul(U256::from(gas_limit))
-			.ok_or(Error::<T>::FeeOverflow)?;
+		let total_fee = if apply {
+			ensure!(gas_price >= T::FeeCalculator::min_gas_price(), Error::<T>::GasPriceTooLow);
+			gas_price.checked_mul(U256::from(gas_limit)).ok_or(Error::<T>::FeeOverflow)?
+		} else {
+			U256::zero()
+		};
 		let total_payment = value.checked_add(total_fee).ok_or(Error::<T>::PaymentOverflow)?;
 		let source_account = Accounts::get(&source);
 		ensure!(source_account.balance >= total_payment, Error::<T>::BalanceLow);
@@ -429,19 +746,71 @@ impl<T: Trait> Module<T> {
 
 		let (retv, reason) = f(&mut executor);
 
-		let ret = match reason {
-			ExitReason::Succeed(_) => Ok(retv),
-			ExitReason::Error(_) => Err(Error::<T>::ExitReasonFailed),
-			ExitReason::Revert(_) => Err(Error::<T>::ExitReasonRevert),
-			ExitReason::Fatal(_) => Err(Error::<T>::ExitReasonFatal),
-		};
-
-		let actual_fee = executor.fee(gas_price);
-		executor.deposit(source, total_fee.saturating_sub(actual_fee));
+		let used_gas = U256::from(executor.used_gas());
+		if apply {
+			let actual_fee = executor.fee(gas_price);
+			executor.deposit(source, total_fee.saturating_sub(actual_fee));
+		}
 
 		let (values, logs) = executor.deconstruct();
-		backend.apply(values, logs, true);
+		let logs = logs.into_iter().collect::<Vec<_>>();
+		if apply {
+			backend.apply(values, logs.clone(), true);
+		}
+
+		Ok(ExecutionInfo {
+			exit_reason: reason,
+			value: retv,
+			used_gas,
+			logs: logs.into_iter().map(|log| Log {
+				address: log.address,
+				topics: log.topics,
+				data: log.data,
+			}).collect(),
+		})
+	}
+}
+
+impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
+	type Call = Call<T>;
+
+	fn validate_unsigned(
+		_source: TransactionSource,
+		call: &Self::Call,
+	) -> TransactionValidity {
+		if let Call::transact(transaction) = call {
+			let (sender, account) = Self::check_transaction(transaction)?;
+			if transaction.nonce < account.nonce {
+				return InvalidTransaction::Stale.into();
+			}
+
+			let mut builder = ValidTransaction::with_tag_prefix("EVM")
+				.priority(transaction.gas_price.saturated_into::<u64>())
+				.and_provides((sender, transaction.nonce))
+				.propagate(true);
+			// Transactions with a future nonce wait in the pool for their predecessor.
+			if transaction.nonce > account.nonce {
+				builder = builder.and_requires((sender, transaction.nonce - U256::one()));
+			}
+			builder.build()
+		} else {
+			InvalidTransaction::Call.into()
+		}
+	}
 
-		ret
+	// `transact` pays no fee, so a transaction must not be included unless it can be executed.
+	fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+		if let Call::transact(transaction) = call {
+			let (_, account) = Self::check_transaction(transaction)?;
+			if transaction.nonce < account.nonce {
+				return Err(InvalidTransaction::Stale.into());
+			}
+			if transaction.nonce > account.nonce {
+				return Err(InvalidTransaction::Future.into());
+			}
+			Ok(())
+		} else {
+			Err(InvalidTransaction::Call.into())
+		}
 	}
 }
@@ -0,0 +1,325 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+
+use frame_support::{
+	assert_ok, assert_noop, impl_outer_event, impl_outer_origin, parameter_types, StorageMap,
+	traits::OnFinalize, unsigned::ValidateUnsigned, weights::GetDispatchInfo,
+};
+use sp_runtime::{Perbill, traits::{BlakeTwo256, IdentityLookup}, testing::Header};
+
+mod evm {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum MetaEvent for Test {
+		system<T>,
+		evm<T>,
+	}
+}
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 10;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type Call = ();
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = MetaEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type MaximumBlockLength = MaximumBlockLength;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = ();
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+}
+parameter_types! {
+	pub const MinimumPeriod: u64 = 1;
+}
+impl pallet_timestamp::Trait for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+}
+parameter_types! {
+	pub const EVMModuleId: ModuleId = ModuleId(*b"py/evmpa");
+	pub const ChainId: u64 = 42;
+}
+impl Trait for Test {
+	type ModuleId = EVMModuleId;
+	type FeeCalculator = ();
+	type ConvertAccountId = HashTruncateConvertAccountId<BlakeTwo256>;
+	type Currency = Balances;
+	type Event = MetaEvent;
+	type Precompiles = ();
+	type ChainId = ChainId;
+	type GasToWeight = ();
+}
+type System = frame_system::Module<Test>;
+type Balances = pallet_balances::Module<Test>;
+type EVM = Module<Test>;
+
+const SECRET: [u8; 32] = [1u8; 32];
+const RECEIVER: H160 = H160([2u8; 20]);
+const GAS_LIMIT: u64 = 100_000;
+/// The gas used by a plain transfer of value.
+const TRANSFER_GAS: u64 = 21_000;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+}
+
+/// The Ethereum address of the account with the secret key `SECRET`.
+fn sender() -> H160 {
+	let secret = secp256k1::SecretKey::parse(&SECRET).unwrap();
+	let public = secp256k1::PublicKey::from_secret_key(&secret);
+	H160::from_slice(&Keccak256::digest(&public.serialize()[1..])[12..])
+}
+
+/// A transfer of `value` to `RECEIVER` signed by `sender()` for the chain with the given id.
+fn transfer(nonce: u64, value: u64, chain_id: u64) -> EthereumTransaction {
+	let mut transaction = EthereumTransaction {
+		nonce: nonce.into(),
+		gas_price: 1.into(),
+		gas_limit: GAS_LIMIT.into(),
+		action: TransactionAction::Call(RECEIVER),
+		value: value.into(),
+		input: Vec::new(),
+		signature: TransactionSignature {
+			v: chain_id * 2 + 35,
+			r: H256::zero(),
+			s: H256::zero(),
+		},
+	};
+
+	let secret = secp256k1::SecretKey::parse(&SECRET).unwrap();
+	let message = secp256k1::Message::parse(&transaction.signing_hash().0);
+	let (signature, recovery_id) = secp256k1::sign(&message, &secret);
+	let signature = signature.serialize();
+	transaction.signature = TransactionSignature {
+		v: chain_id * 2 + 35 + recovery_id.serialize() as u64,
+		r: H256::from_slice(&signature[0..32]),
+		s: H256::from_slice(&signature[32..64]),
+	};
+	transaction
+}
+
+fn fund(address: H160, balance: u64) {
+	Accounts::insert(address, Account { nonce: 0.into(), balance: balance.into() });
+}
+
+#[test]
+fn transaction_sender_is_recovered() {
+	assert_eq!(transfer(0, 0, 42).sender(), Some(sender()));
+}
+
+#[test]
+fn transact_weight_is_derived_from_the_gas_limit() {
+	let info = Call::<Test>::transact(transfer(0, 0, 42)).get_dispatch_info();
+	assert_eq!(info.weight, GAS_LIMIT);
+	assert_eq!(info.pays_fee, Pays::No);
+}
+
+#[test]
+fn transact_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		fund(sender(), 1_000_000);
+		let transaction = transfer(0, 1_000, 42);
+		let hash = transaction.hash();
+
+		assert_ok!(EVM::transact(Origin::NONE, transaction));
+
+		// Only the used gas is paid for.
+		assert_eq!(EVM::accounts(sender()).balance, (1_000_000 - 1_000 - TRANSFER_GAS).into());
+		assert_eq!(EVM::accounts(sender()).nonce, 1.into());
+		assert_eq!(EVM::accounts(RECEIVER).balance, 1_000.into());
+
+		EVM::on_finalize(1);
+		assert_eq!(EVM::transaction_statuses(), vec![TransactionStatus {
+			transaction_hash: hash,
+			transaction_index: 0,
+			from: sender(),
+			to: Some(RECEIVER),
+			contract_address: None,
+			gas_used: TRANSFER_GAS.into(),
+			succeeded: true,
+			logs: vec![],
+		}]);
+		assert_ne!(EVM::ethereum_block_hash(1), H256::zero());
+		assert_eq!(EVM::block_number_by_ethereum_hash(EVM::ethereum_block_hash(1)), Some(1));
+	});
+}
+
+#[test]
+fn only_recent_block_hashes_are_kept() {
+	new_test_ext().execute_with(|| {
+		for n in 1..=BlockHashCount::get() + 1 {
+			EVM::on_finalize(n);
+		}
+
+		let oldest = EVM::ethereum_block_hash(1);
+		assert_eq!(oldest, H256::zero());
+		assert_ne!(EVM::ethereum_block_hash(2), H256::zero());
+		assert_eq!(
+			EVM::block_number_by_ethereum_hash(EVM::ethereum_block_hash(2)),
+			Some(2),
+		);
+		assert_eq!(BlockNumbers::<Test>::iter().count() as u64, BlockHashCount::get());
+	});
+}
+
+#[test]
+fn transact_should_check_the_transaction() {
+	new_test_ext().execute_with(|| {
+		fund(sender(), 1_000_000);
+
+		assert_noop!(
+			EVM::transact(Origin::signed(1), transfer(0, 0, 42)),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(EVM::transact(Origin::NONE, transfer(0, 0, 1)), Error::<Test>::InvalidChainId);
+		assert_noop!(EVM::transact(Origin::NONE, transfer(1, 0, 42)), Error::<Test>::InvalidNonce);
+		assert_noop!(
+			EVM::transact(Origin::NONE, transfer(0, 1_000_000, 42)),
+			Error::<Test>::BalanceLow,
+		);
+
+		let mut transaction = transfer(0, 0, 42);
+		transaction.signature.v = 0;
+		assert_noop!(EVM::transact(Origin::NONE, transaction), Error::<Test>::InvalidSignature);
+	});
+}
+
+#[test]
+fn validate_unsigned_should_work() {
+	new_test_ext().execute_with(|| {
+		fund(sender(), 1_000_000);
+		let validate = |transaction| EVM::validate_unsigned(
+			TransactionSource::External,
+			&Call::transact(transaction),
+		);
+
+		let valid = validate(transfer(0, 0, 42)).unwrap();
+		assert_eq!(valid.priority, 1);
+		assert_eq!(valid.provides, vec![("EVM", (sender(), U256::zero())).encode()]);
+		assert!(valid.requires.is_empty());
+
+		// Transactions with a future nonce require their predecessor.
+		let valid = validate(transfer(2, 0, 42)).unwrap();
+		assert_eq!(valid.requires, vec![("EVM", (sender(), U256::one())).encode()]);
+
+		Accounts::mutate(sender(), |account| account.nonce = 1.into());
+		assert_eq!(validate(transfer(0, 0, 42)), InvalidTransaction::Stale.into());
+		assert_eq!(validate(transfer(1, 0, 1)), InvalidTransaction::BadProof.into());
+		assert_eq!(validate(transfer(1, 1_000_000, 42)), InvalidTransaction::Payment.into());
+
+		assert_eq!(
+			EVM::validate_unsigned(
+				TransactionSource::External,
+				&Call::withdraw_balance(1),
+			),
+			InvalidTransaction::Call.into(),
+		);
+	});
+}
+
+#[test]
+fn pre_dispatch_should_require_the_current_nonce() {
+	new_test_ext().execute_with(|| {
+		fund(sender(), 1_000_000);
+		Accounts::mutate(sender(), |account| account.nonce = 1.into());
+		let pre_dispatch = |transaction| EVM::pre_dispatch(&Call::transact(transaction));
+
+		assert_eq!(pre_dispatch(transfer(1, 0, 42)), Ok(()));
+		assert_eq!(pre_dispatch(transfer(0, 0, 42)), Err(InvalidTransaction::Stale.into()));
+		assert_eq!(pre_dispatch(transfer(2, 0, 42)), Err(InvalidTransaction::Future.into()));
+
+		// The sender must still be able to pay for the transaction.
+		assert_eq!(
+			pre_dispatch(transfer(1, 1_000_000, 42)),
+			Err(InvalidTransaction::Payment.into()),
+		);
+		assert_eq!(pre_dispatch(transfer(1, 0, 1)), Err(InvalidTransaction::BadProof.into()));
+		assert_eq!(
+			EVM::pre_dispatch(&Call::withdraw_balance(1)),
+			Err(InvalidTransaction::Call.into()),
+		);
+	});
+}
+
+#[test]
+fn dry_run_should_not_pay_a_fee() {
+	new_test_ext().execute_with(|| {
+		fund(sender(), 1_000);
+
+		// The gas price defaults to zero, so an account without funds for the fee can dry-run.
+		assert_eq!(
+			EVM::dry_run(sender(), Some(RECEIVER), vec![], 1_000.into(), GAS_LIMIT as u32, None),
+			Ok((vec![], TRANSFER_GAS.into())),
+		);
+		assert_eq!(
+			EVM::dry_run(
+				sender(),
+				Some(RECEIVER),
+				vec![],
+				0.into(),
+				GAS_LIMIT as u32,
+				Some(1.into()),
+			),
+			Ok((vec![], TRANSFER_GAS.into())),
+		);
+
+		// Nothing is applied.
+		assert_eq!(EVM::accounts(sender()).balance, 1_000.into());
+		assert_eq!(EVM::accounts(sender()).nonce, 0.into());
+		assert_eq!(EVM::accounts(RECEIVER).balance, 0.into());
+	});
+}
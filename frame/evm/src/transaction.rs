@@ -0,0 +1,228 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Ethereum transactions and the receipts recorded for them.
+//!
+//! Only legacy transactions are supported, signed either with or without the replay protection
+//! of [EIP-155](https://eips.ethereum.org/EIPS/eip-155).
+
+use sp_std::vec::Vec;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+use codec::{Encode, Decode};
+use sp_core::{U256, H256, H160};
+use sp_runtime::RuntimeDebug;
+use sha3::{Keccak256, Digest};
+use rlp::{Rlp, RlpStream, DecoderError};
+use crate::Log;
+
+/// Target of an Ethereum transaction.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub enum TransactionAction {
+	/// Message call to the given address.
+	Call(H160),
+	/// Contract creation.
+	Create,
+}
+
+impl rlp::Decodable for TransactionAction {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		if rlp.is_empty() {
+			if rlp.is_data() {
+				Ok(TransactionAction::Create)
+			} else {
+				Err(DecoderError::RlpExpectedToBeData)
+			}
+		} else {
+			Ok(TransactionAction::Call(rlp.as_val()?))
+		}
+	}
+}
+
+impl rlp::Encodable for TransactionAction {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		match self {
+			TransactionAction::Call(address) => { s.append(address); },
+			TransactionAction::Create => { s.append_empty_data(); },
+		}
+	}
+}
+
+/// The `v`, `r` and `s` values of an Ethereum transaction signature.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct TransactionSignature {
+	/// Recovery id combined with the chain id as specified by EIP-155.
+	pub v: u64,
+	/// The `r` value of the signature.
+	pub r: H256,
+	/// The `s` value of the signature.
+	pub s: H256,
+}
+
+impl TransactionSignature {
+	/// The chain id the transaction is bound to or `None` if it isn't replay protected.
+	pub fn chain_id(&self) -> Option<u64> {
+		if self.v >= 35 {
+			Some((self.v - 35) / 2)
+		} else {
+			None
+		}
+	}
+
+	/// The recovery id, or `None` if `v` is invalid.
+	pub fn recovery_id(&self) -> Option<u8> {
+		match self.v {
+			27 | 28 => Some((self.v - 27) as u8),
+			v if v >= 35 => Some(((v - 35) % 2) as u8),
+			_ => None,
+		}
+	}
+}
+
+/// A signed legacy Ethereum transaction.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct EthereumTransaction {
+	/// Nonce of the sender.
+	pub nonce: U256,
+	/// Price paid for each unit of gas.
+	pub gas_price: U256,
+	/// Maximum amount of gas the transaction may use.
+	pub gas_limit: U256,
+	/// Whether a contract is called or created.
+	pub action: TransactionAction,
+	/// Value transferred to the callee or the created contract.
+	pub value: U256,
+	/// Call data or init code.
+	pub input: Vec<u8>,
+	/// Signature of the sender.
+	pub signature: TransactionSignature,
+}
+
+impl rlp::Decodable for EthereumTransaction {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		if rlp.item_count()? != 9 {
+			return Err(DecoderError::RlpIncorrectListLen);
+		}
+
+		Ok(EthereumTransaction {
+			nonce: rlp.val_at(0)?,
+			gas_price: rlp.val_at(1)?,
+			gas_limit: rlp.val_at(2)?,
+			action: rlp.val_at(3)?,
+			value: rlp.val_at(4)?,
+			input: rlp.val_at(5)?,
+			signature: TransactionSignature {
+				v: rlp.val_at(6)?,
+				r: u256_to_h256(rlp.val_at(7)?),
+				s: u256_to_h256(rlp.val_at(8)?),
+			},
+		})
+	}
+}
+
+/// Signature values are RLP encoded as integers, so leading zeros are stripped.
+fn u256_to_h256(value: U256) -> H256 {
+	let mut bytes = [0u8; 32];
+	value.to_big_endian(&mut bytes);
+	H256::from(bytes)
+}
+
+impl rlp::Encodable for EthereumTransaction {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(9);
+		self.append_unsigned(s);
+		s.append(&self.signature.v);
+		s.append(&U256::from_big_endian(self.signature.r.as_bytes()));
+		s.append(&U256::from_big_endian(self.signature.s.as_bytes()));
+	}
+}
+
+impl EthereumTransaction {
+	/// Decode a transaction from its RLP encoding, as sent with `eth_sendRawTransaction`.
+	pub fn from_rlp(bytes: &[u8]) -> Option<Self> {
+		rlp::decode(bytes).ok()
+	}
+
+	/// The hash identifying the transaction, which is the keccak hash of its RLP encoding.
+	pub fn hash(&self) -> H256 {
+		H256::from_slice(Keccak256::digest(&rlp::encode(self)).as_slice())
+	}
+
+	/// The hash that was signed by the sender.
+	pub fn signing_hash(&self) -> H256 {
+		let mut s = RlpStream::new();
+		match self.signature.chain_id() {
+			Some(chain_id) => {
+				s.begin_list(9);
+				self.append_unsigned(&mut s);
+				s.append(&chain_id);
+				s.append(&0u8);
+				s.append(&0u8);
+			},
+			None => {
+				s.begin_list(6);
+				self.append_unsigned(&mut s);
+			},
+		}
+		H256::from_slice(Keccak256::digest(&s.out()).as_slice())
+	}
+
+	/// Recover the address of the sender from the signature.
+	///
+	/// Returns `None` if the signature is invalid.
+	pub fn sender(&self) -> Option<H160> {
+		let mut sig = [0u8; 65];
+		sig[0..32].copy_from_slice(self.signature.r.as_bytes());
+		sig[32..64].copy_from_slice(self.signature.s.as_bytes());
+		sig[64] = self.signature.recovery_id()?;
+
+		let pubkey = sp_io::crypto::secp256k1_ecdsa_recover(&sig, &self.signing_hash().0).ok()?;
+		Some(H160::from_slice(&Keccak256::digest(&pubkey)[12..]))
+	}
+
+	fn append_unsigned(&self, s: &mut RlpStream) {
+		s.append(&self.nonce);
+		s.append(&self.gas_price);
+		s.append(&self.gas_limit);
+		s.append(&self.action);
+		s.append(&self.value);
+		s.append(&self.input);
+	}
+}
+
+/// The outcome of an Ethereum transaction included in a block.
+///
+/// Holds everything needed to build the receipt of the transaction.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct TransactionStatus {
+	/// Hash of the transaction.
+	pub transaction_hash: H256,
+	/// Index of the transaction among the Ethereum transactions of its block.
+	pub transaction_index: u32,
+	/// Sender of the transaction.
+	pub from: H160,
+	/// Callee of the transaction, `None` for a contract creation.
+	pub to: Option<H160>,
+	/// Address of the created contract, `None` for a message call.
+	pub contract_address: Option<H160>,
+	/// Gas used by the transaction.
+	pub gas_used: U256,
+	/// Whether the execution succeeded.
+	pub succeeded: bool,
+	/// Logs emitted by the transaction.
+	pub logs: Vec<Log>,
+}
@@ -80,6 +80,10 @@
 //! done by setting an optional generic parameter. The custom logic will be called before
 //! the on runtime upgrade logic of all modules is called.
 //!
+//! After all of the on runtime upgrade logic ran, the version of every module stored on chain is
+//! set to the current version of the module (see `frame_support::traits::GetPalletVersion`).
+//! Migrations can compare against this version to only run once.
+//!
 //! ```
 //! # use sp_runtime::generic;
 //! # use frame_executive as executive;
@@ -117,7 +121,9 @@
 use sp_std::{prelude::*, marker::PhantomData};
 use frame_support::{
 	storage::StorageValue, weights::{GetDispatchInfo, DispatchInfo},
-	traits::{OnInitialize, OnFinalize, OnRuntimeUpgrade, OffchainWorker},
+	traits::{
+		OnInitialize, OnFinalize, OnRuntimeUpgrade, OffchainWorker, Filter, IsType, StorePalletVersion,
+	},
 };
 use sp_runtime::{
 	generic::Digest, ApplyExtrinsicResult,
@@ -125,7 +131,7 @@ use sp_runtime::{
 		self, Header, Zero, One, Checkable, Applyable, CheckEqual, ValidateUnsigned, NumberFor,
 		Block as BlockT, Dispatchable, Saturating,
 	},
-	transaction_validity::{TransactionValidity, TransactionSource},
+	transaction_validity::{TransactionValidity, TransactionSource, InvalidTransaction},
 };
 use codec::{Codec, Encode};
 use frame_system::{extrinsics_root, DigestOf};
@@ -162,6 +168,7 @@ impl<
 	UnsignedValidator,
 	AllModules:
 		OnRuntimeUpgrade +
+		StorePalletVersion +
 		OnInitialize<System::BlockNumber> +
 		OnFinalize<System::BlockNumber> +
 		OffchainWorker<System::BlockNumber>,
@@ -173,7 +180,7 @@ where
 	CheckedOf<Block::Extrinsic, Context>:
 		Applyable +
 		GetDispatchInfo,
-	CallOf<Block::Extrinsic, Context>: Dispatchable<Info=DispatchInfo>,
+	CallOf<Block::Extrinsic, Context>: Dispatchable<Info=DispatchInfo> + IsType<System::Call>,
 	OriginOf<Block::Extrinsic, Context>: From<Option<System::AccountId>>,
 	UnsignedValidator: ValidateUnsigned<Call=CallOf<Block::Extrinsic, Context>>,
 {
@@ -189,6 +196,7 @@ impl<
 	UnsignedValidator,
 	AllModules:
 		OnRuntimeUpgrade +
+		StorePalletVersion +
 		OnInitialize<System::BlockNumber> +
 		OnFinalize<System::BlockNumber> +
 		OffchainWorker<System::BlockNumber>,
@@ -199,7 +207,7 @@ where
 	CheckedOf<Block::Extrinsic, Context>:
 		Applyable +
 		GetDispatchInfo,
-	CallOf<Block::Extrinsic, Context>: Dispatchable<Info=DispatchInfo>,
+	CallOf<Block::Extrinsic, Context>: Dispatchable<Info=DispatchInfo> + IsType<System::Call>,
 	OriginOf<Block::Extrinsic, Context>: From<Option<System::AccountId>>,
 	UnsignedValidator: ValidateUnsigned<Call=CallOf<Block::Extrinsic, Context>>,
 {
@@ -224,6 +232,25 @@ where
 		digest
 	}
 
+	/// Execute all `OnRuntimeUpgrade` hooks of this runtime, returning the consumed weight.
+	///
+	/// Once all hooks ran, the on chain version of every module is updated to its current version,
+	/// so that the hooks can use the version to not run a migration twice.
+	///
+	/// This is called when initializing the first block after a runtime upgrade, it is exposed
+	/// for testing migrations against existing state.
+	pub fn execute_on_runtime_upgrade() -> frame_support::weights::Weight {
+		// System is not part of `AllModules`, so we need to call this manually.
+		let mut weight = <frame_system::Module::<System> as OnRuntimeUpgrade>::on_runtime_upgrade();
+		weight = weight.saturating_add(COnRuntimeUpgrade::on_runtime_upgrade());
+		weight = weight.saturating_add(<AllModules as OnRuntimeUpgrade>::on_runtime_upgrade());
+
+		weight = weight.saturating_add(
+			<frame_system::Module::<System> as StorePalletVersion>::store_pallet_version()
+		);
+		weight.saturating_add(<AllModules as StorePalletVersion>::store_pallet_version())
+	}
+
 	fn initialize_block_impl(
 		block_number: &System::BlockNumber,
 		parent_hash: &System::Hash,
@@ -231,10 +258,7 @@ where
 		digest: &Digest<System::Hash>,
 	) {
 		if Self::runtime_upgraded() {
-			// System is not part of `AllModules`, so we need to call this manually.
-			let mut weight = <frame_system::Module::<System> as OnRuntimeUpgrade>::on_runtime_upgrade();
-			weight = weight.saturating_add(COnRuntimeUpgrade::on_runtime_upgrade());
-			weight = weight.saturating_add(<AllModules as OnRuntimeUpgrade>::on_runtime_upgrade());
+			let weight = Self::execute_on_runtime_upgrade();
 			<frame_system::Module<System>>::register_extra_weight_unchecked(weight);
 		}
 		<frame_system::Module<System>>::initialize(
@@ -354,6 +378,11 @@ where
 		// Verify that the signature is good.
 		let xt = uxt.check(&Default::default())?;
 
+		// Calls disabled by the runtime's call filter may not be applied.
+		if !System::BaseCallFilter::filter(xt.call().into_ref()) {
+			return Err(InvalidTransaction::Call.into())
+		}
+
 		// We don't need to make sure to `note_extrinsic` only after we know it's going to be
 		// executed to prevent it from leaking in storage since at this point, it will either
 		// execute or panic (and revert storage changes).
@@ -410,6 +439,10 @@ where
 
 		let xt = tracing_span!{ "check"; uxt.check(&Default::default())? };
 
+		if !System::BaseCallFilter::filter(xt.call().into_ref()) {
+			return Err(InvalidTransaction::Call.into())
+		}
+
 		let dispatch_info = tracing_span!{ "dispatch_info"; xt.get_dispatch_info() };
 
 		tracing_span! {
@@ -458,20 +491,26 @@ mod tests {
 	use frame_support::{
 		impl_outer_event, impl_outer_origin, parameter_types, impl_outer_dispatch,
 		weights::{Weight, RuntimeDbWeight},
-		traits::{Currency, LockIdentifier, LockableCurrency, WithdrawReasons, WithdrawReason},
+		traits::{
+			Currency, LockIdentifier, LockableCurrency, WithdrawReasons, WithdrawReason,
+			GetPalletVersion, PalletVersion,
+		},
 	};
 	use frame_system::{self as system, Call as SystemCall, ChainContext, LastRuntimeUpgradeInfo};
 	use pallet_balances::Call as BalancesCall;
 	use hex_literal::hex;
 	const TEST_KEY: &[u8] = &*b":test:key:";
+	// Will contain the on chain version of the custom module seen by its `on_runtime_upgrade`.
+	const PALLET_VERSION_KEY: &[u8] = &*b":test:pallet_version:";
 
 	mod custom {
-		use frame_support::weights::{Weight, DispatchClass};
+		use frame_support::{weights::{Weight, DispatchClass}, traits::GetPalletVersion};
+		use codec::Encode;
 
 		pub trait Trait: frame_system::Trait {}
 
 		frame_support::decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system = frame_system {
 				#[weight = 100]
 				fn some_function(origin) {
 					// NOTE: does not make any different.
@@ -499,6 +538,10 @@ mod tests {
 
 				fn on_runtime_upgrade() -> Weight {
 					sp_io::storage::set(super::TEST_KEY, "module".as_bytes());
+					sp_io::storage::set(
+						super::PALLET_VERSION_KEY,
+						&<Self as GetPalletVersion>::on_chain_storage_version().encode(),
+					);
 					0
 				}
 			}
@@ -528,6 +571,13 @@ mod tests {
 		}
 	}
 
+	pub struct BaseFilter;
+	impl Filter<Call> for BaseFilter {
+		fn filter(call: &Call) -> bool {
+			!matches!(call, Call::Balances(BalancesCall::transfer_keep_alive(..)))
+		}
+	}
+
 	#[derive(Clone, Eq, PartialEq)]
 	pub struct Runtime;
 	parameter_types! {
@@ -543,6 +593,7 @@ mod tests {
 		};
 	}
 	impl frame_system::Trait for Runtime {
+		type BaseCallFilter = BaseFilter;
 		type Origin = Origin;
 		type Index = u64;
 		type Call = Call;
@@ -850,6 +901,21 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn filtered_calls_are_rejected() {
+		let xt = TestXt::new(Call::Balances(BalancesCall::transfer_keep_alive(33, 69)), sign_extra(1, 0, 0));
+		let mut t = new_test_ext(1);
+
+		t.execute_with(|| {
+			assert_eq!(
+				Executive::validate_transaction(TransactionSource::External, xt.clone()),
+				Err(InvalidTransaction::Call.into()),
+			);
+			assert_eq!(Executive::apply_extrinsic(xt), Err(InvalidTransaction::Call.into()));
+			assert_eq!(<pallet_balances::Module<Runtime>>::total_balance(&33), 0);
+		});
+	}
+
 	#[test]
 	fn can_pay_for_tx_fee_on_full_lock() {
 		let id: LockIdentifier = *b"0       ";
@@ -1000,4 +1066,22 @@ mod tests {
 			assert_eq!(sp_io::storage::get(CUSTOM_ON_RUNTIME_KEY).unwrap(), true.encode());
 		});
 	}
+
+	#[test]
+	fn pallet_version_is_stored_after_runtime_upgrade() {
+		new_test_ext(1).execute_with(|| {
+			assert_eq!(Custom::on_chain_storage_version(), None);
+			assert_eq!(Custom::current_storage_version(), frame_support::crate_to_pallet_version!());
+
+			RUNTIME_VERSION.with(|v| *v.borrow_mut() = sp_version::RuntimeVersion {
+				spec_version: 1,
+				..Default::default()
+			});
+			Executive::initialize_block(&Header::new_from_number(1));
+
+			// The version was still unset while the module migrated.
+			assert_eq!(sp_io::storage::get(PALLET_VERSION_KEY), Some(None::<PalletVersion>.encode()));
+			assert_eq!(Custom::on_chain_storage_version(), Some(Custom::current_storage_version()));
+		});
+	}
 }
@@ -0,0 +1,35 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition to query the generic asset pallet.
+//!
+//! This API should be imported and implemented by the runtime
+//! of a node which includes the generic asset pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+sp_api::decl_runtime_apis! {
+	/// The API to query the approvals of generic assets.
+	pub trait GenericAssetApi<AssetId, AccountId, Balance> where
+		AssetId: codec::Codec,
+		AccountId: codec::Codec,
+		Balance: codec::Codec,
+	{
+		/// Get the amount of asset `id` that `delegate` may still transfer from the account of
+		/// `owner`.
+		fn allowance(id: AssetId, owner: AccountId, delegate: AccountId) -> Balance;
+	}
+}
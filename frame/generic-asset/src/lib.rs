@@ -54,6 +54,9 @@
 //! simply dropped, it should automatically maintain any book-keeping such as total issuance.)
 //! - **Lock:** A freeze on a specified amount of an account's free balance until a specified block number. Multiple
 //! locks always operate over the same funds, so they "overlay" rather than "stack".
+//! - **Approval:** An allowance given by an account to a delegate account, to transfer up to some amount of an asset
+//! from its free balance. A deposit of the spending asset is reserved from the approving account while the approval
+//! exists.
 //!
 //! ### Implementations
 //!
@@ -82,12 +85,16 @@
 //! - `mint`: Mint an asset, increases its total issuance. The origin of this call must have mint permissions.
 //! - `burn`: Burn an asset, decreases its total issuance. The origin of this call must have burn permissions.
 //! - `create_reserved`: Create a new kind of reserved asset. The origin of this call must be root.
+//! - `approve_transfer`: Allow a delegate account to transfer some amount of an asset of the origin.
+//! - `cancel_approval`: Revoke the approval given by the origin to a delegate account.
+//! - `transfer_approved`: Transfer some amount of an asset of an account which approved the origin as delegate.
 //!
 //! ### Public Functions
 //!
 //! - `total_balance`: Get an account's total balance of an asset kind.
 //! - `free_balance`: Get an account's free balance of an asset kind.
 //! - `reserved_balance`: Get an account's reserved balance of an asset kind.
+//! - `allowance`: Get the amount of an asset kind a delegate account may still transfer from another account.
 //! - `create_asset`: Creates an asset.
 //! - `make_transfer`: Transfer some liquid free balance from one account to another.
 //! This will not emit the `Transferred` event.
@@ -166,9 +173,9 @@ use frame_support::{
 	decl_event, decl_module, decl_storage, ensure, decl_error,
 	traits::{
 		Currency, ExistenceRequirement, Imbalance, LockIdentifier, LockableCurrency, ReservableCurrency,
-		SignedImbalance, WithdrawReason, WithdrawReasons, TryDrop, BalanceStatus,
+		SignedImbalance, WithdrawReason, WithdrawReasons, TryDrop, BalanceStatus, Get, MultiAsset,
 	},
-	Parameter, StorageMap,
+	Parameter, StorageMap, StorageDoubleMap,
 };
 use frame_system::{self as system, ensure_signed, ensure_root};
 
@@ -187,6 +194,8 @@ pub trait Trait: frame_system::Trait {
 		+ Debug;
 	type AssetId: Parameter + Member + AtLeast32Bit + Default + Copy;
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+	/// The amount of the spending asset held on deposit for a stored approval.
+	type ApprovalDeposit: Get<Self::Balance>;
 }
 
 pub trait Subtrait: frame_system::Trait {
@@ -215,6 +224,15 @@ pub struct AssetOptions<Balance: HasCompact, AccountId> {
 	pub permissions: PermissionLatest<AccountId>,
 }
 
+/// An approval to transfer some amount of an asset from the account which gave it.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct Approval<Balance> {
+	/// The amount which may still be transferred.
+	pub amount: Balance,
+	/// The amount of the spending asset reserved by the approving account for this approval.
+	pub deposit: Balance,
+}
+
 /// Owner of an asset.
 #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug)]
 pub enum Owner<AccountId> {
@@ -350,6 +368,8 @@ decl_error! {
 		InsufficientBalance,
 		/// The account liquidity restrictions prevent withdrawal.
 		LiquidityRestrictions,
+		/// No approval exists that would allow the transfer.
+		Unapproved,
 	}
 }
 
@@ -429,6 +449,81 @@ decl_module! {
 			ensure_root(origin)?;
 			Self::create_asset(Some(asset_id), None, options)
 		}
+
+		/// Approve an amount of asset for transfer by a delegate account.
+		///
+		/// A deposit of `ApprovalDeposit` of the spending asset is reserved from the origin for the
+		/// first approval to `delegate`. If an approval is already in place, this acts additively.
+		#[weight = 0]
+		fn approve_transfer(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			delegate: T::AccountId,
+			#[compact] amount: T::Balance
+		) {
+			let owner = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+			<Approvals<T>>::try_mutate(asset_id, (&owner, &delegate), |maybe_approval| -> DispatchResult {
+				if maybe_approval.is_none() {
+					let deposit = T::ApprovalDeposit::get();
+					Self::reserve(&Self::spending_asset_id(), &owner, deposit)?;
+					*maybe_approval = Some(Approval { amount: Zero::zero(), deposit });
+				}
+				if let Some(approval) = maybe_approval {
+					approval.amount = approval.amount.saturating_add(amount);
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::ApprovedTransfer(asset_id, owner, delegate, amount));
+		}
+
+		/// Cancel the approval given to `delegate` to transfer an asset of the origin, returning
+		/// its deposit.
+		#[weight = 0]
+		fn cancel_approval(origin, #[compact] asset_id: T::AssetId, delegate: T::AccountId) {
+			let owner = ensure_signed(origin)?;
+
+			let approval = <Approvals<T>>::take(asset_id, (&owner, &delegate))
+				.ok_or(Error::<T>::Unapproved)?;
+			Self::unreserve(&Self::spending_asset_id(), &owner, approval.deposit);
+
+			Self::deposit_event(RawEvent::ApprovalCancelled(asset_id, owner, delegate));
+		}
+
+		/// Transfer some liquid free balance of `owner`, which approved the origin as delegate, to
+		/// another account.
+		///
+		/// Once the whole approved amount has been transferred, the approval is removed and its
+		/// deposit returned to `owner`.
+		#[weight = 0]
+		fn transfer_approved(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			owner: T::AccountId,
+			to: T::AccountId,
+			#[compact] amount: T::Balance
+		) {
+			let delegate = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+			let mut approval = <Approvals<T>>::get(asset_id, (&owner, &delegate))
+				.ok_or(Error::<T>::Unapproved)?;
+			let remaining = approval.amount.checked_sub(&amount).ok_or(Error::<T>::Unapproved)?;
+
+			Self::make_transfer(&asset_id, &owner, &to, amount)?;
+
+			if remaining.is_zero() {
+				<Approvals<T>>::remove(asset_id, (&owner, &delegate));
+				Self::unreserve(&Self::spending_asset_id(), &owner, approval.deposit);
+			} else {
+				approval.amount = remaining;
+				<Approvals<T>>::insert(asset_id, (&owner, &delegate), approval);
+			}
+
+			Self::deposit_event(RawEvent::TransferredApproved(asset_id, owner, delegate, to, amount));
+		}
 	}
 }
 
@@ -462,6 +557,11 @@ decl_storage! {
 		pub Permissions get(fn get_permission):
 			map hasher(twox_64_concat) T::AssetId => PermissionVersions<T::AccountId>;
 
+		/// Approved transfers of a given asset, by account which gave the approval and delegate.
+		pub Approvals get(fn approval):
+			double_map hasher(twox_64_concat) T::AssetId, hasher(blake2_128_concat) (T::AccountId, T::AccountId)
+			=> Option<Approval<T::Balance>>;
+
 		/// Any liquidity locks on some account balances.
 		pub Locks get(fn locks):
 			map hasher(blake2_128_concat) T::AccountId => Vec<BalanceLock<T::Balance>>;
@@ -504,6 +604,12 @@ decl_event!(
 		Minted(AssetId, AccountId, Balance),
 		/// Asset burned (asset_id, account, amount).
 		Burned(AssetId, AccountId, Balance),
+		/// Transfer approved (asset_id, owner, delegate, amount).
+		ApprovedTransfer(AssetId, AccountId, AccountId, Balance),
+		/// Transfer approval cancelled (asset_id, owner, delegate).
+		ApprovalCancelled(AssetId, AccountId, AccountId),
+		/// Approved transfer succeeded (asset_id, owner, delegate, to, amount).
+		TransferredApproved(AssetId, AccountId, AccountId, AccountId, Balance),
 	}
 );
 
@@ -525,6 +631,14 @@ impl<T: Trait> Module<T> {
 		<ReservedBalance<T>>::get(asset_id, who)
 	}
 
+	/// Get the amount of an asset kind that `delegate` may still transfer from the free balance of
+	/// `owner`.
+	pub fn allowance(asset_id: &T::AssetId, owner: &T::AccountId, delegate: &T::AccountId) -> T::Balance {
+		Self::approval(asset_id, (owner, delegate))
+			.map(|approval| approval.amount)
+			.unwrap_or_else(Zero::zero)
+	}
+
 	/// Mint to an account's free balance, without event
 	pub fn mint_free(
 		asset_id: &T::AssetId,
@@ -899,6 +1013,43 @@ impl<T: Trait> Module<T> {
 	}
 }
 
+impl<T: Trait> MultiAsset<T::AccountId> for Module<T> {
+	type AssetId = T::AssetId;
+	type Balance = T::Balance;
+
+	fn free_balance(asset_id: T::AssetId, who: &T::AccountId) -> T::Balance {
+		Self::free_balance(&asset_id, who)
+	}
+
+	fn withdraw(
+		asset_id: T::AssetId,
+		who: &T::AccountId,
+		value: T::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		let new_balance = Self::free_balance(&asset_id, who)
+			.checked_sub(&value)
+			.ok_or(Error::<T>::InsufficientBalance)?;
+		Self::ensure_can_withdraw(&asset_id, who, value, reasons, new_balance)?;
+
+		<TotalIssuance<T>>::mutate(&asset_id, |v| *v = v.saturating_sub(value));
+		Self::set_free_balance(&asset_id, who, new_balance);
+		Ok(())
+	}
+
+	fn deposit_into_existing(asset_id: T::AssetId, who: &T::AccountId, value: T::Balance) -> DispatchResult {
+		// No existential deposit rule in GA, every account holds every asset.
+		let new_total_issuance = <TotalIssuance<T>>::get(&asset_id).checked_add(&value)
+			.ok_or(Error::<T>::TotalMintingOverflow)?;
+		let new_balance = Self::free_balance(&asset_id, who).checked_add(&value)
+			.ok_or(Error::<T>::FreeMintingOverflow)?;
+
+		<TotalIssuance<T>>::insert(&asset_id, new_total_issuance);
+		Self::set_free_balance(&asset_id, who, new_balance);
+		Ok(())
+	}
+}
+
 pub trait AssetIdProvider {
 	type AssetId;
 	fn asset_id() -> Self::AssetId;
@@ -1112,6 +1263,7 @@ impl<T: Subtrait> PartialEq for ElevatedTrait<T> {
 }
 impl<T: Subtrait> Eq for ElevatedTrait<T> {}
 impl<T: Subtrait> frame_system::Trait for ElevatedTrait<T> {
+	type BaseCallFilter = ();
 	type Origin = T::Origin;
 	type Call = T::Call;
 	type Index = T::Index;
@@ -1213,3 +1213,74 @@ fn burn_should_raise_event() {
 		},
 	);
 }
+
+#[test]
+fn approved_transfer_should_work() {
+	ExtBuilder::default().free_balance((16001, 1, 100)).build().execute_with(|| {
+		assert_ok!(GenericAsset::approve_transfer(Origin::signed(1), 16001, 2, 30));
+		assert_ok!(GenericAsset::approve_transfer(Origin::signed(1), 16001, 2, 20));
+		assert_eq!(GenericAsset::allowance(&16001, &1, &2), 50);
+		// the deposit is only reserved once.
+		assert_eq!(GenericAsset::reserved_balance(&16001, &1), 1);
+		assert_eq!(GenericAsset::free_balance(&16001, &1), 99);
+
+		assert_ok!(GenericAsset::transfer_approved(Origin::signed(2), 16001, 1, 3, 40));
+		assert_eq!(GenericAsset::allowance(&16001, &1, &2), 10);
+		assert_eq!(GenericAsset::free_balance(&16001, &1), 59);
+		assert_eq!(GenericAsset::free_balance(&16001, &3), 40);
+
+		let expected_event = TestEvent::generic_asset(RawEvent::TransferredApproved(16001, 1, 2, 3, 40));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// exhausting the approval returns the deposit.
+		assert_ok!(GenericAsset::transfer_approved(Origin::signed(2), 16001, 1, 3, 10));
+		assert_eq!(GenericAsset::approval(&16001, (&1, &2)), None);
+		assert_eq!(GenericAsset::reserved_balance(&16001, &1), 0);
+		assert_eq!(GenericAsset::free_balance(&16001, &1), 50);
+	});
+}
+
+#[test]
+fn transfer_approved_above_allowance_should_fail() {
+	ExtBuilder::default().free_balance((16001, 1, 100)).build().execute_with(|| {
+		assert_noop!(
+			GenericAsset::transfer_approved(Origin::signed(2), 16001, 1, 3, 10),
+			Error::<Test>::Unapproved,
+		);
+		assert_ok!(GenericAsset::approve_transfer(Origin::signed(1), 16001, 2, 10));
+		assert_noop!(
+			GenericAsset::transfer_approved(Origin::signed(2), 16001, 1, 3, 11),
+			Error::<Test>::Unapproved,
+		);
+		assert_noop!(
+			GenericAsset::transfer_approved(Origin::signed(3), 16001, 1, 3, 10),
+			Error::<Test>::Unapproved,
+		);
+	});
+}
+
+#[test]
+fn approve_transfer_without_deposit_should_fail() {
+	ExtBuilder::default().free_balance((16001, 1, 100)).build().execute_with(|| {
+		assert_noop!(
+			GenericAsset::approve_transfer(Origin::signed(2), 16001, 3, 10),
+			Error::<Test>::InsufficientBalance,
+		);
+	});
+}
+
+#[test]
+fn cancel_approval_should_work() {
+	ExtBuilder::default().free_balance((16001, 1, 100)).build().execute_with(|| {
+		assert_ok!(GenericAsset::approve_transfer(Origin::signed(1), 16001, 2, 10));
+		assert_noop!(GenericAsset::cancel_approval(Origin::signed(1), 16001, 3), Error::<Test>::Unapproved);
+		assert_ok!(GenericAsset::cancel_approval(Origin::signed(1), 16001, 2));
+		assert_eq!(GenericAsset::allowance(&16001, &1, &2), 0);
+		assert_eq!(GenericAsset::reserved_balance(&16001, &1), 0);
+		assert_eq!(GenericAsset::free_balance(&16001, &1), 100);
+		assert_noop!(
+			GenericAsset::transfer_approved(Origin::signed(2), 16001, 1, 3, 10),
+			Error::<Test>::Unapproved,
+		);
+	});
+}
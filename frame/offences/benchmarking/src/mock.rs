@@ -34,6 +34,7 @@ type BlockNumber = u64;
 type Balance = u64;
 
 impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
 	type Origin = Origin;
 	type Index = AccountIndex;
 	type BlockNumber = BlockNumber;
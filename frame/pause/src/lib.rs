@@ -0,0 +1,230 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Pause Module
+//! A module through which a privileged origin may pause and unpause whole pallets or individual
+//! calls of the runtime without a runtime upgrade.
+//!
+//! - [`pause::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//!
+//! ## Overview
+//!
+//! Pallets and calls are identified by the names under which they appear in the runtime's `Call`
+//! type, as given by `GetCallMetadata`, e.g. `b"Balances"` and `b"transfer"`. Only names which
+//! are known to the runtime may be paused.
+//!
+//! This module provides [`PausedCallFilter`], which rejects every call that is paused, either
+//! individually or through its pallet. It is meant to be used as (part of) the runtime's
+//! `frame_system::Trait::BaseCallFilter`, which makes paused calls unavailable both as extrinsics
+//! and when dispatched on behalf of others by pallets such as `utility` or `sudo`.
+//!
+//! The calls of this module are never rejected by the filter, so that anything which has been
+//! paused can always be unpaused. Runtimes should ensure that inherents and the calls needed by
+//! `PauseOrigin` cannot be paused.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! * `pause_pallet` - Pause all calls of a pallet.
+//! * `unpause_pallet` - Unpause a previously paused pallet.
+//! * `pause_call` - Pause a single call of a pallet.
+//! * `unpause_call` - Unpause a previously paused call.
+//!
+//! [`Call`]: ./enum.Call.html
+//! [`Trait`]: ./trait.Trait.html
+//! [`PausedCallFilter`]: ./struct.PausedCallFilter.html
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::{prelude::*, marker::PhantomData, str};
+use frame_support::{
+	decl_module, decl_event, decl_storage, decl_error, ensure, IsSubType,
+	traits::{Get, EnsureOrigin, Filter, IsType},
+	dispatch::{CallMetadata, GetCallMetadata},
+};
+
+mod tests;
+
+pub trait Trait: frame_system::Trait {
+	/// The overarching event type.
+	type Event: From<Event> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The overarching call type.
+	type Call: GetCallMetadata + IsSubType<Module<Self>, Self>
+		+ IsType<<Self as frame_system::Trait>::Call>;
+
+	/// The origin which may pause and unpause pallets and calls.
+	type PauseOrigin: EnsureOrigin<Self::Origin>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Pause {
+		/// The pallets all of whose calls are paused.
+		pub PausedPallets get(fn paused_pallets): map hasher(twox_64_concat) Vec<u8> => bool;
+
+		/// The calls which are paused individually, keyed by pallet name and then call name.
+		pub PausedCalls get(fn paused_calls):
+			double_map hasher(twox_64_concat) Vec<u8>, hasher(twox_64_concat) Vec<u8> => bool;
+	}
+}
+
+decl_event!(
+	pub enum Event {
+		/// All calls of a pallet were paused.
+		PalletPaused(Vec<u8>),
+		/// The calls of a pallet were unpaused.
+		PalletUnpaused(Vec<u8>),
+		/// A call of a pallet was paused. First is the pallet name, second is the call name.
+		CallPaused(Vec<u8>, Vec<u8>),
+		/// A call of a pallet was unpaused. First is the pallet name, second is the call name.
+		CallUnpaused(Vec<u8>, Vec<u8>),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The runtime has no pallet with the given name.
+		UnknownPallet,
+		/// The pallet has no call with the given name.
+		UnknownCall,
+		/// The pallet or call is already paused.
+		AlreadyPaused,
+		/// The pallet or call is not paused.
+		NotPaused,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin, system = frame_system {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Pause all calls of the pallet `pallet_name`.
+		///
+		/// The dispatch origin for this call must be `PauseOrigin`.
+		///
+		/// # <weight>
+		/// - `O(P)` where `P` is the number of pallets in the runtime.
+		/// - DB Weight: reads and writes `PausedPallets`.
+		/// # </weight>
+		#[weight = 15_000_000 + T::DbWeight::get().reads_writes(1, 1)]
+		fn pause_pallet(origin, pallet_name: Vec<u8>) {
+			T::PauseOrigin::ensure_origin(origin)?;
+			Self::ensure_pallet(&pallet_name)?;
+			ensure!(!PausedPallets::contains_key(&pallet_name), Error::<T>::AlreadyPaused);
+
+			PausedPallets::insert(&pallet_name, true);
+			Self::deposit_event(Event::PalletPaused(pallet_name));
+		}
+
+		/// Unpause the pallet `pallet_name`.
+		///
+		/// Calls of the pallet which are paused individually stay paused.
+		///
+		/// The dispatch origin for this call must be `PauseOrigin`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - DB Weight: reads and writes `PausedPallets`.
+		/// # </weight>
+		#[weight = 10_000_000 + T::DbWeight::get().reads_writes(1, 1)]
+		fn unpause_pallet(origin, pallet_name: Vec<u8>) {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(PausedPallets::contains_key(&pallet_name), Error::<T>::NotPaused);
+
+			PausedPallets::remove(&pallet_name);
+			Self::deposit_event(Event::PalletUnpaused(pallet_name));
+		}
+
+		/// Pause the call `call_name` of the pallet `pallet_name`.
+		///
+		/// The dispatch origin for this call must be `PauseOrigin`.
+		///
+		/// # <weight>
+		/// - `O(P + C)` where `P` is the number of pallets in the runtime and `C` the number of
+		///   calls of the pallet.
+		/// - DB Weight: reads and writes `PausedCalls`.
+		/// # </weight>
+		#[weight = 20_000_000 + T::DbWeight::get().reads_writes(1, 1)]
+		fn pause_call(origin, pallet_name: Vec<u8>, call_name: Vec<u8>) {
+			T::PauseOrigin::ensure_origin(origin)?;
+			let pallet = Self::ensure_pallet(&pallet_name)?;
+			ensure!(
+				<T as Trait>::Call::get_call_names(pallet).iter().any(|c| c.as_bytes() == &call_name[..]),
+				Error::<T>::UnknownCall,
+			);
+			ensure!(!PausedCalls::contains_key(&pallet_name, &call_name), Error::<T>::AlreadyPaused);
+
+			PausedCalls::insert(&pallet_name, &call_name, true);
+			Self::deposit_event(Event::CallPaused(pallet_name, call_name));
+		}
+
+		/// Unpause the call `call_name` of the pallet `pallet_name`.
+		///
+		/// The call stays paused if its whole pallet is paused.
+		///
+		/// The dispatch origin for this call must be `PauseOrigin`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - DB Weight: reads and writes `PausedCalls`.
+		/// # </weight>
+		#[weight = 10_000_000 + T::DbWeight::get().reads_writes(1, 1)]
+		fn unpause_call(origin, pallet_name: Vec<u8>, call_name: Vec<u8>) {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(PausedCalls::contains_key(&pallet_name, &call_name), Error::<T>::NotPaused);
+
+			PausedCalls::remove(&pallet_name, &call_name);
+			Self::deposit_event(Event::CallUnpaused(pallet_name, call_name));
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Ensure that the runtime has a pallet named `pallet_name`, returning the name as a `str`.
+	fn ensure_pallet(pallet_name: &[u8]) -> Result<&str, Error<T>> {
+		str::from_utf8(pallet_name).ok()
+			.filter(|name| <T as Trait>::Call::get_module_names().iter().any(|m| m == name))
+			.ok_or(Error::<T>::UnknownPallet)
+	}
+
+	/// Whether the call `call_name` of the pallet `pallet_name` is paused, either individually or
+	/// through its pallet.
+	pub fn is_paused(pallet_name: &[u8], call_name: &[u8]) -> bool {
+		let pallet_name = pallet_name.to_vec();
+		PausedPallets::contains_key(&pallet_name) || PausedCalls::contains_key(&pallet_name, call_name.to_vec())
+	}
+}
+
+/// A call filter which rejects all calls that are paused, either individually or through their
+/// pallet. Calls of this module are always accepted.
+///
+/// Every call passed to the filter costs up to two storage reads.
+pub struct PausedCallFilter<T>(PhantomData<T>);
+
+impl<T: Trait> Filter<<T as frame_system::Trait>::Call> for PausedCallFilter<T> {
+	fn filter(call: &<T as frame_system::Trait>::Call) -> bool {
+		let call = <<T as Trait>::Call as IsType<<T as frame_system::Trait>::Call>>::from_ref(call);
+		if call.is_sub_type().is_some() {
+			return true
+		}
+		let CallMetadata { function_name, pallet_name } = call.get_call_metadata();
+		!Module::<T>::is_paused(pallet_name.as_bytes(), function_name.as_bytes())
+	}
+}
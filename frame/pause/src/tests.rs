@@ -0,0 +1,208 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+// Tests for Pause Pallet
+
+#![cfg(test)]
+
+use super::*;
+
+use frame_support::{
+	assert_ok, assert_noop, impl_outer_origin, parameter_types, impl_outer_dispatch,
+	weights::Weight, impl_outer_event,
+};
+use sp_core::H256;
+use sp_runtime::{Perbill, DispatchError, traits::{BlakeTwo256, IdentityLookup}, testing::Header};
+use frame_system::EnsureRoot;
+use crate as pause;
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		system<T>,
+		pallet_balances<T>,
+		pause,
+	}
+}
+impl_outer_dispatch! {
+	pub enum Call for Test where origin: Origin {
+		frame_system::System,
+		pallet_balances::Balances,
+		pause::Pause,
+	}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type BaseCallFilter = PausedCallFilter<Test>;
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = Call;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = TestEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+}
+impl Trait for Test {
+	type Event = TestEvent;
+	type Call = Call;
+	type PauseOrigin = EnsureRoot<u64>;
+}
+type System = frame_system::Module<Test>;
+type Pause = Module<Test>;
+
+use frame_system::Call as SystemCall;
+use pallet_balances::Call as BalancesCall;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn last_event() -> TestEvent {
+	System::events().pop().map(|e| e.event).expect("Event expected")
+}
+
+fn is_callable(call: &Call) -> bool {
+	<Test as frame_system::Trait>::BaseCallFilter::filter(call)
+}
+
+#[test]
+fn pause_pallet_works() {
+	new_test_ext().execute_with(|| {
+		let transfer = Call::Balances(BalancesCall::transfer(2, 1));
+		let remark = Call::System(SystemCall::remark(vec![]));
+		assert!(is_callable(&transfer));
+
+		assert_noop!(Pause::pause_pallet(Origin::signed(1), b"Balances".to_vec()), DispatchError::BadOrigin);
+		assert_ok!(Pause::pause_pallet(Origin::ROOT, b"Balances".to_vec()));
+		assert_eq!(last_event(), TestEvent::pause(Event::PalletPaused(b"Balances".to_vec())));
+		assert!(!is_callable(&transfer));
+		assert!(is_callable(&remark));
+		assert_noop!(Pause::pause_pallet(Origin::ROOT, b"Balances".to_vec()), Error::<Test>::AlreadyPaused);
+
+		assert_ok!(Pause::unpause_pallet(Origin::ROOT, b"Balances".to_vec()));
+		assert_eq!(last_event(), TestEvent::pause(Event::PalletUnpaused(b"Balances".to_vec())));
+		assert!(is_callable(&transfer));
+		assert_noop!(Pause::unpause_pallet(Origin::ROOT, b"Balances".to_vec()), Error::<Test>::NotPaused);
+	});
+}
+
+#[test]
+fn pause_call_works() {
+	new_test_ext().execute_with(|| {
+		let transfer = Call::Balances(BalancesCall::transfer(2, 1));
+		let transfer_keep_alive = Call::Balances(BalancesCall::transfer_keep_alive(2, 1));
+
+		assert_ok!(Pause::pause_call(Origin::ROOT, b"Balances".to_vec(), b"transfer".to_vec()));
+		assert_eq!(
+			last_event(),
+			TestEvent::pause(Event::CallPaused(b"Balances".to_vec(), b"transfer".to_vec())),
+		);
+		assert!(!is_callable(&transfer));
+		assert!(is_callable(&transfer_keep_alive));
+		assert_noop!(
+			Pause::pause_call(Origin::ROOT, b"Balances".to_vec(), b"transfer".to_vec()),
+			Error::<Test>::AlreadyPaused,
+		);
+
+		assert_ok!(Pause::unpause_call(Origin::ROOT, b"Balances".to_vec(), b"transfer".to_vec()));
+		assert!(is_callable(&transfer));
+		assert_noop!(
+			Pause::unpause_call(Origin::ROOT, b"Balances".to_vec(), b"transfer".to_vec()),
+			Error::<Test>::NotPaused,
+		);
+	});
+}
+
+#[test]
+fn paused_call_stays_paused_with_its_pallet() {
+	new_test_ext().execute_with(|| {
+		let transfer = Call::Balances(BalancesCall::transfer(2, 1));
+		assert_ok!(Pause::pause_call(Origin::ROOT, b"Balances".to_vec(), b"transfer".to_vec()));
+		assert_ok!(Pause::pause_pallet(Origin::ROOT, b"Balances".to_vec()));
+
+		assert_ok!(Pause::unpause_call(Origin::ROOT, b"Balances".to_vec(), b"transfer".to_vec()));
+		assert!(!is_callable(&transfer));
+		assert_ok!(Pause::unpause_pallet(Origin::ROOT, b"Balances".to_vec()));
+		assert!(is_callable(&transfer));
+	});
+}
+
+#[test]
+fn unknown_names_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Pause::pause_pallet(Origin::ROOT, b"Nope".to_vec()), Error::<Test>::UnknownPallet);
+		assert_noop!(
+			Pause::pause_call(Origin::ROOT, b"Nope".to_vec(), b"transfer".to_vec()),
+			Error::<Test>::UnknownPallet,
+		);
+		assert_noop!(
+			Pause::pause_call(Origin::ROOT, b"Balances".to_vec(), b"nope".to_vec()),
+			Error::<Test>::UnknownCall,
+		);
+		assert_noop!(
+			Pause::pause_call(Origin::ROOT, b"Balances".to_vec(), vec![0xff]),
+			Error::<Test>::UnknownCall,
+		);
+	});
+}
+
+#[test]
+fn own_calls_are_never_filtered() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pause::pause_pallet(Origin::ROOT, b"Pause".to_vec()));
+		let unpause = Call::Pause(pause::Call::unpause_pallet(b"Pause".to_vec()));
+		assert!(is_callable(&unpause));
+	});
+}
@@ -0,0 +1,777 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Proxy Module
+//! A module allowing accounts to give permission to other accounts to dispatch types of calls from
+//! their signed origin.
+//!
+//! - [`proxy::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//!
+//! ## Overview
+//!
+//! Any account may register a number of delegates (proxies), each with a `ProxyType` defined by
+//! the runtime. The proxy type acts as a filter over the calls which the delegate may dispatch on
+//! behalf of the account, e.g. all calls, all calls but balance transfers, or only governance
+//! calls.
+//!
+//! A proxy may be registered with a non-zero delay. In that case the delegate must first announce
+//! the hash of the call it wishes to make and may only dispatch it once the delay has passed. The
+//! account on whose behalf the call is made may reject the announcement in the meantime and in
+//! doing so veto the execution.
+//!
+//! Accounts may also spawn "anonymous" accounts, for which no private key exists and which are
+//! only controlled through the proxy that spawned them.
+//!
+//! Deposits are held for every proxy and every pending announcement, since each adds to the
+//! storage footprint of an account.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! * `proxy` - Dispatch a call on behalf of an account for which the sender is a proxy.
+//! * `add_proxy` - Register a delegate for the sender's account.
+//! * `remove_proxy` - Unregister a delegate of the sender's account.
+//! * `remove_proxies` - Unregister all delegates of the sender's account.
+//! * `anonymous` - Spawn a fresh account controlled only by the sender as its proxy.
+//! * `kill_anonymous` - Remove an anonymous account, returning the deposit to its spawner.
+//! * `announce` - Publish the hash of a call to be made later through a delayed proxy.
+//! * `remove_announcement` - Remove an announcement made by the sender.
+//! * `reject_announcement` - Remove an announcement made by a delegate of the sender.
+//! * `proxy_announced` - Dispatch a previously announced call once its delay has passed.
+//!
+//! [`Call`]: ./enum.Call.html
+//! [`Trait`]: ./trait.Trait.html
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use sp_io::hashing::blake2_256;
+use sp_runtime::{DispatchResult, DispatchError, traits::{Dispatchable, Zero, Hash, Member, Saturating}};
+use frame_support::{
+	decl_module, decl_event, decl_error, decl_storage, Parameter, ensure, RuntimeDebug, IsSubType,
+	traits::{Get, ReservableCurrency, Currency, InstanceFilter, IsType},
+	weights::{Weight, GetDispatchInfo, FunctionOf, Pays},
+	dispatch::PostDispatchInfo,
+};
+use frame_system::{self as system, ensure_signed};
+
+mod tests;
+mod benchmarking;
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+/// Configuration trait.
+pub trait Trait: frame_system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The overarching call type.
+	type Call: Parameter + Dispatchable<Origin=Self::Origin, PostInfo=PostDispatchInfo>
+		+ GetDispatchInfo + From<frame_system::Call<Self>> + IsSubType<Module<Self>, Self>
+		+ IsType<<Self as frame_system::Trait>::Call>;
+
+	/// The currency mechanism.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// A kind of proxy; specified with the proxy and passed in to the `InstanceFilter` to decide
+	/// whether a given call may be dispatched through it.
+	///
+	/// The default value must be the most permissive one (i.e. allow any call), since only such
+	/// proxies may remove all proxies or kill an anonymous account.
+	type ProxyType: Parameter + Member + Ord + PartialOrd + InstanceFilter<<Self as Trait>::Call>
+		+ Default;
+
+	/// The base amount of currency needed to reserve for creating a proxy.
+	///
+	/// This is held for an additional storage item whose value size is
+	/// `sizeof(Balance)` bytes and whose key size is `sizeof(AccountId)` bytes.
+	type ProxyDepositBase: Get<BalanceOf<Self>>;
+
+	/// The amount of currency needed per proxy added.
+	///
+	/// This is held for adding 32 bytes plus an instance of `ProxyType` and `BlockNumber` more
+	/// into a pre-existing storage value.
+	type ProxyDepositFactor: Get<BalanceOf<Self>>;
+
+	/// The maximum amount of proxies allowed for a single account.
+	type MaxProxies: Get<u16>;
+
+	/// The maximum amount of time-delayed announcements that are allowed to be pending.
+	type MaxPending: Get<u32>;
+
+	/// The base amount of currency needed to reserve for creating an announcement.
+	///
+	/// This is held when a new storage item holding a `Balance` is created (typically 16 bytes).
+	type AnnouncementDepositBase: Get<BalanceOf<Self>>;
+
+	/// The amount of currency needed per announcement made.
+	///
+	/// This is held for adding an `AccountId`, `Hash` and `BlockNumber` (typically 68 bytes)
+	/// into a pre-existing storage value.
+	type AnnouncementDepositFactor: Get<BalanceOf<Self>>;
+}
+
+/// The parameters under which a particular account has a proxy relationship with some other
+/// account.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug)]
+pub struct ProxyDefinition<AccountId, ProxyType, BlockNumber> {
+	/// The account which may act on behalf of another.
+	pub delegate: AccountId,
+	/// A value defining the subset of calls that it is allowed to make.
+	pub proxy_type: ProxyType,
+	/// The number of blocks that an announcement must be in place for before the corresponding
+	/// call may be dispatched. If zero, then no announcement is needed.
+	pub delay: BlockNumber,
+}
+
+/// Details surrounding a specific instance of an announcement to make a call.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub struct Announcement<AccountId, Hash, BlockNumber> {
+	/// The account which made the announcement.
+	pub real: AccountId,
+	/// The hash of the call to be made.
+	pub call_hash: Hash,
+	/// The height at which the announcement was made.
+	pub height: BlockNumber,
+}
+
+type ProxyDefinitionOf<T> = ProxyDefinition<
+	<T as frame_system::Trait>::AccountId,
+	<T as Trait>::ProxyType,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+
+type AnnouncementOf<T> = Announcement<
+	<T as frame_system::Trait>::AccountId,
+	<T as frame_system::Trait>::Hash,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Proxy {
+		/// The set of account proxies. Maps the account which has delegated to the accounts
+		/// which are being delegated to, together with the amount held on deposit.
+		pub Proxies get(fn proxies): map hasher(twox_64_concat) T::AccountId
+			=> (Vec<ProxyDefinitionOf<T>>, BalanceOf<T>);
+
+		/// The announcements made by the proxy (key), together with the amount held on deposit.
+		pub Announcements get(fn announcements): map hasher(twox_64_concat) T::AccountId
+			=> (Vec<AnnouncementOf<T>>, BalanceOf<T>);
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// There are too many proxies registered or too many announcements pending.
+		TooMany,
+		/// Proxy registration or announcement not found.
+		NotFound,
+		/// Sender is not a proxy of the account to be proxied.
+		NotProxy,
+		/// A call which is incompatible with the proxy type's filter was attempted.
+		Unproxyable,
+		/// Account is already a proxy.
+		Duplicate,
+		/// Call may not be made by proxy because it may escalate its privileges.
+		NoPermission,
+		/// Announcement, if made at all, was made too recently.
+		Unannounced,
+		/// Cannot add self as proxy.
+		NoSelfProxy,
+	}
+}
+
+decl_event! {
+	/// Events type.
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		ProxyType = <T as Trait>::ProxyType,
+		Hash = <T as frame_system::Trait>::Hash,
+	{
+		/// A proxy was executed correctly, with the given result.
+		ProxyExecuted(DispatchResult),
+		/// Anonymous account has been created by new proxy with given disambiguation index and
+		/// proxy type. First param is the anonymous account, second is the spawning account.
+		AnonymousCreated(AccountId, AccountId, ProxyType, u16),
+		/// An announcement was placed to make a call in the future. First param is the account
+		/// on whose behalf the call is to be made, second is the proxy, third is the hash of the
+		/// call.
+		Announced(AccountId, AccountId, Hash),
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The base amount of currency needed to reserve for creating a proxy.
+		const ProxyDepositBase: BalanceOf<T> = T::ProxyDepositBase::get();
+
+		/// The amount of currency needed per proxy added.
+		const ProxyDepositFactor: BalanceOf<T> = T::ProxyDepositFactor::get();
+
+		/// The maximum amount of proxies allowed for a single account.
+		const MaxProxies: u16 = T::MaxProxies::get();
+
+		/// The maximum amount of time-delayed announcements that are allowed to be pending.
+		const MaxPending: u32 = T::MaxPending::get();
+
+		/// The base amount of currency needed to reserve for creating an announcement.
+		const AnnouncementDepositBase: BalanceOf<T> = T::AnnouncementDepositBase::get();
+
+		/// The amount of currency needed per announcement made.
+		const AnnouncementDepositFactor: BalanceOf<T> = T::AnnouncementDepositFactor::get();
+
+		/// Deposit one of this module's events by using the default implementation.
+		fn deposit_event() = default;
+
+		/// Dispatch the given `call` from an account that the sender is authorised for through
+		/// `add_proxy`.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Parameters:
+		/// - `real`: The account that the proxy will make a call on behalf of.
+		/// - `force_proxy_type`: Specify the exact proxy type to be used and checked for this call.
+		/// - `call`: The call to be made by the `real` account.
+		///
+		/// Fails with `Unannounced` if the proxy was registered with a delay; such calls must go
+		/// through `announce` and `proxy_announced`.
+		///
+		/// # <weight>
+		/// - `O(P)` where `P` is the number of proxies the `real` account has, capped by
+		///   `MaxProxies`.
+		/// - DB Weight: reads `Proxies`.
+		/// - Plus the weight of the `call`.
+		/// # </weight>
+		#[weight = FunctionOf(
+			|args: (&T::AccountId, &Option<T::ProxyType>, &Box<<T as Trait>::Call>)| {
+				args.2.get_dispatch_info().weight
+					.saturating_add(20_000_000)
+					.saturating_add((T::MaxProxies::get() as Weight).saturating_mul(100_000))
+					.saturating_add(T::DbWeight::get().reads(1))
+			},
+			|args: (&T::AccountId, &Option<T::ProxyType>, &Box<<T as Trait>::Call>)| {
+				args.2.get_dispatch_info().class
+			},
+			Pays::Yes,
+		)]
+		fn proxy(origin,
+			real: T::AccountId,
+			force_proxy_type: Option<T::ProxyType>,
+			call: Box<<T as Trait>::Call>,
+		) {
+			let who = ensure_signed(origin)?;
+			let def = Self::find_proxy(&real, &who, force_proxy_type)?;
+			ensure!(def.delay.is_zero(), Error::<T>::Unannounced);
+
+			Self::do_proxy(def, real, *call)?;
+		}
+
+		/// Register a proxy account for the sender that is able to make calls on its behalf.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Payment: `ProxyDepositBase` will be reserved for the first proxy, plus
+		/// `ProxyDepositFactor` for each proxy registered.
+		///
+		/// Parameters:
+		/// - `delegate`: The account that the `caller` would like to make a proxy.
+		/// - `proxy_type`: The permissions allowed for this proxy account.
+		/// - `delay`: The announcement period required of the initial proxy. Will generally be
+		///   zero.
+		///
+		/// # <weight>
+		/// - `O(P)` where `P` is the number of proxies the sender has, capped by `MaxProxies`.
+		/// - DB Weight: reads and writes `Proxies` and the sender's account.
+		/// # </weight>
+		#[weight = 30_000_000
+			+ 200_000 * T::MaxProxies::get() as Weight
+			+ T::DbWeight::get().reads_writes(2, 2)]
+		fn add_proxy(origin,
+			delegate: T::AccountId,
+			proxy_type: T::ProxyType,
+			delay: T::BlockNumber,
+		) {
+			let who = ensure_signed(origin)?;
+			Self::add_proxy_delegate(&who, delegate, proxy_type, delay)?;
+		}
+
+		/// Unregister a proxy account for the sender.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Parameters:
+		/// - `delegate`: The account that the `caller` would like to remove as a proxy.
+		/// - `proxy_type`: The permissions currently enabled for the removed proxy account.
+		/// - `delay`: The announcement period of the removed proxy account.
+		///
+		/// # <weight>
+		/// - `O(P)` where `P` is the number of proxies the sender has, capped by `MaxProxies`.
+		/// - DB Weight: reads and writes `Proxies` and the sender's account.
+		/// # </weight>
+		#[weight = 30_000_000
+			+ 200_000 * T::MaxProxies::get() as Weight
+			+ T::DbWeight::get().reads_writes(2, 2)]
+		fn remove_proxy(origin,
+			delegate: T::AccountId,
+			proxy_type: T::ProxyType,
+			delay: T::BlockNumber,
+		) {
+			let who = ensure_signed(origin)?;
+			Self::remove_proxy_delegate(&who, delegate, proxy_type, delay)?;
+		}
+
+		/// Unregister all proxy accounts for the sender.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// WARNING: This may be called on accounts created by `anonymous`, however if done, then
+		/// the unreserved fees will be inaccessible. **All access to this account will be lost.**
+		///
+		/// # <weight>
+		/// - `O(P)` where `P` is the number of proxies the sender has, capped by `MaxProxies`.
+		/// - DB Weight: reads and writes `Proxies` and the sender's account.
+		/// # </weight>
+		#[weight = 25_000_000
+			+ 100_000 * T::MaxProxies::get() as Weight
+			+ T::DbWeight::get().reads_writes(2, 2)]
+		fn remove_proxies(origin) {
+			let who = ensure_signed(origin)?;
+			let (_, old_deposit) = Proxies::<T>::take(&who);
+			T::Currency::unreserve(&who, old_deposit);
+		}
+
+		/// Spawn a fresh new account that is guaranteed to be otherwise inaccessible, and
+		/// initialize it with a proxy of `proxy_type` for the sender.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Payment: `ProxyDepositBase` and `ProxyDepositFactor` will be reserved from the sender
+		/// and returned once the anonymous account is killed.
+		///
+		/// Parameters:
+		/// - `proxy_type`: The type of the proxy that the sender will be registered as over the
+		///   new account. This will almost always be the most permissive `ProxyType` possible to
+		///   allow for maximum flexibility.
+		/// - `delay`: The announcement period required of the initial proxy. Will generally be
+		///   zero.
+		/// - `index`: A disambiguation index, in case this is called multiple times in the same
+		///   transaction (e.g. with `utility::batch`). Unless you're using `batch` you probably just
+		///   want to use `0`.
+		///
+		/// Fails with `Duplicate` if this has already been called in this transaction, from the
+		/// same sender, with the same parameters.
+		///
+		/// Fails if there are insufficient funds to pay for deposit.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - DB Weight: reads and writes `Proxies` and the sender's account.
+		/// # </weight>
+		#[weight = 40_000_000 + T::DbWeight::get().reads_writes(2, 2)]
+		fn anonymous(origin, proxy_type: T::ProxyType, delay: T::BlockNumber, index: u16) {
+			let who = ensure_signed(origin)?;
+
+			let anonymous = Self::anonymous_account(&who, &proxy_type, index, None);
+			ensure!(!Proxies::<T>::contains_key(&anonymous), Error::<T>::Duplicate);
+			let deposit = T::ProxyDepositBase::get() + T::ProxyDepositFactor::get();
+			T::Currency::reserve(&who, deposit)?;
+			let proxy_def = ProxyDefinition {
+				delegate: who.clone(),
+				proxy_type: proxy_type.clone(),
+				delay,
+			};
+			Proxies::<T>::insert(&anonymous, (vec![proxy_def], deposit));
+			Self::deposit_event(RawEvent::AnonymousCreated(anonymous, who, proxy_type, index));
+		}
+
+		/// Removes a previously spawned anonymous proxy.
+		///
+		/// WARNING: **All access to this account will be lost.** Any funds held in it will be
+		/// inaccessible.
+		///
+		/// Requires a `Signed` origin, and the sender account must have been created by a call to
+		/// `anonymous` with corresponding parameters.
+		///
+		/// - `spawner`: The account that originally called `anonymous` to create this account.
+		/// - `proxy_type`: The proxy type originally passed to `anonymous`.
+		/// - `index`: The disambiguation index originally passed to `anonymous`. Probably `0`.
+		/// - `height`: The height of the chain when the call to `anonymous` was processed.
+		/// - `ext_index`: The extrinsic index in which the call to `anonymous` was processed.
+		///
+		/// Fails with `NoPermission` in case the caller is not a previously created anonymous
+		/// account whose `anonymous` call has corresponding parameters.
+		///
+		/// # <weight>
+		/// - `O(P)` where `P` is the number of proxies the sender has, capped by `MaxProxies`.
+		/// - DB Weight: reads and writes `Proxies` and the spawner's account.
+		/// # </weight>
+		#[weight = 25_000_000
+			+ 100_000 * T::MaxProxies::get() as Weight
+			+ T::DbWeight::get().reads_writes(2, 2)]
+		fn kill_anonymous(origin,
+			spawner: T::AccountId,
+			proxy_type: T::ProxyType,
+			index: u16,
+			#[compact] height: T::BlockNumber,
+			#[compact] ext_index: u32,
+		) {
+			let who = ensure_signed(origin)?;
+
+			let when = (height, ext_index);
+			let proxy = Self::anonymous_account(&spawner, &proxy_type, index, Some(when));
+			ensure!(proxy == who, Error::<T>::NoPermission);
+
+			let (_, deposit) = Proxies::<T>::take(&who);
+			T::Currency::unreserve(&spawner, deposit);
+		}
+
+		/// Publish the hash of a proxy-call that will be made in the future.
+		///
+		/// This must be called some number of blocks before the corresponding `proxy_announced`
+		/// is attempted if the delay associated with the proxy relationship is greater than zero.
+		///
+		/// No more than `MaxPending` announcements may be made at any one time.
+		///
+		/// Payment: `AnnouncementDepositBase` will be reserved for the first announcement, plus
+		/// `AnnouncementDepositFactor` for each announcement pending.
+		///
+		/// The dispatch origin for this call must be _Signed_ and a proxy of `real`.
+		///
+		/// Parameters:
+		/// - `real`: The account that the proxy will make a call on behalf of.
+		/// - `call_hash`: The hash of the call to be made by the `real` account.
+		///
+		/// # <weight>
+		/// - `O(A + P)` where `A` is the number of pending announcements of the sender, capped by
+		///   `MaxPending`, and `P` the number of proxies of `real`, capped by `MaxProxies`.
+		/// - DB Weight: reads `Proxies`, reads and writes `Announcements` and the sender's account.
+		/// # </weight>
+		#[weight = 35_000_000
+			+ 100_000 * T::MaxPending::get() as Weight
+			+ 100_000 * T::MaxProxies::get() as Weight
+			+ T::DbWeight::get().reads_writes(3, 2)]
+		fn announce(origin, real: T::AccountId, call_hash: T::Hash) {
+			let who = ensure_signed(origin)?;
+			Proxies::<T>::get(&real).0.into_iter()
+				.find(|x| &x.delegate == &who)
+				.ok_or(Error::<T>::NotProxy)?;
+
+			let announcement = Announcement {
+				real: real.clone(),
+				call_hash: call_hash.clone(),
+				height: system::Module::<T>::block_number(),
+			};
+
+			Announcements::<T>::try_mutate(&who, |(pending, deposit)| -> DispatchResult {
+				ensure!(pending.len() < T::MaxPending::get() as usize, Error::<T>::TooMany);
+				pending.push(announcement);
+				*deposit = Self::rejig_deposit(
+					&who,
+					*deposit,
+					T::AnnouncementDepositBase::get(),
+					T::AnnouncementDepositFactor::get(),
+					pending.len(),
+				)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::Announced(real, who, call_hash));
+		}
+
+		/// Remove a given announcement.
+		///
+		/// May be called by a proxy account to remove a call they previously announced and return
+		/// the deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Parameters:
+		/// - `real`: The account that the proxy will make a call on behalf of.
+		/// - `call_hash`: The hash of the call to be made by the `real` account.
+		///
+		/// # <weight>
+		/// - `O(A)` where `A` is the number of pending announcements of the sender, capped by
+		///   `MaxPending`.
+		/// - DB Weight: reads and writes `Announcements` and the sender's account.
+		/// # </weight>
+		#[weight = 25_000_000
+			+ 100_000 * T::MaxPending::get() as Weight
+			+ T::DbWeight::get().reads_writes(2, 2)]
+		fn remove_announcement(origin, real: T::AccountId, call_hash: T::Hash) {
+			let who = ensure_signed(origin)?;
+			Self::edit_announcements(&who, |ann| ann.real != real || ann.call_hash != call_hash)?;
+		}
+
+		/// Remove the given announcement of a delegate.
+		///
+		/// May be called by a target (proxied) account to remove a call that one of their
+		/// delegates (`delegate`) has announced they want to execute. The deposit is returned.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Parameters:
+		/// - `delegate`: The account that previously announced the call.
+		/// - `call_hash`: The hash of the call to be made.
+		///
+		/// # <weight>
+		/// - `O(A)` where `A` is the number of pending announcements of `delegate`, capped by
+		///   `MaxPending`.
+		/// - DB Weight: reads and writes `Announcements` and the delegate's account.
+		/// # </weight>
+		#[weight = 25_000_000
+			+ 100_000 * T::MaxPending::get() as Weight
+			+ T::DbWeight::get().reads_writes(2, 2)]
+		fn reject_announcement(origin, delegate: T::AccountId, call_hash: T::Hash) {
+			let who = ensure_signed(origin)?;
+			Self::edit_announcements(&delegate, |ann| ann.real != who || ann.call_hash != call_hash)?;
+		}
+
+		/// Dispatch the given `call` from an account that the `delegate` is authorised for
+		/// through `add_proxy`, once the announcement of it has been in place for long enough.
+		///
+		/// Removes any corresponding announcement(s) and returns their deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Parameters:
+		/// - `delegate`: The account that announced the call.
+		/// - `real`: The account that the proxy will make a call on behalf of.
+		/// - `force_proxy_type`: Specify the exact proxy type to be used and checked for this call.
+		/// - `call`: The call to be made by the `real` account.
+		///
+		/// # <weight>
+		/// - `O(A + P)` where `A` is the number of pending announcements of `delegate`, capped by
+		///   `MaxPending`, and `P` the number of proxies of `real`, capped by `MaxProxies`.
+		/// - DB Weight: reads `Proxies`, reads and writes `Announcements` and the delegate's
+		///   account.
+		/// - Plus the weight of the `call`.
+		/// # </weight>
+		#[weight = FunctionOf(
+			|args: (&T::AccountId, &T::AccountId, &Option<T::ProxyType>, &Box<<T as Trait>::Call>)| {
+				args.3.get_dispatch_info().weight
+					.saturating_add(40_000_000)
+					.saturating_add((T::MaxPending::get() as Weight).saturating_mul(100_000))
+					.saturating_add((T::MaxProxies::get() as Weight).saturating_mul(100_000))
+					.saturating_add(T::DbWeight::get().reads_writes(3, 2))
+			},
+			|args: (&T::AccountId, &T::AccountId, &Option<T::ProxyType>, &Box<<T as Trait>::Call>)| {
+				args.3.get_dispatch_info().class
+			},
+			Pays::Yes,
+		)]
+		fn proxy_announced(origin,
+			delegate: T::AccountId,
+			real: T::AccountId,
+			force_proxy_type: Option<T::ProxyType>,
+			call: Box<<T as Trait>::Call>,
+		) {
+			ensure_signed(origin)?;
+			let def = Self::find_proxy(&real, &delegate, force_proxy_type)?;
+
+			let call_hash = T::Hashing::hash_of(&call);
+			let now = system::Module::<T>::block_number();
+			Self::edit_announcements(&delegate, |ann|
+				ann.real != real || ann.call_hash != call_hash || now.saturating_sub(ann.height) < def.delay
+			).map_err(|_| Error::<T>::Unannounced)?;
+
+			Self::do_proxy(def, real, *call)?;
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Calculate the address of an anonymous account.
+	///
+	/// - `who`: The spawner account.
+	/// - `proxy_type`: The type of the proxy that the sender will be registered as over the
+	///   new account. This will almost always be the most permissive `ProxyType` possible to
+	///   allow for maximum flexibility.
+	/// - `index`: A disambiguation index, in case this is called multiple times in the same
+	///   transaction (e.g. with `utility::batch`). Unless you're using `batch` you probably just
+	///   want to use `0`.
+	/// - `maybe_when`: The block height and extrinsic index of when the anonymous account was
+	///   created. None to use current block height and extrinsic index.
+	pub fn anonymous_account(
+		who: &T::AccountId,
+		proxy_type: &T::ProxyType,
+		index: u16,
+		maybe_when: Option<(T::BlockNumber, u32)>,
+	) -> T::AccountId {
+		let (height, ext_index) = maybe_when.unwrap_or_else(|| (
+			system::Module::<T>::block_number(),
+			system::Module::<T>::extrinsic_index().unwrap_or_default()
+		));
+		let entropy = (b"modlpy/proxy____", who, height, ext_index, proxy_type, index)
+			.using_encoded(blake2_256);
+		T::AccountId::decode(&mut &entropy[..]).unwrap_or_default()
+	}
+
+	/// Register a proxy account for the delegator that is able to make calls on its behalf.
+	///
+	/// Parameters:
+	/// - `delegator`: The delegator account.
+	/// - `delegatee`: The account that the `delegator` would like to make a proxy.
+	/// - `proxy_type`: The permissions allowed for this proxy account.
+	/// - `delay`: The announcement period required of the initial proxy. Will generally be
+	///   zero.
+	pub fn add_proxy_delegate(
+		delegator: &T::AccountId,
+		delegatee: T::AccountId,
+		proxy_type: T::ProxyType,
+		delay: T::BlockNumber,
+	) -> DispatchResult {
+		ensure!(delegator != &delegatee, Error::<T>::NoSelfProxy);
+		Proxies::<T>::try_mutate(delegator, |(proxies, deposit)| {
+			ensure!(proxies.len() < T::MaxProxies::get() as usize, Error::<T>::TooMany);
+			let proxy_def = ProxyDefinition { delegate: delegatee, proxy_type, delay };
+			let i = proxies.binary_search(&proxy_def).err().ok_or(Error::<T>::Duplicate)?;
+			proxies.insert(i, proxy_def);
+			*deposit = Self::rejig_deposit(
+				delegator,
+				*deposit,
+				T::ProxyDepositBase::get(),
+				T::ProxyDepositFactor::get(),
+				proxies.len(),
+			)?;
+			Ok(())
+		})
+	}
+
+	/// Unregister a proxy account for the delegator.
+	///
+	/// Parameters:
+	/// - `delegator`: The delegator account.
+	/// - `delegatee`: The account that the `delegator` would like to remove as a proxy.
+	/// - `proxy_type`: The permissions currently enabled for the removed proxy account.
+	/// - `delay`: The announcement period of the removed proxy account.
+	pub fn remove_proxy_delegate(
+		delegator: &T::AccountId,
+		delegatee: T::AccountId,
+		proxy_type: T::ProxyType,
+		delay: T::BlockNumber,
+	) -> DispatchResult {
+		Proxies::<T>::try_mutate_exists(delegator, |x| {
+			let (mut proxies, old_deposit) = x.take().ok_or(Error::<T>::NotFound)?;
+			let proxy_def = ProxyDefinition { delegate: delegatee, proxy_type, delay };
+			let i = proxies.binary_search(&proxy_def).ok().ok_or(Error::<T>::NotFound)?;
+			proxies.remove(i);
+			let new_deposit = Self::rejig_deposit(
+				delegator,
+				old_deposit,
+				T::ProxyDepositBase::get(),
+				T::ProxyDepositFactor::get(),
+				proxies.len(),
+			)?;
+			if !proxies.is_empty() {
+				*x = Some((proxies, new_deposit))
+			}
+			Ok(())
+		})
+	}
+
+	/// The deposit held for `len` items, given the `base` and per-item `factor` amounts.
+	fn deposit(base: BalanceOf<T>, factor: BalanceOf<T>, len: usize) -> BalanceOf<T> {
+		if len == 0 {
+			Zero::zero()
+		} else {
+			base + factor * (len as u32).into()
+		}
+	}
+
+	/// Reserve or unreserve the difference between the deposit held by `who` for `len` items and
+	/// the `old_deposit`, returning the new deposit.
+	fn rejig_deposit(
+		who: &T::AccountId,
+		old_deposit: BalanceOf<T>,
+		base: BalanceOf<T>,
+		factor: BalanceOf<T>,
+		len: usize,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let new_deposit = Self::deposit(base, factor, len);
+		if new_deposit > old_deposit {
+			T::Currency::reserve(who, new_deposit - old_deposit)?;
+		} else if new_deposit < old_deposit {
+			T::Currency::unreserve(who, old_deposit - new_deposit);
+		}
+		Ok(new_deposit)
+	}
+
+	/// Retain the announcements of `delegate` which match `f`, failing with `NotFound` if none
+	/// were removed, and return the deposit of the removed ones.
+	fn edit_announcements<F: FnMut(&AnnouncementOf<T>) -> bool>(
+		delegate: &T::AccountId,
+		f: F,
+	) -> DispatchResult {
+		Announcements::<T>::try_mutate_exists(delegate, |x| {
+			let (mut pending, old_deposit) = x.take().ok_or(Error::<T>::NotFound)?;
+			let orig_pending_len = pending.len();
+			pending.retain(f);
+			ensure!(orig_pending_len > pending.len(), Error::<T>::NotFound);
+			let new_deposit = Self::rejig_deposit(
+				delegate,
+				old_deposit,
+				T::AnnouncementDepositBase::get(),
+				T::AnnouncementDepositFactor::get(),
+				pending.len(),
+			)?;
+			if !pending.is_empty() {
+				*x = Some((pending, new_deposit))
+			}
+			Ok(())
+		})
+	}
+
+	/// Find the proxy definition of `delegate` over `real`, optionally of the given type only.
+	pub fn find_proxy(
+		real: &T::AccountId,
+		delegate: &T::AccountId,
+		force_proxy_type: Option<T::ProxyType>,
+	) -> Result<ProxyDefinitionOf<T>, DispatchError> {
+		let f = |x: &ProxyDefinitionOf<T>| -> bool {
+			&x.delegate == delegate && force_proxy_type.as_ref().map_or(true, |y| &x.proxy_type == y)
+		};
+		Ok(Proxies::<T>::get(real).0.into_iter().find(f).ok_or(Error::<T>::NotProxy)?)
+	}
+
+	/// Dispatch `call` from `real` through the proxy `def`, depositing the result in an event.
+	fn do_proxy(def: ProxyDefinitionOf<T>, real: T::AccountId, call: <T as Trait>::Call) -> DispatchResult {
+		match call.is_sub_type() {
+			// Proxy call cannot add or remove a proxy with more permissions than it already has.
+			Some(Call::add_proxy(_, pt, _)) | Some(Call::remove_proxy(_, pt, _))
+				if !def.proxy_type.is_superset(pt) => Err(Error::<T>::NoPermission)?,
+			// Proxy call cannot remove all proxies or kill anonymous proxies unless it has full
+			// permissions.
+			Some(Call::remove_proxies(..)) | Some(Call::kill_anonymous(..))
+				if def.proxy_type != T::ProxyType::default() => Err(Error::<T>::NoPermission)?,
+			_ => (),
+		}
+		ensure!(def.proxy_type.filter(&call), Error::<T>::Unproxyable);
+		ensure!(
+			frame_system::Module::<T>::is_callable(call.into_ref()),
+			frame_system::Error::<T>::CallFiltered,
+		);
+
+		let e = call.dispatch(frame_system::RawOrigin::Signed(real).into());
+		Self::deposit_event(RawEvent::ProxyExecuted(e.map(|_| ()).map_err(|e| e.error)));
+		Ok(())
+	}
+}
@@ -160,7 +160,7 @@ use codec::{Encode, Decode};
 use frame_support::{
 	decl_module, decl_event, decl_storage, decl_error, ensure,
 	Parameter, RuntimeDebug, weights::{GetDispatchInfo, FunctionOf, Pays},
-	traits::{Currency, ReservableCurrency, Get, BalanceStatus},
+	traits::{Currency, ReservableCurrency, Get, BalanceStatus, IsType},
 	dispatch::PostDispatchInfo,
 };
 use frame_system::{self as system, ensure_signed, ensure_root};
@@ -179,7 +179,8 @@ pub trait Trait: frame_system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
 	/// The overarching call type.
-	type Call: Parameter + Dispatchable<Origin=Self::Origin, PostInfo=PostDispatchInfo> + GetDispatchInfo;
+	type Call: Parameter + Dispatchable<Origin=Self::Origin, PostInfo=PostDispatchInfo> + GetDispatchInfo
+		+ IsType<<Self as frame_system::Trait>::Call>;
 
 	/// The currency mechanism.
 	type Currency: ReservableCurrency<Self::AccountId>;
@@ -348,6 +349,10 @@ decl_module! {
 			// Check `who` is allowed to make a call on behalf of `account`
 			let target = Self::proxy(&who).ok_or(Error::<T>::NotAllowed)?;
 			ensure!(&target == &account, Error::<T>::NotAllowed);
+			ensure!(
+				frame_system::Module::<T>::is_callable((*call).into_ref()),
+				frame_system::Error::<T>::CallFiltered,
+			);
 			call.dispatch(frame_system::RawOrigin::Signed(account).into())
 				.map(|_| ()).map_err(|e| e.error)
 		}
@@ -26,6 +26,9 @@
 //! or designate a new account to replace them as the sudo key.
 //! Only one account can be the sudo key at a time.
 //!
+//! Calls rejected by the runtime's base call filter (`frame_system::Trait::BaseCallFilter`)
+//! cannot be dispatched through sudo either.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -91,6 +94,7 @@ use sp_runtime::{DispatchResult, traits::{StaticLookup, Dispatchable}};
 
 use frame_support::{
 	Parameter, decl_module, decl_event, decl_storage, decl_error, ensure,
+	traits::IsType,
 };
 use frame_support::weights::{GetDispatchInfo, FunctionOf, Pays};
 use frame_system::{self as system, ensure_signed};
@@ -100,7 +104,8 @@ pub trait Trait: frame_system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
 	/// A sudo-able call.
-	type Call: Parameter + Dispatchable<Origin=Self::Origin> + GetDispatchInfo;
+	type Call: Parameter + Dispatchable<Origin=Self::Origin> + GetDispatchInfo
+		+ IsType<<Self as frame_system::Trait>::Call>;
 }
 
 decl_module! {
@@ -129,6 +134,10 @@ decl_module! {
 			// This is a public call, so we ensure that the origin is some signed account.
 			let sender = ensure_signed(origin)?;
 			ensure!(sender == Self::key(), Error::<T>::RequireSudo);
+			ensure!(
+				frame_system::Module::<T>::is_callable(call.into_ref()),
+				frame_system::Error::<T>::CallFiltered,
+			);
 
 			let res = call.dispatch(frame_system::RawOrigin::Root.into());
 			Self::deposit_event(RawEvent::Sudid(res.map(|_| ()).map_err(|e| e.error)));
@@ -178,6 +187,10 @@ decl_module! {
 			// This is a public call, so we ensure that the origin is some signed account.
 			let sender = ensure_signed(origin)?;
 			ensure!(sender == Self::key(), Error::<T>::RequireSudo);
+			ensure!(
+				frame_system::Module::<T>::is_callable(call.into_ref()),
+				frame_system::Error::<T>::CallFiltered,
+			);
 
 			let who = T::Lookup::lookup(who)?;
 
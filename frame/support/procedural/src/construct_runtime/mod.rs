@@ -85,7 +85,7 @@ fn construct_runtime_parsed(definition: RuntimeDefinition) -> Result<TokenStream
 
 	let dispatch = decl_outer_dispatch(&name, modules.iter(), &scrate);
 	let metadata = decl_runtime_metadata(&name, modules.iter(), &scrate, &unchecked_extrinsic);
-	let outer_config = decl_outer_config(&name, modules.iter(), &scrate);
+	let outer_config = decl_outer_config(&name, modules.iter(), system_module, &scrate);
 	let inherent = decl_outer_inherent(&block, &unchecked_extrinsic, modules.iter(), &scrate);
 	let validate_unsigned = decl_validate_unsigned(&name, modules.iter(), &scrate);
 
@@ -171,6 +171,7 @@ fn decl_outer_inherent<'a>(
 fn decl_outer_config<'a>(
 	runtime: &'a Ident,
 	module_declarations: impl Iterator<Item = &'a ModuleDeclaration>,
+	system_module: &'a Ident,
 	scrate: &'a TokenStream2,
 ) -> TokenStream2 {
 	let modules_tokens = module_declarations
@@ -197,9 +198,12 @@ fn decl_outer_config<'a>(
 					#module #(#instance)* #(#generics)*,
 			)
 		});
+	// The versions of all modules are written once their genesis configurations were built.
 	quote!(
 		#scrate::sp_runtime::impl_outer_config! {
-			pub struct GenesisConfig for #runtime {
+			pub struct GenesisConfig for #runtime where OnGenesis = (
+				#scrate::traits::store_pallet_versions_at_genesis::<(#system_module, AllModules)>
+			) {
 				#(#modules_tokens)*
 			}
 		}
@@ -375,12 +379,13 @@ fn decl_module_to_index<'a>(
 	num_modules: usize,
 	scrate: &TokenStream2,
 ) -> TokenStream2 {
-	let names = module_declarations.map(|d| &d.name);
+	let names = module_declarations.map(|d| &d.name).collect::<Vec<_>>();
+	let name_strings = names.iter().map(|n| n.to_string());
 	let indices = 0..num_modules;
 
 	quote!(
 		/// Provides an implementation of `ModuleToIndex` to map a module
-		/// to its index in the runtime.
+		/// to its index and its name in the runtime.
 		pub struct ModuleToIndex;
 
 		impl #scrate::traits::ModuleToIndex for ModuleToIndex {
@@ -394,6 +399,17 @@ fn decl_module_to_index<'a>(
 
 				None
 			}
+
+			fn module_to_name<M: 'static>() -> Option<&'static str> {
+				let type_id = #scrate::sp_std::any::TypeId::of::<M>();
+				#(
+					if type_id == #scrate::sp_std::any::TypeId::of::<#names>() {
+						return Some(#name_strings)
+					}
+				)*
+
+				None
+			}
 		}
 	)
 }
@@ -123,6 +123,26 @@ use proc_macro::TokenStream;
 ///   Twox128(module_prefix) ++ Twox128(storage_prefix) ++ Hasher1(encode(key1)) ++ Hasher2(encode(key2))
 ///   ```
 ///
+/// * N-map: `Foo: nmap hasher($hash1) u32, hasher($hash2) u32, hasher($hash3) u64 => u32`:
+///   Implements the [`StorageNMap`](../frame_support/storage/trait.StorageNMap.html) trait using
+///   the [`StorageNMap generator`](../frame_support/storage/generator/trait.StorageNMap.html).
+///   And [`StoragePrefixedMap`](../frame_support/storage/trait.StoragePrefixedMap.html).
+///
+///   Any number of keys can be given, each with its own hasher. Keys are passed as a tuple, e.g.
+///   `Foo::get((k1, k2, k3))`, and any leading subset of them can be used as a prefix, e.g.
+///   `Foo::iter_prefix((k1,))` or `Foo::remove_prefix((k1, k2))`. The genesis config takes a
+///   `Vec<((key1, key2, key3), value)>`.
+///
+///   The generator is implemented with:
+///   * `module_prefix`: $module_prefix
+///   * `storage_prefix`: storage_name
+///   * Key generator: `(Key<$hash1, u32>, Key<$hash2, u32>, Key<$hash3, u64>)`
+///
+///   Thus keys are stored at:
+///   ```nocompile
+///   Twox128(module_prefix) ++ Twox128(storage_prefix) ++ Hasher1(encode(key1)) ++ ... ++ HasherN(encode(keyN))
+///   ```
+///
 /// Supported hashers (ordered from least to best security):
 ///
 /// * `identity` - Just the unrefined key material. Use only when it is known to be a secure hash
@@ -103,6 +103,10 @@ impl GenesisConfigDef {
 
 					parse_quote!( Vec<(#key1, #key2, #value_type)> )
 				},
+				StorageLineTypeDef::NMap(map) => {
+					let keys = map.to_keys_tuple();
+					parse_quote!( Vec<(#keys, #value_type)> )
+				},
 			};
 
 			let default = line.default_value.as_ref()
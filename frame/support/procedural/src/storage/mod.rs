@@ -240,12 +240,17 @@ impl StorageLineDefExt {
 					|| ext::type_contains_ident(&map.key2, &def.module_runtime_generic)
 					|| ext::type_contains_ident(&map.value, &def.module_runtime_generic)
 			}
+			StorageLineTypeDef::NMap(map) => {
+				map.keys.iter().any(|key| ext::type_contains_ident(key, &def.module_runtime_generic))
+					|| ext::type_contains_ident(&map.value, &def.module_runtime_generic)
+			}
 		};
 
 		let query_type = match &storage_def.storage_type {
 			StorageLineTypeDef::Simple(value) => value.clone(),
 			StorageLineTypeDef::Map(map) => map.value.clone(),
 			StorageLineTypeDef::DoubleMap(map) => map.value.clone(),
+			StorageLineTypeDef::NMap(map) => map.value.clone(),
 		};
 		let is_option = ext::extract_type_option(&query_type).is_some();
 		let value_type = ext::extract_type_option(&query_type).unwrap_or(query_type.clone());
@@ -291,6 +296,10 @@ impl StorageLineDefExt {
 				let key2 = &map.key2;
 				quote!( StorageDoubleMap<#key1, #key2, #value_type> )
 			},
+			StorageLineTypeDef::NMap(map) => {
+				let key_generator = map.to_key_generator(&def.hidden_crate);
+				quote!( StorageNMap<#key_generator, #value_type> )
+			},
 		};
 
 		let storage_trait = quote!( storage::#storage_trait_truncated );
@@ -328,6 +337,7 @@ impl StorageLineDefExt {
 pub enum StorageLineTypeDef {
 	Map(MapDef),
 	DoubleMap(DoubleMapDef),
+	NMap(NMapDef),
 	Simple(syn::Type),
 }
 
@@ -347,6 +357,39 @@ pub struct DoubleMapDef {
 	pub value: syn::Type,
 }
 
+pub struct NMapDef {
+	pub hashers: Vec<HasherKind>,
+	pub keys: Vec<syn::Type>,
+	/// This is the query value not the inner value used in storage trait implementation.
+	pub value: syn::Type,
+}
+
+impl NMapDef {
+	/// The key generator of the map: a `Key` for a single key, a tuple of them otherwise.
+	fn to_key_generator(&self, hidden_crate: &Option<syn::Ident>) -> proc_macro2::TokenStream {
+		let hidden_crate_name = hidden_crate.as_ref().map(|i| i.to_string())
+			.unwrap_or_else(|| "decl_storage".to_string());
+		let scrate = generate_crate_access(&hidden_crate_name, "frame-support");
+
+		let mut key_generator = self.hashers.iter().zip(self.keys.iter()).map(|(hasher, key)| {
+			let hasher = hasher.to_storage_hasher_struct();
+			quote!( #scrate::storage::key::Key<#scrate::#hasher, #key> )
+		}).collect::<Vec<_>>();
+
+		if key_generator.len() == 1 {
+			key_generator.remove(0)
+		} else {
+			quote!( ( #( #key_generator, )* ) )
+		}
+	}
+
+	/// The tuple of the key types.
+	fn to_keys_tuple(&self) -> proc_macro2::TokenStream {
+		let keys = &self.keys;
+		quote!( ( #( #keys, )* ) )
+	}
+}
+
 pub struct ExtraGenesisLineDef {
 	attrs: Vec<syn::Attribute>,
 	name: syn::Ident,
@@ -414,6 +457,7 @@ pub fn decl_storage_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStr
 			StorageValue as _,
 			StorageMap as _,
 			StorageDoubleMap as _,
+			StorageNMap as _,
 			StoragePrefixedMap as _,
 		};
 
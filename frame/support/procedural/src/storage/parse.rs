@@ -28,6 +28,7 @@ mod keyword {
 	syn::custom_keyword!(get);
 	syn::custom_keyword!(map);
 	syn::custom_keyword!(double_map);
+	syn::custom_keyword!(nmap);
 	syn::custom_keyword!(opaque_blake2_256);
 	syn::custom_keyword!(opaque_blake2_128);
 	syn::custom_keyword!(blake2_128_concat);
@@ -198,6 +199,7 @@ impl_parse_for_opt!(DeclStorageBuild => keyword::build);
 enum DeclStorageType {
 	Map(DeclStorageMap),
 	DoubleMap(DeclStorageDoubleMap),
+	NMap(DeclStorageNMap),
 	Simple(syn::Type),
 }
 
@@ -207,6 +209,8 @@ impl syn::parse::Parse for DeclStorageType {
 			Ok(Self::Map(input.parse()?))
 		} else if input.peek(keyword::double_map) {
 			Ok(Self::DoubleMap(input.parse()?))
+		} else if input.peek(keyword::nmap) {
+			Ok(Self::NMap(input.parse()?))
 		} else {
 			Ok(Self::Simple(input.parse()?))
 		}
@@ -234,6 +238,20 @@ struct DeclStorageDoubleMap {
 	pub value: syn::Type,
 }
 
+#[derive(Parse, ToTokens, Debug)]
+struct DeclStorageNMapKey {
+	pub hasher: Opt<SetHasher>,
+	pub key: syn::Type,
+}
+
+#[derive(Parse, ToTokens, Debug)]
+struct DeclStorageNMap {
+	pub map_keyword: keyword::nmap,
+	pub keys: ext::PunctuatedTrailing<DeclStorageNMapKey, Token![,]>,
+	pub ass_keyword: Token![=>],
+	pub value: syn::Type,
+}
+
 #[derive(ToTokens, Debug)]
 enum Hasher {
 	Blake2_256(keyword::opaque_blake2_256),
@@ -485,6 +503,21 @@ fn parse_storage_line_defs(
 					value: map.value,
 				}
 			),
+			DeclStorageType::NMap(map) => {
+				let mut hashers = Vec::new();
+				let mut keys = Vec::new();
+				for key in map.keys.inner.into_iter() {
+					hashers.push(key.hasher.inner.ok_or_else(no_hasher_error)?.into());
+					keys.push(key.key);
+				}
+				super::StorageLineTypeDef::NMap(
+					super::NMapDef {
+						hashers,
+						keys,
+						value: map.value,
+					}
+				)
+			},
 			DeclStorageType::Simple(expr) => super::StorageLineTypeDef::Simple(expr),
 		};
 
@@ -255,6 +255,12 @@ impl<T> Parameter for T where T: Codec + EncodeLike + Clone + Eq + fmt::Debug {}
 /// As such, **calling other modules must be avoided**!! Using this function will implement the
 /// [`OnRuntimeUpgrade`](../sp_runtime/traits/trait.OnRuntimeUpgrade.html) trait.
 /// Function signature must be `fn on_runtime_upgrade() -> frame_support::weights::Weight`.
+/// To make a migration run only once, compare
+/// [`GetPalletVersion::on_chain_storage_version`](./traits/trait.GetPalletVersion.html) against the
+/// version the migration upgrades to. The on chain version is set to
+/// [`GetPalletVersion::current_storage_version`](./traits/trait.GetPalletVersion.html), the version
+/// of the crate declaring the module, at genesis and after all `on_runtime_upgrade` functions of the
+/// runtime ran.
 ///
 /// * `on_initialize`: Executes at the beginning of a block. Using this function will
 /// implement the [`OnInitialize`](./trait.OnInitialize.html) trait.
@@ -1080,6 +1086,48 @@ macro_rules! decl_module {
 		{}
 	};
 
+	(@impl_pallet_version
+		$module:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>;
+		$system:ident;
+		{ $( $other_where_bounds:tt )* }
+	) => {
+		impl<$trait_instance: $trait_name$(<I>, $instance: $instantiable)?>
+			$crate::traits::GetPalletVersion
+			for $module<$trait_instance$(, $instance)?> where $( $other_where_bounds )*
+		{
+			fn current_storage_version() -> $crate::traits::PalletVersion {
+				$crate::crate_to_pallet_version!()
+			}
+
+			fn on_chain_storage_version() -> Option<$crate::traits::PalletVersion> {
+				let key = $crate::traits::PalletVersion::storage_key::<
+					<$trait_instance as $system::Trait>::ModuleToIndex, Self
+				>()?;
+
+				$crate::storage::unhashed::get(&key)
+			}
+		}
+
+		impl<$trait_instance: $trait_name$(<I>, $instance: $instantiable)?>
+			$crate::traits::StorePalletVersion
+			for $module<$trait_instance$(, $instance)?> where $( $other_where_bounds )*
+		{
+			fn store_pallet_version() -> $crate::dispatch::Weight {
+				let version = <Self as $crate::traits::GetPalletVersion>::current_storage_version();
+				let stored = version.put_into_storage::<
+					<$trait_instance as $system::Trait>::ModuleToIndex, Self
+				>();
+
+				if stored {
+					<
+						<$trait_instance as $system::Trait>::DbWeight as $crate::traits::Get<_>
+					>::get().writes(1)
+				} else {
+					0
+				}
+			}
+		}
+	};
 
 	(@impl_on_finalize
 		$module:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>;
@@ -1365,6 +1413,12 @@ macro_rules! decl_module {
 			$( $on_runtime_upgrade )*
 		}
 
+		$crate::decl_module! {
+			@impl_pallet_version
+			$mod_type<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?>;
+			$system;
+			{ $( $other_where_bounds )* }
+		}
 
 		$crate::decl_module! {
 			@impl_on_finalize
@@ -2045,7 +2099,8 @@ mod tests {
 	use super::*;
 	use crate::weights::{DispatchInfo, DispatchClass, Pays};
 	use crate::traits::{
-		CallMetadata, GetCallMetadata, GetCallName, OnInitialize, OnFinalize, OnRuntimeUpgrade
+		CallMetadata, GetCallMetadata, GetCallName, OnInitialize, OnFinalize, OnRuntimeUpgrade,
+		GetPalletVersion, StorePalletVersion, PalletVersion,
 	};
 
 	pub trait Trait: system::Trait + Sized where Self::AccountId: From<u32> {
@@ -2057,8 +2112,10 @@ mod tests {
 	pub mod system {
 		use super::*;
 
-		pub trait Trait {
+		pub trait Trait: 'static {
 			type AccountId;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		pub fn ensure_root<R>(_: R) -> DispatchResult {
@@ -2183,6 +2240,8 @@ mod tests {
 
 	impl system::Trait for TraitImpl {
 		type AccountId = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	#[test]
@@ -2241,6 +2300,27 @@ mod tests {
 		assert_eq!(<Module<TraitImpl> as OnRuntimeUpgrade>::on_runtime_upgrade(), 10);
 	}
 
+	#[test]
+	fn pallet_version_should_work() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert_eq!(<Module<TraitImpl> as GetPalletVersion>::on_chain_storage_version(), None);
+			assert_eq!(
+				<Module<TraitImpl> as GetPalletVersion>::current_storage_version(),
+				crate::crate_to_pallet_version!(),
+			);
+
+			<Module<TraitImpl> as StorePalletVersion>::store_pallet_version();
+
+			let current = <Module<TraitImpl> as GetPalletVersion>::current_storage_version();
+			assert_eq!(<Module<TraitImpl> as GetPalletVersion>::on_chain_storage_version(), Some(current));
+
+			let key = PalletVersion::storage_key::<(), Module<TraitImpl>>().unwrap();
+			let pallet_name = <crate::hash::Twox128 as crate::hash::StorageHasher>::hash(b"test");
+			assert_eq!(&key[..16], &pallet_name[..]);
+			assert_eq!(crate::storage::unhashed::get::<PalletVersion>(&key), Some(current));
+		});
+	}
+
 	#[test]
 	fn weight_should_attach_to_call_enum() {
 		// operational.
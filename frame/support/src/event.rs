@@ -543,13 +543,15 @@ mod tests {
 	use codec::{Encode, Decode};
 
 	mod system {
-		pub trait Trait {
+		pub trait Trait: 'static {
 			type Origin;
 			type BlockNumber;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 
 		decl_event!(
@@ -560,13 +562,15 @@ mod tests {
 	}
 
 	mod system_renamed {
-		pub trait Trait {
+		pub trait Trait: 'static {
 			type Origin;
 			type BlockNumber;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 
 		decl_event!(
@@ -577,14 +581,16 @@ mod tests {
 	}
 
 	mod event_module {
-		pub trait Trait {
+		pub trait Trait: 'static {
 			type Origin;
 			type Balance;
 			type BlockNumber;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 
 		decl_event!(
@@ -600,14 +606,16 @@ mod tests {
 	}
 
 	mod event_module2 {
-		pub trait Trait {
+		pub trait Trait: 'static {
 			type Origin;
 			type Balance;
 			type BlockNumber;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 
 		decl_event!(
@@ -631,14 +639,16 @@ mod tests {
 	}
 
 	mod event_module4 {
-		pub trait Trait {
+		pub trait Trait: 'static {
 			type Origin;
 			type Balance;
 			type BlockNumber;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 
 		decl_event!(
@@ -653,14 +663,16 @@ mod tests {
 	}
 
 	mod event_module5 {
-		pub trait Trait {
+		pub trait Trait: 'static {
 			type Origin;
 			type Balance;
 			type BlockNumber;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 
 		decl_event!(
@@ -706,34 +718,46 @@ mod tests {
 		type Origin = u32;
 		type Balance = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	impl event_module2::Trait for TestRuntime {
 		type Origin = u32;
 		type Balance = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	impl system::Trait for TestRuntime {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	impl event_module::Trait for TestRuntime2 {
 		type Origin = u32;
 		type Balance = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	impl event_module2::Trait for TestRuntime2 {
 		type Origin = u32;
 		type Balance = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	impl system_renamed::Trait for TestRuntime2 {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	const EXPECTED_METADATA: OuterEventMetadata = OuterEventMetadata {
@@ -73,8 +73,8 @@ pub use self::hash::{
 	StorageHasher, ReversibleStorageHasher
 };
 pub use self::storage::{
-	StorageValue, StorageMap, StorageDoubleMap, StoragePrefixedMap, IterableStorageMap,
-	IterableStorageDoubleMap, migration
+	StorageValue, StorageMap, StorageDoubleMap, StorageNMap, StoragePrefixedMap, IterableStorageMap,
+	IterableStorageDoubleMap, IterableStorageNMap, migration
 };
 pub use self::dispatch::{Parameter, Callable, IsSubType};
 pub use sp_runtime::{self, ConsensusEngineId, print, traits::Printable};
@@ -236,6 +236,25 @@ macro_rules! assert_ok {
 	}
 }
 
+/// Return the [`PalletVersion`](./traits/struct.PalletVersion.html) of the crate this macro is
+/// called in, as given by the `version` in its `Cargo.toml`.
+#[macro_export]
+macro_rules! crate_to_pallet_version {
+	() => {
+		$crate::traits::PalletVersion {
+			major: env!("CARGO_PKG_VERSION_MAJOR")
+				.parse::<u16>()
+				.expect("Major version needs to fit into u16"),
+			minor: env!("CARGO_PKG_VERSION_MINOR")
+				.parse::<u8>()
+				.expect("Minor version needs to fit into u8"),
+			patch: env!("CARGO_PKG_VERSION_PATCH")
+				.parse::<u8>()
+				.expect("Patch version needs to fit into u8"),
+		}
+	}
+}
+
 /// The void type - it cannot exist.
 // Oh rust, you crack me up...
 #[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
@@ -254,10 +273,13 @@ mod tests {
 		StorageEntryModifier, DefaultByteGetter, StorageHasher,
 	};
 	use sp_std::marker::PhantomData;
+	use storage::{unhashed, generator::StorageNMap as _};
 
-	pub trait Trait {
+	pub trait Trait: 'static {
 		type BlockNumber: Codec + EncodeLike + Default;
 		type Origin;
+		type ModuleToIndex: crate::traits::ModuleToIndex;
+		type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 	}
 
 	mod module {
@@ -266,7 +288,7 @@ mod tests {
 		use super::Trait;
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 	}
 	use self::module::Module;
@@ -290,6 +312,12 @@ mod tests {
 				=> Option<T::BlockNumber>;
 			pub AppendableDM:
 				double_map hasher(blake2_128_concat) u32, hasher(blake2_128_concat) T::BlockNumber => Vec<u32>;
+			pub DataNM build(|_| vec![((15u32, 16u32, 17u64), 42u64)]):
+				nmap hasher(twox_64_concat) u32, hasher(blake2_128_concat) u32, hasher(identity) u64
+				=> u64;
+			pub GenericDataNM get(fn generic_data_nm):
+				nmap hasher(blake2_128_concat) T::BlockNumber, hasher(twox_64_concat) u32
+				=> Option<T::BlockNumber>;
 		}
 	}
 
@@ -297,6 +325,8 @@ mod tests {
 	impl Trait for Test {
 		type BlockNumber = u32;
 		type Origin = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	fn new_test_ext() -> sp_io::TestExternalities {
@@ -505,6 +535,68 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn n_map_basic_insert_remove_should_work() {
+		new_test_ext().execute_with(|| {
+			type NMap = DataNM;
+
+			// initialized during genesis
+			assert_eq!(NMap::get((15u32, 16u32, 17u64)), 42u64);
+
+			// get / insert / take
+			let key = (17u32, 18u32, 19u64);
+			assert_eq!(NMap::get(key), 0u64);
+			NMap::insert(key, 4u64);
+			assert_eq!(NMap::get(key), 4u64);
+			assert_eq!(NMap::take(key), 4u64);
+			assert_eq!(NMap::get(key), 0u64);
+
+			// mutate
+			NMap::mutate(&key, |val| {
+				*val = 15;
+			});
+			assert_eq!(NMap::get(key), 15u64);
+
+			// swap
+			NMap::swap(key, (15u32, 16u32, 17u64));
+			assert_eq!(NMap::get(key), 42u64);
+			assert_eq!(NMap::get((15u32, 16u32, 17u64)), 15u64);
+
+			// remove
+			NMap::remove(key);
+			assert_eq!(NMap::get(key), 0u64);
+
+			// remove prefix
+			NMap::insert((17u32, 18u32, 20u64), 5u64);
+			NMap::insert((17u32, 19u32, 20u64), 6u64);
+			NMap::remove_prefix((17u32, 18u32));
+			assert_eq!(NMap::get((17u32, 18u32, 20u64)), 0u64);
+			assert_eq!(NMap::get((17u32, 19u32, 20u64)), 6u64);
+			NMap::remove_prefix((17u32,));
+			assert_eq!(NMap::get((17u32, 19u32, 20u64)), 0u64);
+			assert_eq!(NMap::get((15u32, 16u32, 17u64)), 15u64);
+		});
+	}
+
+	#[test]
+	fn n_map_getter_and_migration_should_work() {
+		new_test_ext().execute_with(|| {
+			type NMap = GenericDataNM<Test>;
+			type OldKey = (storage::key::Key<Blake2_256, u32>, storage::key::Key<Twox128, u32>);
+
+			NMap::insert((1u32, 2u32), 3u32);
+			assert_eq!(Module::<Test>::generic_data_nm((1u32, 2u32)), Some(3));
+			assert_eq!(Module::<Test>::generic_data_nm((1u32, 3u32)), None);
+
+			let old_key = NMap::storage_n_map_final_key::<OldKey, _>((4u32, 5u32));
+			unhashed::put(&old_key, &6u32);
+			assert_eq!(NMap::migrate_keys::<OldKey, _>((4u32, 5u32)), Some(6));
+			assert!(!unhashed::exists(&old_key));
+			assert_eq!(NMap::get((4u32, 5u32)), Some(6));
+			assert_eq!(NMap::migrate_keys::<OldKey, _>((4u32, 5u32)), None);
+		});
+	}
+
 	const EXPECTED_METADATA: StorageMetadata = StorageMetadata {
 		prefix: DecodeDifferent::Encode("Test"),
 		entries: DecodeDifferent::Encode(
@@ -625,6 +717,39 @@ mod tests {
 					),
 					documentation: DecodeDifferent::Encode(&[]),
 				},
+				StorageEntryMetadata {
+					name: DecodeDifferent::Encode("DataNM"),
+					modifier: StorageEntryModifier::Default,
+					ty: StorageEntryType::NMap {
+						keys: DecodeDifferent::Encode(&["u32", "u32", "u64"]),
+						hashers: DecodeDifferent::Encode(&[
+							StorageHasher::Twox64Concat,
+							StorageHasher::Blake2_128Concat,
+							StorageHasher::Identity,
+						]),
+						value: DecodeDifferent::Encode("u64"),
+					},
+					default: DecodeDifferent::Encode(
+						DefaultByteGetter(&__GetByteStructDataNM(PhantomData::<Test>))
+					),
+					documentation: DecodeDifferent::Encode(&[]),
+				},
+				StorageEntryMetadata {
+					name: DecodeDifferent::Encode("GenericDataNM"),
+					modifier: StorageEntryModifier::Optional,
+					ty: StorageEntryType::NMap {
+						keys: DecodeDifferent::Encode(&["T::BlockNumber", "u32"]),
+						hashers: DecodeDifferent::Encode(&[
+							StorageHasher::Blake2_128Concat,
+							StorageHasher::Twox64Concat,
+						]),
+						value: DecodeDifferent::Encode("T::BlockNumber"),
+					},
+					default: DecodeDifferent::Encode(
+						DefaultByteGetter(&__GetByteStructGenericDataNM(PhantomData::<Test>))
+					),
+					documentation: DecodeDifferent::Encode(&[]),
+				},
 			]
 		),
 	};
@@ -26,12 +26,14 @@ pub use frame_metadata::{
 /// Example:
 /// ```
 ///# mod module0 {
-///#    pub trait Trait {
+///#    pub trait Trait: 'static {
 ///#        type Origin;
 ///#        type BlockNumber;
+///#        type ModuleToIndex: frame_support::traits::ModuleToIndex;
+///#        type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 ///#    }
 ///#    frame_support::decl_module! {
-///#        pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+///#        pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 ///#    }
 ///#
 ///#    frame_support::decl_storage! {
@@ -43,6 +45,8 @@ pub use frame_metadata::{
 ///# impl module0::Trait for Runtime {
 ///#     type Origin = u32;
 ///#     type BlockNumber = u32;
+///#     type ModuleToIndex = ();
+///#     type DbWeight = ();
 ///# }
 ///#
 ///# type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<(), (), (), ()>;
@@ -296,6 +300,7 @@ mod tests {
 			type BlockNumber: From<u32> + Encode;
 			type SomeValue: Get<u32>;
 			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_module! {
@@ -334,6 +339,7 @@ mod tests {
 
 	mod event_module {
 		use crate::dispatch::DispatchResult;
+		use super::system;
 
 		pub trait Trait: super::system::Trait {
 			type Balance;
@@ -368,10 +374,12 @@ mod tests {
 	}
 
 	mod event_module2 {
-		pub trait Trait {
+		pub trait Trait: 'static {
 			type Origin;
 			type Balance;
 			type BlockNumber;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_event!(
@@ -382,7 +390,7 @@ mod tests {
 		);
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 
 		crate::decl_storage! {
@@ -428,6 +436,8 @@ mod tests {
 		type Origin = Origin;
 		type Balance = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	crate::parameter_types! {
@@ -440,6 +450,7 @@ mod tests {
 		type BlockNumber = u32;
 		type SomeValue = SystemValue;
 		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	impl_runtime_metadata!(
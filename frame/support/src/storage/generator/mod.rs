@@ -25,10 +25,12 @@
 
 mod map;
 mod double_map;
+mod nmap;
 mod value;
 
 pub use map::StorageMap;
 pub use double_map::StorageDoubleMap;
+pub use nmap::StorageNMap;
 pub use value::StorageValue;
 
 #[cfg(test)]
@@ -40,18 +42,22 @@ mod tests {
 	use crate::{assert_noop, assert_ok};
 
 	struct Runtime {}
-	pub trait Trait {
+	pub trait Trait: 'static {
 		type Origin;
 		type BlockNumber;
+		type ModuleToIndex: crate::traits::ModuleToIndex;
+		type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 	}
 
 	impl Trait for Runtime {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	decl_module! {
-		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 	}
 
 	crate::decl_storage! {
@@ -0,0 +1,312 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Keys of a `StorageNMap`.
+//!
+//! A key is described by a [`Key`] for each of its parts, naming the hasher and the type of that
+//! part; a key made of several parts is a tuple of those (e.g.
+//! `(Key<Blake2_128Concat, u32>, Key<Twox64Concat, u64>)`).
+
+use sp_std::{prelude::*, marker::PhantomData};
+use codec::{Encode, EncodeLike, FullCodec};
+use crate::hash::{StorageHasher, ReversibleStorageHasher};
+
+/// A single part of the key of a `StorageNMap`, hashed with `Hasher`.
+pub struct Key<Hasher, KeyType>(PhantomData<(Hasher, KeyType)>);
+
+/// A type which can generate the hashed part of a storage key from the parts of the key.
+pub trait KeyGenerator {
+	/// The type of the full key.
+	type Key: EncodeLike<Self::Key>;
+	/// The tuple of the types of every part of the key, used to constrain key arguments.
+	type KArg: Encode;
+
+	/// Generate the hashed key, i.e. the concatenation of every part of `key` hashed with its
+	/// hasher.
+	fn final_key<KArg: EncodeLikeTuple<Self::KArg> + TupleToEncodedIter>(key: KArg) -> Vec<u8>;
+}
+
+/// A key generator for a single part of a key.
+pub trait KeyGeneratorInner: KeyGenerator {
+	/// The hasher of this part of the key.
+	type Hasher: StorageHasher;
+
+	/// Hash the already encoded part of the key.
+	fn final_hash(encoded: &[u8]) -> Vec<u8>;
+}
+
+/// A key generator whose key can be decoded back from the hashed key.
+pub trait ReversibleKeyGenerator: KeyGenerator {
+	/// Decode the key from the start of `key_material`, returning it along with the rest of the
+	/// material.
+	fn decode_final_key(key_material: &[u8]) -> Result<(Self::Key, &[u8]), codec::Error>;
+}
+
+/// A key generator whose leading parts, given as the tuple `P`, can be used as a prefix.
+pub trait HasKeyPrefix<P>: KeyGenerator {
+	/// The remaining parts of the key, as a single type or a tuple of them.
+	type Suffix;
+
+	/// Generate the hashed prefix of the keys starting with `prefix`.
+	fn partial_key(prefix: P) -> Vec<u8>;
+}
+
+/// A key generator whose remaining parts can be decoded back after the prefix `P`.
+pub trait HasReversibleKeyPrefix<P>: ReversibleKeyGenerator + HasKeyPrefix<P> {
+	/// Decode the remaining parts of the key from the hashed key material following the prefix.
+	fn decode_partial_key(key_material: &[u8]) -> Result<Self::Suffix, codec::Error>;
+}
+
+/// A tuple whose elements can be encoded like the elements of the tuple `T`.
+pub trait EncodeLikeTuple<T> {}
+
+/// A tuple which can be encoded element by element.
+pub trait TupleToEncodedIter {
+	/// Encode each element of the tuple.
+	fn to_encoded_iter(&self) -> sp_std::vec::IntoIter<Vec<u8>>;
+}
+
+impl<T: EncodeLikeTuple<U>, U> EncodeLikeTuple<U> for &T {}
+
+impl<T: TupleToEncodedIter> TupleToEncodedIter for &T {
+	fn to_encoded_iter(&self) -> sp_std::vec::IntoIter<Vec<u8>> {
+		(*self).to_encoded_iter()
+	}
+}
+
+impl<H: StorageHasher, K: FullCodec> KeyGenerator for Key<H, K> {
+	type Key = K;
+	type KArg = (K,);
+
+	fn final_key<KArg: EncodeLikeTuple<Self::KArg> + TupleToEncodedIter>(key: KArg) -> Vec<u8> {
+		let encoded = key.to_encoded_iter().next().expect("`KArg` is a tuple of one element; qed");
+		Self::final_hash(&encoded)
+	}
+}
+
+impl<H: StorageHasher, K: FullCodec> KeyGeneratorInner for Key<H, K> {
+	type Hasher = H;
+
+	fn final_hash(encoded: &[u8]) -> Vec<u8> {
+		H::hash(encoded).as_ref().to_vec()
+	}
+}
+
+impl<H: ReversibleStorageHasher, K: FullCodec> ReversibleKeyGenerator for Key<H, K> {
+	fn decode_final_key(key_material: &[u8]) -> Result<(K, &[u8]), codec::Error> {
+		let mut current_key_material = H::reverse(key_material);
+		let key = K::decode(&mut current_key_material)?;
+		Ok((key, current_key_material))
+	}
+}
+
+/// Implement the tuple traits for every tuple suffix of the given `(KeyGenerator Arg Key)` list.
+macro_rules! impl_for_tuples {
+	($first:tt) => {
+		impl_tuple!($first);
+	};
+	($first:tt $($rest:tt)+) => {
+		impl_tuple!($first $($rest)+);
+		impl_for_tuples!($($rest)+);
+	};
+}
+
+macro_rules! impl_tuple {
+	($(($KG:ident $A:ident $K:ident))+) => {
+		impl<$($K: Encode, $A: EncodeLike<$K>),+> EncodeLikeTuple<($($K,)+)> for ($($A,)+) {}
+
+		impl<$($A: Encode),+> TupleToEncodedIter for ($($A,)+) {
+			#[allow(non_snake_case)]
+			fn to_encoded_iter(&self) -> sp_std::vec::IntoIter<Vec<u8>> {
+				let ($($A,)+) = self;
+				vec![$($A.encode()),+].into_iter()
+			}
+		}
+
+		impl_key_generator!($(($KG $A $K))+);
+	};
+}
+
+macro_rules! impl_key_generator {
+	(($KG:ident $A:ident $K:ident)) => {};
+	(($KG0:ident $A0:ident $K0:ident) $(($KG:ident $A:ident $K:ident))+) => {
+		impl<$KG0: KeyGeneratorInner, $($KG: KeyGeneratorInner),+> KeyGenerator
+			for ($KG0, $($KG,)+)
+		{
+			type Key = ($KG0::Key, $($KG::Key,)+);
+			type KArg = Self::Key;
+
+			fn final_key<KArg: EncodeLikeTuple<Self::KArg> + TupleToEncodedIter>(
+				key: KArg,
+			) -> Vec<u8> {
+				let mut encoded = key.to_encoded_iter();
+				let mut final_key = $KG0::final_hash(
+					&encoded.next().expect("`KArg` has as many elements as the key; qed"),
+				);
+				$(
+					final_key.extend_from_slice(&$KG::final_hash(
+						&encoded.next().expect("`KArg` has as many elements as the key; qed"),
+					));
+				)+
+				final_key
+			}
+		}
+
+		impl<
+			$KG0: KeyGeneratorInner + ReversibleKeyGenerator,
+			$($KG: KeyGeneratorInner + ReversibleKeyGenerator),+
+		> ReversibleKeyGenerator for ($KG0, $($KG,)+) {
+			fn decode_final_key(key_material: &[u8]) -> Result<(Self::Key, &[u8]), codec::Error> {
+				let (key0, mut current_key_material) = $KG0::decode_final_key(key_material)?;
+				let key = (
+					key0,
+					$({
+						let (key, key_material) = $KG::decode_final_key(current_key_material)?;
+						current_key_material = key_material;
+						key
+					},)+
+				);
+				Ok((key, current_key_material))
+			}
+		}
+
+		impl_key_prefix_for!(($KG0) ($($KG),+));
+	};
+}
+
+/// Implement `HasKeyPrefix` for every split of the key generators into a non-empty prefix and a
+/// non-empty suffix.
+macro_rules! impl_key_prefix_for {
+	(($($P:ident),+) ($S:ident)) => {
+		impl_key_prefix!(($($P),+) ($S));
+	};
+	(($($P:ident),+) ($S:ident, $($R:ident),+)) => {
+		impl_key_prefix!(($($P),+) ($S, $($R),+));
+		impl_key_prefix_for!(($($P,)+ $S) ($($R),+));
+	};
+}
+
+macro_rules! impl_key_prefix {
+	(($($P:ident),+) ($S:ident)) => {
+		impl<$($P: KeyGeneratorInner,)+ $S: KeyGeneratorInner> HasKeyPrefix<($($P::Key,)+)>
+			for ($($P,)+ $S,)
+		{
+			type Suffix = $S::Key;
+
+			fn partial_key(prefix: ($($P::Key,)+)) -> Vec<u8> {
+				impl_key_prefix!(@partial_key prefix $($P)+)
+			}
+		}
+
+		impl<
+			$($P: KeyGeneratorInner + ReversibleKeyGenerator,)+
+			$S: KeyGeneratorInner + ReversibleKeyGenerator
+		> HasReversibleKeyPrefix<($(<$P as KeyGenerator>::Key,)+)> for ($($P,)+ $S,) {
+			fn decode_partial_key(key_material: &[u8]) -> Result<Self::Suffix, codec::Error> {
+				$S::decode_final_key(key_material).map(|(key, _)| key)
+			}
+		}
+	};
+	(($($P:ident),+) ($($S:ident),+)) => {
+		impl<$($P: KeyGeneratorInner,)+ $($S: KeyGeneratorInner),+> HasKeyPrefix<($($P::Key,)+)>
+			for ($($P,)+ $($S,)+)
+		{
+			type Suffix = ($($S::Key,)+);
+
+			fn partial_key(prefix: ($($P::Key,)+)) -> Vec<u8> {
+				impl_key_prefix!(@partial_key prefix $($P)+)
+			}
+		}
+
+		impl<
+			$($P: KeyGeneratorInner + ReversibleKeyGenerator,)+
+			$($S: KeyGeneratorInner + ReversibleKeyGenerator),+
+		> HasReversibleKeyPrefix<($(<$P as KeyGenerator>::Key,)+)> for ($($P,)+ $($S,)+) {
+			fn decode_partial_key(key_material: &[u8]) -> Result<Self::Suffix, codec::Error> {
+				<($($S,)+) as ReversibleKeyGenerator>::decode_final_key(key_material)
+					.map(|(key, _)| key)
+			}
+		}
+	};
+	(@partial_key $prefix:ident $($P:ident)+) => {{
+		let mut encoded = $prefix.to_encoded_iter();
+		let mut partial_key = Vec::new();
+		$(
+			partial_key.extend_from_slice(&$P::final_hash(
+				&encoded.next().expect("`prefix` has as many elements as the prefix keys; qed"),
+			));
+		)+
+		partial_key
+	}};
+}
+
+impl_for_tuples!(
+	(KG1 A1 K1) (KG2 A2 K2) (KG3 A3 K3) (KG4 A4 K4) (KG5 A5 K5) (KG6 A6 K6)
+	(KG7 A7 K7) (KG8 A8 K8) (KG9 A9 K9) (KG10 A10 K10) (KG11 A11 K11) (KG12 A12 K12)
+	(KG13 A13 K13) (KG14 A14 K14) (KG15 A15 K15) (KG16 A16 K16) (KG17 A17 K17) (KG18 A18 K18)
+);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::hash::{Blake2_128Concat, Twox64Concat, Identity};
+
+	type TripleKey = (Key<Blake2_128Concat, u16>, Key<Twox64Concat, u32>, Key<Identity, u64>);
+
+	#[test]
+	fn final_key_is_concatenation_of_hashed_parts() {
+		let expected = [
+			Blake2_128Concat::hash(&1u16.encode()),
+			Twox64Concat::hash(&2u32.encode()),
+			Identity::hash(&3u64.encode()),
+		].concat();
+		assert_eq!(TripleKey::final_key((1u16, 2u32, 3u64)), expected);
+		assert_eq!(TripleKey::final_key(&(1u16, 2u32, 3u64)), expected);
+		assert_eq!(Key::<Identity, u64>::final_key((3u64,)), 3u64.encode());
+	}
+
+	#[test]
+	fn final_key_can_be_decoded() {
+		let final_key = TripleKey::final_key((1u16, 2u32, 3u64));
+		assert_eq!(
+			TripleKey::decode_final_key(&final_key).unwrap(),
+			((1u16, 2u32, 3u64), &[][..]),
+		);
+	}
+
+	#[test]
+	fn partial_keys_work() {
+		let final_key = TripleKey::final_key((1u16, 2u32, 3u64));
+
+		let prefix = <TripleKey as HasKeyPrefix<(u16,)>>::partial_key((1,));
+		assert!(final_key.starts_with(&prefix));
+		assert_eq!(
+			<TripleKey as HasReversibleKeyPrefix<(u16,)>>::decode_partial_key(
+				&final_key[prefix.len()..],
+			).unwrap(),
+			(2, 3),
+		);
+
+		let prefix = <TripleKey as HasKeyPrefix<(u16, u32)>>::partial_key((1, 2));
+		assert!(final_key.starts_with(&prefix));
+		assert_eq!(
+			<TripleKey as HasReversibleKeyPrefix<(u16, u32)>>::decode_partial_key(
+				&final_key[prefix.len()..],
+			).unwrap(),
+			3,
+		);
+	}
+}
@@ -20,6 +20,9 @@ use sp_std::prelude::*;
 use codec::{Encode, Decode};
 use crate::{StorageHasher, Twox128};
 use crate::hash::ReversibleStorageHasher;
+use crate::storage::key::{
+	KeyGenerator, ReversibleKeyGenerator, HasKeyPrefix, EncodeLikeTuple, TupleToEncodedIter,
+};
 
 /// Utility to iterate through raw items in storage.
 pub struct StorageIterator<T> {
@@ -145,6 +148,69 @@ impl<K: Decode + Sized, T: Decode + Sized, H: ReversibleStorageHasher> Iterator
 	}
 }
 
+/// Utility to iterate through the items of an n-map in storage, decoding their keys.
+pub struct StorageNMapKeyIterator<K: ReversibleKeyGenerator, T> {
+	prefix: Vec<u8>,
+	previous_key: Vec<u8>,
+	drain: bool,
+	_phantom: ::sp_std::marker::PhantomData<(K, T)>,
+}
+
+impl<K: ReversibleKeyGenerator, T> StorageNMapKeyIterator<K, T> {
+	/// Construct iterator to iterate over map items in `module` for the map called `item`.
+	pub fn new(module: &[u8], item: &[u8]) -> Self {
+		Self::with_suffix(module, item, &[][..])
+	}
+
+	/// Construct iterator to iterate over map items in `module` for the map called `item`.
+	pub fn with_suffix(module: &[u8], item: &[u8], suffix: &[u8]) -> Self {
+		let mut prefix = Vec::new();
+		prefix.extend_from_slice(&Twox128::hash(module));
+		prefix.extend_from_slice(&Twox128::hash(item));
+		prefix.extend_from_slice(suffix);
+		let previous_key = prefix.clone();
+		Self { prefix, previous_key, drain: false, _phantom: Default::default() }
+	}
+
+	/// Mutate this iterator into a draining iterator; items iterated are removed from storage.
+	pub fn drain(mut self) -> Self {
+		self.drain = true;
+		self
+	}
+}
+
+impl<K: ReversibleKeyGenerator, T: Decode + Sized> Iterator for StorageNMapKeyIterator<K, T> {
+	type Item = (K::Key, T);
+
+	fn next(&mut self) -> Option<(K::Key, T)> {
+		loop {
+			let maybe_next = sp_io::storage::next_key(&self.previous_key)
+				.filter(|n| n.starts_with(&self.prefix));
+			break match maybe_next {
+				Some(next) => {
+					self.previous_key = next.clone();
+					match K::decode_final_key(&next[self.prefix.len()..]) {
+						Ok((key, _)) => {
+							let maybe_value = frame_support::storage::unhashed::get::<T>(&next);
+							match maybe_value {
+								Some(value) => {
+									if self.drain {
+										frame_support::storage::unhashed::kill(&next);
+									}
+									Some((key, value))
+								}
+								None => continue,
+							}
+						}
+						Err(_) => continue,
+					}
+				}
+				None => None,
+			}
+		}
+	}
+}
+
 /// Get a particular value in storage by the `module`, the map's `item` name and the key `hash`.
 pub fn have_storage_value(module: &[u8], item: &[u8], hash: &[u8]) -> bool {
 	get_storage_value::<()>(module, item, hash).is_some()
@@ -194,3 +260,23 @@ pub fn take_storage_item<K: Encode + Sized, T: Decode + Sized, H: StorageHasher>
 ) -> Option<T> {
 	take_storage_value(module, item, key.using_encoded(H::hash).as_ref())
 }
+
+/// Take a particular value in storage by the `module`, the n-map's `item` name and the `key`,
+/// hashed as described by the key generator `K`.
+pub fn take_storage_n_map_item<K, KArg, T>(module: &[u8], item: &[u8], key: KArg) -> Option<T> where
+	K: KeyGenerator,
+	KArg: EncodeLikeTuple<K::KArg> + TupleToEncodedIter,
+	T: Decode + Sized,
+{
+	take_storage_value(module, item, &K::final_key(key))
+}
+
+/// Remove all values in storage under the `module`, the n-map's `item` name and the leading keys
+/// `partial_key`, hashed as described by the key generator `K`.
+pub fn remove_storage_n_map_prefix<K: HasKeyPrefix<KP>, KP>(
+	module: &[u8],
+	item: &[u8],
+	partial_key: KP,
+) {
+	remove_storage_prefix(module, item, &K::partial_key(partial_key))
+}
@@ -29,6 +29,7 @@ use sp_runtime::{
 };
 use crate::dispatch::Parameter;
 use crate::storage::StorageMap;
+use crate::hash::{Twox128, StorageHasher};
 use impl_trait_for_tuples::impl_for_tuples;
 
 /// An abstraction of a value stored within storage, but possibly as part of a larger composite
@@ -190,6 +191,54 @@ impl<T: Default> Get<T> for () {
 	fn get() -> T { T::default() }
 }
 
+/// Simple trait for providing a filter over a reference to some type.
+pub trait Filter<T> {
+	/// Determine if a given value should be allowed through the filter (returns `true`) or not.
+	fn filter(_: &T) -> bool;
+}
+
+impl<T> Filter<T> for () {
+	fn filter(_: &T) -> bool { true }
+}
+
+/// Trait to be used when types are exactly same.
+///
+/// This allows to convert back and forth from the type, a reference and a mutable reference.
+pub trait IsType<T>: Into<T> + From<T> {
+	/// Cast reference.
+	fn from_ref(t: &T) -> &Self;
+
+	/// Cast reference.
+	fn into_ref(&self) -> &T;
+
+	/// Cast mutable reference.
+	fn from_mut(t: &mut T) -> &mut Self;
+
+	/// Cast mutable reference.
+	fn into_mut(&mut self) -> &mut T;
+}
+
+impl<T> IsType<T> for T {
+	fn from_ref(t: &T) -> &Self { t }
+	fn into_ref(&self) -> &T { self }
+	fn from_mut(t: &mut T) -> &mut Self { t }
+	fn into_mut(&mut self) -> &mut T { self }
+}
+
+/// Simple trait for providing a filter over a reference to some type, given an instance of itself.
+pub trait InstanceFilter<T>: Sized + Send + Sync {
+	/// Determine if a given value should be allowed through the filter (returns `true`) or not.
+	fn filter(&self, _: &T) -> bool;
+
+	/// Determines whether `self` matches at least everything that `_o` does.
+	fn is_superset(&self, _o: &Self) -> bool { false }
+}
+
+impl<T> InstanceFilter<T> for () {
+	fn filter(&self, _: &T) -> bool { true }
+	fn is_superset(&self, _o: &Self) -> bool { true }
+}
+
 /// A trait for querying whether a type can be said to "contain" a value.
 pub trait Contains<T: Ord> {
 	/// Return `true` if this "contains" the given value `t`.
@@ -848,6 +897,41 @@ pub trait LockableCurrency<AccountId>: Currency<AccountId> {
 	);
 }
 
+/// Abstraction over a store of many fungible assets, each identified by an `AssetId`.
+pub trait MultiAsset<AccountId> {
+	/// The identifier of an asset.
+	type AssetId: FullCodec + Copy + Eq + PartialEq + Debug;
+
+	/// The balance of an account in any one asset.
+	type Balance: AtLeast32Bit + FullCodec + Copy + Debug + Default;
+
+	/// The free balance of `who` in the asset `asset_id`.
+	fn free_balance(asset_id: Self::AssetId, who: &AccountId) -> Self::Balance;
+
+	/// Remove `value` of the asset `asset_id` from the free balance of `who`, reducing the total
+	/// supply of the asset.
+	///
+	/// Returns an `Err` and changes nothing if the asset is unknown, the balance is too low or
+	/// the withdrawal is otherwise not allowed for the given `reasons`.
+	fn withdraw(
+		asset_id: Self::AssetId,
+		who: &AccountId,
+		value: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult;
+
+	/// Add `value` of the asset `asset_id` to the free balance of `who`, increasing the total
+	/// supply of the asset.
+	///
+	/// Returns an `Err` and changes nothing if `who` does not already hold the asset or the
+	/// deposit is otherwise not possible.
+	fn deposit_into_existing(
+		asset_id: Self::AssetId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> DispatchResult;
+}
+
 /// A vesting schedule over a currency. This allows a particular currency to have vesting limits
 /// applied to it.
 pub trait VestingSchedule<AccountId> {
@@ -861,10 +945,10 @@ pub trait VestingSchedule<AccountId> {
 	/// Returns `None` if the account has no vesting schedule.
 	fn vesting_balance(who: &AccountId) -> Option<<Self::Currency as Currency<AccountId>>::Balance>;
 
-	/// Adds a vesting schedule to a given account.
+	/// Adds a vesting schedule to a given account, alongside any existing ones.
 	///
-	/// If there already exists a vesting schedule for the given account, an `Err` is returned
-	/// and nothing is updated.
+	/// If the account cannot take another vesting schedule, or the schedule is invalid, an `Err`
+	/// is returned and nothing is updated.
 	///
 	/// Is a no-op if the amount to be vested is zero.
 	///
@@ -876,10 +960,18 @@ pub trait VestingSchedule<AccountId> {
 		starting_block: Self::Moment,
 	) -> DispatchResult;
 
-	/// Remove a vesting schedule for a given account.
+	/// Checks if `add_vesting_schedule` would work against `who`.
+	fn can_add_vesting_schedule(
+		who: &AccountId,
+		locked: <Self::Currency as Currency<AccountId>>::Balance,
+		per_block: <Self::Currency as Currency<AccountId>>::Balance,
+		starting_block: Self::Moment,
+	) -> DispatchResult;
+
+	/// Remove the vesting schedule at `schedule_index` for a given account.
 	///
 	/// NOTE: This doesn't alter the free balance of the account.
-	fn remove_vesting_schedule(who: &AccountId);
+	fn remove_vesting_schedule(who: &AccountId, schedule_index: u32) -> DispatchResult;
 }
 
 bitmask! {
@@ -1077,16 +1169,22 @@ pub trait ValidatorRegistration<ValidatorId> {
 	fn is_registered(id: &ValidatorId) -> bool;
 }
 
-/// Something that can convert a given module into the index of the module in the runtime.
+/// Something that can convert a given module into the index and the name of the module in the
+/// runtime.
 ///
-/// The index of a module is determined by the position it appears in `construct_runtime!`.
+/// The index of a module is determined by the position it appears in `construct_runtime!`, the
+/// name is the one given to the module in `construct_runtime!`.
 pub trait ModuleToIndex {
 	/// Convert the given module `M` into an index.
 	fn module_to_index<M: 'static>() -> Option<usize>;
+
+	/// Convert the given module `M` into its name.
+	fn module_to_name<M: 'static>() -> Option<&'static str>;
 }
 
 impl ModuleToIndex for () {
 	fn module_to_index<M: 'static>() -> Option<usize> { Some(0) }
+	fn module_to_name<M: 'static>() -> Option<&'static str> { Some("test") }
 }
 
 /// The function and pallet name of the Call.
@@ -1160,6 +1258,121 @@ impl OnRuntimeUpgrade for Tuple {
 	}
 }
 
+/// The storage key postfix that is used to store the [`PalletVersion`] per pallet.
+///
+/// The full storage key is built by using:
+/// Twox128([`ModuleToIndex::module_to_name`]) ++ Twox128([`PALLET_VERSION_STORAGE_KEY_POSTFIX`])
+pub const PALLET_VERSION_STORAGE_KEY_POSTFIX: &[u8] = b":__PALLET_VERSION__:";
+
+/// The version of a pallet.
+///
+/// Each pallet version is stored in the state under a fixed key. See
+/// [`PALLET_VERSION_STORAGE_KEY_POSTFIX`] for how this key is built.
+#[derive(RuntimeDebug, Eq, PartialEq, Encode, Decode, Ord, PartialOrd, Clone, Copy)]
+pub struct PalletVersion {
+	/// The major version of the pallet.
+	pub major: u16,
+	/// The minor version of the pallet.
+	pub minor: u8,
+	/// The patch version of the pallet.
+	pub patch: u8,
+}
+
+impl PalletVersion {
+	/// Creates a new instance of `Self`.
+	pub fn new(major: u16, minor: u8, patch: u8) -> Self {
+		Self { major, minor, patch }
+	}
+
+	/// Returns the storage key for the version of the module `M`.
+	///
+	/// See [`PALLET_VERSION_STORAGE_KEY_POSTFIX`] on how this key is built.
+	///
+	/// Returns `None` if the given `M` isn't part of the runtime and thus has no name.
+	pub fn storage_key<MI: ModuleToIndex, M: 'static>() -> Option<[u8; 32]> {
+		let name = MI::module_to_name::<M>()?;
+
+		let pallet_name = Twox128::hash(name.as_bytes());
+		let postfix = Twox128::hash(PALLET_VERSION_STORAGE_KEY_POSTFIX);
+
+		let mut final_key = [0u8; 32];
+		final_key[..16].copy_from_slice(&pallet_name);
+		final_key[16..].copy_from_slice(&postfix);
+
+		Some(final_key)
+	}
+
+	/// Put this version into the storage as the version of the module `M`.
+	///
+	/// It will use the storage key that is associated with the given module `M`.
+	///
+	/// Returns `false` if the given `M` isn't part of the runtime and nothing was written.
+	pub fn put_into_storage<MI: ModuleToIndex, M: 'static>(&self) -> bool {
+		match Self::storage_key::<MI, M>() {
+			Some(key) => {
+				crate::storage::unhashed::put(&key, self);
+				true
+			},
+			None => false,
+		}
+	}
+}
+
+/// Provides information about the version of a pallet.
+///
+/// The current version of a pallet is taken from the `Cargo.toml` of the crate declaring the
+/// pallet (see [`crate_to_pallet_version!`]). The version stored on chain is set to the current
+/// version by [`StorePalletVersion`] at genesis and after all runtime upgrade hooks ran.
+///
+/// This trait is implemented by `decl_module!` for every module.
+pub trait GetPalletVersion {
+	/// Returns the current version of the pallet, as declared by the crate of the pallet.
+	fn current_storage_version() -> PalletVersion;
+
+	/// Returns the version of the pallet that is stored on chain.
+	///
+	/// Returns `None` if no version was stored yet, i.e. the chain started before the pallet
+	/// version was introduced and the runtime was never upgraded since, or if the pallet isn't
+	/// part of the runtime.
+	///
+	/// Migrations should compare this version against the version they migrate to, so that
+	/// running them a second time is a no-op.
+	fn on_chain_storage_version() -> Option<PalletVersion>;
+}
+
+/// Stores the current version of a pallet on chain.
+///
+/// This is called when building the genesis storage of a runtime declared with
+/// `construct_runtime!` and by the executive after all `OnRuntimeUpgrade` hooks of the runtime
+/// ran, so that migrations see the version of the pallet before the upgrade.
+///
+/// This trait is implemented by `decl_module!` for every module.
+pub trait StorePalletVersion {
+	/// Put the [`GetPalletVersion::current_storage_version`] into storage.
+	///
+	/// Return the weight consumed.
+	fn store_pallet_version() -> crate::weights::Weight { 0 }
+}
+
+#[impl_for_tuples(30)]
+impl StorePalletVersion for Tuple {
+	fn store_pallet_version() -> crate::weights::Weight {
+		let mut weight = 0;
+		for_tuples!( #( weight = weight.saturating_add(Tuple::store_pallet_version()); )* );
+		weight
+	}
+}
+
+/// Store the current version of all `Modules` into the genesis `storage`.
+///
+/// Used by the `GenesisConfig` generated by `construct_runtime!`.
+#[cfg(feature = "std")]
+pub fn store_pallet_versions_at_genesis<Modules: StorePalletVersion>(storage: &mut sp_runtime::Storage) {
+	crate::BasicExternalities::execute_with_storage(storage, || {
+		Modules::store_pallet_version();
+	});
+}
+
 /// Off-chain computation trait.
 ///
 /// Implementing this trait on a module allows you to perform long-running tasks
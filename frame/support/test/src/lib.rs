@@ -22,14 +22,18 @@
 #![deny(warnings)]
 
 /// The configuration trait
-pub trait Trait {
+pub trait Trait: 'static {
 	/// The runtime origin type.
 	type Origin;
 	/// The block number type.
 	type BlockNumber;
+	/// The information about the modules of the runtime.
+	type ModuleToIndex: frame_support::traits::ModuleToIndex;
+	/// The db weights.
+	type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 }
 
 frame_support::decl_module! {
 	/// Some test module
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 }
@@ -94,6 +94,7 @@ impl system::Trait for Runtime {
 	type AccountId = AccountId;
 	type Event = Event;
 	type ModuleToIndex = ModuleToIndex;
+	type DbWeight = ();
 }
 
 frame_support::construct_runtime!(
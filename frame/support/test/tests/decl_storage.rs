@@ -24,12 +24,14 @@ mod tests {
 	use codec::{Encode, Decode, EncodeLike};
 
 	frame_support::decl_module! {
-		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 	}
 
-	pub trait Trait {
+	pub trait Trait: 'static {
 		type Origin: Encode + Decode + EncodeLike + std::default::Default;
 		type BlockNumber;
+		type ModuleToIndex: frame_support::traits::ModuleToIndex;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 
 	frame_support::decl_storage! {
@@ -87,6 +89,8 @@ mod tests {
 	impl Trait for TraitImpl {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	const EXPECTED_METADATA: StorageMetadata = StorageMetadata {
@@ -413,13 +417,15 @@ mod tests {
 #[cfg(test)]
 #[allow(dead_code)]
 mod test2 {
-	pub trait Trait {
+	pub trait Trait: 'static {
 		type Origin;
 		type BlockNumber;
+		type ModuleToIndex: frame_support::traits::ModuleToIndex;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 
 	frame_support::decl_module! {
-		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 	}
 
 	type PairOf<T> = (T, T);
@@ -443,18 +449,22 @@ mod test2 {
 	impl Trait for TraitImpl {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 }
 
 #[cfg(test)]
 #[allow(dead_code)]
 mod test3 {
-	pub trait Trait {
+	pub trait Trait: 'static {
 		type Origin;
 		type BlockNumber;
+		type ModuleToIndex: frame_support::traits::ModuleToIndex;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 	frame_support::decl_module! {
-		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 	}
 	frame_support::decl_storage! {
 		trait Store for Module<T: Trait> as Test {
@@ -469,6 +479,8 @@ mod test3 {
 	impl Trait for TraitImpl {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 }
 
@@ -478,13 +490,15 @@ mod test_append_and_len {
 	use sp_io::TestExternalities;
 	use codec::{Encode, Decode};
 
-	pub trait Trait {
+	pub trait Trait: 'static {
 		type Origin;
 		type BlockNumber;
+		type ModuleToIndex: frame_support::traits::ModuleToIndex;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 
 	frame_support::decl_module! {
-		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 	}
 
 	#[derive(PartialEq, Eq, Clone, Encode, Decode)]
@@ -513,6 +527,8 @@ mod test_append_and_len {
 	impl Trait for Test {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	#[test]
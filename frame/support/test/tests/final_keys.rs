@@ -16,19 +16,21 @@
 
 use frame_support::storage::unhashed;
 use codec::Encode;
-use frame_support::{StorageDoubleMap, StorageMap, StorageValue, StoragePrefixedMap};
+use frame_support::{StorageDoubleMap, StorageMap, StorageNMap, StorageValue, StoragePrefixedMap};
 use sp_io::{TestExternalities, hashing::{twox_64, twox_128, blake2_128}};
 
 mod no_instance {
 	use codec::{Encode, Decode, EncodeLike};
 
-	pub trait Trait {
+	pub trait Trait: 'static {
 		type Origin;
 		type BlockNumber: Encode + Decode + EncodeLike + Default + Clone;
+		type ModuleToIndex: frame_support::traits::ModuleToIndex;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 
 	frame_support::decl_module! {
-		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 	}
 
 	frame_support::decl_storage!{
@@ -41,6 +43,9 @@ mod no_instance {
 			pub DoubleMap: double_map hasher(blake2_128_concat) u32, hasher(blake2_128_concat) u32 => u32;
 			pub DoubleMap2: double_map hasher(twox_64_concat) u32, hasher(twox_64_concat) u32 => u32;
 
+			pub NMap: nmap hasher(blake2_128_concat) u32, hasher(twox_64_concat) u16,
+				hasher(identity) u8 => u32;
+
 			pub TestGenericValue get(fn test_generic_value) config(): Option<T::BlockNumber>;
 			pub TestGenericDoubleMap get(fn foo2) config(test_generic_double_map):
 				double_map hasher(blake2_128_concat) u32, hasher(blake2_128_concat) T::BlockNumber => Option<u32>;
@@ -49,11 +54,13 @@ mod no_instance {
 }
 
 mod instance {
-	pub trait Trait<I = DefaultInstance>: super::no_instance::Trait {}
+	use super::no_instance;
+
+	pub trait Trait<I = DefaultInstance>: no_instance::Trait {}
 
 	frame_support::decl_module! {
 		pub struct Module<T: Trait<I>, I: Instantiable = DefaultInstance>
-			for enum Call where origin: T::Origin {}
+			for enum Call where origin: T::Origin, system=no_instance {}
 	}
 
 	frame_support::decl_storage!{
@@ -68,6 +75,9 @@ mod instance {
 			pub DoubleMap: double_map hasher(blake2_128_concat) u32, hasher(blake2_128_concat) u32 => u32;
 			pub DoubleMap2: double_map hasher(twox_64_concat) u32, hasher(twox_64_concat) u32 => u32;
 
+			pub NMap: nmap hasher(blake2_128_concat) u32, hasher(twox_64_concat) u16,
+				hasher(identity) u8 => u32;
+
 			pub TestGenericValue get(fn test_generic_value) config(): Option<T::BlockNumber>;
 			pub TestGenericDoubleMap get(fn foo2) config(test_generic_double_map):
 				double_map hasher(blake2_128_concat) u32, hasher(blake2_128_concat) T::BlockNumber => Option<u32>;
@@ -123,6 +133,14 @@ fn final_keys_no_instance() {
 		k.extend(2u32.using_encoded(twox_64_concat));
 		assert_eq!(unhashed::get::<u32>(&k), Some(3u32));
 		assert_eq!(&k[..32], &<no_instance::DoubleMap2>::final_prefix());
+
+		<no_instance::NMap>::insert((1u32, 2u16, 3u8), 4u32);
+		let mut k = [twox_128(b"FinalKeysNone"), twox_128(b"NMap")].concat();
+		k.extend(1u32.using_encoded(blake2_128_concat));
+		k.extend(2u16.using_encoded(twox_64_concat));
+		k.extend(3u8.encode());
+		assert_eq!(unhashed::get::<u32>(&k), Some(4u32));
+		assert_eq!(&k[..32], &<no_instance::NMap>::final_prefix());
 	});
 }
 
@@ -158,6 +176,14 @@ fn final_keys_default_instance() {
 		k.extend(2u32.using_encoded(twox_64_concat));
 		assert_eq!(unhashed::get::<u32>(&k), Some(3u32));
 		assert_eq!(&k[..32], &<instance::DoubleMap2<instance::DefaultInstance>>::final_prefix());
+
+		<instance::NMap<instance::DefaultInstance>>::insert((1u32, 2u16, 3u8), 4u32);
+		let mut k = [twox_128(b"FinalKeysSome"), twox_128(b"NMap")].concat();
+		k.extend(1u32.using_encoded(blake2_128_concat));
+		k.extend(2u16.using_encoded(twox_64_concat));
+		k.extend(3u8.encode());
+		assert_eq!(unhashed::get::<u32>(&k), Some(4u32));
+		assert_eq!(&k[..32], &<instance::NMap<instance::DefaultInstance>>::final_prefix());
 	});
 }
 
@@ -193,5 +219,13 @@ fn final_keys_instance_2() {
 		k.extend(2u32.using_encoded(twox_64_concat));
 		assert_eq!(unhashed::get::<u32>(&k), Some(3u32));
 		assert_eq!(&k[..32], &<instance::DoubleMap2<instance::Instance2>>::final_prefix());
+
+		<instance::NMap<instance::Instance2>>::insert((1u32, 2u16, 3u8), 4u32);
+		let mut k = [twox_128(b"Instance2FinalKeysSome"), twox_128(b"NMap")].concat();
+		k.extend(1u32.using_encoded(blake2_128_concat));
+		k.extend(2u16.using_encoded(twox_64_concat));
+		k.extend(3u8.encode());
+		assert_eq!(unhashed::get::<u32>(&k), Some(4u32));
+		assert_eq!(&k[..32], &<instance::NMap<instance::Instance2>>::final_prefix());
 	});
 }
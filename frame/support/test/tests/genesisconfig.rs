@@ -14,13 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
-pub trait Trait {
+pub trait Trait: 'static {
 	type BlockNumber: codec::Codec + codec::EncodeLike + Default;
 	type Origin;
+	type ModuleToIndex: frame_support::traits::ModuleToIndex;
+	type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 }
 
 frame_support::decl_module! {
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 }
 
 frame_support::decl_storage! {
@@ -34,6 +36,8 @@ struct Test;
 impl Trait for Test {
 	type BlockNumber = u32;
 	type Origin = ();
+	type ModuleToIndex = ();
+	type DbWeight = ();
 }
 
 #[test]
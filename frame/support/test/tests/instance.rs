@@ -238,6 +238,7 @@ impl system::Trait for Runtime {
 	type AccountId = AccountId;
 	type Event = Event;
 	type ModuleToIndex = ();
+	type DbWeight = ();
 }
 
 frame_support::construct_runtime!(
@@ -163,6 +163,7 @@ impl system::Trait for Runtime {
 	type AccountId = AccountId;
 	type Event = Event;
 	type ModuleToIndex = ();
+	type DbWeight = ();
 }
 
 impl module::Trait for Runtime {}
@@ -0,0 +1,140 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests that the versions of the modules are stored at genesis.
+
+use sp_runtime::{generic, BuildStorage, traits::{BlakeTwo256, Verify}};
+use frame_support::{
+	crate_to_pallet_version, parameter_types,
+	traits::GetPalletVersion,
+	weights::RuntimeDbWeight,
+};
+use sp_core::{H256, sr25519};
+
+mod system {
+	use frame_support::codec::{Encode, Decode, EncodeLike};
+
+	pub trait Trait: 'static + Eq + Clone {
+		type Origin: Into<Result<RawOrigin<Self::AccountId>, Self::Origin>>
+			+ From<RawOrigin<Self::AccountId>>;
+		type BlockNumber: Decode + Encode + EncodeLike + Clone + Default;
+		type Hash;
+		type AccountId: Encode + EncodeLike + Decode;
+		type Event: From<Event>;
+		type ModuleToIndex: frame_support::traits::ModuleToIndex;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
+	}
+
+	frame_support::decl_module! {
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system = self {}
+	}
+
+	frame_support::decl_event!(
+		pub enum Event {
+			ExtrinsicSuccess,
+		}
+	);
+
+	#[derive(PartialEq, Eq, Clone, sp_runtime::RuntimeDebug)]
+	pub enum RawOrigin<AccountId> {
+		Root,
+		Signed(AccountId),
+		None,
+	}
+
+	impl<AccountId> From<Option<AccountId>> for RawOrigin<AccountId> {
+		fn from(s: Option<AccountId>) -> RawOrigin<AccountId> {
+			match s {
+				Some(who) => RawOrigin::Signed(who),
+				None => RawOrigin::None,
+			}
+		}
+	}
+
+	pub type Origin<T> = RawOrigin<<T as Trait>::AccountId>;
+}
+
+/// A module declaring its system explicitly.
+mod module1 {
+	use super::system;
+
+	pub trait Trait: system::Trait {}
+
+	frame_support::decl_module! {
+		pub struct Module<T: Trait> for enum Call
+			where origin: <T as system::Trait>::Origin, system = system
+		{}
+	}
+}
+
+/// A module using the default `system`.
+mod module2 {
+	use super::system;
+
+	pub trait Trait: system::Trait {}
+
+	frame_support::decl_module! {
+		pub struct Module<T: Trait> for enum Call where origin: <T as system::Trait>::Origin {}
+	}
+}
+
+pub type Signature = sr25519::Signature;
+pub type AccountId = <Signature as Verify>::Signer;
+pub type BlockNumber = u64;
+
+parameter_types! {
+	pub const DbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 1 };
+}
+
+impl system::Trait for Runtime {
+	type Hash = H256;
+	type Origin = Origin;
+	type BlockNumber = BlockNumber;
+	type AccountId = AccountId;
+	type Event = Event;
+	type ModuleToIndex = ModuleToIndex;
+	type DbWeight = DbWeight;
+}
+
+impl module1::Trait for Runtime {}
+impl module2::Trait for Runtime {}
+
+frame_support::construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: system::{Module, Call, Event},
+		Module1: module1::{Module, Call},
+		Module2: module2::{Module, Call},
+	}
+);
+
+pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
+pub type Block = generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<u32, Call, Signature, ()>;
+
+#[test]
+fn pallet_versions_are_stored_at_genesis() {
+	let mut ext: sp_io::TestExternalities = GenesisConfig {}.build_storage().unwrap().into();
+
+	ext.execute_with(|| {
+		assert_eq!(System::on_chain_storage_version(), Some(crate_to_pallet_version!()));
+		assert_eq!(Module1::on_chain_storage_version(), Some(crate_to_pallet_version!()));
+		assert_eq!(Module2::on_chain_storage_version(), Some(crate_to_pallet_version!()));
+	});
+}
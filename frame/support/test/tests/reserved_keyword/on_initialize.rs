@@ -4,9 +4,11 @@ macro_rules! reserved {
 			mod $reserved {
 				pub use frame_support::dispatch;
 
-				pub trait Trait {
+				pub trait Trait: 'static {
 					type Origin;
 					type BlockNumber: Into<u32>;
+					type ModuleToIndex: frame_support::traits::ModuleToIndex;
+					type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 				}
 
 				pub mod system {
@@ -18,7 +20,7 @@ macro_rules! reserved {
 				}
 
 				frame_support::decl_module! {
-					pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+					pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {
 						#[weight = 0]
 						fn $reserved(_origin) -> dispatch::DispatchResult { unreachable!() }
 					}
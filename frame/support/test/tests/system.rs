@@ -25,10 +25,11 @@ pub trait Trait: 'static + Eq + Clone {
 	type AccountId: Encode + EncodeLike + Decode;
 	type Event: From<Event<Self>>;
 	type ModuleToIndex: frame_support::traits::ModuleToIndex;
+	type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 }
 
 frame_support::decl_module! {
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 }
 
 impl<T: Trait> Module<T> {
@@ -119,7 +119,7 @@ use frame_support::{
 	storage,
 	traits::{
 		Contains, Get, ModuleToIndex, OnNewAccount, OnKilledAccount, IsDeadAccount, Happened,
-		StoredMap, EnsureOrigin,
+		StoredMap, EnsureOrigin, Filter,
 	},
 	weights::{
 		Weight, RuntimeDbWeight, DispatchInfo, PostDispatchInfo, DispatchClass,
@@ -144,6 +144,12 @@ pub fn extrinsics_data_root<H: Hash>(xts: Vec<Vec<u8>>) -> H::Output {
 }
 
 pub trait Trait: 'static + Eq + Clone {
+	/// The basic call filter to use when dispatching calls.
+	///
+	/// Calls which do not pass the filter are neither applied as extrinsics nor dispatched by
+	/// pallets which dispatch calls on behalf of others (e.g. `utility` or `sudo`).
+	type BaseCallFilter: Filter<Self::Call>;
+
 	/// The aggregated `Origin` type used by dispatchable calls.
 	type Origin:
 		Into<Result<RawOrigin<Self::AccountId>, Self::Origin>>
@@ -474,11 +480,13 @@ decl_error! {
 		NonDefaultComposite,
 		/// There is a non-zero reference count preventing the account from being purged.
 		NonZeroRefCount,
+		/// The call was disabled by the runtime's call filter.
+		CallFiltered,
 	}
 }
 
 decl_module! {
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {
 		type Error = Error<T>;
 
 		/// The maximum weight of a block.
@@ -822,6 +830,11 @@ impl<T: Trait> Module<T> {
 		Account::<T>::get(who).refcount == 0
 	}
 
+	/// Whether `call` may be dispatched according to the runtime's base call filter.
+	pub fn is_callable(call: &T::Call) -> bool {
+		T::BaseCallFilter::filter(call)
+	}
+
 	/// Deposits an event into this block's event record adding this event
 	/// to the corresponding topic indexes.
 	///
@@ -1735,6 +1748,7 @@ pub(crate) mod tests {
 	}
 
 	impl Trait for Test {
+		type BaseCallFilter = ();
 		type Origin = Origin;
 		type Call = Call;
 		type Index = u64;
@@ -0,0 +1,262 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Payment of transaction fees in assets other than the native currency.
+//!
+//! [`ChargeAssetTransactionPayment`] is an alternative to [`ChargeTransactionPayment`] which lets
+//! the signer choose an asset in which to pay. The fee is computed in the native currency exactly
+//! as for [`ChargeTransactionPayment`], converted into an amount of the chosen asset through
+//! [`Trait::FeeConversion`] and withdrawn from [`Trait::Assets`], e.g. `pallet-assets` or
+//! `pallet-generic-asset`. After dispatch, the share of the fee paid for unused weight is refunded
+//! in the same asset.
+//!
+//! [`ChargeAssetTransactionPayment`]: ./struct.ChargeAssetTransactionPayment.html
+//! [`ChargeTransactionPayment`]: ../struct.ChargeTransactionPayment.html
+//! [`Trait::FeeConversion`]: ./trait.Trait.html#associatedtype.FeeConversion
+//! [`Trait::Assets`]: ./trait.Trait.html#associatedtype.Assets
+
+use codec::{Encode, Decode};
+use frame_support::{
+	traits::{MultiAsset, WithdrawReason},
+	weights::{DispatchInfo, PostDispatchInfo},
+	dispatch::DispatchResult,
+};
+use sp_runtime::{
+	Perbill,
+	transaction_validity::{
+		TransactionPriority, ValidTransaction, InvalidTransaction, TransactionValidityError,
+		TransactionValidity,
+	},
+	traits::{
+		Zero, Saturating, SignedExtension, SaturatedConversion, Dispatchable, DispatchInfoOf,
+		PostDispatchInfoOf,
+	},
+};
+use super::{Module, BalanceOf, NegativeImbalanceOf, ChargeTransactionPayment};
+
+/// The identifier of an asset in which fees may be paid.
+pub type AssetIdOf<T> =
+	<<T as Trait>::Assets as MultiAsset<<T as frame_system::Trait>::AccountId>>::AssetId;
+/// The balance type of the assets in which fees may be paid.
+pub type AssetBalanceOf<T> =
+	<<T as Trait>::Assets as MultiAsset<<T as frame_system::Trait>::AccountId>>::Balance;
+
+/// Conversion of fees from the native currency into other assets.
+pub trait FeeConversion<Balance, AssetId, AssetBalance> {
+	/// Convert `fee`, given in the native currency, into the amount of the asset `asset_id` to be
+	/// charged instead. Returns `None` if fees cannot be paid in `asset_id`.
+	fn to_asset_balance(fee: Balance, asset_id: AssetId) -> Option<AssetBalance>;
+}
+
+/// Handler for the transaction fees paid in assets other than the native currency.
+pub trait OnAssetTransactionPayment<AccountId, AssetId, Balance> {
+	/// Handle the payment of `fee` and `tip` in the asset `asset_id`, both of which have already
+	/// been withdrawn from `who`.
+	fn on_payment(who: &AccountId, asset_id: AssetId, fee: Balance, tip: Balance);
+}
+
+impl<AccountId, AssetId, Balance> OnAssetTransactionPayment<AccountId, AssetId, Balance> for () {
+	fn on_payment(_: &AccountId, _: AssetId, _: Balance, _: Balance) {}
+}
+
+pub trait Trait: super::Trait {
+	/// The assets in which transaction fees may be paid.
+	type Assets: MultiAsset<Self::AccountId>;
+
+	/// Conversion of fees from the native currency into amounts of `Assets`.
+	type FeeConversion: FeeConversion<BalanceOf<Self>, AssetIdOf<Self>, AssetBalanceOf<Self>>;
+
+	/// Handler for the fees paid in `Assets`. Fees which are not otherwise handled are burned.
+	type OnAssetTransactionPayment: OnAssetTransactionPayment<
+		Self::AccountId,
+		AssetIdOf<Self>,
+		AssetBalanceOf<Self>,
+	>;
+}
+
+/// The fee withdrawn before dispatch, to be corrected once the actual weight is known.
+pub enum InitialPayment<T: Trait> {
+	/// No fee was withdrawn.
+	Nothing,
+	/// The fee was withdrawn in the native currency.
+	Native(NegativeImbalanceOf<T>),
+	/// The fee was withdrawn in an asset. Given are the asset, the fee in the native currency and
+	/// the amount of the asset which was withdrawn for it.
+	Asset(AssetIdOf<T>, BalanceOf<T>, AssetBalanceOf<T>),
+}
+
+impl<T: Trait> Default for InitialPayment<T> {
+	fn default() -> Self {
+		InitialPayment::Nothing
+	}
+}
+
+/// Require the transactor pay for themselves, either in the native currency or in an asset of
+/// their choice, and maybe include a tip to gain additional priority in the queue.
+///
+/// The tip is given in the native currency and converted along with the rest of the fee.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct ChargeAssetTransactionPayment<T: Trait + Send + Sync> {
+	#[codec(compact)]
+	tip: BalanceOf<T>,
+	asset_id: Option<AssetIdOf<T>>,
+}
+
+impl<T: Trait + Send + Sync> ChargeAssetTransactionPayment<T> where
+	T::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo>,
+	BalanceOf<T>: Send + Sync,
+{
+	/// utility constructor. Used only in client/factory code.
+	pub fn from(tip: BalanceOf<T>, asset_id: Option<AssetIdOf<T>>) -> Self {
+		Self { tip, asset_id }
+	}
+
+	fn withdraw_fee(
+		&self,
+		who: &T::AccountId,
+		info: &DispatchInfoOf<T::Call>,
+		len: usize,
+	) -> Result<(BalanceOf<T>, InitialPayment<T>), TransactionValidityError> {
+		let asset_id = match self.asset_id {
+			Some(asset_id) => asset_id,
+			None => {
+				let (fee, imbalance) = ChargeTransactionPayment::<T>::from(self.tip)
+					.withdraw_fee(who, info, len)?;
+				return Ok((fee, imbalance.map_or(InitialPayment::Nothing, InitialPayment::Native)));
+			}
+		};
+
+		let fee = Module::<T>::compute_fee(len as u32, info, self.tip);
+
+		// Only mess with balances if fee is not zero.
+		if fee.is_zero() {
+			return Ok((fee, InitialPayment::Nothing));
+		}
+
+		let asset_fee = T::FeeConversion::to_asset_balance(fee, asset_id)
+			.ok_or(TransactionValidityError::from(InvalidTransaction::Payment))?;
+		T::Assets::withdraw(
+			asset_id,
+			who,
+			asset_fee,
+			if self.tip.is_zero() {
+				WithdrawReason::TransactionPayment.into()
+			} else {
+				WithdrawReason::TransactionPayment | WithdrawReason::Tip
+			},
+		).map_err(|_| TransactionValidityError::from(InvalidTransaction::Payment))?;
+
+		Ok((fee, InitialPayment::Asset(asset_id, fee, asset_fee)))
+	}
+}
+
+impl<T: Trait + Send + Sync> sp_std::fmt::Debug for ChargeAssetTransactionPayment<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ChargeAssetTransactionPayment<{:?}, {:?}>", self.tip, self.asset_id)
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for ChargeAssetTransactionPayment<T> where
+	BalanceOf<T>: Send + Sync + From<u64>,
+	AssetIdOf<T>: Send + Sync,
+	T::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "ChargeAssetTransactionPayment";
+	type AccountId = T::AccountId;
+	type Call = T::Call;
+	type AdditionalSigned = ();
+	type Pre = (BalanceOf<T>, Self::AccountId, InitialPayment<T>);
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> { Ok(()) }
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		_call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		let (fee, _) = self.withdraw_fee(who, info, len)?;
+
+		let mut r = ValidTransaction::default();
+		// The priority is based on the fee in the native currency, whichever asset it is paid in.
+		r.priority = fee.saturated_into::<TransactionPriority>();
+		Ok(r)
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		_call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let (_, initial_payment) = self.withdraw_fee(who, info, len)?;
+		Ok((self.tip, who.clone(), initial_payment))
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		let (tip, who, initial_payment) = pre;
+		match initial_payment {
+			InitialPayment::Nothing => Ok(()),
+			InitialPayment::Native(imbalance) => ChargeTransactionPayment::<T>::post_dispatch(
+				(tip, who, Some(imbalance)),
+				info,
+				post_info,
+				len,
+				result,
+			),
+			InitialPayment::Asset(asset_id, fee, paid) => {
+				let refund = Module::<T>::weight_to_fee_with_adjustment::<BalanceOf<T>>(
+					post_info.calc_unspent(info)
+				);
+				// Refund the same share of the asset as the refund makes up of the native fee, which
+				// avoids converting again at a possibly different rate.
+				let asset_refund = Perbill::from_rational_approximation(refund.min(fee), fee) * paid;
+				let asset_refund = if asset_refund.is_zero() {
+					asset_refund
+				} else {
+					match T::Assets::deposit_into_existing(asset_id, &who, asset_refund) {
+						Ok(()) => asset_refund,
+						// We do not recreate the account using the refund. The up front payment
+						// is gone in that case.
+						Err(_) => Zero::zero(),
+					}
+				};
+				let actual_payment = paid.saturating_sub(asset_refund);
+				let asset_tip = (Perbill::from_rational_approximation(tip.min(fee), fee) * paid)
+					.min(actual_payment);
+				T::OnAssetTransactionPayment::on_payment(
+					&who,
+					asset_id,
+					actual_payment - asset_tip,
+					asset_tip,
+				);
+				Ok(())
+			}
+		}
+	}
+}
@@ -28,6 +28,9 @@
 //!   - A means of updating the fee for the next block, via defining a multiplier, based on the
 //!     final state of the chain at the end of the previous block. This can be configured via
 //!     [`Trait::FeeMultiplierUpdate`]
+//!
+//! Fees may also be paid in assets other than [`Trait::Currency`] through the
+//! [`assets::ChargeAssetTransactionPayment`] signed extension.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -35,7 +38,10 @@ use sp_std::prelude::*;
 use codec::{Encode, Decode};
 use frame_support::{
 	decl_storage, decl_module,
-	traits::{Currency, Get, OnUnbalanced, ExistenceRequirement, WithdrawReason, Imbalance},
+	traits::{
+		Currency, ReservableCurrency, Get, OnUnbalanced, ExistenceRequirement, WithdrawReason,
+		Imbalance,
+	},
 	weights::{Weight, DispatchInfo, PostDispatchInfo, GetDispatchInfo, Pays},
 	dispatch::DispatchResult,
 };
@@ -52,6 +58,8 @@ use sp_runtime::{
 };
 use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
 
+pub mod assets;
+
 type Multiplier = Fixed128;
 type BalanceOf<T> =
 	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
@@ -60,7 +68,10 @@ type NegativeImbalanceOf<T> =
 
 pub trait Trait: frame_system::Trait {
 	/// The currency type in which fees will be paid.
-	type Currency: Currency<Self::AccountId> + Send + Sync;
+	///
+	/// It must be reservable, as other modules reserve deposits in it, e.g. `pallet-contracts`
+	/// for the code it stores.
+	type Currency: ReservableCurrency<Self::AccountId> + Send + Sync;
 
 	/// Handler for the unbalanced reduction when taking transaction fees. This is either one or
 	/// two separate imbalances, the first is the transaction fee paid, the second is the tip paid,
@@ -84,7 +95,7 @@ decl_storage! {
 }
 
 decl_module! {
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin, system = frame_system {
 		/// The fee to be paid for making a transaction; the per-byte portion.
 		const TransactionByteFee: BalanceOf<T> = T::TransactionByteFee::get();
 
@@ -316,7 +327,7 @@ mod tests {
 	use core::num::NonZeroI128;
 	use codec::Encode;
 	use frame_support::{
-		impl_outer_dispatch, impl_outer_origin, parameter_types,
+		assert_ok, impl_outer_dispatch, impl_outer_origin, parameter_types,
 		weights::{DispatchClass, DispatchInfo, PostDispatchInfo, GetDispatchInfo, Weight},
 	};
 	use pallet_balances::Call as BalancesCall;
@@ -364,6 +375,7 @@ mod tests {
 	}
 
 	impl frame_system::Trait for Runtime {
+		type BaseCallFilter = ();
 		type Origin = Origin;
 		type Index = u64;
 		type BlockNumber = u64;
@@ -424,7 +436,54 @@ mod tests {
 		type FeeMultiplierUpdate = ();
 	}
 
+	parameter_types! {
+		pub const AssetDeposit: u64 = 1;
+		pub const MetadataDepositBase: u64 = 1;
+		pub const MetadataDepositPerByte: u64 = 1;
+		pub const StringLimit: u32 = 50;
+		pub const ApprovalDeposit: u64 = 1;
+	}
+
+	impl pallet_assets::Trait for Runtime {
+		type Event = ();
+		type Balance = u64;
+		type AssetId = u32;
+		type Currency = Balances;
+		type ForceOrigin = frame_system::EnsureRoot<u64>;
+		type AssetDeposit = AssetDeposit;
+		type MetadataDepositBase = MetadataDepositBase;
+		type MetadataDepositPerByte = MetadataDepositPerByte;
+		type StringLimit = StringLimit;
+		type ApprovalDeposit = ApprovalDeposit;
+	}
+
+	/// Fees may only be paid in asset `1`, at twice the amount of the native fee.
+	pub struct DoubleFeeInAssetOne;
+	impl assets::FeeConversion<u64, u32, u64> for DoubleFeeInAssetOne {
+		fn to_asset_balance(fee: u64, asset_id: u32) -> Option<u64> {
+			if asset_id == 1 { Some(fee * 2) } else { None }
+		}
+	}
+
+	thread_local! {
+		static ASSET_PAYMENTS: RefCell<Vec<(u64, u32, u64, u64)>> = RefCell::new(vec![]);
+	}
+
+	pub struct RecordAssetPayments;
+	impl assets::OnAssetTransactionPayment<u64, u32, u64> for RecordAssetPayments {
+		fn on_payment(who: &u64, asset_id: u32, fee: u64, tip: u64) {
+			ASSET_PAYMENTS.with(|v| v.borrow_mut().push((*who, asset_id, fee, tip)));
+		}
+	}
+
+	impl assets::Trait for Runtime {
+		type Assets = Assets;
+		type FeeConversion = DoubleFeeInAssetOne;
+		type OnAssetTransactionPayment = RecordAssetPayments;
+	}
+
 	type Balances = pallet_balances::Module<Runtime>;
+	type Assets = pallet_assets::Module<Runtime>;
 	type System = frame_system::Module<Runtime>;
 	type TransactionPayment = Module<Runtime>;
 
@@ -830,4 +889,90 @@ mod tests {
 			assert_eq!(Balances::free_balance(2), 200 - 5 - 10 - 100 - 5);
 		});
 	}
+
+	#[test]
+	fn asset_transaction_payment_works() {
+		ExtBuilder::default()
+			.balance_factor(10)
+			.base_weight(5)
+			.build()
+			.execute_with(||
+		{
+			assert_ok!(Assets::force_create(Origin::ROOT, 1, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 1, 2, 1000));
+
+			let len = 10;
+			let pre = assets::ChargeAssetTransactionPayment::<Runtime>::from(5 /* tipped */, Some(1))
+				.pre_dispatch(&2, CALL, &info_from_weight(100), len)
+				.unwrap();
+			assert_eq!(Balances::free_balance(2), 200);
+			assert_eq!(Assets::balance(1, 2), 1000 - 2 * (5 + 10 + 100 + 5));
+
+			assert!(
+				assets::ChargeAssetTransactionPayment::<Runtime>
+					::post_dispatch(pre, &info_from_weight(100), &post_info_from_weight(50), len, &Ok(()))
+					.is_ok()
+			);
+			assert_eq!(Balances::free_balance(2), 200);
+			assert_eq!(Assets::balance(1, 2), 1000 - 2 * (5 + 10 + 50 + 5));
+			assert_eq!(
+				ASSET_PAYMENTS.with(|v| v.borrow().clone()),
+				vec![(2, 1, 2 * (5 + 10 + 50), 2 * 5)],
+			);
+		});
+	}
+
+	#[test]
+	fn asset_transaction_payment_rejects_unusable_assets() {
+		ExtBuilder::default()
+			.balance_factor(10)
+			.base_weight(5)
+			.build()
+			.execute_with(||
+		{
+			assert_ok!(Assets::force_create(Origin::ROOT, 1, 1, 1));
+			assert_ok!(Assets::force_create(Origin::ROOT, 2, 1, 1));
+			assert_ok!(Assets::mint(Origin::signed(1), 1, 2, 100));
+			assert_ok!(Assets::mint(Origin::signed(1), 2, 2, 1000));
+
+			let len = 10;
+			// no conversion into asset 2.
+			assert!(
+				assets::ChargeAssetTransactionPayment::<Runtime>::from(0, Some(2))
+					.pre_dispatch(&2, CALL, &info_from_weight(5), len)
+					.is_err()
+			);
+			// too little of asset 1.
+			assert!(
+				assets::ChargeAssetTransactionPayment::<Runtime>::from(0, Some(1))
+					.pre_dispatch(&2, CALL, &info_from_weight(100), len)
+					.is_err()
+			);
+			assert_eq!(Assets::balance(1, 2), 100);
+			assert_eq!(Assets::balance(2, 2), 1000);
+		});
+	}
+
+	#[test]
+	fn asset_transaction_payment_without_asset_pays_natively() {
+		ExtBuilder::default()
+			.balance_factor(10)
+			.base_weight(5)
+			.build()
+			.execute_with(||
+		{
+			let len = 10;
+			let pre = assets::ChargeAssetTransactionPayment::<Runtime>::from(5 /* tipped */, None)
+				.pre_dispatch(&2, CALL, &info_from_weight(100), len)
+				.unwrap();
+			assert_eq!(Balances::free_balance(2), 200 - 5 - 10 - 100 - 5);
+
+			assert!(
+				assets::ChargeAssetTransactionPayment::<Runtime>
+					::post_dispatch(pre, &info_from_weight(100), &post_info_from_weight(50), len, &Ok(()))
+					.is_ok()
+			);
+			assert_eq!(Balances::free_balance(2), 200 - 5 - 10 - 50 - 5);
+		});
+	}
 }
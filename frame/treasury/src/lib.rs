@@ -43,6 +43,12 @@
 //! countdown period, the median of all declared tips is paid to the reported beneficiary, along
 //! with any finders fee, in case of a public (and bonded) original report.
 //!
+//! ### Spending by other modules
+//!
+//! At the end of each spend period, after the approved proposals have been paid, the remaining
+//! budget is offered to the modules configured as `SpendFunds`, e.g. the bounties module, before
+//! the surplus gets burnt.
+//!
 //! ### Terminology
 //!
 //! - **Proposal:** A suggestion to allocate funds from the pot to a beneficiary.
@@ -106,9 +112,9 @@ use frame_system::{self as system, ensure_signed, ensure_root};
 mod tests;
 mod benchmarking;
 
-type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
-type PositiveImbalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::PositiveImbalance;
-type NegativeImbalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+pub type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+pub type PositiveImbalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::PositiveImbalance;
+pub type NegativeImbalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
 
 pub trait Trait: frame_system::Trait {
 	/// The treasury's module id, used for deriving its sovereign account ID.
@@ -158,6 +164,24 @@ pub trait Trait: frame_system::Trait {
 
 	/// Percentage of spare funds (if any) that are burnt per spend period.
 	type Burn: Get<Permill>;
+
+	/// Runtime hooks to external modules spending the funds left after the approved proposals.
+	type SpendFunds: SpendFunds<Self>;
+}
+
+/// A hook through which other modules, e.g. bounties, spend treasury funds once per spend period.
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait SpendFunds<T: Trait> {
+	/// Spend some of `budget_remaining`, subsuming the created funds into `imbalance`.
+	///
+	/// `total_weight` must be increased by the weight consumed and `missed_any` set if some
+	/// spending could not be funded, in which case nothing is burnt.
+	fn spend_funds(
+		budget_remaining: &mut BalanceOf<T>,
+		imbalance: &mut PositiveImbalanceOf<T>,
+		total_weight: &mut Weight,
+		missed_any: &mut bool,
+	);
 }
 
 /// An index of a proposal. Just a `u32`.
@@ -580,14 +604,12 @@ decl_module! {
 		/// - Db reads and writes per approval:
 		///   `Proposals`, `proposer account data`, `beneficiary account data`
 		/// - The weight is overestimated if some approvals got missed.
+		/// - Plus the weight of `T::SpendFunds`.
 		/// # </weight>
 		fn on_initialize(n: T::BlockNumber) -> Weight {
 			// Check to see if we should spend some funds!
 			if (n % T::SpendPeriod::get()).is_zero() {
-				let approvals_len = Self::spend_funds();
-
-				270_000_000 * approvals_len
-					+ T::DbWeight::get().reads_writes(2 + approvals_len * 3, 2 + approvals_len * 3)
+				Self::spend_funds()
 			} else {
 				0
 			}
@@ -682,8 +704,8 @@ impl<T: Trait> Module<T> {
 		Self::deposit_event(RawEvent::TipClosed(hash, tip.who, payout));
 	}
 
-	/// Spend some money! returns number of approvals before spend.
-	fn spend_funds() -> u64 {
+	/// Spend some money! returns the weight consumed.
+	fn spend_funds() -> Weight {
 		let mut budget_remaining = Self::pot();
 		Self::deposit_event(RawEvent::Spending(budget_remaining));
 
@@ -717,6 +739,17 @@ impl<T: Trait> Module<T> {
 			prior_approvals_len
 		});
 
+		let mut total_weight = 270_000_000 * prior_approvals_len
+			+ T::DbWeight::get().reads_writes(2 + prior_approvals_len * 3, 2 + prior_approvals_len * 3);
+
+		// Give the other spending modules their share of the remaining budget.
+		T::SpendFunds::spend_funds(
+			&mut budget_remaining,
+			&mut imbalance,
+			&mut total_weight,
+			&mut missed_any,
+		);
+
 		if !missed_any {
 			// burn some proportion of the remaining budget if we run a surplus.
 			let burn = (T::Burn::get() * budget_remaining).min(budget_remaining);
@@ -742,12 +775,12 @@ impl<T: Trait> Module<T> {
 
 		Self::deposit_event(RawEvent::Rollover(budget_remaining));
 
-		prior_approvals_len
+		total_weight
 	}
 
 	/// Return the amount of money in the pot.
 	// The existential deposit is not part of the pot so treasury account never gets deleted.
-	fn pot() -> BalanceOf<T> {
+	pub fn pot() -> BalanceOf<T> {
 		T::Currency::free_balance(&Self::account_id())
 			// Must never be less than 0 but better be safe.
 			.saturating_sub(T::Currency::minimum_balance())
@@ -0,0 +1,32 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API used to test runtime upgrades against the state of an existing chain.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::weights::Weight;
+
+sp_api::decl_runtime_apis! {
+	/// Runtime api for testing the migrations of a runtime upgrade.
+	pub trait TryRuntime {
+		/// Execute all `OnRuntimeUpgrade` hooks of the runtime, returning the consumed weight.
+		///
+		/// This is meant to be called on top of the state of a chain running a previous
+		/// version of the runtime.
+		fn on_runtime_upgrade() -> Weight;
+	}
+}
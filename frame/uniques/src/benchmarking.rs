@@ -0,0 +1,341 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Uniques pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_system::RawOrigin;
+use frame_support::dispatch::Dispatchable;
+use frame_benchmarking::{benchmarks, account};
+use sp_runtime::traits::Bounded;
+
+use crate::Module as Uniques;
+
+const SEED: u32 = 0;
+const MAX_USER_INDEX: u32 = 1000;
+const MAX_INSTANCES: u32 = 1000;
+const MAX_ATTRIBUTES: u32 = 1000;
+
+// Create the class `0` owned by `caller`, holding deposits for its instances, and return its
+// owner.
+fn create_default_class<T: Trait>() -> Result<T::AccountId, &'static str> {
+	let caller: T::AccountId = account("caller", 0, SEED);
+	let caller_lookup = T::Lookup::unlookup(caller.clone());
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	Uniques::<T>::force_create(
+		T::ForceOrigin::successful_origin(),
+		Default::default(),
+		caller_lookup,
+		false,
+	)?;
+	Ok(caller)
+}
+
+// Mint the instance `index` of the class `0` to its owner, with some metadata.
+fn mint_instance<T: Trait>(owner: &T::AccountId, index: u32) -> Result<T::InstanceId, &'static str> {
+	let instance: T::InstanceId = index.into();
+	Uniques::<T>::mint(
+		RawOrigin::Signed(owner.clone()).into(),
+		Default::default(),
+		instance,
+		T::Lookup::unlookup(owner.clone()),
+	)?;
+	Uniques::<T>::set_metadata(
+		RawOrigin::Signed(owner.clone()).into(),
+		Default::default(),
+		instance,
+		vec![0; T::StringLimit::get() as usize],
+		false,
+	)?;
+	Ok(instance)
+}
+
+// Set the attribute `index` of the class `0`.
+fn add_attribute<T: Trait>(owner: &T::AccountId, index: u32) -> Result<(), &'static str> {
+	Uniques::<T>::set_attribute(
+		RawOrigin::Signed(owner.clone()).into(),
+		Default::default(),
+		None,
+		index.encode(),
+		vec![0; T::ValueLimit::get() as usize],
+	)?;
+	Ok(())
+}
+
+benchmarks! {
+	_ {
+		let u in 1 .. MAX_USER_INDEX => ();
+	}
+
+	create {
+		let u in ...;
+		let caller: T::AccountId = account("caller", u, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), caller_lookup)
+	verify {
+		assert_eq!(Uniques::<T>::class_owner(Default::default()), Some(caller));
+	}
+
+	force_create {
+		let u in ...;
+		let caller: T::AccountId = account("caller", u, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let origin = T::ForceOrigin::successful_origin();
+		let call = Call::<T>::force_create(Default::default(), caller_lookup, true);
+	}: { call.dispatch(origin)? }
+	verify {
+		assert_eq!(Uniques::<T>::class_owner(Default::default()), Some(caller));
+	}
+
+	// Worst case: every instance has metadata.
+	destroy {
+		let n in 0 .. MAX_INSTANCES;
+		let a in 0 .. MAX_ATTRIBUTES;
+		let caller = create_default_class::<T>()?;
+		for i in 0 .. n {
+			mint_instance::<T>(&caller, i)?;
+		}
+		for i in 0 .. a {
+			add_attribute::<T>(&caller, i)?;
+		}
+		let witness = DestroyWitness { instances: n, instance_metadatas: n, attributes: a };
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), witness)
+	verify {
+		assert_eq!(Uniques::<T>::class_owner(Default::default()), None);
+	}
+
+	mint {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		let target: T::AccountId = account("target", u, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), Default::default(), target_lookup)
+	verify {
+		assert_eq!(Uniques::<T>::owner(Default::default(), Default::default()), Some(target));
+	}
+
+	// Worst case: the instance has metadata.
+	burn {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		let instance = mint_instance::<T>(&caller, u)?;
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), instance, Some(caller_lookup))
+	verify {
+		assert_eq!(Uniques::<T>::owner(Default::default(), instance), None);
+	}
+
+	transfer {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		let instance = mint_instance::<T>(&caller, 0)?;
+		let target: T::AccountId = account("target", u, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), instance, target_lookup)
+	verify {
+		assert_eq!(Uniques::<T>::owner(Default::default(), instance), Some(target));
+	}
+
+	freeze {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		let instance = mint_instance::<T>(&caller, u)?;
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), instance)
+	verify {
+		assert!(Asset::<T>::get(T::ClassId::default(), instance).unwrap().is_frozen);
+	}
+
+	thaw {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		let instance = mint_instance::<T>(&caller, u)?;
+		Uniques::<T>::freeze(RawOrigin::Signed(caller.clone()).into(), Default::default(), instance)?;
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), instance)
+	verify {
+		assert!(!Asset::<T>::get(T::ClassId::default(), instance).unwrap().is_frozen);
+	}
+
+	freeze_class {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+	}: _(RawOrigin::Signed(caller.clone()), Default::default())
+	verify {
+		assert!(Class::<T>::get(T::ClassId::default()).unwrap().is_frozen);
+	}
+
+	thaw_class {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		Uniques::<T>::freeze_class(RawOrigin::Signed(caller.clone()).into(), Default::default())?;
+	}: _(RawOrigin::Signed(caller.clone()), Default::default())
+	verify {
+		assert!(!Class::<T>::get(T::ClassId::default()).unwrap().is_frozen);
+	}
+
+	transfer_ownership {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		let target: T::AccountId = account("target", u, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), target_lookup)
+	verify {
+		assert_eq!(Uniques::<T>::class_owner(Default::default()), Some(target));
+	}
+
+	set_team {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		let target0 = T::Lookup::unlookup(account("target", 0, SEED));
+		let target1 = T::Lookup::unlookup(account("target", 1, SEED));
+		let target2 = T::Lookup::unlookup(account("target", 2, SEED));
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), target0, target1, target2)
+
+	approve_transfer {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		let instance = mint_instance::<T>(&caller, 0)?;
+		let delegate: T::AccountId = account("delegate", u, SEED);
+		let delegate_lookup = T::Lookup::unlookup(delegate.clone());
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), instance, delegate_lookup)
+	verify {
+		assert_eq!(Asset::<T>::get(T::ClassId::default(), instance).unwrap().approved, Some(delegate));
+	}
+
+	cancel_approval {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		let instance = mint_instance::<T>(&caller, 0)?;
+		let delegate: T::AccountId = account("delegate", u, SEED);
+		let delegate_lookup = T::Lookup::unlookup(delegate.clone());
+		Uniques::<T>::approve_transfer(
+			RawOrigin::Signed(caller.clone()).into(),
+			Default::default(),
+			instance,
+			delegate_lookup.clone(),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), instance, Some(delegate_lookup))
+	verify {
+		assert_eq!(Asset::<T>::get(T::ClassId::default(), instance).unwrap().approved, None);
+	}
+
+	set_attribute {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		let instance = mint_instance::<T>(&caller, 0)?;
+		let key = vec![0u8; T::KeyLimit::get() as usize];
+		let value = vec![0u8; T::ValueLimit::get() as usize];
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), Some(instance), key.clone(), value.clone())
+	verify {
+		assert_eq!(Uniques::<T>::attribute(Default::default(), Some(instance), &key), Some(value));
+	}
+
+	clear_attribute {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		let instance = mint_instance::<T>(&caller, 0)?;
+		let key = vec![0u8; T::KeyLimit::get() as usize];
+		Uniques::<T>::set_attribute(
+			RawOrigin::Signed(caller.clone()).into(),
+			Default::default(),
+			Some(instance),
+			key.clone(),
+			vec![0u8; T::ValueLimit::get() as usize],
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), Some(instance), key.clone())
+	verify {
+		assert_eq!(Uniques::<T>::attribute(Default::default(), Some(instance), &key), None);
+	}
+
+	set_metadata {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		let instance = mint_instance::<T>(&caller, 0)?;
+		Uniques::<T>::clear_metadata(RawOrigin::Signed(caller.clone()).into(), Default::default(), instance)?;
+		let data = vec![0u8; T::StringLimit::get() as usize];
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), instance, data, false)
+	verify {
+		assert!(InstanceMetadataOf::<T>::contains_key(T::ClassId::default(), instance));
+	}
+
+	clear_metadata {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		let instance = mint_instance::<T>(&caller, 0)?;
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), instance)
+	verify {
+		assert!(!InstanceMetadataOf::<T>::contains_key(T::ClassId::default(), instance));
+	}
+
+	set_class_metadata {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		let data = vec![0u8; T::StringLimit::get() as usize];
+	}: _(RawOrigin::Signed(caller.clone()), Default::default(), data, false)
+	verify {
+		assert!(ClassMetadataOf::<T>::contains_key(T::ClassId::default()));
+	}
+
+	clear_class_metadata {
+		let u in ...;
+		let caller = create_default_class::<T>()?;
+		Uniques::<T>::set_class_metadata(
+			RawOrigin::Signed(caller.clone()).into(),
+			Default::default(),
+			vec![0u8; T::StringLimit::get() as usize],
+			false,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), Default::default())
+	verify {
+		assert!(!ClassMetadataOf::<T>::contains_key(T::ClassId::default()));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create::<Test>());
+			assert_ok!(test_benchmark_force_create::<Test>());
+			assert_ok!(test_benchmark_destroy::<Test>());
+			assert_ok!(test_benchmark_mint::<Test>());
+			assert_ok!(test_benchmark_burn::<Test>());
+			assert_ok!(test_benchmark_transfer::<Test>());
+			assert_ok!(test_benchmark_freeze::<Test>());
+			assert_ok!(test_benchmark_thaw::<Test>());
+			assert_ok!(test_benchmark_freeze_class::<Test>());
+			assert_ok!(test_benchmark_thaw_class::<Test>());
+			assert_ok!(test_benchmark_transfer_ownership::<Test>());
+			assert_ok!(test_benchmark_set_team::<Test>());
+			assert_ok!(test_benchmark_approve_transfer::<Test>());
+			assert_ok!(test_benchmark_cancel_approval::<Test>());
+			assert_ok!(test_benchmark_set_attribute::<Test>());
+			assert_ok!(test_benchmark_clear_attribute::<Test>());
+			assert_ok!(test_benchmark_set_metadata::<Test>());
+			assert_ok!(test_benchmark_clear_metadata::<Test>());
+			assert_ok!(test_benchmark_set_class_metadata::<Test>());
+			assert_ok!(test_benchmark_clear_class_metadata::<Test>());
+		});
+	}
+}
@@ -0,0 +1,1832 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Uniques Module
+//!
+//! A simple, secure module for dealing with non-fungible assets.
+//!
+//! ## Overview
+//!
+//! The Uniques module provides functionality for the management of classes of non-fungible
+//! assets, including:
+//!
+//! * Asset Class Creation
+//! * Asset Instance Minting
+//! * Asset Instance Transfer and Approvals
+//! * Asset Freezing
+//! * Metadata and Attributes
+//! * Asset Destruction
+//!
+//! To use it in your runtime, you need to implement the uniques [`Trait`](./trait.Trait.html).
+//!
+//! The supported dispatchable functions are documented in the [`Call`](./enum.Call.html) enum.
+//!
+//! ### Terminology
+//!
+//! * **Asset class:** A collection of asset instances, identified by a `ClassId`. The creator
+//!   holds a deposit for the class, which is returned when it is destroyed.
+//! * **Asset instance:** A unique asset within a class, identified by an `InstanceId` and owned
+//!   by a single account. A deposit is held for each instance by the owner of its class.
+//! * **Class owner:** The account which created a class, which may destroy it, change its team
+//!   and set its metadata and attributes. It holds all the deposits of the class.
+//! * **Class team:** The accounts holding the privileged roles of a class: the issuer may mint
+//!   new instances, the admin may burn and transfer any instance and thaw instances, and the
+//!   freezer may freeze instances.
+//! * **Freezing:** Preventing an instance, or all instances of a class, from being transferred.
+//! * **Approval:** A permission given by the owner of an instance to a delegate account, to
+//!   transfer that instance once.
+//! * **Metadata:** Opaque data describing a class or an instance, such as a link to an off-chain
+//!   resource. It may be frozen, after which only the `ForceOrigin` may change it.
+//! * **Attribute:** A key/value pair stored against a class or one of its instances.
+//!
+//! ### Goals
+//!
+//! The uniques system in Substrate is designed to make the following possible:
+//!
+//! * Create a new class of assets, either by depositing some funds or through a privileged
+//!   origin.
+//! * Mint and burn unique instances of a class, under the control of the class team.
+//! * Move instances between accounts, directly or through an approved delegate.
+//! * Freeze and thaw instances, or whole classes.
+//! * Store metadata and key/value attributes for classes and instances, backed by deposits.
+//! * Remove a class and all of its instances when requested by its owner.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! * `create` - Creates a new asset class, taking the required deposit.
+//! * `destroy` - Destroys an entire asset class; called by the class's Owner.
+//! * `mint` - Mints a new instance of a class; called by the class's Issuer.
+//! * `burn` - Destroys an instance; called by the instance's owner or the class's Admin.
+//! * `transfer` - Transfers an instance; called by its owner, its approved delegate or the
+//! class's Admin.
+//! * `freeze` - Disallows further `transfer`s of an instance; called by the class's Freezer.
+//! * `thaw` - Allows further `transfer`s of an instance; called by the class's Admin.
+//! * `freeze_class` - Disallows further `transfer`s of all instances of a class; called by the
+//! class's Freezer.
+//! * `thaw_class` - Allows further `transfer`s of the instances of a class; called by the class's
+//! Admin.
+//! * `transfer_ownership` - Changes a class's Owner; called by the class's Owner.
+//! * `set_team` - Changes a class's Issuer, Admin and Freezer; called by the class's Owner.
+//! * `approve_transfer` - Allows a delegate account to transfer an instance; called by the
+//! instance's owner or the class's Admin.
+//! * `cancel_approval` - Revokes the approval of the delegate of an instance; called by the
+//! instance's owner or the class's Admin.
+//! * `set_attribute` - Sets an attribute of a class or an instance; called by the class's Owner.
+//! * `clear_attribute` - Removes an attribute of a class or an instance; called by the class's
+//! Owner.
+//! * `set_metadata` - Sets the metadata of an instance; called by the class's Owner.
+//! * `clear_metadata` - Removes the metadata of an instance; called by the class's Owner.
+//! * `set_class_metadata` - Sets the metadata of a class; called by the class's Owner.
+//! * `clear_class_metadata` - Removes the metadata of a class; called by the class's Owner.
+//!
+//! The following functions are only callable by the `ForceOrigin`:
+//!
+//! * `force_create` - Creates a new asset class without taking any deposit, optionally without
+//! taking deposits for its instances either.
+//!
+//! The `ForceOrigin` may also call `destroy` and all the metadata and attribute functions, for
+//! which it does not need to respect their freezing and takes no additional deposit.
+//!
+//! Please refer to the [`Call`](./enum.Call.html) enum and its associated variants for documentation on each function.
+//!
+//! ### Public Functions
+//!
+//! * `owner` - Get the owner of an instance, if it exists.
+//! * `class_owner` - Get the owner of a class, if it exists.
+//! * `attribute` - Get the value of an attribute of a class or an instance.
+//!
+//! Please refer to the [`Module`](./struct.Module.html) struct for details on publicly available functions.
+//!
+//! ## Related Modules
+//!
+//! * [`Assets`](../pallet_assets/index.html)
+//! * [`System`](../frame_system/index.html)
+//! * [`Support`](../frame_support/index.html)
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use frame_support::{
+	Parameter, decl_module, decl_event, decl_storage, decl_error, ensure, IterableStorageDoubleMap,
+	traits::{Currency, ReservableCurrency, EnsureOrigin, Get, BalanceStatus},
+	weights::Weight,
+};
+use sp_runtime::{DispatchError, DispatchResult, RuntimeDebug};
+use sp_runtime::traits::{AtLeast32Bit, Zero, StaticLookup, Saturating};
+use frame_system::{self as system, ensure_signed};
+
+mod benchmarking;
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+/// The module configuration trait.
+pub trait Trait: frame_system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Identifier for the class of asset.
+	type ClassId: Parameter + AtLeast32Bit + Default + Copy;
+
+	/// The type used to identify a unique asset within a class.
+	type InstanceId: Parameter + AtLeast32Bit + Default + Copy;
+
+	/// The currency mechanism, used for the deposits of classes, instances, metadata and
+	/// attributes.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The origin which may forcibly create or destroy a class, or change its metadata and
+	/// attributes.
+	type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The amount held on deposit for a created class.
+	type ClassDeposit: Get<BalanceOf<Self>>;
+
+	/// The amount held on deposit for a minted instance.
+	type InstanceDeposit: Get<BalanceOf<Self>>;
+
+	/// The basic amount held on deposit for the metadata of a class or an instance.
+	type MetadataDepositBase: Get<BalanceOf<Self>>;
+
+	/// The basic amount held on deposit for an attribute.
+	type AttributeDepositBase: Get<BalanceOf<Self>>;
+
+	/// The additional amount held on deposit for each byte of metadata, or of the key and value
+	/// of an attribute.
+	type DepositPerByte: Get<BalanceOf<Self>>;
+
+	/// The maximum length of the metadata of a class or an instance.
+	type StringLimit: Get<u32>;
+
+	/// The maximum length of the key of an attribute.
+	type KeyLimit: Get<u32>;
+
+	/// The maximum length of the value of an attribute.
+	type ValueLimit: Get<u32>;
+}
+
+/// The details of a class of assets.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct ClassDetails<AccountId, DepositBalance> {
+	/// Can change `owner`, `issuer`, `freezer` and `admin` accounts.
+	owner: AccountId,
+	/// Can mint instances.
+	issuer: AccountId,
+	/// Can thaw instances, force transfers and burn instances from any account.
+	admin: AccountId,
+	/// Can freeze instances.
+	freezer: AccountId,
+	/// The total balance deposited by the owner for this class, its instances, their metadata
+	/// and attributes.
+	total_deposit: DepositBalance,
+	/// Whether no deposit is taken for the instances of this class.
+	free_holding: bool,
+	/// The number of instances of this class.
+	instances: u32,
+	/// The number of instances of this class with metadata.
+	instance_metadatas: u32,
+	/// The number of attributes of this class and of its instances.
+	attributes: u32,
+	/// Whether the instances of this class are frozen for transfers.
+	is_frozen: bool,
+}
+
+/// The details of an instance of a class.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct InstanceDetails<AccountId, DepositBalance> {
+	/// The owner of this instance.
+	owner: AccountId,
+	/// The account which may transfer this instance on behalf of its owner, if any.
+	approved: Option<AccountId>,
+	/// Whether the instance is frozen for transfers.
+	is_frozen: bool,
+	/// The balance deposited by the class owner for this instance.
+	deposit: DepositBalance,
+}
+
+/// The metadata of a class or an instance.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct Metadata<DepositBalance> {
+	/// The balance deposited by the class owner for this metadata.
+	deposit: DepositBalance,
+	/// General information concerning this class or instance. Limited in length by
+	/// `StringLimit`. This will generally be either a JSON dump or the hash of some JSON which
+	/// can be found on a hash-addressable global publication system such as IPFS.
+	data: Vec<u8>,
+	/// Whether the metadata may only be changed by the `ForceOrigin`.
+	is_frozen: bool,
+}
+
+/// Witness data for the destruction of a class.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct DestroyWitness {
+	/// An upper bound on the number of instances of the class.
+	#[codec(compact)]
+	pub instances: u32,
+	/// An upper bound on the number of instances of the class with metadata.
+	#[codec(compact)]
+	pub instance_metadatas: u32,
+	/// An upper bound on the number of attributes of the class and of its instances.
+	#[codec(compact)]
+	pub attributes: u32,
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Issue a new class of non-fungible assets from a public origin.
+		///
+		/// This new class has no instances initially. Its owner is the origin, and
+		/// `ClassDeposit` is reserved from it.
+		///
+		/// The origin must be Signed and the sender must have sufficient funds free.
+		///
+		/// - `class`: The identifier of the new class. This must not be currently in use.
+		/// - `admin`: The admin of this class. The admin is the initial address of each member of
+		/// the class's admin team.
+		///
+		/// Emits `Created` event when successful.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read and write.
+		/// - 1 balance reservation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 50_000_000]
+		fn create(origin, #[compact] class: T::ClassId, admin: <T::Lookup as StaticLookup>::Source) {
+			let owner = ensure_signed(origin)?;
+			let admin = T::Lookup::lookup(admin)?;
+
+			ensure!(!Class::<T>::contains_key(class), Error::<T>::InUse);
+
+			let deposit = T::ClassDeposit::get();
+			T::Currency::reserve(&owner, deposit)?;
+
+			Class::<T>::insert(class, ClassDetails {
+				owner: owner.clone(),
+				issuer: admin.clone(),
+				admin: admin.clone(),
+				freezer: admin.clone(),
+				total_deposit: deposit,
+				free_holding: false,
+				instances: 0,
+				instance_metadatas: 0,
+				attributes: 0,
+				is_frozen: false,
+			});
+			Self::deposit_event(RawEvent::Created(class, owner, admin));
+		}
+
+		/// Issue a new class of non-fungible assets from a privileged origin.
+		///
+		/// This new class has no instances initially and no deposit is taken.
+		///
+		/// The origin must conform to `ForceOrigin`.
+		///
+		/// - `class`: The identifier of the new class. This must not be currently in use.
+		/// - `owner`: The owner of this class. The owner has full superuser permissions over this
+		/// class, but may later change and configure the permissions using `transfer_ownership`
+		/// and `set_team`.
+		/// - `free_holding`: Whether a deposit is taken for holding an instance of this class.
+		///
+		/// Emits `ForceCreated` event when successful.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read and write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 25_000_000]
+		fn force_create(origin,
+			#[compact] class: T::ClassId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			free_holding: bool,
+		) {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			ensure!(!Class::<T>::contains_key(class), Error::<T>::InUse);
+
+			Class::<T>::insert(class, ClassDetails {
+				owner: owner.clone(),
+				issuer: owner.clone(),
+				admin: owner.clone(),
+				freezer: owner.clone(),
+				total_deposit: Zero::zero(),
+				free_holding,
+				instances: 0,
+				instance_metadatas: 0,
+				attributes: 0,
+				is_frozen: false,
+			});
+			Self::deposit_event(RawEvent::ForceCreated(class, owner));
+		}
+
+		/// Destroy a class of non-fungible assets, along with all of its instances, metadata and
+		/// attributes.
+		///
+		/// The origin must conform to `ForceOrigin` or must be Signed and the sender must be the
+		/// owner of the `class`. All the deposits of the class are returned to its owner.
+		///
+		/// - `class`: The identifier of the class to be destroyed.
+		/// - `witness`: Upper bounds on the number of instances of the class, of its instances
+		/// with metadata and of its attributes.
+		///
+		/// Emits `Destroyed` event when successful.
+		///
+		/// # <weight>
+		/// - `O(n + m + a)` where `n` is `witness.instances`, `m` is `witness.instance_metadatas`
+		/// and `a` is `witness.attributes`.
+		/// - `n + 1` storage reads.
+		/// - `2 * n + m + a + 2` storage deletions.
+		/// - 1 balance unreservation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(
+				(witness.instances as Weight).saturating_add(1),
+				(witness.instances as Weight).saturating_mul(2)
+					.saturating_add(witness.instance_metadatas as Weight)
+					.saturating_add(witness.attributes as Weight)
+					.saturating_add(2),
+			)
+			.saturating_add(50_000_000)
+			.saturating_add((witness.instances as Weight).saturating_mul(15_000_000))
+			.saturating_add((witness.instance_metadatas as Weight).saturating_mul(5_000_000))
+			.saturating_add((witness.attributes as Weight).saturating_mul(5_000_000))
+		]
+		fn destroy(origin, #[compact] class: T::ClassId, witness: DestroyWitness) {
+			let maybe_check_owner = Self::ensure_force_or_signed(origin)?;
+			let details = Class::<T>::get(class).ok_or(Error::<T>::Unknown)?;
+			if let Some(check_owner) = maybe_check_owner {
+				ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+			}
+			ensure!(details.instances <= witness.instances, Error::<T>::BadWitness);
+			ensure!(details.instance_metadatas <= witness.instance_metadatas, Error::<T>::BadWitness);
+			ensure!(details.attributes <= witness.attributes, Error::<T>::BadWitness);
+
+			for (instance, instance_details) in Asset::<T>::drain_prefix(&class) {
+				Account::<T>::remove(&instance_details.owner, (class, instance));
+			}
+			InstanceMetadataOf::<T>::remove_prefix(&class);
+			ClassMetadataOf::<T>::remove(class);
+			Attribute::<T>::remove_prefix(&class);
+			Class::<T>::remove(class);
+			T::Currency::unreserve(&details.owner, details.total_deposit);
+
+			Self::deposit_event(RawEvent::Destroyed(class));
+		}
+
+		/// Mint an instance of a particular class.
+		///
+		/// The origin must be Signed and the sender must be the Issuer of the `class`. Unless the
+		/// class was force-created with free holding, `InstanceDeposit` is reserved from the owner
+		/// of the class.
+		///
+		/// - `class`: The class of the instance to be minted.
+		/// - `instance`: The identifier of the instance to be minted. This must not be currently
+		/// in use within the class.
+		/// - `owner`: The initial owner of the minted instance.
+		///
+		/// Emits `Issued` event when successful.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 storage reads.
+		/// - 3 storage writes.
+		/// - 1 balance reservation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 3) + 50_000_000]
+		fn mint(origin,
+			#[compact] class: T::ClassId,
+			#[compact] instance: T::InstanceId,
+			owner: <T::Lookup as StaticLookup>::Source,
+		) {
+			let origin = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			ensure!(!Asset::<T>::contains_key(class, instance), Error::<T>::AlreadyExists);
+
+			Class::<T>::try_mutate(class, |maybe_class_details| -> DispatchResult {
+				let class_details = maybe_class_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == class_details.issuer, Error::<T>::NoPermission);
+
+				let instances = class_details.instances.checked_add(1).ok_or(Error::<T>::Overflow)?;
+				let deposit = if class_details.free_holding {
+					Zero::zero()
+				} else {
+					T::InstanceDeposit::get()
+				};
+				T::Currency::reserve(&class_details.owner, deposit)?;
+				class_details.total_deposit = class_details.total_deposit.saturating_add(deposit);
+				class_details.instances = instances;
+
+				Account::<T>::insert(&owner, (class, instance), ());
+				Asset::<T>::insert(class, instance, InstanceDetails {
+					owner: owner.clone(),
+					approved: None,
+					is_frozen: false,
+					deposit,
+				});
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::Issued(class, instance, owner));
+		}
+
+		/// Destroy a single instance.
+		///
+		/// The origin must be Signed and the sender should be the Admin of the `class` or the
+		/// owner of the `instance`. The deposits of the instance and of its metadata are returned
+		/// to the owner of the class; its attributes are kept.
+		///
+		/// - `class`: The class of the instance to be burned.
+		/// - `instance`: The instance to be burned.
+		/// - `check_owner`: If `Some` then the operation will fail with `WrongOwner` unless the
+		/// instance is owned by this value.
+		///
+		/// Emits `Burned`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 3 storage reads.
+		/// - 4 storage writes.
+		/// - 1 balance unreservation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(3, 4) + 50_000_000]
+		fn burn(origin,
+			#[compact] class: T::ClassId,
+			#[compact] instance: T::InstanceId,
+			check_owner: Option<<T::Lookup as StaticLookup>::Source>,
+		) {
+			let origin = ensure_signed(origin)?;
+			let check_owner = check_owner.map(T::Lookup::lookup).transpose()?;
+
+			let owner = Class::<T>::try_mutate(class, |maybe_class_details| -> Result<_, DispatchError> {
+				let class_details = maybe_class_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				let details = Asset::<T>::get(class, instance).ok_or(Error::<T>::Unknown)?;
+				ensure!(
+					origin == class_details.admin || origin == details.owner,
+					Error::<T>::NoPermission,
+				);
+				ensure!(check_owner.map_or(true, |o| o == details.owner), Error::<T>::WrongOwner);
+
+				let mut deposit = details.deposit;
+				if let Some(metadata) = InstanceMetadataOf::<T>::take(class, instance) {
+					deposit = deposit.saturating_add(metadata.deposit);
+					class_details.instance_metadatas = class_details.instance_metadatas.saturating_sub(1);
+				}
+				T::Currency::unreserve(&class_details.owner, deposit);
+				class_details.total_deposit = class_details.total_deposit.saturating_sub(deposit);
+				class_details.instances = class_details.instances.saturating_sub(1);
+				Ok(details.owner)
+			})?;
+
+			Asset::<T>::remove(class, instance);
+			Account::<T>::remove(&owner, (class, instance));
+
+			Self::deposit_event(RawEvent::Burned(class, instance, owner));
+		}
+
+		/// Move an instance from the sender account to another.
+		///
+		/// The origin must be Signed and the sender must be one of:
+		/// - the Admin of the `class`;
+		/// - the owner of the `instance`;
+		/// - the approved delegate for the `instance`, in which case the approval is consumed.
+		///
+		/// Neither the instance nor its class must be frozen. Any approval of the instance is
+		/// removed.
+		///
+		/// - `class`: The class of the instance to be transferred.
+		/// - `instance`: The instance to be transferred.
+		/// - `dest`: The account to receive ownership of the instance.
+		///
+		/// Emits `Transferred`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 static lookup
+		/// - 2 storage reads.
+		/// - 3 storage writes.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 3) + 50_000_000]
+		fn transfer(origin,
+			#[compact] class: T::ClassId,
+			#[compact] instance: T::InstanceId,
+			dest: <T::Lookup as StaticLookup>::Source,
+		) {
+			let origin = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+
+			let class_details = Class::<T>::get(class).ok_or(Error::<T>::Unknown)?;
+			ensure!(!class_details.is_frozen, Error::<T>::Frozen);
+
+			let mut details = Asset::<T>::get(class, instance).ok_or(Error::<T>::Unknown)?;
+			ensure!(!details.is_frozen, Error::<T>::Frozen);
+			if origin != details.owner && origin != class_details.admin {
+				let approved = details.approved.as_ref().map_or(false, |approved| approved == &origin);
+				ensure!(approved, Error::<T>::NoPermission);
+			}
+
+			let source = details.owner;
+			Account::<T>::remove(&source, (class, instance));
+			Account::<T>::insert(&dest, (class, instance), ());
+			details.owner = dest.clone();
+			details.approved = None;
+			Asset::<T>::insert(class, instance, details);
+
+			Self::deposit_event(RawEvent::Transferred(class, instance, source, dest));
+		}
+
+		/// Disallow further unprivileged transfer of an instance.
+		///
+		/// The origin must be Signed and the sender should be the Freezer of the `class`.
+		///
+		/// - `class`: The class of the instance to be frozen.
+		/// - `instance`: The instance to be frozen.
+		///
+		/// Emits `Frozen`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 storage reads.
+		/// - 1 storage write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 1) + 30_000_000]
+		fn freeze(origin, #[compact] class: T::ClassId, #[compact] instance: T::InstanceId) {
+			let origin = ensure_signed(origin)?;
+
+			let class_details = Class::<T>::get(class).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == class_details.freezer, Error::<T>::NoPermission);
+
+			Asset::<T>::try_mutate(class, instance, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				details.is_frozen = true;
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::Frozen(class, instance));
+		}
+
+		/// Re-allow unprivileged transfer of an instance.
+		///
+		/// The origin must be Signed and the sender should be the Admin of the `class`.
+		///
+		/// - `class`: The class of the instance to be thawed.
+		/// - `instance`: The instance to be thawed.
+		///
+		/// Emits `Thawed`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 storage reads.
+		/// - 1 storage write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 1) + 30_000_000]
+		fn thaw(origin, #[compact] class: T::ClassId, #[compact] instance: T::InstanceId) {
+			let origin = ensure_signed(origin)?;
+
+			let class_details = Class::<T>::get(class).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == class_details.admin, Error::<T>::NoPermission);
+
+			Asset::<T>::try_mutate(class, instance, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				details.is_frozen = false;
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::Thawed(class, instance));
+		}
+
+		/// Disallow further unprivileged transfers for a whole class.
+		///
+		/// The origin must be Signed and the sender should be the Freezer of the `class`.
+		///
+		/// - `class`: The class to be frozen.
+		///
+		/// Emits `ClassFrozen`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read and write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 25_000_000]
+		fn freeze_class(origin, #[compact] class: T::ClassId) {
+			let origin = ensure_signed(origin)?;
+
+			Class::<T>::try_mutate(class, |maybe_class_details| -> DispatchResult {
+				let class_details = maybe_class_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == class_details.freezer, Error::<T>::NoPermission);
+
+				class_details.is_frozen = true;
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::ClassFrozen(class));
+		}
+
+		/// Re-allow unprivileged transfers for a whole class.
+		///
+		/// The origin must be Signed and the sender should be the Admin of the `class`.
+		///
+		/// - `class`: The class to be thawed.
+		///
+		/// Emits `ClassThawed`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read and write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 25_000_000]
+		fn thaw_class(origin, #[compact] class: T::ClassId) {
+			let origin = ensure_signed(origin)?;
+
+			Class::<T>::try_mutate(class, |maybe_class_details| -> DispatchResult {
+				let class_details = maybe_class_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == class_details.admin, Error::<T>::NoPermission);
+
+				class_details.is_frozen = false;
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::ClassThawed(class));
+		}
+
+		/// Change the Owner of a class.
+		///
+		/// The origin must be Signed and the sender should be the Owner of the `class`. All the
+		/// deposits of the class are moved to the new owner.
+		///
+		/// - `class`: The class whose owner should be changed.
+		/// - `owner`: The new Owner of this class.
+		///
+		/// Emits `OwnerChanged`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read and write.
+		/// - 1 balance repatriation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 45_000_000]
+		fn transfer_ownership(origin,
+			#[compact] class: T::ClassId,
+			owner: <T::Lookup as StaticLookup>::Source,
+		) {
+			let origin = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			Class::<T>::try_mutate(class, |maybe_class_details| -> DispatchResult {
+				let class_details = maybe_class_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == class_details.owner, Error::<T>::NoPermission);
+				if class_details.owner == owner {
+					return Ok(())
+				}
+
+				T::Currency::repatriate_reserved(
+					&class_details.owner,
+					&owner,
+					class_details.total_deposit,
+					BalanceStatus::Reserved,
+				)?;
+				class_details.owner = owner.clone();
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::OwnerChanged(class, owner));
+		}
+
+		/// Change the Issuer, Admin and Freezer of a class.
+		///
+		/// The origin must be Signed and the sender should be the Owner of the `class`.
+		///
+		/// - `class`: The class whose team should be changed.
+		/// - `issuer`: The new Issuer of this class.
+		/// - `admin`: The new Admin of this class.
+		/// - `freezer`: The new Freezer of this class.
+		///
+		/// Emits `TeamChanged`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read and write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 25_000_000]
+		fn set_team(origin,
+			#[compact] class: T::ClassId,
+			issuer: <T::Lookup as StaticLookup>::Source,
+			admin: <T::Lookup as StaticLookup>::Source,
+			freezer: <T::Lookup as StaticLookup>::Source,
+		) {
+			let origin = ensure_signed(origin)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+			let admin = T::Lookup::lookup(admin)?;
+			let freezer = T::Lookup::lookup(freezer)?;
+
+			Class::<T>::try_mutate(class, |maybe_class_details| -> DispatchResult {
+				let class_details = maybe_class_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == class_details.owner, Error::<T>::NoPermission);
+
+				class_details.issuer = issuer.clone();
+				class_details.admin = admin.clone();
+				class_details.freezer = freezer.clone();
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::TeamChanged(class, issuer, admin, freezer));
+		}
+
+		/// Approve an instance to be transferred by a delegated third-party account.
+		///
+		/// The origin must be Signed and the sender must be either the owner of the `instance` or
+		/// the Admin of the `class`. Any previous approval of the instance is replaced.
+		///
+		/// - `class`: The class of the instance to be approved for delegated transfer.
+		/// - `instance`: The instance to be approved for delegated transfer.
+		/// - `delegate`: The account to delegate permission to transfer the instance.
+		///
+		/// Emits `ApprovedTransfer` on success.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 storage reads.
+		/// - 1 storage write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 1) + 35_000_000]
+		fn approve_transfer(origin,
+			#[compact] class: T::ClassId,
+			#[compact] instance: T::InstanceId,
+			delegate: <T::Lookup as StaticLookup>::Source,
+		) {
+			let origin = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+
+			let class_details = Class::<T>::get(class).ok_or(Error::<T>::Unknown)?;
+			let mut details = Asset::<T>::get(class, instance).ok_or(Error::<T>::Unknown)?;
+			ensure!(
+				origin == details.owner || origin == class_details.admin,
+				Error::<T>::NoPermission,
+			);
+
+			details.approved = Some(delegate.clone());
+			let owner = details.owner.clone();
+			Asset::<T>::insert(class, instance, details);
+
+			Self::deposit_event(RawEvent::ApprovedTransfer(class, instance, owner, delegate));
+		}
+
+		/// Cancel the prior approval for the transfer of an instance by a delegate.
+		///
+		/// The origin must be Signed and the sender must be either the owner of the `instance` or
+		/// the Admin of the `class`.
+		///
+		/// - `class`: The class of the instance whose approval will be cancelled.
+		/// - `instance`: The instance whose approval will be cancelled.
+		/// - `maybe_check_delegate`: If `Some` will ensure that the given account is the one to
+		/// which permission of transfer is delegated.
+		///
+		/// Emits `ApprovalCancelled` on success.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 storage reads.
+		/// - 1 storage write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 1) + 35_000_000]
+		fn cancel_approval(origin,
+			#[compact] class: T::ClassId,
+			#[compact] instance: T::InstanceId,
+			maybe_check_delegate: Option<<T::Lookup as StaticLookup>::Source>,
+		) {
+			let origin = ensure_signed(origin)?;
+			let maybe_check_delegate = maybe_check_delegate.map(T::Lookup::lookup).transpose()?;
+
+			let class_details = Class::<T>::get(class).ok_or(Error::<T>::Unknown)?;
+			let mut details = Asset::<T>::get(class, instance).ok_or(Error::<T>::Unknown)?;
+			ensure!(
+				origin == details.owner || origin == class_details.admin,
+				Error::<T>::NoPermission,
+			);
+
+			let old = details.approved.take().ok_or(Error::<T>::NoDelegate)?;
+			if let Some(check_delegate) = maybe_check_delegate {
+				ensure!(check_delegate == old, Error::<T>::WrongDelegate);
+			}
+			let owner = details.owner.clone();
+			Asset::<T>::insert(class, instance, details);
+
+			Self::deposit_event(RawEvent::ApprovalCancelled(class, instance, owner, old));
+		}
+
+		/// Set an attribute for a class or an instance.
+		///
+		/// The origin must conform to `ForceOrigin` or must be Signed and the sender must be the
+		/// Owner of the `class`, in which case the metadata of the class, or of the instance,
+		/// must not be frozen.
+		///
+		/// Funds of the class owner are reserved according to the formula:
+		/// `AttributeDepositBase + DepositPerByte * (key.len + value.len)` taking into account
+		/// any already reserved funds. No additional funds are reserved when the origin is the
+		/// `ForceOrigin`.
+		///
+		/// - `class`: The identifier of the class whose instance's attribute to set.
+		/// - `maybe_instance`: The identifier of the instance whose attribute to set, or `None`
+		/// to set an attribute of the class itself.
+		/// - `key`: The key of the attribute. Limited in length by `KeyLimit`.
+		/// - `value`: The value to which to set the attribute. Limited in length by `ValueLimit`.
+		///
+		/// Emits `AttributeSet`.
+		///
+		/// # <weight>
+		/// - `O(K + V)` where `K` is the length of the key and `V` the length of the value.
+		/// - 4 storage reads.
+		/// - 2 storage writes.
+		/// - 1 balance reservation or unreservation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(4, 2)
+			.saturating_add(50_000_000)
+			.saturating_add(((key.len() + value.len()) as Weight).saturating_mul(10_000))
+		]
+		fn set_attribute(origin,
+			#[compact] class: T::ClassId,
+			maybe_instance: Option<T::InstanceId>,
+			key: Vec<u8>,
+			value: Vec<u8>,
+		) {
+			let maybe_check_owner = Self::ensure_force_or_signed(origin)?;
+
+			ensure!(key.len() <= T::KeyLimit::get() as usize, Error::<T>::BadLimit);
+			ensure!(value.len() <= T::ValueLimit::get() as usize, Error::<T>::BadLimit);
+
+			let mut class_details = Class::<T>::get(class).ok_or(Error::<T>::Unknown)?;
+			if let Some(check_owner) = &maybe_check_owner {
+				ensure!(check_owner == &class_details.owner, Error::<T>::NoPermission);
+				ensure!(!Self::is_metadata_frozen(class, maybe_instance), Error::<T>::Frozen);
+			}
+			if let Some(instance) = maybe_instance {
+				ensure!(Asset::<T>::contains_key(class, instance), Error::<T>::Unknown);
+			}
+
+			let attribute = Attribute::<T>::get(class, (maybe_instance, &key));
+			if attribute.is_none() {
+				class_details.attributes = class_details.attributes.checked_add(1)
+					.ok_or(Error::<T>::Overflow)?;
+			}
+			let old_deposit = attribute.map_or(Zero::zero(), |(_, deposit)| deposit);
+			let deposit = if maybe_check_owner.is_some() {
+				T::DepositPerByte::get()
+					.saturating_mul(((key.len() + value.len()) as u32).into())
+					.saturating_add(T::AttributeDepositBase::get())
+			} else {
+				old_deposit
+			};
+			Self::update_deposit(&mut class_details, old_deposit, deposit)?;
+
+			Attribute::<T>::insert(class, (maybe_instance, &key), (&value, deposit));
+			Class::<T>::insert(class, class_details);
+			Self::deposit_event(RawEvent::AttributeSet(class, maybe_instance, key, value));
+		}
+
+		/// Clear an attribute for a class or an instance.
+		///
+		/// The origin must conform to `ForceOrigin` or must be Signed and the sender must be the
+		/// Owner of the `class`, in which case the metadata of the class, or of the instance,
+		/// must not be frozen.
+		///
+		/// Any deposit is returned to the class owner.
+		///
+		/// - `class`: The identifier of the class whose instance's attribute to clear.
+		/// - `maybe_instance`: The identifier of the instance whose attribute to clear, or `None`
+		/// to clear an attribute of the class itself.
+		/// - `key`: The key of the attribute.
+		///
+		/// Emits `AttributeCleared`.
+		///
+		/// # <weight>
+		/// - `O(K)` where `K` is the length of the key.
+		/// - 3 storage reads.
+		/// - 2 storage writes.
+		/// - 1 balance unreservation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(3, 2)
+			.saturating_add(45_000_000)
+			.saturating_add((key.len() as Weight).saturating_mul(10_000))
+		]
+		fn clear_attribute(origin,
+			#[compact] class: T::ClassId,
+			maybe_instance: Option<T::InstanceId>,
+			key: Vec<u8>,
+		) {
+			let maybe_check_owner = Self::ensure_force_or_signed(origin)?;
+
+			let mut class_details = Class::<T>::get(class).ok_or(Error::<T>::Unknown)?;
+			if let Some(check_owner) = &maybe_check_owner {
+				ensure!(check_owner == &class_details.owner, Error::<T>::NoPermission);
+				ensure!(!Self::is_metadata_frozen(class, maybe_instance), Error::<T>::Frozen);
+			}
+
+			let (_, deposit) = Attribute::<T>::take(class, (maybe_instance, &key))
+				.ok_or(Error::<T>::Unknown)?;
+			class_details.attributes = class_details.attributes.saturating_sub(1);
+			Self::update_deposit(&mut class_details, deposit, Zero::zero())?;
+
+			Class::<T>::insert(class, class_details);
+			Self::deposit_event(RawEvent::AttributeCleared(class, maybe_instance, key));
+		}
+
+		/// Set the metadata for an instance.
+		///
+		/// The origin must conform to `ForceOrigin` or must be Signed and the sender must be the
+		/// Owner of the `class`, in which case the metadata must not be frozen.
+		///
+		/// Funds of the class owner are reserved according to the formula:
+		/// `MetadataDepositBase + DepositPerByte * data.len` taking into account any already
+		/// reserved funds. No additional funds are reserved when the origin is the `ForceOrigin`.
+		///
+		/// - `class`: The identifier of the class whose instance's metadata to set.
+		/// - `instance`: The identifier of the instance whose metadata to set.
+		/// - `data`: The general information of this instance. Limited in length by
+		/// `StringLimit`.
+		/// - `is_frozen`: Whether the metadata should be frozen against further changes by the
+		/// class owner.
+		///
+		/// Emits `MetadataSet`.
+		///
+		/// # <weight>
+		/// - `O(N)` where `N` is the length of the data.
+		/// - 3 storage reads.
+		/// - 2 storage writes.
+		/// - 1 balance reservation or unreservation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(3, 2)
+			.saturating_add(50_000_000)
+			.saturating_add((data.len() as Weight).saturating_mul(10_000))
+		]
+		fn set_metadata(origin,
+			#[compact] class: T::ClassId,
+			#[compact] instance: T::InstanceId,
+			data: Vec<u8>,
+			is_frozen: bool,
+		) {
+			let maybe_check_owner = Self::ensure_force_or_signed(origin)?;
+
+			ensure!(data.len() <= T::StringLimit::get() as usize, Error::<T>::BadLimit);
+
+			let mut class_details = Class::<T>::get(class).ok_or(Error::<T>::Unknown)?;
+			if let Some(check_owner) = &maybe_check_owner {
+				ensure!(check_owner == &class_details.owner, Error::<T>::NoPermission);
+			}
+			ensure!(Asset::<T>::contains_key(class, instance), Error::<T>::Unknown);
+
+			let metadata = InstanceMetadataOf::<T>::get(class, instance);
+			if metadata.is_none() {
+				class_details.instance_metadatas = class_details.instance_metadatas.checked_add(1)
+					.ok_or(Error::<T>::Overflow)?;
+			}
+			let old_deposit = Self::check_metadata(&metadata, &maybe_check_owner)?;
+			let deposit = if maybe_check_owner.is_some() {
+				Self::metadata_deposit(data.len())
+			} else {
+				old_deposit
+			};
+			Self::update_deposit(&mut class_details, old_deposit, deposit)?;
+
+			InstanceMetadataOf::<T>::insert(class, instance, Metadata { deposit, data: data.clone(), is_frozen });
+			Class::<T>::insert(class, class_details);
+			Self::deposit_event(RawEvent::MetadataSet(class, instance, data, is_frozen));
+		}
+
+		/// Clear the metadata for an instance.
+		///
+		/// The origin must conform to `ForceOrigin` or must be Signed and the sender must be the
+		/// Owner of the `class`, in which case the metadata must not be frozen.
+		///
+		/// Any deposit is returned to the class owner.
+		///
+		/// - `class`: The identifier of the class whose instance's metadata to clear.
+		/// - `instance`: The identifier of the instance whose metadata to clear.
+		///
+		/// Emits `MetadataCleared`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 storage reads.
+		/// - 2 storage writes.
+		/// - 1 balance unreservation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 45_000_000]
+		fn clear_metadata(origin, #[compact] class: T::ClassId, #[compact] instance: T::InstanceId) {
+			let maybe_check_owner = Self::ensure_force_or_signed(origin)?;
+
+			let mut class_details = Class::<T>::get(class).ok_or(Error::<T>::Unknown)?;
+			if let Some(check_owner) = &maybe_check_owner {
+				ensure!(check_owner == &class_details.owner, Error::<T>::NoPermission);
+			}
+
+			let metadata = InstanceMetadataOf::<T>::get(class, instance);
+			ensure!(metadata.is_some(), Error::<T>::Unknown);
+			let deposit = Self::check_metadata(&metadata, &maybe_check_owner)?;
+			class_details.instance_metadatas = class_details.instance_metadatas.saturating_sub(1);
+			Self::update_deposit(&mut class_details, deposit, Zero::zero())?;
+
+			InstanceMetadataOf::<T>::remove(class, instance);
+			Class::<T>::insert(class, class_details);
+			Self::deposit_event(RawEvent::MetadataCleared(class, instance));
+		}
+
+		/// Set the metadata for a class.
+		///
+		/// The origin must conform to `ForceOrigin` or must be Signed and the sender must be the
+		/// Owner of the `class`, in which case the metadata must not be frozen.
+		///
+		/// Funds of the class owner are reserved according to the formula:
+		/// `MetadataDepositBase + DepositPerByte * data.len` taking into account any already
+		/// reserved funds. No additional funds are reserved when the origin is the `ForceOrigin`.
+		///
+		/// - `class`: The identifier of the class whose metadata to set.
+		/// - `data`: The general information of this class. Limited in length by `StringLimit`.
+		/// - `is_frozen`: Whether the metadata should be frozen against further changes by the
+		/// class owner.
+		///
+		/// Emits `ClassMetadataSet`.
+		///
+		/// # <weight>
+		/// - `O(N)` where `N` is the length of the data.
+		/// - 2 storage reads.
+		/// - 2 storage writes.
+		/// - 1 balance reservation or unreservation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 2)
+			.saturating_add(50_000_000)
+			.saturating_add((data.len() as Weight).saturating_mul(10_000))
+		]
+		fn set_class_metadata(origin, #[compact] class: T::ClassId, data: Vec<u8>, is_frozen: bool) {
+			let maybe_check_owner = Self::ensure_force_or_signed(origin)?;
+
+			ensure!(data.len() <= T::StringLimit::get() as usize, Error::<T>::BadLimit);
+
+			let mut class_details = Class::<T>::get(class).ok_or(Error::<T>::Unknown)?;
+			if let Some(check_owner) = &maybe_check_owner {
+				ensure!(check_owner == &class_details.owner, Error::<T>::NoPermission);
+			}
+
+			let metadata = ClassMetadataOf::<T>::get(class);
+			let old_deposit = Self::check_metadata(&metadata, &maybe_check_owner)?;
+			let deposit = if maybe_check_owner.is_some() {
+				Self::metadata_deposit(data.len())
+			} else {
+				old_deposit
+			};
+			Self::update_deposit(&mut class_details, old_deposit, deposit)?;
+
+			ClassMetadataOf::<T>::insert(class, Metadata { deposit, data: data.clone(), is_frozen });
+			Class::<T>::insert(class, class_details);
+			Self::deposit_event(RawEvent::ClassMetadataSet(class, data, is_frozen));
+		}
+
+		/// Clear the metadata for a class.
+		///
+		/// The origin must conform to `ForceOrigin` or must be Signed and the sender must be the
+		/// Owner of the `class`, in which case the metadata must not be frozen.
+		///
+		/// Any deposit is returned to the class owner.
+		///
+		/// - `class`: The identifier of the class whose metadata to clear.
+		///
+		/// Emits `ClassMetadataCleared`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 storage reads.
+		/// - 2 storage writes.
+		/// - 1 balance unreservation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 45_000_000]
+		fn clear_class_metadata(origin, #[compact] class: T::ClassId) {
+			let maybe_check_owner = Self::ensure_force_or_signed(origin)?;
+
+			let mut class_details = Class::<T>::get(class).ok_or(Error::<T>::Unknown)?;
+			if let Some(check_owner) = &maybe_check_owner {
+				ensure!(check_owner == &class_details.owner, Error::<T>::NoPermission);
+			}
+
+			let metadata = ClassMetadataOf::<T>::get(class);
+			ensure!(metadata.is_some(), Error::<T>::Unknown);
+			let deposit = Self::check_metadata(&metadata, &maybe_check_owner)?;
+			Self::update_deposit(&mut class_details, deposit, Zero::zero())?;
+
+			ClassMetadataOf::<T>::remove(class);
+			Class::<T>::insert(class, class_details);
+			Self::deposit_event(RawEvent::ClassMetadataCleared(class));
+		}
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		<T as frame_system::Trait>::AccountId,
+		<T as Trait>::ClassId,
+		<T as Trait>::InstanceId,
+	{
+		/// An asset class was created. [class, creator, admin]
+		Created(ClassId, AccountId, AccountId),
+		/// An asset class was force-created. [class, owner]
+		ForceCreated(ClassId, AccountId),
+		/// An asset class was destroyed. [class]
+		Destroyed(ClassId),
+		/// An asset instance was issued. [class, instance, owner]
+		Issued(ClassId, InstanceId, AccountId),
+		/// An asset instance was transferred. [class, instance, from, to]
+		Transferred(ClassId, InstanceId, AccountId, AccountId),
+		/// An asset instance was destroyed. [class, instance, owner]
+		Burned(ClassId, InstanceId, AccountId),
+		/// Some asset instance was frozen. [class, instance]
+		Frozen(ClassId, InstanceId),
+		/// Some asset instance was thawed. [class, instance]
+		Thawed(ClassId, InstanceId),
+		/// Some asset class was frozen. [class]
+		ClassFrozen(ClassId),
+		/// Some asset class was thawed. [class]
+		ClassThawed(ClassId),
+		/// The owner changed. [class, owner]
+		OwnerChanged(ClassId, AccountId),
+		/// The management team changed. [class, issuer, admin, freezer]
+		TeamChanged(ClassId, AccountId, AccountId, AccountId),
+		/// An instance of an asset class has been approved by the owner for transfer by a
+		/// delegate. [class, instance, owner, delegate]
+		ApprovedTransfer(ClassId, InstanceId, AccountId, AccountId),
+		/// An approval for a delegate account to transfer the instance of an asset class was
+		/// cancelled by its owner. [class, instance, owner, delegate]
+		ApprovalCancelled(ClassId, InstanceId, AccountId, AccountId),
+		/// New metadata has been set for an asset class. [class, data, is_frozen]
+		ClassMetadataSet(ClassId, Vec<u8>, bool),
+		/// Metadata has been cleared for an asset class. [class]
+		ClassMetadataCleared(ClassId),
+		/// New metadata has been set for an asset instance. [class, instance, data, is_frozen]
+		MetadataSet(ClassId, InstanceId, Vec<u8>, bool),
+		/// Metadata has been cleared for an asset instance. [class, instance]
+		MetadataCleared(ClassId, InstanceId),
+		/// New attribute has been set for an asset class or instance.
+		/// [class, maybe_instance, key, value]
+		AttributeSet(ClassId, Option<InstanceId>, Vec<u8>, Vec<u8>),
+		/// Attribute has been cleared for an asset class or instance.
+		/// [class, maybe_instance, key]
+		AttributeCleared(ClassId, Option<InstanceId>, Vec<u8>),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The signing account has no permission to do the operation.
+		NoPermission,
+		/// The given asset ID is unknown.
+		Unknown,
+		/// The asset instance ID has already been used for an asset.
+		AlreadyExists,
+		/// The owner turned out to be different to what was expected.
+		WrongOwner,
+		/// Invalid witness data given.
+		BadWitness,
+		/// The asset class ID is already taken.
+		InUse,
+		/// The asset instance, its class or its metadata is frozen.
+		Frozen,
+		/// The delegate turned out to be different to what was expected.
+		WrongDelegate,
+		/// There is no delegate approved.
+		NoDelegate,
+		/// Some data exceeds its length limit.
+		BadLimit,
+		/// An operation would overflow a counter.
+		Overflow,
+	}
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Uniques {
+		/// Details of an asset class.
+		Class: map hasher(blake2_128_concat) T::ClassId
+			=> Option<ClassDetails<T::AccountId, BalanceOf<T>>>;
+		/// The asset instances held by any given account.
+		Account: double_map hasher(blake2_128_concat) T::AccountId,
+			hasher(blake2_128_concat) (T::ClassId, T::InstanceId)
+			=> Option<()>;
+		/// The asset instances in existence and their ownership details.
+		Asset: double_map hasher(blake2_128_concat) T::ClassId, hasher(blake2_128_concat) T::InstanceId
+			=> Option<InstanceDetails<T::AccountId, BalanceOf<T>>>;
+		/// Metadata of an asset class.
+		ClassMetadataOf: map hasher(blake2_128_concat) T::ClassId => Option<Metadata<BalanceOf<T>>>;
+		/// Metadata of an asset instance.
+		InstanceMetadataOf: double_map hasher(blake2_128_concat) T::ClassId,
+			hasher(blake2_128_concat) T::InstanceId
+			=> Option<Metadata<BalanceOf<T>>>;
+		/// Attributes of an asset class, or of one of its instances, and their deposit.
+		Attribute: double_map hasher(blake2_128_concat) T::ClassId,
+			hasher(blake2_128_concat) (Option<T::InstanceId>, Vec<u8>)
+			=> Option<(Vec<u8>, BalanceOf<T>)>;
+	}
+}
+
+// The main implementation block for the module.
+impl<T: Trait> Module<T> {
+	// Public immutables
+
+	/// Get the owner of the asset `instance` of `class`, if it exists.
+	pub fn owner(class: T::ClassId, instance: T::InstanceId) -> Option<T::AccountId> {
+		Asset::<T>::get(class, instance).map(|details| details.owner)
+	}
+
+	/// Get the owner of the asset `class`, if it exists.
+	pub fn class_owner(class: T::ClassId) -> Option<T::AccountId> {
+		Class::<T>::get(class).map(|details| details.owner)
+	}
+
+	/// Get the value of the attribute `key` of `class`, or of its `maybe_instance`, if it exists.
+	pub fn attribute(class: T::ClassId, maybe_instance: Option<T::InstanceId>, key: &[u8]) -> Option<Vec<u8>> {
+		Attribute::<T>::get(class, (maybe_instance, key)).map(|(value, _)| value)
+	}
+
+	/// Ensure that the origin is either the `ForceOrigin`, returning `None`, or Signed, returning
+	/// the signer to be checked against the owner of a class.
+	fn ensure_force_or_signed(origin: T::Origin) -> Result<Option<T::AccountId>, DispatchError> {
+		match T::ForceOrigin::try_origin(origin) {
+			Ok(_) => Ok(None),
+			Err(origin) => Ok(Some(ensure_signed(origin)?)),
+		}
+	}
+
+	/// Whether the metadata of `class`, or of its `maybe_instance`, is frozen.
+	fn is_metadata_frozen(class: T::ClassId, maybe_instance: Option<T::InstanceId>) -> bool {
+		let metadata = match maybe_instance {
+			Some(instance) => InstanceMetadataOf::<T>::get(class, instance),
+			None => ClassMetadataOf::<T>::get(class),
+		};
+		metadata.map_or(false, |metadata| metadata.is_frozen)
+	}
+
+	/// Ensure that existing `metadata` may be changed by the owner to check, if any, returning
+	/// its deposit.
+	fn check_metadata(
+		metadata: &Option<Metadata<BalanceOf<T>>>,
+		maybe_check_owner: &Option<T::AccountId>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		match metadata {
+			Some(metadata) => {
+				ensure!(maybe_check_owner.is_none() || !metadata.is_frozen, Error::<T>::Frozen);
+				Ok(metadata.deposit)
+			},
+			None => Ok(Zero::zero()),
+		}
+	}
+
+	/// The deposit required for metadata of `len` bytes.
+	fn metadata_deposit(len: usize) -> BalanceOf<T> {
+		T::DepositPerByte::get()
+			.saturating_mul((len as u32).into())
+			.saturating_add(T::MetadataDepositBase::get())
+	}
+
+	/// Replace a deposit of `old` held by the owner of a class by one of `new`, reserving or
+	/// unreserving the difference.
+	fn update_deposit(
+		class_details: &mut ClassDetails<T::AccountId, BalanceOf<T>>,
+		old: BalanceOf<T>,
+		new: BalanceOf<T>,
+	) -> DispatchResult {
+		if new > old {
+			T::Currency::reserve(&class_details.owner, new - old)?;
+		} else {
+			T::Currency::unreserve(&class_details.owner, old - new);
+		}
+		class_details.total_deposit = class_details.total_deposit.saturating_add(new).saturating_sub(old);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use frame_support::{impl_outer_origin, assert_ok, assert_noop, parameter_types, weights::Weight};
+	use sp_core::H256;
+	// The testing primitives are very useful for avoiding having to work with signatures
+	// or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+	use sp_runtime::{Perbill, traits::{BadOrigin, BlakeTwo256, IdentityLookup}, testing::Header};
+	use frame_system::EnsureRoot;
+
+	impl_outer_origin! {
+		pub enum Origin for Test  where system = frame_system {}
+	}
+
+	// For testing the pallet, we construct most of a mock runtime. This means
+	// first constructing a configuration type (`Test`) which `impl`s each of the
+	// configuration traits of pallets we want to use.
+	#[derive(Clone, Eq, PartialEq)]
+	pub struct Test;
+	parameter_types! {
+		pub const BlockHashCount: u64 = 250;
+		pub const MaximumBlockWeight: Weight = 1024;
+		pub const MaximumBlockLength: u32 = 2 * 1024;
+		pub const AvailableBlockRatio: Perbill = Perbill::one();
+	}
+	impl frame_system::Trait for Test {
+		type BaseCallFilter = ();
+		type Origin = Origin;
+		type Index = u64;
+		type Call = ();
+		type BlockNumber = u64;
+		type Hash = H256;
+		type Hashing = BlakeTwo256;
+		type AccountId = u64;
+		type Lookup = IdentityLookup<Self::AccountId>;
+		type Header = Header;
+		type Event = ();
+		type BlockHashCount = BlockHashCount;
+		type MaximumBlockWeight = MaximumBlockWeight;
+		type DbWeight = ();
+		type BlockExecutionWeight = ();
+		type ExtrinsicBaseWeight = ();
+		type AvailableBlockRatio = AvailableBlockRatio;
+		type MaximumBlockLength = MaximumBlockLength;
+		type Version = ();
+		type ModuleToIndex = ();
+		type AccountData = pallet_balances::AccountData<u64>;
+		type OnNewAccount = ();
+		type OnKilledAccount = ();
+	}
+	parameter_types! {
+		pub const ExistentialDeposit: u64 = 1;
+	}
+	impl pallet_balances::Trait for Test {
+		type Balance = u64;
+		type Event = ();
+		type DustRemoval = ();
+		type ExistentialDeposit = ExistentialDeposit;
+		type AccountStore = System;
+	}
+	parameter_types! {
+		pub const ClassDeposit: u64 = 2;
+		pub const InstanceDeposit: u64 = 1;
+		pub const MetadataDepositBase: u64 = 1;
+		pub const AttributeDepositBase: u64 = 1;
+		pub const DepositPerByte: u64 = 1;
+		pub const StringLimit: u32 = 50;
+		pub const KeyLimit: u32 = 50;
+		pub const ValueLimit: u32 = 50;
+	}
+	impl Trait for Test {
+		type Event = ();
+		type ClassId = u32;
+		type InstanceId = u32;
+		type Currency = Balances;
+		type ForceOrigin = EnsureRoot<u64>;
+		type ClassDeposit = ClassDeposit;
+		type InstanceDeposit = InstanceDeposit;
+		type MetadataDepositBase = MetadataDepositBase;
+		type AttributeDepositBase = AttributeDepositBase;
+		type DepositPerByte = DepositPerByte;
+		type StringLimit = StringLimit;
+		type KeyLimit = KeyLimit;
+		type ValueLimit = ValueLimit;
+	}
+	type System = frame_system::Module<Test>;
+	type Balances = pallet_balances::Module<Test>;
+	type Uniques = Module<Test>;
+
+	// This function basically just builds a genesis storage key/value store according to
+	// our desired mockup.
+	pub fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	}
+
+	fn witness(instances: u32, instance_metadatas: u32, attributes: u32) -> DestroyWitness {
+		DestroyWitness { instances, instance_metadatas, attributes }
+	}
+
+	fn assets() -> Vec<(u64, u32, u32)> {
+		let mut r: Vec<_> = Account::<Test>::iter().map(|(owner, (class, instance), _)| (owner, class, instance)).collect();
+		r.sort();
+		r
+	}
+
+	#[test]
+	fn basic_minting_should_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+			assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 1));
+			assert_eq!(Uniques::owner(0, 42), Some(1));
+			assert_ok!(Uniques::force_create(Origin::root(), 1, 2, true));
+			assert_ok!(Uniques::mint(Origin::signed(2), 1, 69, 1));
+			assert_eq!(assets(), vec![(1, 0, 42), (1, 1, 69)]);
+			assert_noop!(Uniques::mint(Origin::signed(1), 0, 42, 2), Error::<Test>::AlreadyExists);
+			assert_noop!(Uniques::mint(Origin::signed(2), 0, 43, 2), Error::<Test>::NoPermission);
+		});
+	}
+
+	#[test]
+	fn lifecycle_should_work() {
+		new_test_ext().execute_with(|| {
+			Balances::make_free_balance_be(&1, 100);
+			assert_ok!(Uniques::create(Origin::signed(1), 0, 1));
+			assert_eq!(Balances::reserved_balance(&1), 2);
+			assert_eq!(Uniques::class_owner(0), Some(1));
+
+			assert_ok!(Uniques::set_class_metadata(Origin::signed(1), 0, vec![0, 0], false));
+			assert_eq!(Balances::reserved_balance(&1), 5);
+
+			assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 10));
+			assert_ok!(Uniques::mint(Origin::signed(1), 0, 69, 20));
+			assert_eq!(Balances::reserved_balance(&1), 7);
+			assert_eq!(assets(), vec![(10, 0, 42), (20, 0, 69)]);
+
+			assert_ok!(Uniques::set_metadata(Origin::signed(1), 0, 42, vec![42], false));
+			assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, Some(42), vec![0], vec![0]));
+			assert_eq!(Balances::reserved_balance(&1), 12);
+
+			let w = Class::<Test>::get(0).unwrap();
+			assert_noop!(Uniques::destroy(Origin::signed(1), 0, witness(1, 1, 1)), Error::<Test>::BadWitness);
+			assert_noop!(Uniques::destroy(Origin::signed(2), 0, witness(2, 1, 1)), Error::<Test>::NoPermission);
+			assert_ok!(Uniques::destroy(
+				Origin::signed(1),
+				0,
+				witness(w.instances, w.instance_metadatas, w.attributes),
+			));
+			assert_eq!(Balances::reserved_balance(&1), 0);
+
+			assert!(!Class::<Test>::contains_key(0));
+			assert!(!Asset::<Test>::contains_key(0, 42));
+			assert!(!ClassMetadataOf::<Test>::contains_key(0));
+			assert!(!InstanceMetadataOf::<Test>::contains_key(0, 42));
+			assert_eq!(Uniques::attribute(0, Some(42), &[0]), None);
+			assert_eq!(assets(), vec![]);
+		});
+	}
+
+	#[test]
+	fn mint_should_reserve_deposit_from_class_owner() {
+		new_test_ext().execute_with(|| {
+			Balances::make_free_balance_be(&1, 2);
+			assert_ok!(Uniques::create(Origin::signed(1), 0, 2));
+			assert_noop!(
+				Uniques::mint(Origin::signed(2), 0, 42, 2),
+				pallet_balances::Error::<Test, _>::InsufficientBalance,
+			);
+			Balances::make_free_balance_be(&1, 100);
+			assert_ok!(Uniques::mint(Origin::signed(2), 0, 42, 2));
+			assert_eq!(Balances::reserved_balance(&1), 3);
+			assert_ok!(Uniques::burn(Origin::signed(2), 0, 42, None));
+			assert_eq!(Balances::reserved_balance(&1), 2);
+		});
+	}
+
+	#[test]
+	fn transfer_should_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+			assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 2));
+
+			assert_ok!(Uniques::transfer(Origin::signed(2), 0, 42, 3));
+			assert_eq!(assets(), vec![(3, 0, 42)]);
+			assert_noop!(Uniques::transfer(Origin::signed(2), 0, 42, 4), Error::<Test>::NoPermission);
+
+			// the admin may transfer any instance.
+			assert_ok!(Uniques::transfer(Origin::signed(1), 0, 42, 4));
+			assert_eq!(assets(), vec![(4, 0, 42)]);
+		});
+	}
+
+	#[test]
+	fn freezing_should_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+			assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 1));
+			assert_noop!(Uniques::freeze(Origin::signed(2), 0, 42), Error::<Test>::NoPermission);
+			assert_ok!(Uniques::freeze(Origin::signed(1), 0, 42));
+			assert_noop!(Uniques::transfer(Origin::signed(1), 0, 42, 2), Error::<Test>::Frozen);
+
+			assert_ok!(Uniques::thaw(Origin::signed(1), 0, 42));
+			assert_ok!(Uniques::freeze_class(Origin::signed(1), 0));
+			assert_noop!(Uniques::transfer(Origin::signed(1), 0, 42, 2), Error::<Test>::Frozen);
+
+			assert_ok!(Uniques::thaw_class(Origin::signed(1), 0));
+			assert_ok!(Uniques::transfer(Origin::signed(1), 0, 42, 2));
+		});
+	}
+
+	#[test]
+	fn origin_guards_should_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+			assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 1));
+			assert_noop!(Uniques::transfer_ownership(Origin::signed(2), 0, 2), Error::<Test>::NoPermission);
+			assert_noop!(Uniques::set_team(Origin::signed(2), 0, 2, 2, 2), Error::<Test>::NoPermission);
+			assert_noop!(Uniques::freeze_class(Origin::signed(2), 0), Error::<Test>::NoPermission);
+			assert_noop!(Uniques::thaw_class(Origin::signed(2), 0), Error::<Test>::NoPermission);
+			assert_noop!(Uniques::thaw(Origin::signed(2), 0, 42), Error::<Test>::NoPermission);
+			assert_noop!(Uniques::mint(Origin::signed(2), 0, 69, 2), Error::<Test>::NoPermission);
+			assert_noop!(Uniques::burn(Origin::signed(2), 0, 42, None), Error::<Test>::NoPermission);
+			assert_noop!(Uniques::force_create(Origin::signed(1), 1, 1, true), BadOrigin);
+		});
+	}
+
+	#[test]
+	fn transfer_owner_should_work() {
+		new_test_ext().execute_with(|| {
+			Balances::make_free_balance_be(&1, 100);
+			Balances::make_free_balance_be(&2, 100);
+			assert_ok!(Uniques::create(Origin::signed(1), 0, 1));
+			assert_ok!(Uniques::transfer_ownership(Origin::signed(1), 0, 2));
+			assert_eq!(Balances::total_balance(&1), 98);
+			assert_eq!(Balances::total_balance(&2), 102);
+			assert_eq!(Balances::reserved_balance(&1), 0);
+			assert_eq!(Balances::reserved_balance(&2), 2);
+
+			assert_noop!(Uniques::transfer_ownership(Origin::signed(1), 0, 1), Error::<Test>::NoPermission);
+
+			// Mint and set metadata now and make sure that deposit gets transferred back.
+			assert_ok!(Uniques::set_class_metadata(Origin::signed(2), 0, vec![0; 20], false));
+			assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 1));
+			assert_ok!(Uniques::set_metadata(Origin::signed(2), 0, 42, vec![0; 20], false));
+			assert_ok!(Uniques::transfer_ownership(Origin::signed(2), 0, 1));
+			assert_eq!(Balances::total_balance(&1), 143);
+			assert_eq!(Balances::total_balance(&2), 57);
+			assert_eq!(Balances::reserved_balance(&1), 45);
+			assert_eq!(Balances::reserved_balance(&2), 0);
+		});
+	}
+
+	#[test]
+	fn set_team_should_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+			assert_ok!(Uniques::set_team(Origin::signed(1), 0, 2, 3, 4));
+
+			assert_ok!(Uniques::mint(Origin::signed(2), 0, 42, 2));
+			assert_ok!(Uniques::freeze(Origin::signed(4), 0, 42));
+			assert_ok!(Uniques::thaw(Origin::signed(3), 0, 42));
+			assert_ok!(Uniques::transfer(Origin::signed(3), 0, 42, 3));
+			assert_ok!(Uniques::burn(Origin::signed(3), 0, 42, None));
+		});
+	}
+
+	#[test]
+	fn set_class_metadata_should_work() {
+		new_test_ext().execute_with(|| {
+			// Cannot add metadata to unknown asset
+			assert_noop!(
+				Uniques::set_class_metadata(Origin::signed(1), 0, vec![0u8; 20], false),
+				Error::<Test>::Unknown,
+			);
+			assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+			// Cannot add metadata to unowned asset
+			assert_noop!(
+				Uniques::set_class_metadata(Origin::signed(2), 0, vec![0u8; 20], false),
+				Error::<Test>::NoPermission,
+			);
+
+			// Successfully add metadata and take deposit
+			Balances::make_free_balance_be(&1, 30);
+			assert_ok!(Uniques::set_class_metadata(Origin::signed(1), 0, vec![0u8; 20], false));
+			assert_eq!(Balances::free_balance(&1), 9);
+			assert!(ClassMetadataOf::<Test>::contains_key(0));
+
+			// Force origin works, too.
+			assert_ok!(Uniques::set_class_metadata(Origin::root(), 0, vec![0u8; 18], false));
+
+			// Update deposit
+			assert_ok!(Uniques::set_class_metadata(Origin::signed(1), 0, vec![0u8; 15], false));
+			assert_eq!(Balances::free_balance(&1), 14);
+			assert_ok!(Uniques::set_class_metadata(Origin::signed(1), 0, vec![0u8; 25], false));
+			assert_eq!(Balances::free_balance(&1), 4);
+
+			// Cannot over-reserve
+			assert_noop!(
+				Uniques::set_class_metadata(Origin::signed(1), 0, vec![0u8; 40], false),
+				pallet_balances::Error::<Test, _>::InsufficientBalance,
+			);
+
+			// Can't set or clear metadata once frozen
+			assert_ok!(Uniques::set_class_metadata(Origin::signed(1), 0, vec![0u8; 15], true));
+			assert_noop!(
+				Uniques::set_class_metadata(Origin::signed(1), 0, vec![0u8; 15], false),
+				Error::<Test>::Frozen,
+			);
+			assert_noop!(Uniques::clear_class_metadata(Origin::signed(1), 0), Error::<Test>::Frozen);
+
+			// Clear Metadata
+			assert_ok!(Uniques::set_class_metadata(Origin::root(), 0, vec![0u8; 15], false));
+			assert_noop!(Uniques::clear_class_metadata(Origin::signed(2), 0), Error::<Test>::NoPermission);
+			assert_noop!(Uniques::clear_class_metadata(Origin::signed(1), 1), Error::<Test>::Unknown);
+			assert_ok!(Uniques::clear_class_metadata(Origin::signed(1), 0));
+			assert!(!ClassMetadataOf::<Test>::contains_key(0));
+			assert_eq!(Balances::free_balance(&1), 30);
+		});
+	}
+
+	#[test]
+	fn set_instance_metadata_should_work() {
+		new_test_ext().execute_with(|| {
+			Balances::make_free_balance_be(&1, 30);
+
+			// Cannot add metadata to unknown instance
+			assert_ok!(Uniques::force_create(Origin::root(), 0, 1, false));
+			assert_noop!(
+				Uniques::set_metadata(Origin::signed(1), 0, 42, vec![0u8; 20], false),
+				Error::<Test>::Unknown,
+			);
+			assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 1));
+			// Cannot add metadata to unowned class
+			assert_noop!(
+				Uniques::set_metadata(Origin::signed(2), 0, 42, vec![0u8; 20], false),
+				Error::<Test>::NoPermission,
+			);
+
+			// Successfully add metadata and take deposit
+			assert_ok!(Uniques::set_metadata(Origin::signed(1), 0, 42, vec![0u8; 20], false));
+			assert_eq!(Balances::free_balance(&1), 8);
+			assert!(InstanceMetadataOf::<Test>::contains_key(0, 42));
+
+			// Update deposit
+			assert_ok!(Uniques::set_metadata(Origin::signed(1), 0, 42, vec![0u8; 15], false));
+			assert_eq!(Balances::free_balance(&1), 13);
+
+			// Cannot go over limit
+			assert_noop!(
+				Uniques::set_metadata(Origin::signed(1), 0, 42, vec![0u8; 51], false),
+				Error::<Test>::BadLimit,
+			);
+
+			// Can't set or clear metadata once frozen
+			assert_ok!(Uniques::set_metadata(Origin::signed(1), 0, 42, vec![0u8; 15], true));
+			assert_noop!(
+				Uniques::set_metadata(Origin::signed(1), 0, 42, vec![0u8; 15], false),
+				Error::<Test>::Frozen,
+			);
+			assert_noop!(Uniques::clear_metadata(Origin::signed(1), 0, 42), Error::<Test>::Frozen);
+
+			// Clear Metadata
+			assert_ok!(Uniques::set_metadata(Origin::root(), 0, 42, vec![0u8; 15], false));
+			assert_noop!(Uniques::clear_metadata(Origin::signed(2), 0, 42), Error::<Test>::NoPermission);
+			assert_noop!(Uniques::clear_metadata(Origin::signed(1), 0, 69), Error::<Test>::Unknown);
+			assert_ok!(Uniques::clear_metadata(Origin::signed(1), 0, 42));
+			assert!(!InstanceMetadataOf::<Test>::contains_key(0, 42));
+			assert_eq!(Balances::free_balance(&1), 29);
+		});
+	}
+
+	#[test]
+	fn set_attribute_should_work() {
+		new_test_ext().execute_with(|| {
+			Balances::make_free_balance_be(&1, 100);
+
+			assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+			assert_noop!(
+				Uniques::set_attribute(Origin::signed(1), 0, Some(0), vec![0], vec![0]),
+				Error::<Test>::Unknown,
+			);
+			assert_ok!(Uniques::mint(Origin::signed(1), 0, 0, 1));
+
+			assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, None, vec![0], vec![0]));
+			assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, Some(0), vec![0], vec![0]));
+			assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, Some(0), vec![1], vec![0]));
+			assert_eq!(Uniques::attribute(0, None, &[0]), Some(vec![0]));
+			assert_eq!(Uniques::attribute(0, Some(0), &[1]), Some(vec![0]));
+			assert_eq!(Balances::reserved_balance(1), 9);
+
+			assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, None, vec![0], vec![0; 10]));
+			assert_eq!(Uniques::attribute(0, None, &[0]), Some(vec![0; 10]));
+			assert_eq!(Balances::reserved_balance(1), 18);
+
+			assert_ok!(Uniques::clear_attribute(Origin::signed(1), 0, Some(0), vec![1]));
+			assert_eq!(Uniques::attribute(0, Some(0), &[1]), None);
+			assert_eq!(Balances::reserved_balance(1), 15);
+			assert_noop!(
+				Uniques::clear_attribute(Origin::signed(1), 0, Some(0), vec![1]),
+				Error::<Test>::Unknown,
+			);
+
+			let w = Class::<Test>::get(0).unwrap();
+			assert_eq!(w.attributes, 2);
+			assert_ok!(Uniques::destroy(Origin::signed(1), 0, witness(1, 0, 2)));
+			assert_eq!(Uniques::attribute(0, None, &[0]), None);
+			assert_eq!(Balances::reserved_balance(1), 0);
+		});
+	}
+
+	#[test]
+	fn set_attribute_should_respect_freeze() {
+		new_test_ext().execute_with(|| {
+			Balances::make_free_balance_be(&1, 100);
+
+			assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+			assert_ok!(Uniques::mint(Origin::signed(1), 0, 0, 1));
+			assert_ok!(Uniques::mint(Origin::signed(1), 0, 1, 1));
+
+			assert_ok!(Uniques::set_class_metadata(Origin::signed(1), 0, vec![], true));
+			assert_noop!(
+				Uniques::set_attribute(Origin::signed(1), 0, None, vec![0], vec![0]),
+				Error::<Test>::Frozen,
+			);
+			assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, Some(0), vec![0], vec![0]));
+
+			assert_ok!(Uniques::set_metadata(Origin::signed(1), 0, 0, vec![], true));
+			assert_noop!(
+				Uniques::set_attribute(Origin::signed(1), 0, Some(0), vec![0], vec![1]),
+				Error::<Test>::Frozen,
+			);
+			assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, Some(1), vec![0], vec![1]));
+
+			// the force origin ignores freezing.
+			assert_ok!(Uniques::set_attribute(Origin::root(), 0, None, vec![0], vec![0]));
+		});
+	}
+
+	#[test]
+	fn approval_lifecycle_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+			assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 2));
+			assert_noop!(Uniques::approve_transfer(Origin::signed(3), 0, 42, 3), Error::<Test>::NoPermission);
+			assert_ok!(Uniques::approve_transfer(Origin::signed(2), 0, 42, 3));
+			assert_ok!(Uniques::transfer(Origin::signed(3), 0, 42, 4));
+			assert_eq!(assets(), vec![(4, 0, 42)]);
+
+			// the approval is consumed by the transfer.
+			assert_noop!(Uniques::transfer(Origin::signed(3), 0, 42, 3), Error::<Test>::NoPermission);
+			assert!(Asset::<Test>::get(0, 42).unwrap().approved.is_none());
+		});
+	}
+
+	#[test]
+	fn cancel_approval_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+			assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 2));
+
+			assert_noop!(Uniques::cancel_approval(Origin::signed(2), 0, 42, None), Error::<Test>::NoDelegate);
+			assert_ok!(Uniques::approve_transfer(Origin::signed(2), 0, 42, 3));
+			assert_noop!(Uniques::cancel_approval(Origin::signed(3), 0, 42, None), Error::<Test>::NoPermission);
+			assert_noop!(
+				Uniques::cancel_approval(Origin::signed(2), 0, 42, Some(4)),
+				Error::<Test>::WrongDelegate,
+			);
+			assert_ok!(Uniques::cancel_approval(Origin::signed(2), 0, 42, Some(3)));
+			assert_noop!(Uniques::transfer(Origin::signed(3), 0, 42, 3), Error::<Test>::NoPermission);
+
+			// the admin may cancel any approval too.
+			assert_ok!(Uniques::approve_transfer(Origin::signed(2), 0, 42, 3));
+			assert_ok!(Uniques::cancel_approval(Origin::signed(1), 0, 42, None));
+			assert_noop!(Uniques::transfer(Origin::signed(3), 0, 42, 3), Error::<Test>::NoPermission);
+		});
+	}
+
+	#[test]
+	fn burn_should_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+			assert_ok!(Uniques::set_team(Origin::signed(1), 0, 2, 3, 4));
+
+			assert_noop!(Uniques::burn(Origin::signed(5), 0, 42, Some(5)), Error::<Test>::Unknown);
+
+			assert_ok!(Uniques::mint(Origin::signed(2), 0, 42, 5));
+			assert_ok!(Uniques::mint(Origin::signed(2), 0, 69, 5));
+
+			assert_noop!(Uniques::burn(Origin::signed(0), 0, 42, None), Error::<Test>::NoPermission);
+			assert_noop!(Uniques::burn(Origin::signed(5), 0, 42, Some(6)), Error::<Test>::WrongOwner);
+
+			assert_ok!(Uniques::burn(Origin::signed(5), 0, 42, Some(5)));
+			assert_ok!(Uniques::burn(Origin::signed(3), 0, 69, Some(5)));
+			assert_eq!(assets(), vec![]);
+			assert_eq!(Class::<Test>::get(0).unwrap().instances, 0);
+		});
+	}
+}
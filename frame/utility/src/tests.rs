@@ -50,6 +50,13 @@ impl_outer_dispatch! {
 // For testing the pallet, we construct most of a mock runtime. This means
 // first constructing a configuration type (`Test`) which `impl`s each of the
 // configuration traits of pallets we want to use.
+pub struct TestBaseCallFilter;
+impl Filter<Call> for TestBaseCallFilter {
+	fn filter(c: &Call) -> bool {
+		!matches!(c, Call::Balances(BalancesCall::transfer_keep_alive(..)))
+	}
+}
+
 #[derive(Clone, Eq, PartialEq)]
 pub struct Test;
 parameter_types! {
@@ -59,6 +66,7 @@ parameter_types! {
 	pub const AvailableBlockRatio: Perbill = Perbill::one();
 }
 impl frame_system::Trait for Test {
+	type BaseCallFilter = TestBaseCallFilter;
 	type Origin = Origin;
 	type Index = u64;
 	type BlockNumber = u64;
@@ -443,3 +451,35 @@ fn batch_early_exit_works() {
 		assert_eq!(Balances::free_balance(2), 15);
 	});
 }
+
+#[test]
+fn batch_stops_at_filtered_call() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(
+			Utility::batch(Origin::signed(1), vec![
+				Call::Balances(BalancesCall::transfer(2, 2)),
+				Call::Balances(BalancesCall::transfer_keep_alive(2, 2)),
+				Call::Balances(BalancesCall::transfer(2, 2)),
+			]),
+		);
+		let de = DispatchError::from(frame_system::Error::<Test>::CallFiltered).stripped();
+		expect_event(RawEvent::BatchInterrupted(1, de));
+		assert_eq!(Balances::free_balance(1), 8);
+		assert_eq!(Balances::free_balance(2), 12);
+	});
+}
+
+#[test]
+fn as_sub_and_as_multi_respect_call_filter() {
+	new_test_ext().execute_with(|| {
+		let call = Box::new(Call::Balances(BalancesCall::transfer_keep_alive(2, 3)));
+		assert_noop!(
+			Utility::as_sub(Origin::signed(1), 0, call.clone()),
+			frame_system::Error::<Test>::CallFiltered,
+		);
+		assert_noop!(
+			Utility::as_multi(Origin::signed(1), 1, vec![2, 3], None, call),
+			frame_system::Error::<Test>::CallFiltered,
+		);
+	});
+}
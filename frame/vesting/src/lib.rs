@@ -31,6 +31,9 @@
 //! either `vest` (in typical case where the sender is calling on their own behalf) or `vest_other`
 //! in case the sender is calling on another account's behalf.
 //!
+//! An account may have up to `MaxVestingSchedules` vesting schedules at once, each with its own
+//! curve. The amount locked is the sum of the amounts still unvested under all of them.
+//!
 //! ## Interface
 //!
 //! This module implements the `VestingSchedule` trait.
@@ -40,6 +43,10 @@
 //! - `vest` - Update the lock, reducing it in line with the amount "vested" so far.
 //! - `vest_other` - Update the lock of another account, reducing it in line with the amount
 //!   "vested" so far.
+//! - `vested_transfer` - Transfer some funds to another account under a new vesting schedule.
+//! - `force_vested_transfer` - Transfer some funds between two accounts under a new vesting
+//!   schedule of the destination; called by Root.
+//! - `merge_schedules` - Merge two vesting schedules of the sender into one.
 //!
 //! [`Call`]: ./enum.Call.html
 //! [`Trait`]: ./trait.Trait.html
@@ -50,15 +57,18 @@ use sp_std::prelude::*;
 use sp_std::fmt::Debug;
 use codec::{Encode, Decode};
 use sp_runtime::{DispatchResult, RuntimeDebug, traits::{
-	StaticLookup, Zero, AtLeast32Bit, MaybeSerializeDeserialize, Convert
+	StaticLookup, Zero, AtLeast32Bit, MaybeSerializeDeserialize, Convert, One, Saturating,
 }};
-use frame_support::{decl_module, decl_event, decl_storage, decl_error, ensure};
+use frame_support::{
+	decl_module, decl_event, decl_storage, decl_error, ensure, IterableStorageMap,
+	weights::Weight,
+};
 use frame_support::traits::{
 	Currency, LockableCurrency, VestingSchedule, WithdrawReason, LockIdentifier,
 	ExistenceRequirement, Get
 };
 
-use frame_system::{self as system, ensure_signed};
+use frame_system::{self as system, ensure_signed, ensure_root};
 
 mod benchmarking;
 
@@ -76,10 +86,28 @@ pub trait Trait: frame_system::Trait {
 
 	/// The minimum amount transferred to call `vested_transfer`.
 	type MinVestedTransfer: Get<BalanceOf<Self>>;
+
+	/// The maximum number of vesting schedules an account may have at a given moment.
+	type MaxVestingSchedules: Get<u32>;
 }
 
 const VESTING_ID: LockIdentifier = *b"vesting ";
 
+// A value placed in storage that represents the current version of the Vesting storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+	V0,
+	V1,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V0
+	}
+}
+
 /// Struct to encode the vesting schedule of an individual account.
 #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
 pub struct VestingInfo<Balance, BlockNumber> {
@@ -95,6 +123,11 @@ impl<
 	Balance: AtLeast32Bit + Copy,
 	BlockNumber: AtLeast32Bit + Copy,
 > VestingInfo<Balance, BlockNumber> {
+	/// Whether the schedule ever vests its whole locked amount.
+	pub fn is_valid(&self) -> bool {
+		!self.locked.is_zero() && !self.per_block.is_zero()
+	}
+
 	/// Amount locked at block `n`.
 	pub fn locked_at<
 		BlockNumberToBalance: Convert<BlockNumber, Balance>
@@ -111,6 +144,23 @@ impl<
 			Zero::zero()
 		}
 	}
+
+	/// Block number at which the schedule is fully vested, as a balance. Assumes the schedule
+	/// is valid.
+	pub fn ending_block_as_balance<
+		BlockNumberToBalance: Convert<BlockNumber, Balance>
+	>(&self) -> Balance {
+		let starting_block = BlockNumberToBalance::convert(self.starting_block);
+		let duration = if self.per_block >= self.locked {
+			// the whole amount is vested in the first block.
+			One::one()
+		} else {
+			let remainder = self.locked % self.per_block;
+			let partial_block = if remainder.is_zero() { Zero::zero() } else { One::one() };
+			(self.locked / self.per_block).saturating_add(partial_block)
+		};
+		starting_block.saturating_add(duration)
+	}
 }
 
 decl_storage! {
@@ -118,12 +168,16 @@ decl_storage! {
 		/// Information regarding the vesting of a given account.
 		pub Vesting get(fn vesting):
 			map hasher(blake2_128_concat) T::AccountId
-			=> Option<VestingInfo<BalanceOf<T>, T::BlockNumber>>;
+			=> Option<Vec<VestingInfo<BalanceOf<T>, T::BlockNumber>>>;
+
+		/// Storage version of the pallet.
+		///
+		/// This is set to v1 for new networks.
+		StorageVersion build(|_: &GenesisConfig<T>| Releases::V1): Releases;
 	}
 	add_extra_genesis {
 		config(vesting): Vec<(T::AccountId, T::BlockNumber, T::BlockNumber, BalanceOf<T>)>;
 		build(|config: &GenesisConfig<T>| {
-			use sp_runtime::traits::Saturating;
 			// Generate initial vesting configuration
 			// * who - Account which we are generating vesting configuration for
 			// * begin - Block when the account will start to vest
@@ -135,15 +189,23 @@ decl_storage! {
 				// Total genesis `balance` minus `liquid` equals funds locked for vesting
 				let locked = balance.saturating_sub(liquid);
 				let length_as_balance = T::BlockNumberToBalance::convert(length);
-				let per_block = locked / length_as_balance.max(sp_runtime::traits::One::one());
+				let per_block = locked / length_as_balance.max(One::one());
 
-				Vesting::<T>::insert(who, VestingInfo {
+				let mut schedules = Vesting::<T>::get(who).unwrap_or_default();
+				assert!(
+					schedules.len() < T::MaxVestingSchedules::get() as usize,
+					"Too many vesting schedules at genesis",
+				);
+				schedules.push(VestingInfo {
 					locked: locked,
 					per_block: per_block,
 					starting_block: begin
 				});
+				let total_locked = schedules.iter()
+					.fold(Zero::zero(), |total: BalanceOf<T>, schedule| total.saturating_add(schedule.locked));
+				Vesting::<T>::insert(who, schedules);
 				let reasons = WithdrawReason::Transfer | WithdrawReason::Reserve;
-				T::Currency::set_lock(VESTING_ID, who, locked, reasons);
+				T::Currency::set_lock(VESTING_ID, who, total_locked, reasons);
 			}
 		})
 	}
@@ -164,10 +226,15 @@ decl_error! {
 	pub enum Error for Module<T: Trait> {
 		/// The account given is not vesting.
 		NotVesting,
-		/// An existing vesting schedule already exists for this account that cannot be clobbered.
-		ExistingVestingSchedule,
+		/// The account already has `MaxVestingSchedules` vesting schedules. Merge some of them
+		/// before adding another.
+		AtMaxVestingSchedules,
 		/// Amount being transferred is too low to create a vesting schedule.
 		AmountLow,
+		/// An index was out of bounds of the vesting schedules.
+		ScheduleIndexOutOfBounds,
+		/// Failed to create a new schedule because some parameter was invalid.
+		InvalidScheduleParams,
 	}
 }
 
@@ -179,8 +246,15 @@ decl_module! {
 		/// The minimum amount to be transferred to create a new vesting schedule.
 		const MinVestedTransfer: BalanceOf<T> = T::MinVestedTransfer::get();
 
+		/// The maximum number of vesting schedules an account may have.
+		const MaxVestingSchedules: u32 = T::MaxVestingSchedules::get();
+
 		fn deposit_event() = default;
 
+		fn on_runtime_upgrade() -> Weight {
+			Self::migrate_to_multiple_schedules()
+		}
+
 		/// Unlock any vested funds of the sender account.
 		///
 		/// The dispatch origin for this call must be _Signed_ and the sender must have funds still
@@ -189,7 +263,8 @@ decl_module! {
 		/// Emits either `VestingCompleted` or `VestingUpdated`.
 		///
 		/// # <weight>
-		/// - `O(1)`.
+		/// - `O(S)` where `S` is the number of vesting schedules of the sender, bounded by
+		///   `MaxVestingSchedules`.
 		/// - DbWeight: 2 Reads, 2 Writes
 		///     - Reads: Vesting Storage, Balances Locks, [Sender Account]
 		///     - Writes: Vesting Storage, Balances Locks, [Sender Account]
@@ -214,7 +289,8 @@ decl_module! {
 		/// Emits either `VestingCompleted` or `VestingUpdated`.
 		///
 		/// # <weight>
-		/// - `O(1)`.
+		/// - `O(S)` where `S` is the number of vesting schedules of `target`, bounded by
+		///   `MaxVestingSchedules`.
 		/// - DbWeight: 3 Reads, 3 Writes
 		///     - Reads: Vesting Storage, Balances Locks, Target Account
 		///     - Writes: Vesting Storage, Balances Locks, Target Account
@@ -233,14 +309,16 @@ decl_module! {
 		///
 		/// The dispatch origin for this call must be _Signed_.
 		///
-		/// - `target`: The account that should be transferred the vested funds.
-		/// - `amount`: The amount of funds to transfer and will be vested.
-		/// - `schedule`: The vesting schedule attached to the transfer.
+		/// - `target`: The account that should be transferred the vested funds. It must have
+		/// less than `MaxVestingSchedules` vesting schedules.
+		/// - `schedule`: The vesting schedule attached to the transfer. Its locked amount is
+		/// transferred and must be at least `MinVestedTransfer`.
 		///
-		/// Emits `VestingCreated`.
+		/// Emits `VestingUpdated`.
 		///
 		/// # <weight>
-		/// - `O(1)`.
+		/// - `O(S)` where `S` is the number of vesting schedules of `target`, bounded by
+		///   `MaxVestingSchedules`.
 		/// - DbWeight: 3 Reads, 3 Writes
 		///     - Reads: Vesting Storage, Balances Locks, Target Account, [Sender Account]
 		///     - Writes: Vesting Storage, Balances Locks, Target Account, [Sender Account]
@@ -254,28 +332,113 @@ decl_module! {
 			schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
 		) -> DispatchResult {
 			let transactor = ensure_signed(origin)?;
-			ensure!(schedule.locked >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+			Self::do_vested_transfer(&transactor, T::Lookup::lookup(target)?, schedule)
+		}
+
+		/// Force a vested transfer.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// - `source`: The account whose funds should be transferred.
+		/// - `target`: The account that should be transferred the vested funds. It must have
+		/// less than `MaxVestingSchedules` vesting schedules.
+		/// - `schedule`: The vesting schedule attached to the transfer. Its locked amount is
+		/// transferred and must be at least `MinVestedTransfer`.
+		///
+		/// Emits `VestingUpdated`.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` is the number of vesting schedules of `target`, bounded by
+		///   `MaxVestingSchedules`.
+		/// - DbWeight: 4 Reads, 4 Writes
+		///     - Reads: Vesting Storage, Balances Locks, Target Account, Source Account
+		///     - Writes: Vesting Storage, Balances Locks, Target Account, Source Account
+		/// - Benchmark: 100.3 + .365 * l µs (min square analysis)
+		/// - Using 100 µs fixed. Assuming less than 50 locks on any user, else we may want factor in number of locks.
+		/// # </weight>
+		#[weight = 100_000_000 + T::DbWeight::get().reads_writes(4, 4)]
+		pub fn force_vested_transfer(
+			origin,
+			source: <T::Lookup as StaticLookup>::Source,
+			target: <T::Lookup as StaticLookup>::Source,
+			schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let source = T::Lookup::lookup(source)?;
+			Self::do_vested_transfer(&source, T::Lookup::lookup(target)?, schedule)
+		}
 
-			let who = T::Lookup::lookup(target)?;
-			ensure!(!Vesting::<T>::contains_key(&who), Error::<T>::ExistingVestingSchedule);
+		/// Merge two vesting schedules of the sender together, creating a new vesting schedule
+		/// that unlocks over the highest possible start and end blocks. Schedules which are
+		/// already fully vested are simply removed.
+		///
+		/// The new schedule locks the sum of the amounts still locked by both schedules, and
+		/// starts at the latest of their starting blocks and the current block. It ends at the
+		/// latest of their ending blocks.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have funds still
+		/// locked under this module.
+		///
+		/// - `schedule1_index`: The index of the first schedule to merge.
+		/// - `schedule2_index`: The index of the second schedule to merge.
+		///
+		/// Emits either `VestingCompleted` or `VestingUpdated`.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` is the number of vesting schedules of the sender, bounded by
+		///   `MaxVestingSchedules`.
+		/// - DbWeight: 2 Reads, 2 Writes
+		///     - Reads: Vesting Storage, Balances Locks, [Sender Account]
+		///     - Writes: Vesting Storage, Balances Locks, [Sender Account]
+		/// # </weight>
+		#[weight = 60_000_000 + T::DbWeight::get().reads_writes(2, 2)]
+		fn merge_schedules(origin, schedule1_index: u32, schedule2_index: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			if schedule1_index == schedule2_index {
+				return Ok(())
+			}
+			let schedule1_index = schedule1_index as usize;
+			let schedule2_index = schedule2_index as usize;
 
-			T::Currency::transfer(&transactor, &who, schedule.locked, ExistenceRequirement::AllowDeath)?;
+			let schedules = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
+			ensure!(
+				schedule1_index < schedules.len() && schedule2_index < schedules.len(),
+				Error::<T>::ScheduleIndexOutOfBounds,
+			);
+			let schedule1 = schedules[schedule1_index];
+			let schedule2 = schedules[schedule2_index];
 
-			Self::add_vesting_schedule(&who, schedule.locked, schedule.per_block, schedule.starting_block)
-				.expect("user does not have an existing vesting schedule; q.e.d.");
+			let now = <frame_system::Module<T>>::block_number();
+			let mut schedules: Vec<_> = schedules.into_iter()
+				.enumerate()
+				.filter(|&(index, _)| index != schedule1_index && index != schedule2_index)
+				.map(|(_, schedule)| schedule)
+				.collect();
+			if let Some(merged) = Self::merge_vesting_info(now, schedule1, schedule2) {
+				schedules.push(merged);
+			}
+			Vesting::<T>::insert(&who, schedules);
 
-			Ok(())
+			Self::update_lock(who)
 		}
 	}
 }
 
 impl<T: Trait> Module<T> {
 	/// (Re)set or remove the module's currency lock on `who`'s account in accordance with their
-	/// current unvested amount.
+	/// current unvested amount. Fully vested schedules are removed.
 	fn update_lock(who: T::AccountId) -> DispatchResult {
-		let vesting = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
+		let schedules = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
 		let now = <frame_system::Module<T>>::block_number();
-		let locked_now = vesting.locked_at::<T::BlockNumberToBalance>(now);
+
+		let mut locked_now: BalanceOf<T> = Zero::zero();
+		let schedules: Vec<_> = schedules.into_iter()
+			.filter(|schedule| {
+				let locked = schedule.locked_at::<T::BlockNumberToBalance>(now);
+				locked_now = locked_now.saturating_add(locked);
+				!locked.is_zero()
+			})
+			.collect();
 
 		if locked_now.is_zero() {
 			T::Currency::remove_lock(VESTING_ID, &who);
@@ -284,10 +447,71 @@ impl<T: Trait> Module<T> {
 		} else {
 			let reasons = WithdrawReason::Transfer | WithdrawReason::Reserve;
 			T::Currency::set_lock(VESTING_ID, &who, locked_now, reasons);
+			Vesting::<T>::insert(&who, schedules);
 			Self::deposit_event(RawEvent::VestingUpdated(who, locked_now));
 		}
 		Ok(())
 	}
+
+	/// Transfer the locked amount of `schedule` from `source` to `target`, under that schedule.
+	fn do_vested_transfer(
+		source: &T::AccountId,
+		target: T::AccountId,
+		schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+	) -> DispatchResult {
+		ensure!(schedule.locked >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+		Self::can_add_vesting_schedule(&target, schedule.locked, schedule.per_block, schedule.starting_block)?;
+
+		T::Currency::transfer(source, &target, schedule.locked, ExistenceRequirement::AllowDeath)?;
+
+		Self::add_vesting_schedule(&target, schedule.locked, schedule.per_block, schedule.starting_block)
+			.expect("schedule was checked by `can_add_vesting_schedule`; q.e.d.");
+
+		Ok(())
+	}
+
+	/// Merge two vesting schedules at block `now` into one, or into none if both are fully vested.
+	fn merge_vesting_info(
+		now: T::BlockNumber,
+		schedule1: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		schedule2: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+	) -> Option<VestingInfo<BalanceOf<T>, T::BlockNumber>> {
+		let locked1 = schedule1.locked_at::<T::BlockNumberToBalance>(now);
+		let locked2 = schedule2.locked_at::<T::BlockNumberToBalance>(now);
+		match (locked1.is_zero(), locked2.is_zero()) {
+			(true, true) => return None,
+			(true, false) => return Some(schedule2),
+			(false, true) => return Some(schedule1),
+			(false, false) => {},
+		}
+
+		let locked = locked1.saturating_add(locked2);
+		let ending_block = schedule1.ending_block_as_balance::<T::BlockNumberToBalance>()
+			.max(schedule2.ending_block_as_balance::<T::BlockNumberToBalance>());
+		let starting_block = now.max(schedule1.starting_block).max(schedule2.starting_block);
+		let duration = ending_block
+			.saturating_sub(T::BlockNumberToBalance::convert(starting_block))
+			.max(One::one());
+		let per_block = (locked / duration).max(One::one());
+
+		Some(VestingInfo { locked, per_block, starting_block })
+	}
+
+	/// Migrate the vesting storage from one schedule to a list of schedules per account.
+	fn migrate_to_multiple_schedules() -> Weight {
+		if StorageVersion::get() != Releases::V0 {
+			return 0
+		}
+
+		let translated = sp_std::cell::Cell::new(0 as Weight);
+		Vesting::<T>::translate::<VestingInfo<BalanceOf<T>, T::BlockNumber>, _>(|_, schedule| {
+			translated.set(translated.get() + 1);
+			Some(vec![schedule])
+		});
+		StorageVersion::put(Releases::V1);
+
+		T::DbWeight::get().reads_writes(translated.get() + 1, translated.get() + 1)
+	}
 }
 
 impl<T: Trait> VestingSchedule<T::AccountId> for Module<T> where
@@ -298,19 +522,21 @@ impl<T: Trait> VestingSchedule<T::AccountId> for Module<T> where
 
 	/// Get the amount that is currently being vested and cannot be transferred out of this account.
 	fn vesting_balance(who: &T::AccountId) -> Option<BalanceOf<T>> {
-		if let Some(v) = Self::vesting(who) {
+		if let Some(schedules) = Self::vesting(who) {
 			let now = <frame_system::Module<T>>::block_number();
-			let locked_now = v.locked_at::<T::BlockNumberToBalance>(now);
+			let locked_now = schedules.iter().fold(Zero::zero(), |total: BalanceOf<T>, schedule| {
+				total.saturating_add(schedule.locked_at::<T::BlockNumberToBalance>(now))
+			});
 			Some(T::Currency::free_balance(who).min(locked_now))
 		} else {
 			None
 		}
 	}
 
-	/// Adds a vesting schedule to a given account.
+	/// Adds a vesting schedule to a given account, alongside any existing ones.
 	///
-	/// If there already exists a vesting schedule for the given account, an `Err` is returned
-	/// and nothing is updated.
+	/// If the account already has `MaxVestingSchedules` vesting schedules, or `per_block` is
+	/// zero, an `Err` is returned and nothing is updated.
 	///
 	/// On success, a linearly reducing amount of funds will be locked. In order to realise any
 	/// reduction of the lock over time as it diminishes, the account owner must use `vest` or
@@ -324,25 +550,51 @@ impl<T: Trait> VestingSchedule<T::AccountId> for Module<T> where
 		starting_block: T::BlockNumber
 	) -> DispatchResult {
 		if locked.is_zero() { return Ok(()) }
-		if Vesting::<T>::contains_key(who) {
-			Err(Error::<T>::ExistingVestingSchedule)?
-		}
+		Self::can_add_vesting_schedule(who, locked, per_block, starting_block)?;
+
 		let vesting_schedule = VestingInfo {
 			locked,
 			per_block,
 			starting_block
 		};
-		Vesting::<T>::insert(who, vesting_schedule);
+		let mut schedules = Self::vesting(who).unwrap_or_default();
+		schedules.push(vesting_schedule);
+		Vesting::<T>::insert(who, schedules);
 		// it can't fail, but even if somehow it did, we don't really care.
 		let _ = Self::update_lock(who.clone());
 		Ok(())
 	}
 
-	/// Remove a vesting schedule for a given account.
-	fn remove_vesting_schedule(who: &T::AccountId) {
-		Vesting::<T>::remove(who);
-		// it can't fail, but even if somehow it did, we don't really care.
-		let _ = Self::update_lock(who.clone());
+	/// Checks if `add_vesting_schedule` would work against `who`.
+	fn can_add_vesting_schedule(
+		who: &T::AccountId,
+		locked: BalanceOf<T>,
+		per_block: BalanceOf<T>,
+		starting_block: T::BlockNumber
+	) -> DispatchResult {
+		let schedule = VestingInfo { locked, per_block, starting_block };
+		ensure!(schedule.is_valid(), Error::<T>::InvalidScheduleParams);
+
+		let schedule_count = Self::vesting(who).map_or(0, |schedules| schedules.len());
+		ensure!(schedule_count < T::MaxVestingSchedules::get() as usize, Error::<T>::AtMaxVestingSchedules);
+		Ok(())
+	}
+
+	/// Remove the vesting schedule at `schedule_index` for a given account.
+	fn remove_vesting_schedule(who: &T::AccountId, schedule_index: u32) -> DispatchResult {
+		let mut schedules = Self::vesting(who).ok_or(Error::<T>::NotVesting)?;
+		ensure!((schedule_index as usize) < schedules.len(), Error::<T>::ScheduleIndexOutOfBounds);
+		schedules.remove(schedule_index as usize);
+
+		if schedules.is_empty() {
+			T::Currency::remove_lock(VESTING_ID, who);
+			Vesting::<T>::remove(who);
+		} else {
+			Vesting::<T>::insert(who, schedules);
+			// it can't fail, but even if somehow it did, we don't really care.
+			let _ = Self::update_lock(who.clone());
+		}
+		Ok(())
 	}
 }
 
@@ -361,7 +613,7 @@ mod tests {
 	use sp_runtime::{
 		Perbill,
 		testing::Header,
-		traits::{BlakeTwo256, IdentityLookup, Identity},
+		traits::{BlakeTwo256, IdentityLookup, Identity, BadOrigin},
 	};
 
 	impl_outer_origin! {
@@ -380,6 +632,7 @@ mod tests {
 		pub const AvailableBlockRatio: Perbill = Perbill::one();
 	}
 	impl frame_system::Trait for Test {
+		type BaseCallFilter = ();
 		type Origin = Origin;
 		type Index = u64;
 		type BlockNumber = u64;
@@ -412,12 +665,14 @@ mod tests {
 	}
 	parameter_types! {
 		pub const MinVestedTransfer: u64 = 256 * 2;
+		pub const MaxVestingSchedules: u32 = 3;
 	}
 	impl Trait for Test {
 		type Event = ();
 		type Currency = Balances;
 		type BlockNumberToBalance = Identity;
 		type MinVestedTransfer = MinVestedTransfer;
+		type MaxVestingSchedules = MaxVestingSchedules;
 	}
 	type System = frame_system::Module<Test>;
 	type Balances = pallet_balances::Module<Test>;
@@ -498,9 +753,9 @@ mod tests {
 					per_block: 64, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&1), Some(user1_vesting_schedule)); // Account 1 has a vesting schedule
-				assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule)); // Account 2 has a vesting schedule
-				assert_eq!(Vesting::vesting(&12), Some(user12_vesting_schedule)); // Account 12 has a vesting schedule
+				assert_eq!(Vesting::vesting(&1), Some(vec![user1_vesting_schedule])); // Account 1 has a vesting schedule
+				assert_eq!(Vesting::vesting(&2), Some(vec![user2_vesting_schedule])); // Account 2 has a vesting schedule
+				assert_eq!(Vesting::vesting(&12), Some(vec![user12_vesting_schedule])); // Account 12 has a vesting schedule
 
 				// Account 1 has only 128 units vested from their illiquid 256 * 5 units at block 1
 				assert_eq!(Vesting::vesting_balance(&1), Some(128 * 9));
@@ -621,7 +876,7 @@ mod tests {
 					per_block: 64, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&12), Some(user12_vesting_schedule));
+				assert_eq!(Vesting::vesting(&12), Some(vec![user12_vesting_schedule]));
 
 				// Account 12 can still send liquid funds
 				assert_ok!(Balances::transfer(Some(12).into(), 3, 256 * 5));
@@ -648,7 +903,7 @@ mod tests {
 				};
 				assert_ok!(Vesting::vested_transfer(Some(3).into(), 4, new_vesting_schedule));
 				// Now account 4 should have vesting.
-				assert_eq!(Vesting::vesting(&4), Some(new_vesting_schedule));
+				assert_eq!(Vesting::vesting(&4), Some(vec![new_vesting_schedule]));
 				// Ensure the transfer happened correctly.
 				let user3_free_balance_updated = Balances::free_balance(&3);
 				assert_eq!(user3_free_balance_updated, 256 * 25);
@@ -687,17 +942,17 @@ mod tests {
 					per_block: 256, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule));
+				assert_eq!(Vesting::vesting(&2), Some(vec![user2_vesting_schedule]));
 
-				// The vesting schedule we will try to create, fails due to pre-existence of schedule.
+				// The vesting schedule we will try to create, fails due to invalid parameters.
 				let new_vesting_schedule = VestingInfo {
 					locked: 256 * 5,
-					per_block: 64, // Vesting over 20 blocks
+					per_block: 0,
 					starting_block: 10,
 				};
 				assert_noop!(
 					Vesting::vested_transfer(Some(4).into(), 2, new_vesting_schedule),
-					Error::<Test>::ExistingVestingSchedule,
+					Error::<Test>::InvalidScheduleParams,
 				);
 
 				// Fails due to too low transfer amount.
@@ -716,4 +971,183 @@ mod tests {
 				assert_eq!(user4_free_balance, 256 * 40);
 			});
 	}
+
+	#[test]
+	fn vested_transfer_allows_multiple_schedules() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let user2_vesting_schedule = VestingInfo {
+					locked: 256 * 20,
+					per_block: 256, // Vesting over 20 blocks
+					starting_block: 10,
+				};
+				let new_vesting_schedule = VestingInfo {
+					locked: 256 * 5,
+					per_block: 64, // Vesting over 20 blocks
+					starting_block: 10,
+				};
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, new_vesting_schedule));
+				assert_eq!(Vesting::vesting(&2), Some(vec![user2_vesting_schedule, new_vesting_schedule]));
+				assert_eq!(Balances::free_balance(&2), 256 * 25);
+				assert_eq!(Vesting::vesting_balance(&2), Some(256 * 25));
+
+				// Account 2 may have up to `MaxVestingSchedules` schedules.
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, new_vesting_schedule));
+				assert_noop!(
+					Vesting::vested_transfer(Some(3).into(), 2, new_vesting_schedule),
+					Error::<Test>::AtMaxVestingSchedules,
+				);
+
+				System::set_block_number(20);
+				assert_eq!(Vesting::vesting_balance(&2), Some(256 * 10 + 2 * 64 * 10));
+
+				System::set_block_number(30);
+				assert_eq!(Vesting::vesting_balance(&2), Some(0));
+				assert_ok!(Vesting::vest(Some(2).into()));
+				assert_eq!(Vesting::vesting(&2), None);
+			});
+	}
+
+	#[test]
+	fn force_vested_transfer_works() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let new_vesting_schedule = VestingInfo {
+					locked: 256 * 5,
+					per_block: 64, // Vesting over 20 blocks
+					starting_block: 10,
+				};
+				assert_noop!(
+					Vesting::force_vested_transfer(Some(4).into(), 3, 4, new_vesting_schedule),
+					BadOrigin,
+				);
+				assert_ok!(Vesting::force_vested_transfer(Origin::root(), 3, 4, new_vesting_schedule));
+				assert_eq!(Vesting::vesting(&4), Some(vec![new_vesting_schedule]));
+				assert_eq!(Balances::free_balance(&3), 256 * 25);
+				assert_eq!(Balances::free_balance(&4), 256 * 45);
+				assert_eq!(Vesting::vesting_balance(&4), Some(256 * 5));
+
+				let new_vesting_schedule_too_low = VestingInfo {
+					locked: 256 * 1,
+					per_block: 64,
+					starting_block: 10,
+				};
+				assert_noop!(
+					Vesting::force_vested_transfer(Origin::root(), 3, 4, new_vesting_schedule_too_low),
+					Error::<Test>::AmountLow,
+				);
+			});
+	}
+
+	#[test]
+	fn merge_schedules_works() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				// Vests from block 5 to block 15, while the genesis schedule of account 2 vests
+				// from block 10 to block 30.
+				let new_vesting_schedule = VestingInfo {
+					locked: 256 * 5,
+					per_block: 128,
+					starting_block: 5,
+				};
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, new_vesting_schedule));
+
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 1, 1));
+				assert_eq!(Vesting::vesting(&2).unwrap().len(), 2);
+				assert_noop!(Vesting::merge_schedules(Some(2).into(), 0, 2), Error::<Test>::ScheduleIndexOutOfBounds);
+				assert_noop!(Vesting::merge_schedules(Some(4).into(), 0, 1), Error::<Test>::NotVesting);
+
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
+				let merged_schedule = VestingInfo {
+					locked: 256 * 25,
+					per_block: 256 * 25 / 20,
+					starting_block: 10,
+				};
+				assert_eq!(Vesting::vesting(&2), Some(vec![merged_schedule]));
+				assert_eq!(Vesting::vesting_balance(&2), Some(256 * 25));
+
+				System::set_block_number(30);
+				assert_eq!(Vesting::vesting_balance(&2), Some(0));
+			});
+	}
+
+	#[test]
+	fn merge_schedules_drops_fully_vested_schedules() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let user2_vesting_schedule = VestingInfo {
+					locked: 256 * 20,
+					per_block: 256, // Vesting over 20 blocks
+					starting_block: 10,
+				};
+				let new_vesting_schedule = VestingInfo {
+					locked: 256 * 5,
+					per_block: 128,
+					starting_block: 5,
+				};
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, new_vesting_schedule));
+
+				// The new schedule has fully vested by block 20.
+				System::set_block_number(20);
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
+				assert_eq!(Vesting::vesting(&2), Some(vec![user2_vesting_schedule]));
+				assert_eq!(Vesting::vesting_balance(&2), Some(256 * 10));
+				assert_ok!(Balances::transfer(Some(2).into(), 3, 256 * 15));
+			});
+	}
+
+	#[test]
+	fn vesting_schedule_trait_works() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				assert_noop!(
+					Vesting::add_vesting_schedule(&4, 256 * 5, 0, 10),
+					Error::<Test>::InvalidScheduleParams,
+				);
+				assert_ok!(Vesting::add_vesting_schedule(&4, 256 * 5, 64, 10));
+				assert_ok!(Vesting::add_vesting_schedule(&4, 256 * 10, 64, 10));
+				assert_eq!(Vesting::vesting_balance(&4), Some(256 * 15));
+
+				assert_noop!(Vesting::remove_vesting_schedule(&4, 2), Error::<Test>::ScheduleIndexOutOfBounds);
+				assert_ok!(Vesting::remove_vesting_schedule(&4, 0));
+				assert_eq!(Vesting::vesting_balance(&4), Some(256 * 10));
+				assert_ok!(Vesting::remove_vesting_schedule(&4, 0));
+				assert_eq!(Vesting::vesting(&4), None);
+				assert_noop!(Vesting::remove_vesting_schedule(&4, 0), Error::<Test>::NotVesting);
+
+				// Account 4 is free to move all of its funds again.
+				assert_ok!(Balances::transfer(Some(4).into(), 3, 256 * 40));
+			});
+	}
+
+	#[test]
+	fn migration_to_multiple_schedules_works() {
+		use frame_support::storage::{StorageMap, StorageValue, unhashed};
+
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			let schedule = VestingInfo {
+				locked: 256 * 5,
+				per_block: 64,
+				starting_block: 10,
+			};
+			unhashed::put(&super::Vesting::<Test>::hashed_key_for(&1), &schedule);
+			unhashed::put(&super::Vesting::<Test>::hashed_key_for(&2), &schedule);
+			StorageVersion::put(Releases::V0);
+
+			Vesting::migrate_to_multiple_schedules();
+			assert_eq!(StorageVersion::get(), Releases::V1);
+			assert_eq!(Vesting::vesting(&1), Some(vec![schedule]));
+			assert_eq!(Vesting::vesting(&2), Some(vec![schedule]));
+		});
+	}
 }
@@ -112,6 +112,16 @@ pub struct BlockCheckParams<Block: BlockT> {
 	pub import_existing: bool,
 }
 
+/// State of a block downloaded from peers, imported in place of executing the block
+/// on top of its parent state.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ImportedState<B: BlockT> {
+	/// Hash of the block the state belongs to.
+	pub block: B::Hash,
+	/// Key/value pairs of the top trie and the child tries.
+	pub state: sp_state_machine::KeyValueStates,
+}
+
 /// Data required to import a Block.
 #[non_exhaustive]
 pub struct BlockImportParams<Block: BlockT, Transaction> {
@@ -165,6 +175,9 @@ pub struct BlockImportParams<Block: BlockT, Transaction> {
 	pub import_existing: bool,
 	/// Cached full header hash (with post-digests applied).
 	pub post_hash: Option<Block::Hash>,
+	/// State to import for this block instead of executing it. The block's ancestors
+	/// are not required to be known, and the block must be finalized.
+	pub import_state: Option<ImportedState<Block>>,
 }
 
 impl<Block: BlockT, Transaction> BlockImportParams<Block, Transaction> {
@@ -186,6 +199,7 @@ impl<Block: BlockT, Transaction> BlockImportParams<Block, Transaction> {
 			allow_missing_state: false,
 			import_existing: false,
 			post_hash: None,
+			import_state: None,
 		}
 	}
 
@@ -226,6 +240,7 @@ impl<Block: BlockT, Transaction> BlockImportParams<Block, Transaction> {
 			fork_choice: self.fork_choice,
 			import_existing: self.import_existing,
 			post_hash: self.post_hash,
+			import_state: self.import_state,
 		}
 	}
 
@@ -30,7 +30,7 @@ use sp_runtime::{Justification, traits::{Block as BlockT, Header as _, NumberFor
 use crate::error::Error as ConsensusError;
 use crate::block_import::{
 	BlockImport, BlockOrigin, BlockImportParams, ImportedAux, JustificationImport, ImportResult,
-	BlockCheckParams, FinalityProofImport,
+	BlockCheckParams, FinalityProofImport, ForkChoiceStrategy, ImportedState,
 };
 
 pub use basic_queue::BasicQueue;
@@ -71,6 +71,8 @@ pub struct IncomingBlock<B: BlockT> {
 	pub allow_missing_state: bool,
 	/// Re-validate existing block.
 	pub import_existing: bool,
+	/// State downloaded for this block, imported instead of executing the block.
+	pub state: Option<ImportedState<B>>,
 }
 
 /// Type of keys in the blockchain cache that consensus module could use for its needs.
@@ -231,6 +233,19 @@ pub fn import_single_block<B: BlockT, V: Verifier<B>, Transaction>(
 			}
 		}
 	};
+	if let Some(state) = block.state {
+		// the block was proven final by the sync before its state was downloaded,
+		// and its ancestors are not available to check or verify it against.
+		let mut import_block = BlockImportParams::new(block_origin, header);
+		import_block.body = block.body;
+		import_block.justification = justification;
+		import_block.finalized = true;
+		import_block.fork_choice = Some(ForkChoiceStrategy::Custom(true));
+		import_block.import_state = Some(state);
+
+		return import_error(import_handle.import_block(import_block, HashMap::new()));
+	}
+
 	match import_error(import_handle.check_block(BlockCheckParams {
 		hash,
 		number,
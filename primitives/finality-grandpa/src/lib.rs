@@ -487,7 +487,7 @@ sp_api::decl_runtime_apis! {
 	/// applied in the runtime after those N blocks have passed.
 	///
 	/// The consensus protocol will coordinate the handoff externally.
-	#[api_version(2)]
+	#[api_version(3)]
 	pub trait GrandpaApi {
 		/// Get the current GRANDPA authorities and weights. This should not change except
 		/// for when changes are scheduled and the corresponding delay has passed.
@@ -497,6 +497,10 @@ sp_api::decl_runtime_apis! {
 		/// is finalized by the authorities from block B-1.
 		fn grandpa_authorities() -> AuthorityList;
 
+		/// Get the id of the current GRANDPA authority set, i.e. the set returned
+		/// by `grandpa_authorities` when called at the same block.
+		fn current_set_id() -> SetId;
+
 		/// Submits an extrinsic to report an equivocation. The caller must
 		/// provide the equivocation proof and a key ownership proof (should be
 		/// obtained using `generate_key_ownership_proof`). This method will
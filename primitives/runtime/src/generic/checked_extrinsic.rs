@@ -45,6 +45,10 @@ where
 {
 	type Call = Call;
 
+	fn call(&self) -> &Self::Call {
+		&self.function
+	}
+
 	fn validate<U: ValidateUnsigned<Call = Self::Call>>(
 		&self,
 		// TODO [#5006;ToDr] should source be passed to `SignedExtension`s?
@@ -602,10 +602,13 @@ macro_rules! __impl_outer_config_types {
 /// 	rust_module_one: Option<ModuleOneConfig>,
 /// 	...
 /// }
+///
+/// An optional `where OnGenesis = (path::to::function)` is called with the genesis storage once
+/// all the module configurations were built into it.
 #[macro_export]
 macro_rules! impl_outer_config {
 	(
-		pub struct $main:ident for $concrete:ident {
+		pub struct $main:ident for $concrete:ident $( where OnGenesis = $on_genesis:tt )? {
 			$( $config:ident =>
 				$snake:ident $( $instance:ident )? $( <$generic:ident> )*, )*
 		}
@@ -642,6 +645,7 @@ macro_rules! impl_outer_config {
 							}
 						}
 					)*
+					$( $on_genesis(storage); )?
 					Ok(())
 				}
 			}
@@ -364,6 +364,10 @@ impl<Origin, Call, Extra> Applyable for TestXt<Call, Extra> where
 {
 	type Call = Call;
 
+	fn call(&self) -> &Self::Call {
+		&self.call
+	}
+
 	/// Checks to see if this is a valid *transaction*. It returns information on it if so.
 	fn validate<U: ValidateUnsigned<Call=Self::Call>>(
 		&self,
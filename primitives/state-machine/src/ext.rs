@@ -37,6 +37,37 @@ use log::{warn, trace};
 
 const EXT_NOT_ALLOWED_TO_FAIL: &str = "Externalities not allowed to fail within runtime";
 
+/// Tracing target of the events emitted for every storage access made through [`Ext`].
+///
+/// Every event carries the `method`, the hex encoded `child` storage key (empty for the
+/// top trie) and `key`, and, if a value was read or written, its `size` in bytes.
+pub const STORAGE_ACCESS_TARGET: &str = "state_access";
+
+/// Emit a tracing event describing a single storage access.
+fn trace_storage_access(
+	method: &'static str,
+	child_info: Option<&ChildInfo>,
+	key: &[u8],
+	value: Option<&[u8]>,
+) {
+	let child = child_info.map(|c| c.storage_key()).unwrap_or_default();
+	match value {
+		Some(value) => tracing::trace!(
+			target: STORAGE_ACCESS_TARGET,
+			method,
+			child = ?HexDisplay::from(&child),
+			key = ?HexDisplay::from(&key),
+			size = value.len() as u64,
+		),
+		None => tracing::trace!(
+			target: STORAGE_ACCESS_TARGET,
+			method,
+			child = ?HexDisplay::from(&child),
+			key = ?HexDisplay::from(&key),
+		),
+	}
+}
+
 /// Errors that can occur when interacting with the externalities.
 #[derive(Debug, Copy, Clone)]
 pub enum Error<B, E> {
@@ -180,6 +211,7 @@ where
 			HexDisplay::from(&key),
 			result.as_ref().map(HexDisplay::from)
 		);
+		trace_storage_access("Get", None, key, result.as_ref().map(|v| &v[..]));
 		result
 	}
 
@@ -218,6 +250,7 @@ where
 			HexDisplay::from(&key),
 			result.as_ref().map(HexDisplay::from)
 		);
+		trace_storage_access("GetChild", Some(child_info), key, result.as_ref().map(|v| &v[..]));
 
 		result
 	}
@@ -258,6 +291,7 @@ where
 			HexDisplay::from(&key),
 			result,
 		);
+		trace_storage_access("Exists", None, key, None);
 
 		result
 	}
@@ -282,6 +316,7 @@ where
 			HexDisplay::from(&key),
 			result,
 		);
+		trace_storage_access("ChildExists", Some(child_info), key, None);
 		result
 	}
 
@@ -333,6 +368,12 @@ where
 			HexDisplay::from(&key),
 			value.as_ref().map(HexDisplay::from)
 		);
+		trace_storage_access(
+			if value.is_some() { "Put" } else { "Clear" },
+			None,
+			&key,
+			value.as_ref().map(|v| &v[..]),
+		);
 		let _guard = sp_panic_handler::AbortGuard::force_abort();
 		if is_child_storage_key(&key) {
 			warn!(target: "trie", "Refuse to directly set child storage key");
@@ -355,6 +396,12 @@ where
 			HexDisplay::from(&key),
 			value.as_ref().map(HexDisplay::from)
 		);
+		trace_storage_access(
+			if value.is_some() { "PutChild" } else { "ClearChild" },
+			Some(child_info),
+			&key,
+			value.as_ref().map(|v| &v[..]),
+		);
 		let _guard = sp_panic_handler::AbortGuard::force_abort();
 
 		self.mark_dirty();
@@ -369,6 +416,7 @@ where
 			self.id,
 			HexDisplay::from(&child_info.storage_key()),
 		);
+		trace_storage_access("KillChild", Some(child_info), &[], None);
 		let _guard = sp_panic_handler::AbortGuard::force_abort();
 
 		self.mark_dirty();
@@ -383,6 +431,7 @@ where
 			self.id,
 			HexDisplay::from(&prefix),
 		);
+		trace_storage_access("ClearPrefix", None, prefix, None);
 		let _guard = sp_panic_handler::AbortGuard::force_abort();
 		if is_child_storage_key(prefix) {
 			warn!(target: "trie", "Refuse to directly clear prefix that is part of child storage key");
@@ -406,6 +455,7 @@ where
 			HexDisplay::from(&child_info.storage_key()),
 			HexDisplay::from(&prefix),
 		);
+		trace_storage_access("ClearChildPrefix", Some(child_info), prefix, None);
 		let _guard = sp_panic_handler::AbortGuard::force_abort();
 
 		self.mark_dirty();
@@ -425,6 +475,7 @@ where
 			HexDisplay::from(&key),
 			HexDisplay::from(&value),
 		);
+		trace_storage_access("Append", None, &key, Some(&value));
 
 		let _guard = sp_panic_handler::AbortGuard::force_abort();
 		self.mark_dirty();
@@ -24,7 +24,8 @@ use hash_db::Hasher;
 use codec::{Decode, Encode, Codec};
 use sp_core::{
 	offchain::storage::OffchainOverlayedChanges,
-	storage::ChildInfo, NativeOrEncoded, NeverNativeValue, hexdisplay::HexDisplay,
+	storage::{ChildInfo, ChildType, PrefixedStorageKey}, NativeOrEncoded, NeverNativeValue,
+	hexdisplay::HexDisplay,
 	traits::{CodeExecutor, CallInWasmExt, RuntimeCode},
 };
 use overlayed_changes::OverlayedChangeSet;
@@ -46,7 +47,7 @@ mod stats;
 pub use sp_trie::{trie_types::{Layout, TrieDBMut}, StorageProof, TrieMut, DBValue, MemoryDB};
 pub use testing::TestExternalities;
 pub use basic::BasicExternalities;
-pub use ext::Ext;
+pub use ext::{Ext, STORAGE_ACCESS_TARGET};
 pub use backend::Backend;
 pub use changes_trie::{
 	AnchorBlockId as ChangesTrieAnchorBlockId,
@@ -749,6 +750,279 @@ where
 		.map_err(|e| Box::new(e) as Box<dyn Error>)
 }
 
+/// Key/value pairs of a single trie, read by [`read_range`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyValueStorageLevel {
+	/// Prefixed storage key of the child trie, empty for the top trie.
+	pub parent_storage_key: Vec<u8>,
+	/// Key/value pairs, in key order.
+	pub key_values: Vec<(Vec<u8>, Vec<u8>)>,
+	/// Whether the last key of the trie has been read.
+	pub complete: bool,
+}
+
+/// Key/value pairs of the state read by [`read_range`], one level per trie.
+///
+/// A read starting inside a child trie returns the rest of that child trie first, then the
+/// top trie, followed by the child tries reached while reading the top trie.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyValueStates(pub Vec<KeyValueStorageLevel>);
+
+impl KeyValueStates {
+	/// Number of key/value pairs, in all levels.
+	pub fn len(&self) -> usize {
+		self.0.iter().map(|level| level.key_values.len()).sum()
+	}
+
+	/// Whether there are no key/value pairs.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Whether the end of the state has been reached.
+	pub fn is_complete(&self) -> bool {
+		self.0.iter().any(|level| level.parent_storage_key.is_empty())
+			&& self.0.iter().all(|level| level.complete)
+	}
+
+	/// Start key to continue reading the state after these key/value pairs, which have been
+	/// read starting at `start_at`. `None` if the end of the state has been reached.
+	pub fn next_start(&self, start_at: &[Vec<u8>]) -> Option<Vec<Vec<u8>>> {
+		// reading stops at the first incomplete child trie.
+		let child = self.0.iter()
+			.find(|level| !level.parent_storage_key.is_empty() && !level.complete);
+		if let Some(child) = child {
+			let last_key = match child.key_values.last() {
+				Some((key, _)) => key.clone(),
+				None if start_at.len() == 2 && start_at[0] == child.parent_storage_key =>
+					start_at[1].clone(),
+				None => Vec::new(),
+			};
+			return Some(vec![child.parent_storage_key.clone(), last_key]);
+		}
+
+		if self.is_complete() {
+			return None;
+		}
+
+		let last_top_key = self.0.iter()
+			.find(|level| level.parent_storage_key.is_empty())
+			.and_then(|top| top.key_values.last())
+			.map(|(key, _)| key.clone());
+		Some(match last_top_key {
+			Some(key) => vec![key],
+			None => start_at.iter().take(1).cloned().collect(),
+		})
+	}
+}
+
+/// Read the key/value pairs of the state following `start_at`, including the content of
+/// the child tries, until at least `size_limit` bytes of keys and values have been read.
+///
+/// `start_at` is empty to read from the beginning of the state, or contains the top trie key
+/// to read after. To resume inside a child trie, it contains the prefixed storage key of the
+/// child trie followed by the child trie key to read after (empty for its beginning), reading
+/// then continues in the top trie.
+pub fn read_range<B, H>(
+	backend: &B,
+	start_at: &[Vec<u8>],
+	size_limit: usize,
+) -> Result<KeyValueStates, Box<dyn Error>>
+where
+	B: Backend<H>,
+	H: Hasher,
+{
+	read_range_on_backend(backend, start_at, size_limit, false)
+}
+
+/// Generate a proof of the key/value pairs returned by [`read_range`], and their number.
+pub fn prove_range_read_with_size<B, H>(
+	mut backend: B,
+	start_at: &[Vec<u8>],
+	size_limit: usize,
+) -> Result<(StorageProof, u32), Box<dyn Error>>
+where
+	B: Backend<H>,
+	H: Hasher,
+	H::Out: Ord + Codec,
+{
+	let trie_backend = backend.as_trie_backend()
+		.ok_or_else(|| Box::new(ExecutionError::UnableToGenerateProof) as Box<dyn Error>)?;
+	prove_range_read_with_size_on_trie_backend(trie_backend, start_at, size_limit)
+}
+
+/// Generate range read proof on pre-created trie backend.
+pub fn prove_range_read_with_size_on_trie_backend<S, H>(
+	trie_backend: &TrieBackend<S, H>,
+	start_at: &[Vec<u8>],
+	size_limit: usize,
+) -> Result<(StorageProof, u32), Box<dyn Error>>
+where
+	S: trie_backend_essence::TrieBackendStorage<H>,
+	H: Hasher,
+	H::Out: Ord + Codec,
+{
+	let proving_backend = proving_backend::ProvingBackend::<_, H>::new(trie_backend);
+	let states = read_range_on_backend(&proving_backend, start_at, size_limit, false)?;
+	Ok((proving_backend.extract_proof(), states.len() as u32))
+}
+
+/// Check range read proof, generated by `prove_range_read_with_size` call.
+///
+/// Returns the key/value pairs following `start_at` that are included in the proof. Reading
+/// stops without error where the proof ends.
+pub fn read_range_proof_check<H>(
+	root: H::Out,
+	proof: StorageProof,
+	start_at: &[Vec<u8>],
+) -> Result<KeyValueStates, Box<dyn Error>>
+where
+	H: Hasher,
+	H::Out: Ord + Codec,
+{
+	let proving_backend = create_proof_check_backend::<H>(root, proof)?;
+	read_range_on_backend(&proving_backend, start_at, usize::max_value(), true)
+}
+
+/// Implementation of [`read_range`]. If `stop_on_error` is set, a failed read (for instance
+/// of a trie node missing from a proof) ends the read instead of failing it.
+fn read_range_on_backend<B, H>(
+	backend: &B,
+	start_at: &[Vec<u8>],
+	size_limit: usize,
+	stop_on_error: bool,
+) -> Result<KeyValueStates, Box<dyn Error>>
+where
+	B: Backend<H>,
+	H: Hasher,
+{
+	let mut levels = Vec::new();
+	let mut size = 0;
+	let top_start = match start_at {
+		[] => Vec::new(),
+		[key] => key.clone(),
+		[parent_storage_key, key] => {
+			let child_info = child_info_from_prefixed_key(parent_storage_key)?;
+			let complete = read_range_level(
+				backend,
+				Some(&child_info),
+				key,
+				&mut size,
+				size_limit,
+				stop_on_error,
+				&mut levels,
+			)?;
+			if !complete {
+				return Ok(KeyValueStates(levels));
+			}
+			parent_storage_key.clone()
+		},
+		_ => return Err(Box::new("Invalid range start: too many keys")),
+	};
+
+	read_range_level(backend, None, &top_start, &mut size, size_limit, stop_on_error, &mut levels)?;
+	Ok(KeyValueStates(levels))
+}
+
+/// Read a single trie after `start` (from its beginning if empty) into a new level, and the
+/// child tries it references if it is the top trie. Returns whether the trie has been read
+/// to its end.
+fn read_range_level<B, H>(
+	backend: &B,
+	child_info: Option<&ChildInfo>,
+	start: &[u8],
+	size: &mut usize,
+	size_limit: usize,
+	stop_on_error: bool,
+	levels: &mut Vec<KeyValueStorageLevel>,
+) -> Result<bool, Box<dyn Error>>
+where
+	B: Backend<H>,
+	H: Hasher,
+{
+	let index = levels.len();
+	levels.push(KeyValueStorageLevel {
+		parent_storage_key: child_info
+			.map(|child_info| child_info.prefixed_storage_key().into_inner())
+			.unwrap_or_default(),
+		..Default::default()
+	});
+
+	let mut key = start.to_vec();
+	// the empty key is never returned as a next key, check it explicitly.
+	let mut check_key = start.is_empty();
+	loop {
+		if !check_key {
+			let next = match child_info {
+				Some(child_info) => backend.next_child_storage_key(child_info, &key),
+				None => backend.next_storage_key(&key),
+			};
+			match next {
+				Ok(Some(next)) => key = next,
+				Ok(None) => {
+					levels[index].complete = true;
+					return Ok(true);
+				},
+				Err(e) if stop_on_error => {
+					trace!(target: "state", "Range read stopped: {}", e);
+					return Ok(false);
+				},
+				Err(e) => return Err(Box::new(e)),
+			}
+		}
+
+		let value = match child_info {
+			Some(child_info) => backend.child_storage(child_info, &key),
+			None => backend.storage(&key),
+		};
+		let value = match value {
+			Ok(Some(value)) => value,
+			Ok(None) if check_key => {
+				check_key = false;
+				continue;
+			},
+			Ok(None) => return Err(Box::new("Inconsistent state: missing value of iterated key")),
+			Err(e) if stop_on_error => {
+				trace!(target: "state", "Range read stopped: {}", e);
+				return Ok(false);
+			},
+			Err(e) => return Err(Box::new(e)),
+		};
+		check_key = false;
+
+		*size += key.len() + value.len();
+		levels[index].key_values.push((key.clone(), value));
+
+		if child_info.is_none() && sp_core::storage::well_known_keys::is_child_storage_key(&key) {
+			let child_info = child_info_from_prefixed_key(&key)?;
+			let complete = read_range_level(
+				backend,
+				Some(&child_info),
+				&[],
+				size,
+				size_limit,
+				stop_on_error,
+				levels,
+			)?;
+			if !complete {
+				return Ok(false);
+			}
+		}
+
+		if *size >= size_limit {
+			return Ok(false);
+		}
+	}
+}
+
+/// Child info of the default child trie at the given prefixed storage key.
+fn child_info_from_prefixed_key(prefixed_key: &Vec<u8>) -> Result<ChildInfo, Box<dyn Error>> {
+	match ChildType::from_prefixed_key(PrefixedStorageKey::new_ref(prefixed_key)) {
+		Some((ChildType::ParentKeyId, storage_key)) => Ok(ChildInfo::new_default(storage_key)),
+		None => Err(Box::new("Unsupported child trie type")),
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use std::collections::BTreeMap;
@@ -1277,6 +1551,61 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn prove_range_read_with_child_tries_works() {
+		let remote_backend = trie_backend::tests::test_trie();
+		let remote_root = remote_backend.storage_root(::std::iter::empty()).0;
+		let flatten = |states: KeyValueStates| states.0.into_iter()
+			.flat_map(|level| {
+				let parent = level.parent_storage_key;
+				level.key_values.into_iter().map(move |(k, v)| (parent.clone(), k, v))
+			})
+			.collect::<Vec<_>>();
+
+		let all = read_range(&remote_backend, &[], usize::max_value()).unwrap();
+		assert!(all.is_complete());
+		assert_eq!(all.0.len(), 2);
+		assert_eq!(all.0[1].key_values, vec![
+			(b"value3".to_vec(), vec![142]),
+			(b"value4".to_vec(), vec![124]),
+		]);
+		let mut expected = flatten(all);
+		expected.sort();
+
+		// read the state by chunks, through proofs.
+		let mut start_at = Vec::new();
+		let mut collected = Vec::new();
+		loop {
+			let (proof, count) = prove_range_read_with_size(
+				trie_backend::tests::test_trie(),
+				&start_at,
+				10,
+			).unwrap();
+			let states = read_range_proof_check::<BlakeTwo256>(
+				remote_root,
+				proof,
+				&start_at,
+			).unwrap();
+			assert!(states.len() >= count as usize);
+			let next = states.next_start(&start_at);
+			collected.extend(flatten(states));
+			match next {
+				Some(next) => {
+					assert_ne!(next, start_at);
+					start_at = next;
+				},
+				None => break,
+			}
+		}
+		collected.sort();
+		assert_eq!(collected, expected);
+
+		// a proof for another root is rejected.
+		let (proof, _) = prove_range_read_with_size(trie_backend::tests::test_trie(), &[], 10)
+			.unwrap();
+		assert!(read_range_proof_check::<BlakeTwo256>(Default::default(), proof, &[]).is_err());
+	}
+
 	#[test]
 	fn child_storage_uuid() {
 
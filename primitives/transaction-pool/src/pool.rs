@@ -53,6 +53,52 @@ impl PoolStatus {
 	}
 }
 
+/// Details of a single transaction in the pool.
+///
+/// Exposes the pool internals (e.g. over RPC) to help debugging why a transaction
+/// does not get included.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionDetails<Hash> {
+	/// Transaction hash.
+	pub hash: Hash,
+	/// Transaction priority (higher = better).
+	pub priority: TransactionPriority,
+	/// Block number at which the transaction stops being valid.
+	pub longevity: TransactionLongevity,
+	/// Tags required by the transaction.
+	pub requires: Vec<TransactionTag>,
+	/// Tags that this transaction provides.
+	pub provides: Vec<TransactionTag>,
+	/// Required tags that are not yet provided by any other transaction in the pool.
+	///
+	/// Always empty for transactions in the ready queue.
+	pub missing_tags: Vec<TransactionTag>,
+	/// Time the transaction entered the pool, in milliseconds since UNIX epoch.
+	pub imported_at: u64,
+}
+
+/// Contents of the transaction pool queues.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolContent<Hash> {
+	/// Transactions in the ready queue.
+	pub ready: Vec<TransactionDetails<Hash>>,
+	/// Transactions in the future queue.
+	pub future: Vec<TransactionDetails<Hash>>,
+}
+
+/// Status of a particular transaction hash as seen by the pool.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InPoolStatus<Hash> {
+	/// Transaction is part of the ready queue.
+	Ready(TransactionDetails<Hash>),
+	/// Transaction is part of the future queue.
+	Future(TransactionDetails<Hash>),
+	/// Transaction is not in the pool and has been recently banned from entering it.
+	Banned,
+	/// Transaction is not known to the pool.
+	Unknown,
+}
+
 /// Possible transaction status events.
 ///
 /// This events are being emitted by `TransactionPool` watchers,
@@ -242,6 +288,15 @@ pub trait TransactionPool: Send + Sync {
 
 	/// Return specific ready transaction by hash, if there is one.
 	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>>;
+
+	// *** RPC / debugging
+	/// Returns details of all transactions in the ready and future queues.
+	fn content(&self) -> PoolContent<TxHash<Self>>;
+
+	/// Returns status of the transaction with given hash.
+	///
+	/// Also reports hashes that are not in the pool, but have been recently banned.
+	fn in_pool_status(&self, hash: &TxHash<Self>) -> InPoolStatus<TxHash<Self>>;
 }
 
 /// Events that the transaction pool listens for.
@@ -20,7 +20,7 @@ use std::sync::Arc;
 use sc_network::config::TransportConfig;
 use sc_service::{
 	AbstractService, RpcSession, Role, Configuration,
-	config::{DatabaseConfig, KeystoreConfig, NetworkConfiguration},
+	config::{DatabaseConfig, KeepBlocks, KeystoreConfig, NetworkConfiguration},
 	GenericChainSpec, RuntimeGenesis
 };
 use wasm_bindgen::prelude::*;
@@ -83,6 +83,7 @@ where
 		offchain_worker: Default::default(),
 		prometheus_config: Default::default(),
 		pruning: Default::default(),
+		keep_blocks: KeepBlocks::All,
 		rpc_cors: Default::default(),
 		rpc_http: Default::default(),
 		rpc_ws: Default::default(),
@@ -39,7 +39,7 @@ use sc_rpc_api::state::StateClient;
 /// # use codec::Encode;
 /// # use frame_support::{decl_storage, decl_module};
 /// # use substrate_frame_rpc_support::StorageQuery;
-/// # use frame_system::Trait;
+/// # use frame_system::{self as system, Trait};
 /// # use sc_rpc_api::state::StateClient;
 /// #
 /// # // Hash would normally be <TestRuntime as frame_system::Trait>::Hash, but we don't have
@@ -0,0 +1,383 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! `try-runtime` command: tests the upgrade to a new runtime offline, against the state of
+//! an existing chain.
+//!
+//! The state of a block is loaded from the local database, or from a chain specification with
+//! raw storage such as the output of `export-state`. The `OnRuntimeUpgrade` hooks of the new
+//! runtime are executed on top of it through the `TryRuntime` runtime api, then the first block
+//! of the new runtime is initialized, its inherents are applied and it is finalized. Nothing is
+//! written to the database.
+//!
+//! The node provides the inherent data providers and the pre-runtime digest of the consensus
+//! engines of its chain, which are needed to build the first block.
+
+use codec::{Decode, Encode};
+use frame_support::weights::{Weight, constants::WEIGHT_PER_MILLIS};
+use sc_cli::{
+	BlockNumberOrHash, CliConfiguration, PruningParams, Result, SharedParams, WasmExecutionMethod,
+};
+use sc_client_api::{Backend as _, blockchain::HeaderBackend};
+use sc_client_db::{Backend as DbBackend, DatabaseSettings, KeepBlocks};
+use sc_executor::NativeExecutor;
+use sc_service::{Configuration, NativeExecutionDispatch};
+use sp_core::{
+	hashing::blake2_256,
+	storage::well_known_keys,
+	tasks,
+	traits::{RuntimeCode, WrappedRuntimeCode},
+};
+use sp_externalities::Extensions;
+use sp_inherents::{InherentData, InherentDataProviders};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, HashFor, Header as HeaderT, NumberFor, One, Zero},
+	ApplyExtrinsicResult, Digest,
+};
+use sp_state_machine::{
+	Backend, ChangesTrieState, ExecutionStrategy, InMemoryBackend, OverlayedChanges, StateMachine,
+};
+use std::{fmt::Debug, path::PathBuf, str::FromStr};
+
+/// The `try-runtime` command used to test runtime upgrades against existing state.
+#[derive(Debug, structopt::StructOpt, Clone)]
+pub struct TryRuntimeCmd {
+	/// Path to the wasm blob of the new runtime.
+	#[structopt(long, value_name = "PATH", parse(from_os_str))]
+	pub runtime: PathBuf,
+
+	/// Block whose state is loaded from the database. Defaults to the best block.
+	#[structopt(long, value_name = "HASH or NUMBER")]
+	pub at: Option<BlockNumberOrHash>,
+
+	/// Load the state from the raw storage of the chain specification given with `--chain`,
+	/// e.g. the output of `export-state`, instead of the database.
+	#[structopt(long, conflicts_with = "at")]
+	pub from_chain_spec: bool,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+
+	/// Method for executing Wasm runtime code.
+	#[structopt(
+		long = "wasm-execution",
+		value_name = "METHOD",
+		possible_values = &WasmExecutionMethod::enabled_variants(),
+		case_insensitive = true,
+		default_value = "Interpreted"
+	)]
+	pub wasm_method: WasmExecutionMethod,
+
+	/// Limit the memory the database cache can use.
+	#[structopt(long = "db-cache", value_name = "MiB", default_value = "128")]
+	pub database_cache_size: u32,
+}
+
+impl TryRuntimeCmd {
+	/// Runs the command against the configured chain.
+	///
+	/// The inherents of the first block are created with `inherent_data_providers`, and
+	/// `pre_runtime_digest` returns the digest the first block is initialized with, given the
+	/// inherent data of the block.
+	pub fn run<B, ExecDispatch, D>(
+		&self,
+		config: Configuration,
+		inherent_data_providers: &InherentDataProviders,
+		pre_runtime_digest: D,
+	) -> Result<()>
+	where
+		B: BlockT + Debug,
+		B::Hash: FromStr,
+		<B::Hash as FromStr>::Err: Debug,
+		NumberFor<B>: FromStr,
+		<NumberFor<B> as FromStr>::Err: Debug,
+		ExecDispatch: NativeExecutionDispatch + 'static,
+		D: FnOnce(&InherentData) -> Result<Digest<B::Hash>>,
+	{
+		let code = std::fs::read(&self.runtime)
+			.map_err(|e| format!("Failed to read runtime {}: {}", self.runtime.display(), e))?;
+		let executor = NativeExecutor::<ExecDispatch>::new(
+			self.wasm_method.into(),
+			None, // heap pages
+			2, // The runtime instances cache size.
+		);
+		let inherent_data = inherent_data_providers.create_inherent_data()
+			.map_err(|e| format!("Failed to create the inherent data: {:?}", e))?;
+		let digest = pre_runtime_digest(&inherent_data)?;
+
+		if self.from_chain_spec {
+			let storage = config.chain_spec.build_storage()?;
+			let state = InMemoryBackend::<HashFor<B>>::from(storage);
+			// the hash of the block the state belongs to is not part of the state.
+			let number = read_block_number::<B, _>(&state)?;
+			println!("Testing runtime upgrade on top of the state of block #{} from the chain spec", number);
+			try_runtime::<B, _, _>(
+				&state,
+				&executor,
+				&code,
+				number,
+				Default::default(),
+				inherent_data,
+				digest,
+			)
+		} else {
+			let backend = DbBackend::<B>::new(DatabaseSettings {
+				state_cache_size: config.state_cache_size,
+				state_cache_child_ratio: None,
+				pruning: config.pruning.clone(),
+				keep_blocks: KeepBlocks::All,
+				source: config.database.clone(),
+			// no block is imported, blocks are never canonicalized.
+			}, 0)?;
+			let id = match &self.at {
+				Some(at) => at.parse::<B>()?,
+				None => BlockId::Hash(backend.blockchain().info().best_hash),
+			};
+			let header = backend.blockchain().header(id)?
+				.ok_or_else(|| format!("Unknown block {}", id))?;
+			let state = backend.state_at(id)?;
+			println!(
+				"Testing runtime upgrade on top of the state of block #{} ({})",
+				header.number(),
+				header.hash(),
+			);
+			try_runtime::<B, _, _>(
+				&state,
+				&executor,
+				&code,
+				*header.number(),
+				header.hash(),
+				inherent_data,
+				digest,
+			)
+		}
+	}
+}
+
+/// Reads the number of the last block from the `System` pallet storage.
+fn read_block_number<B: BlockT, S: Backend<HashFor<B>>>(state: &S) -> Result<NumberFor<B>> {
+	let key = [
+		sp_core::hashing::twox_128(b"System"),
+		sp_core::hashing::twox_128(b"Number"),
+	].concat();
+	let number = state.storage(&key)
+		.map_err(|e| format!("Failed to read the block number: {}", e))?
+		.map(|encoded| Decode::decode(&mut &encoded[..]))
+		.transpose()
+		.map_err(|e| format!("Failed to decode the block number: {:?}", e))?;
+	Ok(number.unwrap_or_else(Zero::zero))
+}
+
+/// Executes the runtime upgrade hooks of `code`, then the first block built with it, on top of
+/// `state`, and prints a report of both.
+///
+/// `number` and `hash` identify the block `state` belongs to.
+fn try_runtime<B, S, E>(
+	state: &S,
+	executor: &E,
+	code: &[u8],
+	number: NumberFor<B>,
+	hash: B::Hash,
+	inherent_data: InherentData,
+	digest: Digest<B::Hash>,
+) -> Result<()>
+where
+	B: BlockT,
+	S: Backend<HashFor<B>>,
+	E: sp_core::traits::CodeExecutor + Clone + 'static,
+{
+	let heap_pages = state.storage(well_known_keys::HEAP_PAGES)
+		.map_err(|e| format!("Failed to read the heap pages: {}", e))?
+		.and_then(|pages| Decode::decode(&mut &pages[..]).ok());
+	let code_fetcher = WrappedRuntimeCode(code.into());
+	let runtime_code = RuntimeCode {
+		code_fetcher: &code_fetcher,
+		hash: blake2_256(code).to_vec(),
+		heap_pages,
+	};
+
+	// the upgrade hooks alone.
+	let mut changes = OverlayedChanges::default();
+	let result = execute::<B, _, _>(
+		state,
+		executor,
+		&runtime_code,
+		&mut changes,
+		"TryRuntime_on_runtime_upgrade",
+		&[],
+	);
+	match result.and_then(|encoded| Weight::decode(&mut &encoded[..])
+		.map_err(|e| format!("Failed to decode the consumed weight: {:?}", e))
+	) {
+		Ok(weight) => {
+			println!("Runtime upgrade executed successfully.");
+			println!("  Weight: {} ({} ms)", weight, weight / WEIGHT_PER_MILLIS);
+			print_changes::<B, _>(state, changes)?;
+		},
+		Err(e) => return Err(format!("Runtime upgrade failed: {}", e).into()),
+	}
+
+	// the first block, which executes the upgrade hooks again on initialization.
+	let header = <B::Header as HeaderT>::new(
+		number + One::one(),
+		Default::default(),
+		Default::default(),
+		hash,
+		digest,
+	);
+	let mut changes = OverlayedChanges::default();
+	let result = execute_first_block::<B, _, _>(
+		state,
+		executor,
+		&runtime_code,
+		&mut changes,
+		&header,
+		inherent_data,
+	);
+	match result {
+		Ok(inherents) => {
+			println!(
+				"First block #{} executed successfully with {} inherents.",
+				header.number(),
+				inherents,
+			);
+			print_changes::<B, _>(state, changes)?;
+		},
+		Err(e) => return Err(format!("First block #{} failed: {}", header.number(), e).into()),
+	}
+
+	Ok(())
+}
+
+/// Initializes the block with the given `header`, applies the inherents created from
+/// `inherent_data` and finalizes it.
+///
+/// Returns the number of applied inherents.
+fn execute_first_block<B, S, E>(
+	state: &S,
+	executor: &E,
+	runtime_code: &RuntimeCode,
+	changes: &mut OverlayedChanges,
+	header: &B::Header,
+	inherent_data: InherentData,
+) -> std::result::Result<usize, String>
+where
+	B: BlockT,
+	S: Backend<HashFor<B>>,
+	E: sp_core::traits::CodeExecutor + Clone + 'static,
+{
+	let mut call = |method, data: &[u8]| {
+		execute::<B, _, _>(state, executor, runtime_code, changes, method, data)
+	};
+
+	call("Core_initialize_block", &header.encode())?;
+
+	let inherents = call("BlockBuilder_inherent_extrinsics", &inherent_data.encode())?;
+	let inherents = Vec::<B::Extrinsic>::decode(&mut &inherents[..])
+		.map_err(|e| format!("Failed to decode the inherents: {:?}", e))?;
+	for inherent in &inherents {
+		let result = call("BlockBuilder_apply_extrinsic", &inherent.encode())?;
+		match ApplyExtrinsicResult::decode(&mut &result[..]) {
+			Ok(Ok(Ok(()))) => {},
+			Ok(Ok(Err(e))) => return Err(format!("Inherent {:?} failed: {:?}", inherent, e)),
+			Ok(Err(e)) => return Err(format!("Inherent {:?} is invalid: {:?}", inherent, e)),
+			Err(e) => return Err(format!("Failed to decode the inherent result: {:?}", e)),
+		}
+	}
+
+	call("BlockBuilder_finalize_block", &[])?;
+	Ok(inherents.len())
+}
+
+/// Calls `method` of the runtime with the given code, accumulating the changes in `changes`.
+fn execute<B, S, E>(
+	state: &S,
+	executor: &E,
+	runtime_code: &RuntimeCode,
+	changes: &mut OverlayedChanges,
+	method: &str,
+	data: &[u8],
+) -> std::result::Result<Vec<u8>, String>
+where
+	B: BlockT,
+	S: Backend<HashFor<B>>,
+	E: sp_core::traits::CodeExecutor + Clone + 'static,
+{
+	let mut offchain_changes = Default::default();
+	StateMachine::<_, _, NumberFor<B>, _>::new(
+		state,
+		None,
+		changes,
+		&mut offchain_changes,
+		executor,
+		method,
+		data,
+		Extensions::default(),
+		runtime_code,
+		tasks::executor(),
+	)
+	.execute(ExecutionStrategy::AlwaysWasm)
+	.map_err(|e| format!("{:?}", e))
+}
+
+/// Prints the number and size of the storage changes.
+fn print_changes<B: BlockT, S: Backend<HashFor<B>>>(
+	state: &S,
+	changes: OverlayedChanges,
+) -> Result<()> {
+	let changes = changes.into_storage_changes(
+		state,
+		None::<&ChangesTrieState<HashFor<B>, NumberFor<B>>>,
+		Default::default(),
+		Default::default(),
+	)?;
+	let size = |(key, value): &(Vec<u8>, Option<Vec<u8>>)| {
+		key.len() + value.as_ref().map_or(0, |value| value.len())
+	};
+	let top_size: usize = changes.main_storage_changes.iter().map(size).sum();
+	let child_changes = changes.child_storage_changes.iter().flat_map(|(_, changes)| changes);
+	let child_count = child_changes.clone().count();
+	let child_size: usize = child_changes.map(size).sum();
+
+	println!(
+		"  Storage changes: {} keys ({} bytes), {} child trie keys ({} bytes)",
+		changes.main_storage_changes.len(),
+		top_size,
+		child_count,
+		child_size,
+	);
+	Ok(())
+}
+
+impl CliConfiguration for TryRuntimeCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn state_cache_size(&self) -> Result<usize> {
+		Ok(self.database_cache_size as usize * 1024 * 1024)
+	}
+}